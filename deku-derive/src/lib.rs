@@ -1,8 +1,17 @@
-use darling::{ast, FromDeriveInput, FromField, FromVariant};
+use darling::{ast, util::Override, FromDeriveInput, FromField, FromMeta, FromVariant};
 use proc_macro2::TokenStream;
 use quote::quote;
 mod macros;
-use crate::macros::{deku_read::emit_deku_read, deku_write::emit_deku_write};
+use crate::macros::{
+    deku_codec::{emit_deku_reader, emit_deku_writer},
+    deku_ffi::emit_deku_ffi,
+    deku_python::emit_deku_python,
+    deku_read::emit_deku_read,
+    deku_size::emit_deku_size,
+    deku_view::emit_deku_view,
+    deku_write::emit_deku_write,
+    gen_bytes_to_bits,
+};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
@@ -17,6 +26,9 @@ struct DekuData {
     /// Endianness for all fields
     endian: Option<syn::LitStr>,
 
+    /// Bit order of the bytes of the container, and for all fields
+    bit_order: Option<syn::LitStr>,
+
     /// top-level context, argument list
     ctx: Option<syn::punctuated::Punctuated<syn::FnArg, syn::token::Comma>>,
 
@@ -32,6 +44,36 @@ struct DekuData {
     /// enum only: bit size of the enum `id`
     /// `bytes` is converted to `bits` if provided
     bits: Option<usize>,
+
+    /// struct only: name of the generated owned struct
+    owned: Option<syn::Ident>,
+
+    /// struct only: derives applied to the generated owned struct
+    owned_derive: Option<Punctuated<syn::Path, syn::token::Comma>>,
+
+    /// struct only: generate an accessor-based view of a `#[repr(C)]` struct
+    view: bool,
+
+    /// generate the `WIRE_FORMAT` table of the fields
+    wire_doc: bool,
+
+    /// struct only: function computing the checksum held by the last field
+    trailer_checksum: Option<TokenStream>,
+
+    /// struct only: type of the trailer held by the last field, read from the end of the input
+    trailer: Option<TokenStream>,
+
+    /// bytes read and verified before the first field, and written back
+    magic: Option<syn::LitByteStr>,
+
+    /// dump the generated reader/writer at compile time
+    debug: bool,
+
+    /// `update` a clone of the container before writing it
+    auto_update: bool,
+
+    /// condition on the container, checked after it's read and before it's written
+    assert: Option<syn::LitStr>,
 }
 
 impl DekuData {
@@ -42,19 +84,20 @@ impl DekuData {
         DekuData::validate(&receiver)
             .map_err(|(span, msg)| syn::Error::new(span, msg).to_compile_error())?;
 
+        let default_if_eof = receiver.default_if_eof;
         let data = match receiver.data {
             ast::Data::Struct(fields) => ast::Data::Struct(ast::Fields {
                 style: fields.style,
                 fields: fields
                     .fields
                     .into_iter()
-                    .map(FieldData::from_receiver)
+                    .map(|field| FieldData::from_receiver(field, default_if_eof))
                     .collect::<Result<Vec<_>, _>>()?,
             }),
             ast::Data::Enum(variants) => ast::Data::Enum(
                 variants
                     .into_iter()
-                    .map(VariantData::from_receiver)
+                    .map(|variant| VariantData::from_receiver(variant, default_if_eof))
                     .collect::<Result<Vec<_>, _>>()?,
             ),
         };
@@ -71,6 +114,12 @@ impl DekuData {
             .transpose()
             .map_err(|e| e.to_compile_error())?;
 
+        let owned_derive = receiver
+            .owned_derive
+            .map(|s| s.parse_with(Punctuated::parse_terminated))
+            .transpose()
+            .map_err(|e| e.to_compile_error())?;
+
         let bits = receiver.bytes.map(|b| b * 8).or(receiver.bits);
 
         Ok(Self {
@@ -79,11 +128,22 @@ impl DekuData {
             generics: receiver.generics,
             data,
             endian: receiver.endian,
+            bit_order: receiver.bit_order,
             ctx,
             ctx_default,
             id: receiver.id,
             id_type: receiver.id_type,
             bits,
+            owned: receiver.owned,
+            owned_derive,
+            view: receiver.view,
+            wire_doc: receiver.wire_doc,
+            trailer_checksum: receiver.trailer_checksum,
+            trailer: receiver.trailer,
+            magic: receiver.magic,
+            debug: receiver.debug,
+            auto_update: receiver.auto_update,
+            assert: receiver.assert,
         })
     }
 
@@ -100,10 +160,75 @@ impl DekuData {
             ));
         }
 
-        match receiver.data {
-            ast::Data::Struct(_) => {
+        // Validate `owned` is not used with `temp` fields
+        if receiver.owned.is_some() && DekuData::has_temp_fields(receiver) {
+            return Err((
+                receiver.owned.span(),
+                "`owned` not supported with `temp` fields",
+            ));
+        }
+
+        // Validate `bit_order` of the container is a literal order
+        if let Some(bit_order) = &receiver.bit_order {
+            if bit_order.value() != "lsb" && bit_order.value() != "msb" {
+                return Err((
+                    bit_order.span(),
+                    "`bit_order` must be \"lsb\" or \"msb\" on a struct or enum",
+                ));
+            }
+        }
+
+        // Validate `owned_derive`
+        if receiver.owned_derive.is_some() && receiver.owned.is_none() {
+            return Err((
+                receiver.owned_derive.span(),
+                "`owned_derive` must be used with `owned`",
+            ));
+        }
+
+        // Validate the multi-byte fields have an `endian` with `explicit_endian`
+        if receiver.explicit_endian {
+            DekuData::validate_explicit_endian(receiver)?;
+        }
+
+        match &receiver.data {
+            ast::Data::Struct(fields) => {
+                // Validate `view` is used on a plain `#[repr(C)]` struct
+                if receiver.view {
+                    DekuData::validate_view(receiver, fields)?;
+                }
+
+                // Validate `trailer_checksum` has a field to hold the checksum
+                if receiver.trailer_checksum.is_some() && fields.is_empty() {
+                    return Err((
+                        receiver.ident.span(),
+                        "`trailer_checksum` requires a field to hold the checksum",
+                    ));
+                }
+
+                // Validate `trailer` has a field to hold the trailer
+                if receiver.trailer.is_some() && fields.is_empty() {
+                    return Err((
+                        receiver.ident.span(),
+                        "`trailer` requires a field to hold the trailer",
+                    ));
+                }
+
+                // Validate `trailer` and `trailer_checksum` don't both claim the last field
+                if receiver.trailer.is_some() && receiver.trailer_checksum.is_some() {
+                    return Err((
+                        receiver.trailer.span(),
+                        "conflicting: both `trailer` and `trailer_checksum` specified on struct",
+                    ));
+                }
+
                 // Validate id_* attributes are being used on an enum
-                if receiver.id_type.is_some() {
+                if let Some(id_field) = fields.iter().find(|f| f.id_field) {
+                    Err((
+                        id_field.ty.span(),
+                        "`id_field` only supported on enum variants",
+                    ))
+                } else if receiver.id_type.is_some() {
                     Err((receiver.id_type.span(), "`type` only supported on enum"))
                 } else if receiver.id.is_some() {
                     Err((receiver.id.span(), "`id` only supported on enum"))
@@ -116,6 +241,32 @@ impl DekuData {
                 }
             }
             ast::Data::Enum(_) => {
+                // Validate `owned` is being used on a struct
+                if receiver.owned.is_some() {
+                    return Err((receiver.owned.span(), "`owned` only supported on struct"));
+                }
+
+                // Validate `view` is being used on a struct
+                if receiver.view {
+                    return Err((receiver.ident.span(), "`view` only supported on struct"));
+                }
+
+                // Validate `trailer_checksum` is being used on a struct
+                if receiver.trailer_checksum.is_some() {
+                    return Err((
+                        receiver.trailer_checksum.span(),
+                        "`trailer_checksum` only supported on struct",
+                    ));
+                }
+
+                // Validate `trailer` is being used on a struct
+                if receiver.trailer.is_some() {
+                    return Err((
+                        receiver.trailer.span(),
+                        "`trailer` only supported on struct",
+                    ));
+                }
+
                 // Validate `type` or `id` is specified
                 if receiver.id_type.is_none() && receiver.id.is_none() {
                     return Err((
@@ -153,10 +304,299 @@ impl DekuData {
         }
     }
 
+    fn validate_view<'a>(
+        receiver: &'a DekuReceiver,
+        fields: &'a ast::Fields<DekuFieldReceiver>,
+    ) -> Result<(), (proc_macro2::Span, &'a str)> {
+        if !is_repr_c(&receiver.attrs) {
+            return Err((receiver.ident.span(), "`view` requires `#[repr(C)]`"));
+        }
+
+        if !receiver.generics.params.is_empty() {
+            return Err((
+                receiver.generics.span(),
+                "`view` not supported on generic struct",
+            ));
+        }
+
+        if receiver.bit_order.is_some() {
+            return Err((
+                receiver.bit_order.span(),
+                "`view` not supported with `bit_order`",
+            ));
+        }
+
+        if receiver.default_if_eof {
+            return Err((
+                receiver.ident.span(),
+                "`view` not supported with `default_if_eof`",
+            ));
+        }
+
+        // The accessors read from the start of the input, without the magic or the trailer
+        if receiver.magic.is_some() {
+            return Err((receiver.magic.span(), "`view` not supported with `magic`"));
+        }
+        if receiver.trailer.is_some() || receiver.trailer_checksum.is_some() {
+            return Err((
+                receiver.ident.span(),
+                "`view` not supported with `trailer` or `trailer_checksum`",
+            ));
+        }
+
+        // The offset of each field must match the native layout, only `endian` is allowed
+        for field in fields.iter() {
+            if field.bits.is_some() || field.bytes.is_some() || !is_view_field(field) {
+                return Err((
+                    field.ty.span(),
+                    "`view` only supports fields without attributes other than `endian`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if any field of the struct or its variants is `temp`
+    fn has_temp_fields(receiver: &DekuReceiver) -> bool {
+        match &receiver.data {
+            ast::Data::Struct(fields) => fields.iter().any(|f| f.temp),
+            ast::Data::Enum(variants) => variants.iter().any(|v| v.fields.iter().any(|f| f.temp)),
+        }
+    }
+
+    /// Validate the multi-byte integers and floats read by the fields, and the enum `type`,
+    /// have an `endian`, at top-level or field-level
+    fn validate_explicit_endian(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        if receiver.endian.is_some() {
+            return Ok(());
+        }
+
+        let fields: Vec<&DekuFieldReceiver> = match &receiver.data {
+            ast::Data::Struct(fields) => fields.iter().collect(),
+            ast::Data::Enum(variants) => {
+                if let Some(id_type) = &receiver.id_type {
+                    let id_type = syn::Type::from(syn::TypePath {
+                        qself: None,
+                        path: id_type.clone().into(),
+                    });
+                    if is_multi_byte_type(&id_type) {
+                        return Err((
+                            id_type.span(),
+                            "`explicit_endian`: enum `type` without an `endian`",
+                        ));
+                    }
+                }
+
+                variants.iter().flat_map(|v| v.fields.iter()).collect()
+            }
+        };
+
+        for field in fields {
+            // the endian is passed in `ctx`, or the field isn't read as its type
+            if field.endian.is_some()
+                || field.skip
+                || field.ctx.is_some()
+                || field.map.is_some()
+                || field.reader.is_some()
+                || field.with.is_some()
+            {
+                continue;
+            }
+
+            let read_as = field
+                .read_as
+                .as_ref()
+                .and_then(|read_as| syn::parse2::<syn::Type>(read_as.clone()).ok());
+            if is_multi_byte_type(read_as.as_ref().unwrap_or(&field.ty)) {
+                return Err((
+                    field.ty.span(),
+                    "`explicit_endian`: multi-byte field without an `endian`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate `temp` fields are removed from the struct, requiring `deku_derive`
+    fn validate_temp(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        if DekuData::has_temp_fields(receiver) {
+            return Err((
+                receiver.ident.span(),
+                "`temp` requires `#[deku_derive(DekuRead, DekuWrite)]` instead of `#[derive(..)]`",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validate the input of `DekuView`
+    fn validate_deku_view(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        let fields = match &receiver.data {
+            ast::Data::Struct(fields) => fields,
+            ast::Data::Enum(_) => {
+                return Err((receiver.ident.span(), "`DekuView` only supported on struct"));
+            }
+        };
+
+        if !receiver.generics.params.is_empty() {
+            return Err((
+                receiver.generics.span(),
+                "`DekuView` not supported on generic struct",
+            ));
+        }
+
+        if receiver.view {
+            return Err((
+                receiver.ident.span(),
+                "`view` already implements `DekuView`",
+            ));
+        }
+
+        if receiver.bit_order.is_some() {
+            return Err((
+                receiver.bit_order.span(),
+                "`DekuView` not supported with `bit_order`",
+            ));
+        }
+
+        // The accessors read from the start of the input, without the magic or the trailer
+        if receiver.magic.is_some() {
+            return Err((
+                receiver.magic.span(),
+                "`DekuView` not supported with `magic`",
+            ));
+        }
+        if receiver.trailer.is_some() || receiver.trailer_checksum.is_some() {
+            return Err((
+                receiver.ident.span(),
+                "`DekuView` not supported with `trailer` or `trailer_checksum`",
+            ));
+        }
+
+        // The offset of each field must be known at compile time
+        for field in fields.iter() {
+            if !field.bits.iter().chain(&field.bytes).all(is_int_literal) {
+                return Err((
+                    field.ty.span(),
+                    "`DekuView` only supports integer `bits` and `bytes`",
+                ));
+            }
+
+            if !is_view_field(field) {
+                return Err((
+                    field.ty.span(),
+                    "`DekuView` only supports fields without attributes other than `endian`, `bits` and `bytes`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_deku_python(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        if !receiver.generics.params.is_empty() {
+            return Err((
+                receiver.generics.span(),
+                "`DekuPython` not supported on generic struct or enum",
+            ));
+        }
+
+        if receiver.ctx.is_some() && receiver.ctx_default.is_none() {
+            return Err((
+                receiver.ctx.span(),
+                "`DekuPython` requires `ctx_default` on a container with a `ctx`",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_deku_ffi(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        if !receiver.generics.params.is_empty() {
+            return Err((
+                receiver.generics.span(),
+                "`DekuFfi` not supported on generic struct or enum",
+            ));
+        }
+
+        if receiver.ctx.is_some() && receiver.ctx_default.is_none() {
+            return Err((
+                receiver.ctx.span(),
+                "`DekuFfi` requires `ctx_default` on a container with a `ctx`",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn validate_deku_size(receiver: &DekuReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+        if !receiver.generics.params.is_empty() {
+            return Err((
+                receiver.generics.span(),
+                "`DekuSize` not supported on generic struct or enum",
+            ));
+        }
+
+        if receiver.view {
+            return Err((
+                receiver.ident.span(),
+                "`view` already implements `DekuSize`",
+            ));
+        }
+
+        let fields: Vec<&DekuFieldReceiver> = match &receiver.data {
+            ast::Data::Struct(fields) => fields.iter().collect(),
+            ast::Data::Enum(variants) => {
+                if variants.is_empty() {
+                    return Err((
+                        receiver.ident.span(),
+                        "`DekuSize` requires at least one variant",
+                    ));
+                }
+
+                if let Some(variant) = variants
+                    .iter()
+                    .find(|v| v.reader.is_some() || v.writer.is_some())
+                {
+                    return Err((
+                        variant.ident.span(),
+                        "`DekuSize` not supported on variant with `reader` or `writer`",
+                    ));
+                }
+
+                variants.iter().flat_map(|v| v.fields.iter()).collect()
+            }
+        };
+
+        // The size of each field must be known at compile time
+        for field in fields {
+            if !field.bits.iter().chain(&field.bytes).all(is_int_literal) {
+                return Err((
+                    field.ty.span(),
+                    "`DekuSize` only supports integer `bits` and `bytes`",
+                ));
+            }
+
+            if !is_view_field(field) {
+                return Err((
+                    field.ty.span(),
+                    "`DekuSize` only supports fields with a size known at compile time",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Emit a reader. On error, a compiler error is emitted
     fn emit_reader(&self) -> TokenStream {
         match self.emit_reader_checked() {
-            Ok(tks) => tks,
+            Ok(tks) => {
+                self.emit_debug("DekuRead", &tks);
+                tks
+            }
             Err(e) => e.to_compile_error(),
         }
     }
@@ -164,11 +604,35 @@ impl DekuData {
     /// Emit a writer. On error, a compiler error is emitted
     fn emit_writer(&self) -> TokenStream {
         match self.emit_writer_checked() {
-            Ok(tks) => tks,
+            Ok(tks) => {
+                self.emit_debug("DekuWrite", &tks);
+                tks
+            }
             Err(e) => e.to_compile_error(),
         }
     }
 
+    /// With `debug`, dump the code generated for `derive` to `$DEKU_DEBUG_DIR/<ident>.<derive>.rs`
+    /// if the environment variable is set, or to stderr otherwise
+    fn emit_debug(&self, derive: &str, tokens: &TokenStream) {
+        if !self.debug {
+            return;
+        }
+
+        let code = tokens.to_string();
+        match std::env::var_os("DEKU_DEBUG_DIR") {
+            Some(dir) => {
+                let path = std::path::Path::new(&dir).join(format!("{}.{}.rs", self.ident, derive));
+                if let Err(e) =
+                    std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, code))
+                {
+                    eprintln!("deku: failed to write {}: {}", path.display(), e);
+                }
+            }
+            None => eprintln!("// #[derive({})] for `{}`\n{}", derive, self.ident, code),
+        }
+    }
+
     /// Same as `emit_reader`, but won't auto convert error to compile error
     fn emit_reader_checked(&self) -> Result<TokenStream, syn::Error> {
         emit_deku_read(self)
@@ -178,23 +642,113 @@ impl DekuData {
     fn emit_writer_checked(&self) -> Result<TokenStream, syn::Error> {
         emit_deku_write(self)
     }
+
+    /// Emit a view. On error, a compiler error is emitted
+    fn emit_view(&self) -> TokenStream {
+        match self.emit_view_checked() {
+            Ok(tks) => tks,
+            Err(e) => e.to_compile_error(),
+        }
+    }
+
+    /// Same as `emit_view`, but won't auto convert error to compile error
+    fn emit_view_checked(&self) -> Result<TokenStream, syn::Error> {
+        emit_deku_view(self)
+    }
+
+    /// Emit a size. On error, a compiler error is emitted
+    fn emit_size(&self) -> TokenStream {
+        match self.emit_size_checked() {
+            Ok(tks) => tks,
+            Err(e) => e.to_compile_error(),
+        }
+    }
+
+    /// Same as `emit_size`, but won't auto convert error to compile error
+    fn emit_size_checked(&self) -> Result<TokenStream, syn::Error> {
+        emit_deku_size(self)
+    }
+
+    /// Emit the Python methods. On error, a compiler error is emitted
+    fn emit_python(&self) -> TokenStream {
+        match self.emit_python_checked() {
+            Ok(tks) => tks,
+            Err(e) => e.to_compile_error(),
+        }
+    }
+
+    /// Same as `emit_python`, but won't auto convert error to compile error
+    fn emit_python_checked(&self) -> Result<TokenStream, syn::Error> {
+        emit_deku_python(self)
+    }
+
+    /// Emit the C functions. On error, a compiler error is emitted
+    fn emit_ffi(&self) -> TokenStream {
+        match self.emit_ffi_checked() {
+            Ok(tks) => tks,
+            Err(e) => e.to_compile_error(),
+        }
+    }
+
+    /// Same as `emit_ffi`, but won't auto convert error to compile error
+    fn emit_ffi_checked(&self) -> Result<TokenStream, syn::Error> {
+        emit_deku_ffi(self)
+    }
 }
 
 /// A post-processed version of `FieldReceiver`
 #[derive(Debug)]
 struct FieldData {
+    vis: syn::Visibility,
     ident: Option<syn::Ident>,
     ty: syn::Type,
 
+    /// doc comment of the field
+    doc: Option<String>,
+
     /// endianness for the field
     endian: Option<syn::LitStr>,
 
-    /// field bit size
-    bits: Option<usize>,
+    /// bit order for the field
+    bit_order: Option<syn::LitStr>,
+
+    /// text encoding of the field
+    encoding: Option<syn::LitStr>,
+
+    /// code unit padding a fixed-width string field
+    pad: Option<TokenStream>,
+
+    /// field bit size, a `usize` expression
+    /// `bytes` is converted to `bits` if provided
+    bits: Option<TokenStream>,
 
     /// tokens providing the length of the container
     count: Option<TokenStream>,
 
+    /// tokens providing the number of bytes of the container
+    bytes_read: Option<TokenStream>,
+
+    /// tokens providing the number of bits of the container
+    bits_read: Option<TokenStream>,
+
+    /// read the elements of the container until the end of the input
+    read_all: bool,
+
+    /// integer type of the byte length written before the field
+    prefix: Option<TokenStream>,
+
+    /// nibble order of the packed BCD digits of the field
+    bcd: Option<syn::LitStr>,
+
+    /// fixed-point format of the field, a `deku::ctx::FixedPoint` expression
+    fixed: Option<TokenStream>,
+
+    /// the signed integer field is sign-magnitude, instead of two's complement
+    sign_magnitude: bool,
+
+    /// the `bool` field must be read as `0` or `1`
+    strict_bool: bool,
+
     /// apply a function to the field after it's read
     map: Option<TokenStream>,
 
@@ -204,28 +758,168 @@ struct FieldData {
     /// map field when updating struct
     update: Option<TokenStream>,
 
+    /// name of the field whose byte length is stored in this field when updating
+    len_of: Option<syn::LitStr>,
+
+    /// added to the byte length of `len_of`
+    len_offset: Option<TokenStream>,
+
     /// custom field reader code
     reader: Option<TokenStream>,
 
     /// custom field writer code
     writer: Option<TokenStream>,
 
+    /// path to a module providing the `read` and `write` functions of the field
+    with: Option<TokenStream>,
+
+    /// function applied to the field before it's written, the inverse of `map`
+    map_write: Option<TokenStream>,
+
+    /// function applied to the field before it's written
+    normalize: Option<TokenStream>,
+
+    /// type the field is read and written as, converted with `TryFrom`
+    read_as: Option<TokenStream>,
+
     /// skip field reading/writing
     skip: bool,
 
+    /// skip field reading, the field is set to `default`
+    skip_read: bool,
+
+    /// skip field writing
+    skip_write: bool,
+
+    /// field set to the enum variant id, which is consumed and written in place of the field
+    id_field: bool,
+
+    /// field set to `default` if the input ends before it
+    default_if_eof: bool,
+
+    /// read the field without consuming its bits, it isn't written
+    peek: bool,
+
     /// default value code when used with skip or cond
     default: TokenStream,
 
     /// condition to parse field
     cond: Option<TokenStream>,
-}
 
-impl FieldData {
-    fn from_receiver(receiver: DekuFieldReceiver) -> Result<Self, TokenStream> {
-        FieldData::validate(&receiver)
-            .map_err(|(span, msg)| syn::Error::new(span, msg).to_compile_error())?;
+    /// condition to write field
+    write_cond: Option<TokenStream>,
 
-        let bits = receiver.bytes.map(|b| b * 8).or(receiver.bits);
+    /// condition the field must satisfy when read and written
+    assert: Option<syn::LitStr>,
+
+    /// value the field must equal when read and written
+    assert_eq: Option<TokenStream>,
+
+    /// number of bits of padding before the field
+    pad_bits_before: Option<TokenStream>,
+
+    /// number of bytes of padding before the field
+    pad_bytes_before: Option<TokenStream>,
+
+    /// number of bits of padding after the field
+    pad_bits_after: Option<TokenStream>,
+
+    /// number of bytes of padding after the field
+    pad_bytes_after: Option<TokenStream>,
+
+    /// bit alignment of the field within the container
+    align_bits: Option<TokenStream>,
+
+    /// byte alignment of the field within the container
+    align_bytes: Option<TokenStream>,
+
+    /// check the field starts at a byte boundary of the container
+    align_before: bool,
+
+    /// check the field ends at a byte boundary of the container
+    align_after: bool,
+
+    /// field only read, removed from the struct by `deku_derive`
+    temp: bool,
+
+    /// value written for a `temp` field
+    temp_value: Option<TokenStream>,
+
+    /// number of bytes to move the position by before the field
+    seek_from_current: Option<TokenStream>,
+
+    /// move the position to the start of the container before the field
+    seek_rewind: bool,
+
+    /// absolute byte offset of the field within the input of the container
+    offset: Option<TokenStream>,
+
+    /// number of bytes the field is read from, and written to
+    limit_bytes: Option<TokenStream>,
+
+    /// function transforming the bytes the field is read from
+    process: Option<TokenStream>,
+
+    /// function transforming the bytes the field is written to
+    process_write: Option<TokenStream>,
+
+    /// function computing the checksum held by the field
+    checksum: Option<TokenStream>,
+
+    /// function verifying the signature held by the field
+    verify: Option<TokenStream>,
+
+    /// function computing the signature held by the field
+    sign: Option<TokenStream>,
+
+    /// range of bytes of the container the checksum or signature is computed over
+    over: Option<TokenStream>,
+}
+
+impl FieldData {
+    /// `default_if_eof` is set on all fields by the top-level attribute
+    fn from_receiver(
+        receiver: DekuFieldReceiver,
+        default_if_eof: bool,
+    ) -> Result<Self, TokenStream> {
+        let default_if_eof = receiver.default_if_eof || default_if_eof;
+        FieldData::validate(&receiver, default_if_eof)
+            .map_err(|(span, msg)| syn::Error::new(span, msg).to_compile_error())?;
+
+        // a fixed-point format given as `Qm.n` sets the bit size of the field
+        let (fixed, fixed_bits) = match &receiver.fixed {
+            Some(fixed) => match parse_fixed_point(&fixed.value()) {
+                Some((signed, int_bits, frac_bits)) => {
+                    let bit_size = proc_macro2::Literal::usize_unsuffixed(int_bits + frac_bits);
+                    (
+                        Some(quote! {
+                            deku::ctx::FixedPoint {
+                                signed: #signed,
+                                int_bits: #int_bits,
+                                frac_bits: #frac_bits,
+                            }
+                        }),
+                        Some(quote! { #bit_size }),
+                    )
+                }
+                // treat as variable, possibly from `ctx`
+                None => (
+                    Some(
+                        fixed
+                            .parse::<TokenStream>()
+                            .map_err(|e| e.to_compile_error())?,
+                    ),
+                    None,
+                ),
+            },
+            None => (None, None),
+        };
+
+        let bits = match (receiver.bits, receiver.bytes) {
+            (_, Some(bytes)) => Some(gen_bit_size(bytes, 8)),
+            (Some(bits), None) => Some(gen_bit_size(bits, 1)),
+            (None, None) => fixed_bits,
+        };
 
         let default = receiver.default.unwrap_or(quote! { Default::default() });
 
@@ -236,23 +930,79 @@ impl FieldData {
             .map_err(|e| e.to_compile_error())?;
 
         Ok(Self {
+            vis: receiver.vis,
             ident: receiver.ident,
             ty: receiver.ty,
+            doc: field_doc(&receiver.attrs),
             endian: receiver.endian,
+            bit_order: receiver.bit_order,
+            encoding: receiver.encoding,
+            pad: receiver.pad,
             bits,
             count: receiver.count,
+            bytes_read: receiver.bytes_read,
+            bits_read: receiver.bits_read,
+            read_all: receiver.read_all,
+            prefix: receiver.prefix,
+            bcd: receiver.bcd.map(|bcd| match bcd {
+                Override::Inherit => syn::LitStr::new("big", proc_macro2::Span::call_site()),
+                Override::Explicit(order) => order,
+            }),
+            fixed,
+            sign_magnitude: receiver
+                .signed
+                .as_ref()
+                .is_some_and(|signed| signed.value() == "magnitude"),
+            strict_bool: receiver.strict_bool,
             map: receiver.map,
             ctx,
             update: receiver.update,
+            len_of: receiver.len_of,
+            len_offset: receiver.len_offset,
             reader: receiver.reader,
             writer: receiver.writer,
+            with: receiver.with,
+            map_write: receiver.map_write,
+            normalize: receiver.normalize,
+            read_as: receiver.read_as,
             skip: receiver.skip,
+            skip_read: receiver.skip_read,
+            skip_write: receiver.skip_write,
+            id_field: receiver.id_field,
+            default_if_eof,
+            peek: receiver.peek,
             default,
             cond: receiver.cond,
+            write_cond: receiver.write_cond,
+            assert: receiver.assert,
+            assert_eq: receiver.assert_eq,
+            pad_bits_before: receiver.pad_bits_before,
+            pad_bytes_before: receiver.pad_bytes_before,
+            pad_bits_after: receiver.pad_bits_after,
+            pad_bytes_after: receiver.pad_bytes_after,
+            align_bits: receiver.align_bits,
+            align_bytes: receiver.align_bytes,
+            align_before: receiver.align_before,
+            align_after: receiver.align_after,
+            temp: receiver.temp,
+            temp_value: receiver.temp_value,
+            seek_from_current: receiver.seek_from_current,
+            seek_rewind: receiver.seek_rewind,
+            offset: receiver.offset,
+            limit_bytes: receiver.limit_bytes,
+            process: receiver.process,
+            process_write: receiver.process_write,
+            checksum: receiver.checksum,
+            verify: receiver.verify,
+            sign: receiver.sign,
+            over: receiver.over,
         })
     }
 
-    fn validate(receiver: &DekuFieldReceiver) -> Result<(), (proc_macro2::Span, &str)> {
+    fn validate(
+        receiver: &DekuFieldReceiver,
+        default_if_eof: bool,
+    ) -> Result<(), (proc_macro2::Span, &str)> {
         // Validate either `bits` or `bytes` is specified
         if receiver.bits.is_some() && receiver.bytes.is_some() {
             /*
@@ -273,8 +1023,390 @@ impl FieldData {
             ));
         }
 
+        // Validate only one of `count`, `bytes_read`, `bits_read` or `read_all` is specified
+        let container_sizes = [
+            receiver.count.as_ref(),
+            receiver.bytes_read.as_ref(),
+            receiver.bits_read.as_ref(),
+        ];
+        let container_sizes_len =
+            container_sizes.iter().flatten().count() + usize::from(receiver.read_all);
+        if container_sizes_len > 1 {
+            return Err((
+                container_sizes.iter().flatten().next().span(),
+                "conflicting: only one of `count`, `bytes_read`, `bits_read` or `read_all` can be specified on field",
+            ));
+        }
+
+        // Validate `prefix` is the only size of the field
+        if receiver.prefix.is_some()
+            && (receiver.bits.is_some()
+                || receiver.bytes.is_some()
+                || container_sizes.iter().any(Option::is_some)
+                || receiver.read_all
+                || receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some())
+        {
+            return Err((
+                receiver.prefix.span(),
+                "conflicting: `prefix` specified on field with `bits`, `bytes`, a container size, `reader`, `writer` or `with`",
+            ));
+        }
+
+        // Validate `bcd` is read and written by the codec only
+        if receiver.bcd.is_some()
+            && (container_sizes.iter().any(Option::is_some)
+                || receiver.read_all
+                || receiver.prefix.is_some()
+                || receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some()
+                || receiver.ctx.is_some()
+                || receiver.bit_order.is_some()
+                || receiver.encoding.is_some()
+                || receiver.pad.is_some())
+        {
+            let span = match &receiver.bcd {
+                Some(Override::Explicit(order)) => order.span(),
+                _ => receiver.ty.span(),
+            };
+            return Err((
+                span,
+                "conflicting: `bcd` specified on field with a container size, `prefix`, `reader`, `writer`, `with`, `ctx`, `bit_order`, `encoding` or `pad`",
+            ));
+        }
+
+        // Validate `fixed` is the only size of the field, and a valid format if given as `Qm.n`
+        if let Some(fixed) = &receiver.fixed {
+            if receiver.bits.is_some()
+                || receiver.bytes.is_some()
+                || container_sizes.iter().any(Option::is_some)
+                || receiver.read_all
+                || receiver.prefix.is_some()
+                || receiver.bcd.is_some()
+                || receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some()
+                || receiver.ctx.is_some()
+                || receiver.encoding.is_some()
+                || receiver.pad.is_some()
+            {
+                return Err((
+                    fixed.span(),
+                    "conflicting: `fixed` specified on field with `bits`, `bytes`, a container size, `prefix`, `bcd`, `reader`, `writer`, `with`, `ctx`, `encoding` or `pad`",
+                ));
+            }
+
+            let format = fixed.value();
+            let is_literal = format
+                .strip_prefix("UQ")
+                .or_else(|| format.strip_prefix('Q'))
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+            if is_literal && parse_fixed_point(&format).is_none() {
+                return Err((
+                    fixed.span(),
+                    "invalid `fixed` format: expected `Qm.n` or `UQm.n` of 1 to 64 bits, with `m` at least 1 if signed",
+                ));
+            }
+        }
+
+        // Validate `signed` is a known representation, read and written by the codec only
+        if let Some(signed) = &receiver.signed {
+            if !["twos_complement", "magnitude"].contains(&signed.value().as_str()) {
+                return Err((
+                    signed.span(),
+                    "invalid `signed` representation: expected `twos_complement` or `magnitude`",
+                ));
+            }
+
+            if container_sizes.iter().any(Option::is_some)
+                || receiver.read_all
+                || receiver.prefix.is_some()
+                || receiver.bcd.is_some()
+                || receiver.fixed.is_some()
+                || receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some()
+                || receiver.ctx.is_some()
+                || receiver.bit_order.is_some()
+                || receiver.encoding.is_some()
+                || receiver.pad.is_some()
+            {
+                return Err((
+                    signed.span(),
+                    "conflicting: `signed` specified on field with a container size, `prefix`, `bcd`, `fixed`, `reader`, `writer`, `with`, `ctx`, `bit_order`, `encoding` or `pad`",
+                ));
+            }
+        }
+
+        // Validate `strict_bool` is read by the codec only
+        if receiver.strict_bool
+            && (container_sizes.iter().any(Option::is_some)
+                || receiver.read_all
+                || receiver.prefix.is_some()
+                || receiver.bcd.is_some()
+                || receiver.fixed.is_some()
+                || receiver.signed.is_some()
+                || receiver.reader.is_some()
+                || receiver.with.is_some()
+                || receiver.encoding.is_some()
+                || receiver.pad.is_some())
+        {
+            return Err((
+                receiver.ty.span(),
+                "conflicting: `strict_bool` specified on field with a container size, `prefix`, `bcd`, `fixed`, `signed`, `reader`, `with`, `encoding` or `pad`",
+            ));
+        }
+
+        // Validate usage of `with` attribute
+        if receiver.with.is_some()
+            && (receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || container_sizes.iter().any(Option::is_some)
+                || receiver.read_all)
+        {
+            return Err((
+                receiver.with.span(),
+                "conflicting: `with` specified on field with `reader`, `writer` or a container size",
+            ));
+        }
+
+        // Validate usage of `map_write` attribute
+        if receiver.map_write.is_some()
+            && (receiver.writer.is_some()
+                || receiver.normalize.is_some()
+                || receiver.with.is_some())
+        {
+            return Err((
+                receiver.map_write.span(),
+                "conflicting: `map_write` specified on field with `writer`, `normalize` or `with`",
+            ));
+        }
+
+        // Validate usage of `read_as` attribute
+        if receiver.read_as.is_some()
+            && (receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some()
+                || receiver.map.is_some()
+                || receiver.map_write.is_some()
+                || receiver.normalize.is_some())
+        {
+            return Err((
+                receiver.read_as.span(),
+                "conflicting: `read_as` specified on field with `reader`, `writer`, `with`, `map`, `map_write` or `normalize`",
+            ));
+        }
+
+        // Validate usage of `normalize` attribute
+        if receiver.normalize.is_some() && receiver.writer.is_some() {
+            return Err((
+                receiver.normalize.span(),
+                "conflicting: both `writer` and `normalize` specified on field",
+            ));
+        }
+
+        // Validate usage of `len_of` attribute
+        if receiver.len_of.is_some() && receiver.update.is_some() {
+            return Err((
+                receiver.len_of.span(),
+                "conflicting: both `update` and `len_of` specified on field",
+            ));
+        }
+        if receiver.len_offset.is_some() && receiver.len_of.is_none() {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.len_offset.span(),
+                "`len_offset` must be used with `len_of`",
+            ));
+        }
+
+        // Validate either `pad_bits_before` or `pad_bytes_before` is specified
+        if receiver.pad_bits_before.is_some() && receiver.pad_bytes_before.is_some() {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.pad_bits_before.span(),
+                "conflicting: both `pad_bits_before` and `pad_bytes_before` specified on field",
+            ));
+        }
+
+        // Validate either `pad_bits_after` or `pad_bytes_after` is specified
+        if receiver.pad_bits_after.is_some() && receiver.pad_bytes_after.is_some() {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.pad_bits_after.span(),
+                "conflicting: both `pad_bits_after` and `pad_bytes_after` specified on field",
+            ));
+        }
+
+        // Validate usage of `temp` attribute
+        if receiver.temp_value.is_some() && !receiver.temp {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.temp_value.span(),
+                "`temp_value` must be used with `temp`",
+            ));
+        }
+        if receiver.temp && receiver.ident.is_none() {
+            return Err((receiver.ty.span(), "`temp` only supported on named fields"));
+        }
+        if receiver.temp && (receiver.update.is_some() || receiver.len_of.is_some()) {
+            return Err((
+                receiver.ty.span(),
+                "conflicting: `temp` field can't be updated, use `temp_value`",
+            ));
+        }
+
+        // Validate either `align_bits` or `align_bytes` is specified
+        if receiver.align_bits.is_some() && receiver.align_bytes.is_some() {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.align_bits.span(),
+                "conflicting: both `align_bits` and `align_bytes` specified on field",
+            ));
+        }
+
+        // Validate `offset` isn't combined with attributes moving the position
+        if receiver.offset.is_some()
+            && (receiver.seek_from_current.is_some()
+                || receiver.seek_rewind
+                || receiver.pad_bits_before.is_some()
+                || receiver.pad_bytes_before.is_some()
+                || receiver.pad_bits_after.is_some()
+                || receiver.pad_bytes_after.is_some()
+                || receiver.align_bits.is_some()
+                || receiver.align_bytes.is_some())
+        {
+            return Err((
+                receiver.offset.span(),
+                "conflicting: `offset` specified on field with `seek_*`, `pad_*` or `align_*`",
+            ));
+        }
+
+        // Validate either `seek_from_current` or `seek_rewind` is specified
+        if receiver.seek_from_current.is_some() && receiver.seek_rewind {
+            // FIXME: Same issue with `receiver.bits.span()` see above.
+            return Err((
+                receiver.seek_from_current.span(),
+                "conflicting: both `seek_from_current` and `seek_rewind` specified on field",
+            ));
+        }
+
+        // Validate usage of `skip_read` and `skip_write` attributes
+        if receiver.skip && (receiver.skip_read || receiver.skip_write) {
+            return Err((
+                receiver.ty.span(),
+                "conflicting: `skip` specified on field with `skip_read` or `skip_write`",
+            ));
+        }
+
+        // Validate `peek` is used on a field which is read
+        if receiver.peek && (receiver.skip || receiver.skip_read || receiver.id_field) {
+            return Err((
+                receiver.ty.span(),
+                "conflicting: `peek` specified on field with `skip`, `skip_read` or `id_field`",
+            ));
+        }
+
+        // Validate `limit_bytes` is used on a field read and written in place
+        if receiver.limit_bytes.is_some() && (receiver.peek || receiver.offset.is_some()) {
+            return Err((
+                receiver.limit_bytes.span(),
+                "conflicting: `limit_bytes` specified on field with `peek` or `offset`",
+            ));
+        }
+
+        // Validate `pad` is used on a fixed-width field
+        if receiver.pad.is_some() && receiver.bits.is_none() && receiver.bytes.is_none() {
+            return Err((
+                receiver.pad.span(),
+                "`pad` must be used with `bits` or `bytes`",
+            ));
+        }
+
+        // Validate `process_write` is used with `process`
+        if receiver.process_write.is_some() && receiver.process.is_none() {
+            return Err((
+                receiver.process_write.span(),
+                "`process_write` must be used with `process`",
+            ));
+        }
+
+        // Validate `over` is used with `checksum`, `verify` or `sign`
+        if receiver.over.is_some()
+            && receiver.checksum.is_none()
+            && receiver.verify.is_none()
+            && receiver.sign.is_none()
+        {
+            return Err((
+                receiver.over.span(),
+                "`over` must be used with `checksum`, `verify` or `sign`",
+            ));
+        }
+
+        // Validate `checksum` isn't used with `verify` or `sign`
+        if receiver.checksum.is_some() && (receiver.verify.is_some() || receiver.sign.is_some()) {
+            return Err((
+                receiver.checksum.span(),
+                "conflicting: `checksum` specified on field with `verify` or `sign`",
+            ));
+        }
+
+        // Validate `verify` and `sign` are used on a field read and written in place
+        if (receiver.verify.is_some() || receiver.sign.is_some())
+            && (receiver.skip
+                || receiver.skip_read
+                || receiver.skip_write
+                || receiver.cond.is_some()
+                || receiver.write_cond.is_some()
+                || receiver.peek
+                || receiver.offset.is_some())
+        {
+            return Err((
+                receiver.verify.as_ref().or(receiver.sign.as_ref()).span(),
+                "conflicting: `verify` or `sign` specified on field which isn't read and written in place",
+            ));
+        }
+
+        // Validate `checksum` is used on a field read and written in place
+        if receiver.checksum.is_some()
+            && (receiver.skip
+                || receiver.skip_read
+                || receiver.skip_write
+                || receiver.cond.is_some()
+                || receiver.write_cond.is_some()
+                || receiver.peek
+                || receiver.offset.is_some())
+        {
+            return Err((
+                receiver.checksum.span(),
+                "conflicting: `checksum` specified on field which isn't read and written in place",
+            ));
+        }
+
+        // Validate `id_field` is read and written as the variant id only
+        if receiver.id_field
+            && (receiver.skip
+                || receiver.skip_read
+                || receiver.skip_write
+                || receiver.cond.is_some()
+                || receiver.reader.is_some()
+                || receiver.writer.is_some()
+                || receiver.with.is_some()
+                || receiver.map.is_some()
+                || receiver.temp)
+        {
+            return Err((
+                receiver.ty.span(),
+                "conflicting: `id_field` specified on field with custom reading or writing",
+            ));
+        }
+
         // Validate usage of `default` attribute
-        if receiver.default.is_some() && (!receiver.skip && receiver.cond.is_none()) {
+        if receiver.default.is_some()
+            && (!receiver.skip && !receiver.skip_read && receiver.cond.is_none() && !default_if_eof)
+        {
             // FIXME: Same issue with `receiver.bits.span()` see above.
             return Err((
                 receiver.default.span(),
@@ -285,6 +1417,12 @@ impl FieldData {
         Ok(())
     }
 
+    /// Whether the field is computed over the `over` bytes of its container, with `checksum`,
+    /// `verify` or `sign`
+    fn is_over_bytes(&self) -> bool {
+        self.checksum.is_some() || self.verify.is_some() || self.sign.is_some()
+    }
+
     /// Get ident of the field
     /// `index` is provided in the case of un-named structs
     /// `prefix` is true in the case of variable declarations, false if original field is desired
@@ -314,7 +1452,10 @@ struct VariantData {
 }
 
 impl VariantData {
-    fn from_receiver(receiver: DekuVariantReceiver) -> Result<Self, TokenStream> {
+    fn from_receiver(
+        receiver: DekuVariantReceiver,
+        default_if_eof: bool,
+    ) -> Result<Self, TokenStream> {
         VariantData::validate(&receiver)
             .map_err(|(span, msg)| syn::Error::new(span, msg).to_compile_error())?;
 
@@ -324,7 +1465,7 @@ impl VariantData {
                 .fields
                 .fields
                 .into_iter()
-                .map(FieldData::from_receiver)
+                .map(|field| FieldData::from_receiver(field, default_if_eof))
                 .collect::<Result<Vec<_>, _>>()?,
         };
 
@@ -349,23 +1490,45 @@ impl VariantData {
             ));
         }
 
+        // Validate `id_field` is used at most once, on a variant which doesn't consume its `id`
+        let mut id_fields = receiver.fields.iter().filter(|f| f.id_field);
+        if let Some(id_field) = id_fields.next() {
+            if receiver.id.is_some() {
+                return Err((
+                    id_field.ty.span(),
+                    "`id_field` only supported on `id_pat` or catch-all variants",
+                ));
+            }
+            if let Some(id_field) = id_fields.next() {
+                return Err((
+                    id_field.ty.span(),
+                    "conflicting: `id_field` specified on more than one field",
+                ));
+            }
+        }
+
         Ok(())
     }
 }
 
 /// Receiver for the top-level struct or enum
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(deku), supports(struct_any, enum_any))]
+#[darling(attributes(deku), forward_attrs(repr), supports(struct_any, enum_any))]
 struct DekuReceiver {
     vis: syn::Visibility,
     ident: syn::Ident,
     generics: syn::Generics,
+    attrs: Vec<syn::Attribute>,
     data: ast::Data<DekuVariantReceiver, DekuFieldReceiver>,
 
     /// Endianness for all fields
     #[darling(default)]
     endian: Option<syn::LitStr>,
 
+    /// Bit order of the bytes of the container, and for all fields
+    #[darling(default)]
+    bit_order: Option<syn::LitStr>,
+
     /// top-level context, argument list
     // TODO: The type of it should be
     //       `syn::punctuated::Punctuated<syn::FnArg, syn::token::Comma>`
@@ -395,6 +1558,213 @@ struct DekuReceiver {
     /// enum only: byte size of the enum `id`
     #[darling(default)]
     bytes: Option<usize>,
+
+    /// struct only: name of the generated owned struct
+    #[darling(default)]
+    owned: Option<syn::Ident>,
+
+    /// struct only: derives applied to the generated owned struct
+    // TODO: The type of it should be
+    //       `syn::punctuated::Punctuated<syn::Path, syn::token::Comma>`
+    //       https://github.com/TedDriggs/darling/pull/98
+    #[darling(default)]
+    owned_derive: Option<syn::LitStr>,
+
+    /// struct only: generate an accessor-based view of a `#[repr(C)]` struct
+    #[darling(default)]
+    view: bool,
+
+    /// generate the `WIRE_FORMAT` table of the fields
+    #[darling(default)]
+    wire_doc: bool,
+
+    /// require an `endian` on the multi-byte fields instead of the system endianness
+    #[darling(default)]
+    explicit_endian: bool,
+
+    /// struct only: function computing the checksum held by the last field
+    #[darling(default, map = "option_as_tokenstream")]
+    trailer_checksum: Option<TokenStream>,
+
+    /// struct only: type of the trailer held by the last field, read from the end of the input
+    #[darling(default, map = "option_as_tokenstream")]
+    trailer: Option<TokenStream>,
+
+    /// bytes read and verified before the first field, and written back
+    #[darling(default)]
+    magic: Option<syn::LitByteStr>,
+
+    /// dump the generated reader/writer at compile time
+    #[darling(default)]
+    debug: bool,
+
+    /// fields are set to their `default` if the input ends before them
+    #[darling(default)]
+    default_if_eof: bool,
+
+    /// `update` a clone of the container before writing it
+    #[darling(default)]
+    auto_update: bool,
+
+    /// condition on the container, checked after it's read and before it's written
+    #[darling(default)]
+    assert: Option<syn::LitStr>,
+}
+
+/// Receiver for the spec of `#[deku_reader(..)]` and `#[deku_writer(..)]`
+#[derive(Debug, FromMeta)]
+struct CodecReceiver {
+    /// type read or written
+    #[darling(rename = "type")]
+    ty: syn::LitStr,
+
+    /// endianness of the value
+    #[darling(default)]
+    endian: Option<syn::LitStr>,
+
+    /// bit order of the value
+    #[darling(default)]
+    bit_order: Option<syn::LitStr>,
+
+    /// bit size of the value
+    #[darling(default)]
+    bits: Option<usize>,
+
+    /// byte size of the value
+    #[darling(default)]
+    bytes: Option<usize>,
+
+    /// context passed to the value
+    #[darling(default)]
+    ctx: Option<syn::LitStr>,
+}
+
+impl CodecReceiver {
+    /// Parse the spec of the attribute, and the function it is applied to
+    fn parse(
+        attr: proc_macro::TokenStream,
+        item: proc_macro::TokenStream,
+    ) -> Result<(Self, syn::ItemFn), TokenStream> {
+        let args: syn::AttributeArgs = syn::parse::Parser::parse(
+            Punctuated::<syn::NestedMeta, syn::token::Comma>::parse_terminated,
+            attr,
+        )
+        .map_err(|e| e.to_compile_error())?
+        .into_iter()
+        .collect();
+        let func = syn::parse::<syn::ItemFn>(item).map_err(|e| e.to_compile_error())?;
+        let spec = CodecReceiver::from_list(&args).map_err(|e| e.write_errors())?;
+
+        if spec.bits.is_some() && spec.bytes.is_some() {
+            return Err(syn::Error::new(
+                spec.ty.span(),
+                "conflicting: both `bits` and `bytes` specified",
+            )
+            .to_compile_error());
+        }
+
+        Ok((spec, func))
+    }
+}
+
+/// Check if the attributes contain `#[repr(C)]`
+fn is_repr_c(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => path.is_ident("C"),
+            _ => false,
+        }),
+        _ => false,
+    })
+}
+
+/// Check if the field has a size known at compile time, usable in a view
+fn is_view_field(field: &DekuFieldReceiver) -> bool {
+    field.count.is_none()
+        && field.bytes_read.is_none()
+        && field.bits_read.is_none()
+        && !field.read_all
+        && field.prefix.is_none()
+        && field.bcd.is_none()
+        && field.fixed.is_none()
+        && field.signed.is_none()
+        && !field.strict_bool
+        && field.map.is_none()
+        && field.map_write.is_none()
+        && field.read_as.is_none()
+        && field.ctx.is_none()
+        && field.reader.is_none()
+        && field.writer.is_none()
+        && field.with.is_none()
+        && !field.skip
+        && !field.skip_read
+        && !field.skip_write
+        && !field.default_if_eof
+        && !field.peek
+        && field.cond.is_none()
+        && field.write_cond.is_none()
+        && field.pad_bits_before.is_none()
+        && field.pad_bytes_before.is_none()
+        && field.pad_bits_after.is_none()
+        && field.pad_bytes_after.is_none()
+        && field.align_bits.is_none()
+        && field.align_bytes.is_none()
+        && !field.align_before
+        && !field.align_after
+        && !field.temp
+        && field.seek_from_current.is_none()
+        && !field.seek_rewind
+        && field.offset.is_none()
+        && field.limit_bytes.is_none()
+        && field.process.is_none()
+        && field.process_write.is_none()
+        && field.checksum.is_none()
+        && field.verify.is_none()
+        && field.sign.is_none()
+        && field.bit_order.is_none()
+        && field.encoding.is_none()
+        && field.pad.is_none()
+}
+
+/// Parse a fixed-point format `Qm.n` or `UQm.n` of 1 to 64 bits into `(signed, m, n)`, the same
+/// as `deku::ctx::FixedPoint::from_str`
+fn parse_fixed_point(format: &str) -> Option<(bool, usize, usize)> {
+    let (signed, format) = match (format.strip_prefix("UQ"), format.strip_prefix('Q')) {
+        (Some(format), _) => (false, format),
+        (None, Some(format)) => (true, format),
+        (None, None) => return None,
+    };
+    let mut parts = format.splitn(2, '.');
+    let int_bits: usize = parts.next()?.parse().ok()?;
+    let frac_bits: usize = parts.next()?.parse().ok()?;
+
+    let bit_size = int_bits.checked_add(frac_bits)?;
+    if bit_size == 0 || bit_size > 64 || (signed && int_bits == 0) {
+        return None;
+    }
+
+    Some((signed, int_bits, frac_bits))
+}
+
+/// Join the lines of the doc comment of a field
+fn field_doc(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(doc),
+                ..
+            })) if path.is_ident("doc") => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
 }
 
 /// Parse a TokenStream from an Option<LitStr>
@@ -405,6 +1775,75 @@ fn option_as_tokenstream(input: Option<syn::LitStr>) -> Option<TokenStream> {
     })
 }
 
+/// Parse a TokenStream from an Option<Lit>, either an integer or a string of tokens
+fn option_lit_as_tokenstream(input: Option<syn::Lit>) -> Option<TokenStream> {
+    input.map(|v| match v {
+        syn::Lit::Str(v) => v
+            .parse::<TokenStream>()
+            .expect("could not parse token stream"),
+        v => quote! { #v },
+    })
+}
+
+/// Check if the tokens are an integer literal
+fn is_int_literal(tokens: &TokenStream) -> bool {
+    syn::parse2::<syn::LitInt>(tokens.clone()).is_ok()
+}
+
+/// Check if the type has a multi-byte integer or float, such as `u16`, `Vec<u32>` or `[f32; 2]`,
+/// which is read with an endian
+fn is_multi_byte_type(ty: &syn::Type) -> bool {
+    const MULTI_BYTE: &[&str] = &[
+        "u16", "u32", "u64", "u128", "usize", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+    ];
+
+    match ty {
+        syn::Type::Path(path) => {
+            let is_multi_byte = path.path.segments.last().is_some_and(|segment| {
+                MULTI_BYTE
+                    .iter()
+                    .any(|multi_byte| segment.ident == multi_byte)
+            });
+
+            is_multi_byte
+                || path.path.segments.iter().any(|segment| {
+                    match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| {
+                        matches!(arg, syn::GenericArgument::Type(ty) if is_multi_byte_type(ty))
+                    }),
+                    _ => false,
+                }
+                })
+        }
+        syn::Type::Array(array) => is_multi_byte_type(&array.elem),
+        syn::Type::Slice(slice) => is_multi_byte_type(&slice.elem),
+        syn::Type::Reference(reference) => is_multi_byte_type(&reference.elem),
+        syn::Type::Paren(paren) => is_multi_byte_type(&paren.elem),
+        syn::Type::Group(group) => is_multi_byte_type(&group.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().any(is_multi_byte_type),
+        _ => false,
+    }
+}
+
+/// Generate a bit size from a number of `unit` bits
+///
+/// An integer literal stays a constant, so the size is known at compile time. Other expressions
+/// are converted to `usize` at runtime, a number of bytes overflowing the bits is an error.
+fn gen_bit_size(size: TokenStream, unit: usize) -> TokenStream {
+    if let Ok(lit) = syn::parse2::<syn::LitInt>(size.clone()) {
+        if let Ok(n) = lit.base10_parse::<usize>() {
+            let bits = syn::LitInt::new(&(n * unit).to_string(), lit.span());
+            return quote! { #bits };
+        }
+    }
+
+    match unit {
+        1 => quote! { <usize as core::convert::TryFrom<_>>::try_from(#size)? },
+        8 => gen_bytes_to_bits(&size, "bytes"),
+        _ => unreachable!("bit size of {} bits units", unit),
+    }
+}
+
 /// Generate field name which supports both un-named/named structs/enums
 /// `ident` is Some if the container has named fields
 /// `index` is the numerical index of the current field used in un-named containers
@@ -425,28 +1864,74 @@ fn gen_field_ident<T: ToString>(ident: Option<T>, index: usize, prefix: bool) ->
 
 /// Receiver for the field-level attributes inside a struct/enum variant
 #[derive(Debug, FromField)]
-#[darling(attributes(deku))]
+#[darling(attributes(deku), forward_attrs(doc))]
 struct DekuFieldReceiver {
+    vis: syn::Visibility,
     ident: Option<syn::Ident>,
     ty: syn::Type,
+    attrs: Vec<syn::Attribute>,
 
     /// Endianness for the field
     #[darling(default)]
     endian: Option<syn::LitStr>,
 
-    /// field bit size
+    /// Bit order for the field
     #[darling(default)]
-    bits: Option<usize>,
+    bit_order: Option<syn::LitStr>,
 
-    /// field byte size
+    /// Text encoding of the field
     #[darling(default)]
-    bytes: Option<usize>,
+    encoding: Option<syn::LitStr>,
+
+    /// Code unit padding a fixed-width string field
+    #[darling(default, map = "option_as_tokenstream")]
+    pad: Option<TokenStream>,
+
+    /// field bit size
+    #[darling(default, map = "option_lit_as_tokenstream")]
+    bits: Option<TokenStream>,
+
+    /// field byte size
+    #[darling(default, map = "option_lit_as_tokenstream")]
+    bytes: Option<TokenStream>,
 
     /// tokens providing the length of the container
     #[darling(default, map = "option_as_tokenstream")]
     count: Option<TokenStream>,
 
-    /// apply a function to the field after it's read
+    /// tokens providing the number of bytes of the container
+    #[darling(default, map = "option_as_tokenstream")]
+    bytes_read: Option<TokenStream>,
+
+    /// tokens providing the number of bits of the container
+    #[darling(default, map = "option_as_tokenstream")]
+    bits_read: Option<TokenStream>,
+
+    /// read the elements of the container until the end of the input
+    #[darling(default)]
+    read_all: bool,
+
+    /// integer type of the byte length written before the field
+    #[darling(default, map = "option_as_tokenstream")]
+    prefix: Option<TokenStream>,
+
+    /// packed BCD digits of the field, with an optional nibble order
+    #[darling(default)]
+    bcd: Option<Override<syn::LitStr>>,
+
+    /// fixed-point format of the field, `Qm.n` or `UQm.n`
+    #[darling(default)]
+    fixed: Option<syn::LitStr>,
+
+    /// representation of the signed integer field, `twos_complement` or `magnitude`
+    #[darling(default)]
+    signed: Option<syn::LitStr>,
+
+    /// the `bool` field must be read as `0` or `1`
+    #[darling(default)]
+    strict_bool: bool,
+
+    /// apply a function to the field after it's read
     #[darling(default, map = "option_as_tokenstream")]
     map: Option<TokenStream>,
 
@@ -461,6 +1946,14 @@ struct DekuFieldReceiver {
     #[darling(default, map = "option_as_tokenstream")]
     update: Option<TokenStream>,
 
+    /// name of the field whose byte length is stored in this field when updating
+    #[darling(default)]
+    len_of: Option<syn::LitStr>,
+
+    /// added to the byte length of `len_of`
+    #[darling(default, map = "option_as_tokenstream")]
+    len_offset: Option<TokenStream>,
+
     /// custom field reader code
     #[darling(default, map = "option_as_tokenstream")]
     reader: Option<TokenStream>,
@@ -469,10 +1962,46 @@ struct DekuFieldReceiver {
     #[darling(default, map = "option_as_tokenstream")]
     writer: Option<TokenStream>,
 
+    /// path to a module providing the `read` and `write` functions of the field
+    #[darling(default, map = "option_as_tokenstream")]
+    with: Option<TokenStream>,
+
+    /// function applied to the field before it's written, the inverse of `map`
+    #[darling(default, map = "option_as_tokenstream")]
+    map_write: Option<TokenStream>,
+
+    /// function applied to the field before it's written
+    #[darling(default, map = "option_as_tokenstream")]
+    normalize: Option<TokenStream>,
+
+    /// type the field is read and written as, converted with `TryFrom`
+    #[darling(default, map = "option_as_tokenstream")]
+    read_as: Option<TokenStream>,
+
     /// skip field reading/writing
     #[darling(default)]
     skip: bool,
 
+    /// skip field reading, the field is set to `default`
+    #[darling(default)]
+    skip_read: bool,
+
+    /// skip field writing
+    #[darling(default)]
+    skip_write: bool,
+
+    /// field set to the enum variant id, which is consumed and written in place of the field
+    #[darling(default)]
+    id_field: bool,
+
+    /// field set to `default` if the input ends before it
+    #[darling(default)]
+    default_if_eof: bool,
+
+    /// read the field without consuming its bits, it isn't written
+    #[darling(default)]
+    peek: bool,
+
     /// default value code when used with skip
     #[darling(default, map = "option_as_tokenstream")]
     default: Option<TokenStream>,
@@ -480,6 +2009,98 @@ struct DekuFieldReceiver {
     /// condition to parse field
     #[darling(default, map = "option_as_tokenstream")]
     cond: Option<TokenStream>,
+
+    /// condition to write field
+    #[darling(default, map = "option_as_tokenstream")]
+    write_cond: Option<TokenStream>,
+
+    /// condition the field must satisfy when read and written
+    #[darling(default)]
+    assert: Option<syn::LitStr>,
+
+    /// value the field must equal when read and written
+    #[darling(default, map = "option_as_tokenstream")]
+    assert_eq: Option<TokenStream>,
+
+    /// number of bits of padding before the field
+    #[darling(default, map = "option_as_tokenstream")]
+    pad_bits_before: Option<TokenStream>,
+
+    /// number of bytes of padding before the field
+    #[darling(default, map = "option_as_tokenstream")]
+    pad_bytes_before: Option<TokenStream>,
+
+    /// number of bits of padding after the field
+    #[darling(default, map = "option_as_tokenstream")]
+    pad_bits_after: Option<TokenStream>,
+
+    /// number of bytes of padding after the field
+    #[darling(default, map = "option_as_tokenstream")]
+    pad_bytes_after: Option<TokenStream>,
+
+    /// bit alignment of the field within the container
+    #[darling(default, map = "option_as_tokenstream")]
+    align_bits: Option<TokenStream>,
+
+    /// byte alignment of the field within the container
+    #[darling(default, map = "option_as_tokenstream")]
+    align_bytes: Option<TokenStream>,
+
+    /// check the field starts at a byte boundary of the container
+    #[darling(default)]
+    align_before: bool,
+
+    /// check the field ends at a byte boundary of the container
+    #[darling(default)]
+    align_after: bool,
+
+    /// field only read, removed from the struct by `deku_derive`
+    #[darling(default)]
+    temp: bool,
+
+    /// value written for a `temp` field
+    #[darling(default, map = "option_as_tokenstream")]
+    temp_value: Option<TokenStream>,
+
+    /// number of bytes to move the position by before the field
+    #[darling(default, map = "option_as_tokenstream")]
+    seek_from_current: Option<TokenStream>,
+
+    /// move the position to the start of the container before the field
+    #[darling(default)]
+    seek_rewind: bool,
+
+    /// absolute byte offset of the field within the input of the container
+    #[darling(default, map = "option_as_tokenstream")]
+    offset: Option<TokenStream>,
+
+    /// number of bytes the field is read from, and written to
+    #[darling(default, map = "option_as_tokenstream")]
+    limit_bytes: Option<TokenStream>,
+
+    /// function transforming the bytes the field is read from
+    #[darling(default, map = "option_as_tokenstream")]
+    process: Option<TokenStream>,
+
+    /// function transforming the bytes the field is written to
+    #[darling(default, map = "option_as_tokenstream")]
+    process_write: Option<TokenStream>,
+
+    /// function computing the checksum held by the field
+    #[darling(default, map = "option_as_tokenstream")]
+    checksum: Option<TokenStream>,
+
+    /// function verifying the signature held by the field
+    #[darling(default, map = "option_as_tokenstream")]
+    verify: Option<TokenStream>,
+
+    /// function computing the signature held by the field
+    #[darling(default, map = "option_as_tokenstream")]
+    sign: Option<TokenStream>,
+
+    /// range of bytes of the container the checksum or signature is computed over
+    #[darling(default, map = "option_as_tokenstream")]
+    over: Option<TokenStream>,
 }
 
 /// Receiver for the variant-level attributes inside a enum
@@ -518,6 +2139,10 @@ pub fn proc_deku_read(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         Err(err) => return err.write_errors().into(),
     };
 
+    if let Err((span, msg)) = DekuData::validate_temp(&receiver) {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
     let data = match DekuData::from_receiver(receiver) {
         Ok(data) => data,
         Err(err) => return err.into(),
@@ -538,6 +2163,10 @@ pub fn proc_deku_write(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
         Err(err) => return err.write_errors().into(),
     };
 
+    if let Err((span, msg)) = DekuData::validate_temp(&receiver) {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
     let data = match DekuData::from_receiver(receiver) {
         Ok(data) => data,
         Err(err) => return err.into(),
@@ -546,6 +2175,260 @@ pub fn proc_deku_write(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     data.emit_writer().into()
 }
 
+#[proc_macro_derive(DekuView, attributes(deku))]
+pub fn proc_deku_view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let receiver = match DekuReceiver::from_derive_input(&input) {
+        Ok(receiver) => receiver,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    if let Err((span, msg)) =
+        DekuData::validate_temp(&receiver).and_then(|_| DekuData::validate_deku_view(&receiver))
+    {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
+    let data = match DekuData::from_receiver(receiver) {
+        Ok(data) => data,
+        Err(err) => return err.into(),
+    };
+
+    data.emit_view().into()
+}
+
+#[proc_macro_derive(DekuSize, attributes(deku))]
+pub fn proc_deku_size(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let receiver = match DekuReceiver::from_derive_input(&input) {
+        Ok(receiver) => receiver,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    if let Err((span, msg)) =
+        DekuData::validate_temp(&receiver).and_then(|_| DekuData::validate_deku_size(&receiver))
+    {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
+    let data = match DekuData::from_receiver(receiver) {
+        Ok(data) => data,
+        Err(err) => return err.into(),
+    };
+
+    data.emit_size().into()
+}
+
+/// Derive the `parse` and `build` methods of a `#[pyo3::pyclass]`, reading and writing the
+/// container from Python bytes
+///
+/// The crate deriving it must depend on `pyo3`, see "Python bindings" in the documentation of deku.
+#[proc_macro_derive(DekuPython, attributes(deku))]
+pub fn proc_deku_python(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let receiver = match DekuReceiver::from_derive_input(&input) {
+        Ok(receiver) => receiver,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    if let Err((span, msg)) =
+        DekuData::validate_temp(&receiver).and_then(|_| DekuData::validate_deku_python(&receiver))
+    {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
+    let data = match DekuData::from_receiver(receiver) {
+        Ok(data) => data,
+        Err(err) => return err.into(),
+    };
+
+    data.emit_python().into()
+}
+
+/// Derive `extern "C"` functions parsing the container into an opaque handle, getting its scalar
+/// fields and serializing it
+///
+/// See "C bindings" in the documentation of deku.
+#[proc_macro_derive(DekuFfi, attributes(deku))]
+pub fn proc_deku_ffi(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = match syn::parse(input) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let receiver = match DekuReceiver::from_derive_input(&input) {
+        Ok(receiver) => receiver,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    if let Err((span, msg)) =
+        DekuData::validate_temp(&receiver).and_then(|_| DekuData::validate_deku_ffi(&receiver))
+    {
+        return syn::Error::new(span, msg).to_compile_error().into();
+    }
+
+    let data = match DekuData::from_receiver(receiver) {
+        Ok(data) => data,
+        Err(err) => return err.into(),
+    };
+
+    data.emit_ffi().into()
+}
+
+/// Derive `DekuRead` and `DekuWrite` on a struct or enum with `temp` fields, which are removed
+/// from the emitted definition
+///
+/// `#[deku_derive(DekuRead, DekuWrite)]` is used in place of `#[derive(DekuRead, DekuWrite)]`,
+/// and must come before any `#[derive(..)]`.
+#[proc_macro_attribute]
+pub fn deku_derive(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let derives = match syn::parse::Parser::parse(
+        Punctuated::<syn::Path, syn::token::Comma>::parse_terminated,
+        attr,
+    ) {
+        Ok(derives) => derives,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut input: syn::DeriveInput = match syn::parse(item) {
+        Ok(input) => input,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let receiver = match DekuReceiver::from_derive_input(&input) {
+        Ok(receiver) => receiver,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    let data = match DekuData::from_receiver(receiver) {
+        Ok(data) => data,
+        Err(err) => return err.into(),
+    };
+
+    let mut tokens = TokenStream::new();
+    for derive in derives.iter() {
+        if derive.is_ident("DekuRead") {
+            tokens.extend(data.emit_reader());
+        } else if derive.is_ident("DekuWrite") {
+            tokens.extend(data.emit_writer());
+        } else {
+            tokens.extend(
+                syn::Error::new(
+                    derive.span(),
+                    "`deku_derive` only supports `DekuRead` and `DekuWrite`",
+                )
+                .to_compile_error(),
+            );
+        }
+    }
+
+    remove_deku_attrs(&mut input);
+
+    (quote! {
+        #input
+        #tokens
+    })
+    .into()
+}
+
+/// Generate a reader for `reader = ".."` from a function converting the value read
+///
+/// `#[deku_reader(type = "u8")] fn f(value: u8, args..) -> Result<T, DekuError>` becomes
+/// `fn f(rest: &BitSlice<Msb0, u8>, args..) -> Result<(&BitSlice<Msb0, u8>, T), DekuError>`.
+/// `endian`, `bit_order`, `bits`, `bytes` and `ctx` are passed when reading the value.
+#[proc_macro_attribute]
+pub fn deku_reader(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let (spec, func) = match CodecReceiver::parse(attr, item) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.into(),
+    };
+
+    match emit_deku_reader(&spec, &func) {
+        Ok(tks) => tks.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Generate a writer for `writer = ".."` from a function converting the value to write
+///
+/// `#[deku_writer(type = "u8")] fn f(value: &T, args..) -> Result<u8, DekuError>` becomes
+/// `fn f(value: &T, args..) -> Result<BitVec<Msb0, u8>, DekuError>`.
+/// `endian`, `bit_order`, `bits`, `bytes` and `ctx` are passed when writing the value.
+#[proc_macro_attribute]
+pub fn deku_writer(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let (spec, func) = match CodecReceiver::parse(attr, item) {
+        Ok(parsed) => parsed,
+        Err(err) => return err.into(),
+    };
+
+    match emit_deku_writer(&spec, &func) {
+        Ok(tks) => tks.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Remove the `temp` fields and the `#[deku(..)]` attributes, which are only valid on a derive
+fn remove_deku_attrs(input: &mut syn::DeriveInput) {
+    fn is_deku_attr(attr: &syn::Attribute) -> bool {
+        attr.path.is_ident("deku")
+    }
+
+    fn is_temp_field(field: &syn::Field) -> bool {
+        field.attrs.iter().filter(|attr| is_deku_attr(attr)).any(|attr| {
+            matches!(attr.parse_meta(), Ok(syn::Meta::List(list)) if list.nested.iter().any(|meta| {
+                matches!(meta, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("temp"))
+            }))
+        })
+    }
+
+    fn remove_from_fields(fields: &mut syn::Fields) {
+        if let syn::Fields::Named(named) = fields {
+            named.named = std::mem::take(&mut named.named)
+                .into_pairs()
+                .filter(|pair| !is_temp_field(pair.value()))
+                .collect();
+        }
+
+        for field in fields.iter_mut() {
+            field.attrs.retain(|attr| !is_deku_attr(attr));
+        }
+    }
+
+    input.attrs.retain(|attr| !is_deku_attr(attr));
+
+    match &mut input.data {
+        syn::Data::Struct(data) => remove_from_fields(&mut data.fields),
+        syn::Data::Enum(data) => {
+            for variant in data.variants.iter_mut() {
+                variant.attrs.retain(|attr| !is_deku_attr(attr));
+                remove_from_fields(&mut variant.fields);
+            }
+        }
+        syn::Data::Union(_) => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -584,6 +2467,39 @@ mod tests {
             C { field_n: u8 },
         }"#),
 
+        // Valid owned struct
+        case::struct_owned(r#"
+        #[deku(owned = "TestOwned", owned_derive = "Debug")]
+        struct Test<'a, T> {
+            field_a: &'a [u8],
+            field_b: &'a str,
+            field_c: T,
+        }"#),
+
+        // Valid view
+        case::struct_view(r#"
+        #[deku(view, endian = "big")]
+        #[repr(C)]
+        struct Test {
+            field_a: u32,
+            #[deku(endian = "little")]
+            field_b: u16,
+            field_c: [u8; 2],
+        }"#),
+
+        // Valid magic
+        case::struct_magic(r#"
+        #[deku(magic = b"\x7FELF")]
+        struct Test {
+            field_a: u8,
+        }"#),
+        case::enum_magic(r#"
+        #[deku(magic = b"DK", type = "u8")]
+        enum Test {
+            #[deku(id = "1")]
+            A,
+        }"#),
+
         // TODO: these tests should error/warn eventually?
         // error: trying to store 9 bits in 8 bit type
         case::invalid_storage(r#"struct Test(#[deku(bits=9)] u8);"#),
@@ -601,4 +2517,90 @@ mod tests {
         res_reader.unwrap();
         res_writer.unwrap();
     }
+
+    #[rstest(input,
+        // Valid struct
+        case::struct_empty(r#"struct Test {}"#),
+        case::struct_unnamed(r#"struct Test(#[deku(bits = 4)] u8, u8);"#),
+        case::struct_all_attrs(r#"
+        #[deku(endian = "big")]
+        struct Test {
+            #[deku(bits = 4)]
+            field_a: u8,
+            #[deku(bytes = 2)]
+            field_b: u64,
+            #[deku(endian = "little")]
+            field_c: u32,
+        }"#),
+    )]
+    fn test_macro_view(input: &str) {
+        let parsed = parse_str(input).unwrap();
+
+        let receiver = DekuReceiver::from_derive_input(&parsed).unwrap();
+        DekuData::validate_deku_view(&receiver).unwrap();
+        let data = DekuData::from_receiver(receiver).unwrap();
+
+        data.emit_view_checked().unwrap();
+    }
+
+    #[rstest(
+        input,
+        case::struct_named(r#"struct Test { field_a: u8 }"#),
+        case::enum_all(
+            r#"
+        #[deku(type = "u8")]
+        enum Test {
+            #[deku(id = "1")]
+            A,
+        }"#
+        ),
+        case::struct_ctx_default(
+            r#"
+        #[deku(ctx = "a: u8", ctx_default = "1")]
+        struct Test { field_a: u8 }"#
+        )
+    )]
+    fn test_macro_python(input: &str) {
+        let parsed = parse_str(input).unwrap();
+
+        let receiver = DekuReceiver::from_derive_input(&parsed).unwrap();
+        DekuData::validate_deku_python(&receiver).unwrap();
+        let data = DekuData::from_receiver(receiver).unwrap();
+
+        let tokens = data.emit_python_checked().unwrap().to_string();
+        assert!(tokens.contains("pymethods"));
+    }
+
+    #[rstest(
+        input,
+        expected,
+        case::struct_named(
+            r#"struct DekuTest { field_a: u8, field_b: Vec<u8> }"#,
+            &["deku_test_parse", "deku_test_serialize", "deku_test_get_field_a"]
+        ),
+        case::struct_unnamed(r#"struct DekuTest(u16, [u8; 2]);"#, &["deku_test_get_0"]),
+        case::enum_all(
+            r#"
+        #[deku(type = "u8")]
+        enum DekuTest {
+            #[deku(id = "1")]
+            A(u8),
+        }"#,
+            &["deku_test_parse", "deku_test_free"]
+        )
+    )]
+    fn test_macro_ffi(input: &str, expected: &[&str]) {
+        let parsed = parse_str(input).unwrap();
+
+        let receiver = DekuReceiver::from_derive_input(&parsed).unwrap();
+        DekuData::validate_deku_ffi(&receiver).unwrap();
+        let data = DekuData::from_receiver(receiver).unwrap();
+
+        let tokens = data.emit_ffi_checked().unwrap().to_string();
+        for name in expected {
+            assert!(tokens.contains(name), "missing {}", name);
+        }
+        assert!(!tokens.contains("get_field_b"));
+        assert!(!tokens.contains("get_1"));
+    }
 }
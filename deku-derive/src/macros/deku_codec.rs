@@ -0,0 +1,210 @@
+use crate::macros::gen_field_args;
+use crate::CodecReceiver;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Emit a reader usable with `reader = ".."` from a function converting the value read
+///
+/// `fn f(value: T, args..) -> Result<U, DekuError>` becomes
+/// `fn f(rest: &BitSlice<Msb0, u8>, args..) -> Result<(&BitSlice<Msb0, u8>, U), DekuError>`,
+/// reading `value` with the `type` and field arguments of the spec.
+pub(crate) fn emit_deku_reader(
+    spec: &CodecReceiver,
+    func: &syn::ItemFn,
+) -> Result<TokenStream, syn::Error> {
+    let (value_ty, field_args) = gen_codec_args(spec)?;
+    let (value, args) = split_value_arg(func)?;
+    let output = result_ok_type(func)?;
+
+    let attrs = &func.attrs;
+    let vis = &func.vis;
+    let ident = &func.sig.ident;
+    let inner_ident = format_ident!("__deku_{}", ident);
+    let mut inner = func.clone();
+    inner.sig.ident = inner_ident.clone();
+    inner.attrs.clear();
+    inner.vis = syn::Visibility::Inherited;
+
+    let mut generics = func.sig.generics.clone();
+    generics.params.insert(0, syn::parse_quote! { '__deku_a });
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let turbofish = gen_turbofish(&func.sig.generics);
+    let arg_idents = arg_idents(&args)?;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #ident #impl_generics (
+            rest: &'__deku_a BitSlice<Msb0, u8>,
+            #(#args),*
+        ) -> Result<
+            (&'__deku_a BitSlice<Msb0, u8>, #output),
+            DekuError,
+        > #where_clause {
+            #inner
+
+            let (rest, #value) = <#value_ty as DekuRead<_>>::read(rest, (#field_args))?;
+            let value = #inner_ident #turbofish (#value, #(#arg_idents),*)?;
+            Ok((rest, value))
+        }
+    })
+}
+
+/// Emit a writer usable with `writer = ".."` from a function converting the value to write
+///
+/// `fn f(value: &T, args..) -> Result<U, DekuError>` becomes
+/// `fn f(value: &T, args..) -> Result<BitVec<Msb0, u8>, DekuError>`, writing the converted value
+/// with the `type` and field arguments of the spec.
+pub(crate) fn emit_deku_writer(
+    spec: &CodecReceiver,
+    func: &syn::ItemFn,
+) -> Result<TokenStream, syn::Error> {
+    let (value_ty, field_args) = gen_codec_args(spec)?;
+    let (value, args) = split_value_arg(func)?;
+
+    let attrs = &func.attrs;
+    let vis = &func.vis;
+    let ident = &func.sig.ident;
+    let inner_ident = format_ident!("__deku_{}", ident);
+    let mut inner = func.clone();
+    inner.sig.ident = inner_ident.clone();
+    inner.attrs.clear();
+    inner.vis = syn::Visibility::Inherited;
+
+    let generics = &func.sig.generics;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let turbofish = gen_turbofish(generics);
+    let value_arg = &func.sig.inputs[0];
+    let arg_idents = arg_idents(&args)?;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #ident #impl_generics (
+            #value_arg,
+            #(#args),*
+        ) -> Result<BitVec<Msb0, u8>, DekuError>
+        #where_clause {
+            #inner
+
+            let value: #value_ty = #inner_ident #turbofish (#value, #(#arg_idents),*)?;
+            DekuWrite::write(&value, (#field_args))
+        }
+    })
+}
+
+/// Parse the `type` of the spec and generate the arguments it is read or written with
+fn gen_codec_args(spec: &CodecReceiver) -> Result<(syn::Type, TokenStream), syn::Error> {
+    let value_ty = spec.ty.parse::<syn::Type>()?;
+    let ctx = spec
+        .ctx
+        .as_ref()
+        .map(|s| s.parse_with(syn::punctuated::Punctuated::parse_terminated))
+        .transpose()?;
+    let bits = spec
+        .bytes
+        .map(|b| b * 8)
+        .or(spec.bits)
+        .map(|n| quote! { #n });
+
+    let field_args = gen_field_args(
+        spec.endian.as_ref(),
+        bits.as_ref(),
+        spec.bit_order.as_ref(),
+        None,
+        None,
+        ctx.as_ref(),
+    )?;
+
+    Ok((value_ty, field_args))
+}
+
+/// Split the first argument, the value converted, from the extra arguments
+fn split_value_arg(func: &syn::ItemFn) -> Result<(syn::Pat, Vec<syn::FnArg>), syn::Error> {
+    let mut inputs = func.sig.inputs.iter();
+    let value = match inputs.next() {
+        Some(syn::FnArg::Typed(pat)) => match &*pat.pat {
+            syn::Pat::Ident(pat) => syn::Pat::Ident(syn::PatIdent {
+                mutability: None,
+                ..pat.clone()
+            }),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &pat.pat,
+                    "the value argument must be an identifier",
+                ))
+            }
+        },
+        Some(arg) => {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "expected the value as first argument",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new_spanned(
+                &func.sig,
+                "expected the value as first argument",
+            ))
+        }
+    };
+
+    Ok((value, inputs.cloned().collect()))
+}
+
+/// Identifiers of the extra arguments, passed on to the converting function
+fn arg_idents(args: &[syn::FnArg]) -> Result<Vec<&syn::Ident>, syn::Error> {
+    args.iter()
+        .map(|arg| match arg {
+            syn::FnArg::Typed(syn::PatType { pat, .. }) => match &**pat {
+                syn::Pat::Ident(pat) => Ok(&pat.ident),
+                _ => Err(syn::Error::new_spanned(
+                    pat,
+                    "arguments must be identifiers",
+                )),
+            },
+            syn::FnArg::Receiver(_) => Err(syn::Error::new_spanned(
+                arg,
+                "`self` is not supported, use a free-standing function",
+            )),
+        })
+        .collect()
+}
+
+/// Type `U` of a function returning `Result<U, DekuError>`
+fn result_ok_type(func: &syn::ItemFn) -> Result<&syn::Type, syn::Error> {
+    let error = || {
+        syn::Error::new_spanned(
+            &func.sig.output,
+            "expected a return type of `Result<T, DekuError>`",
+        )
+    };
+
+    let ty = match &func.sig.output {
+        syn::ReturnType::Type(_, ty) => ty,
+        syn::ReturnType::Default => return Err(error()),
+    };
+
+    let segment = match &**ty {
+        syn::Type::Path(path) => path.path.segments.last().ok_or_else(error)?,
+        _ => return Err(error()),
+    };
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Result" => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Ok(ty),
+                _ => Err(error()),
+            }
+        }
+        _ => Err(error()),
+    }
+}
+
+/// Turbofish passing the type parameters of the original function to the converting function
+fn gen_turbofish(generics: &syn::Generics) -> TokenStream {
+    let types = generics.type_params().map(|param| &param.ident);
+    if generics.type_params().next().is_none() {
+        quote! {}
+    } else {
+        quote! { ::<#(#types),*> }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{DekuData, FieldData};
+use darling::ast::Data;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Emit the `WIRE_FORMAT` table of the fields, requested with `#[deku(wire_doc)]`
+///
+/// The table is built from the attributes when the container is derived, and set as the doc
+/// comment of the constant so it shows in the generated documentation.
+pub(crate) fn emit_wire_doc(input: &DekuData) -> TokenStream {
+    let mut rows = vec![];
+
+    if let Some(magic) = &input.magic {
+        let len = magic.value().len();
+        rows.push(gen_row(
+            "magic",
+            &format!("[u8; {}]", len),
+            &(len * 8).to_string(),
+            "",
+            &format!("magic `{}`", escape(&quote! { #magic }.to_string())),
+        ));
+    }
+
+    match &input.data {
+        Data::Struct(fields) => {
+            for (i, f) in fields.iter().enumerate() {
+                if let Some(row) = gen_field_row(input, None, i, f) {
+                    rows.push(row);
+                }
+            }
+        }
+        Data::Enum(variants) => {
+            if let Some(id_type) = &input.id_type {
+                let bits = match input.bits {
+                    Some(bits) => bits.to_string(),
+                    None => type_bits(&id_type.to_string()),
+                };
+                rows.push(gen_row(
+                    "id",
+                    &id_type.to_string(),
+                    &bits,
+                    &endian(input, None),
+                    "variant id",
+                ));
+            }
+
+            for variant in variants {
+                for (i, f) in variant.fields.iter().enumerate() {
+                    if let Some(row) = gen_field_row(input, Some(&variant.ident), i, f) {
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+    }
+
+    let table = format!(
+        "| Field | Type | Bits | Endian | Description |\n|---|---|---|---|---|\n{}",
+        rows.join("")
+    );
+
+    let (imp, ty, wher) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    quote! {
+        impl #imp #ident #ty #wher {
+            #[doc = "Wire format, generated from the deku attributes\n\n"]
+            #[doc = #table]
+            pub const WIRE_FORMAT: &'static str = #table;
+        }
+    }
+}
+
+/// Row of a field on the wire, `None` for a field which isn't
+fn gen_field_row(
+    input: &DekuData,
+    variant: Option<&syn::Ident>,
+    i: usize,
+    f: &FieldData,
+) -> Option<String> {
+    // the `id_field` is the variant id, already in the table
+    if f.skip || f.id_field {
+        return None;
+    }
+
+    let name = match &f.ident {
+        Some(ident) => ident.to_string(),
+        None => i.to_string(),
+    };
+    let name = match variant {
+        Some(variant) => format!("{}::{}", variant, name),
+        None => name,
+    };
+
+    let field_type = &f.ty;
+    let field_type = quote! { #field_type }.to_string().replace(' ', "");
+
+    let bits = match &f.bits {
+        Some(bits) => match syn::parse2::<syn::LitInt>(bits.clone()) {
+            Ok(bits) => bits.to_string(),
+            Err(_) => "variable".to_string(),
+        },
+        None if f.count.is_some()
+            || f.bytes_read.is_some()
+            || f.bits_read.is_some()
+            || f.process.is_some()
+            || f.prefix.is_some() =>
+        {
+            "variable".to_string()
+        }
+        None => type_bits(&field_type),
+    };
+
+    let mut description = f.doc.clone().unwrap_or_default();
+    if let Some(cond) = &f.cond {
+        if !description.is_empty() {
+            description.push(' ');
+        }
+        description.push_str(&format!("(if `{}`)", cond));
+    }
+
+    Some(gen_row(
+        &name,
+        &field_type,
+        &bits,
+        &endian(input, Some(f)),
+        &escape(&description),
+    ))
+}
+
+/// Row of the table, the type is quoted as code
+fn gen_row(name: &str, field_type: &str, bits: &str, endian: &str, description: &str) -> String {
+    format!(
+        "| {} | `{}` | {} | {} | {} |\n",
+        name, field_type, bits, endian, description
+    )
+}
+
+/// Endian of the field, of the container, or the endian of the target
+fn endian(input: &DekuData, f: Option<&FieldData>) -> String {
+    f.and_then(|f| f.endian.as_ref())
+        .or(input.endian.as_ref())
+        .map(|endian| endian.value())
+        .unwrap_or_else(|| "native".to_string())
+}
+
+/// Bit size of a primitive type, or "variable"
+fn type_bits(field_type: &str) -> String {
+    let bits = match field_type {
+        "u8" | "i8" | "bool" => 8,
+        "u16" | "i16" => 16,
+        "u32" | "i32" | "f32" => 32,
+        "u64" | "i64" | "f64" => 64,
+        "u128" | "i128" => 128,
+        _ => return "variable".to_string(),
+    };
+
+    bits.to_string()
+}
+
+/// Escape the separators of a Markdown table
+fn escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
@@ -0,0 +1,189 @@
+use crate::DekuData;
+use darling::ast::Data;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// Field types with a getter, returned by value across the C ABI
+const FFI_SCALARS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize", "f32", "f64", "bool",
+];
+
+/// Emit the `extern "C"` functions parsing the container into an opaque handle, getting its scalar
+/// fields and serializing it
+///
+/// The functions are prefixed with the name of the container in snake case, e.g. `deku_test_parse`
+/// for `DekuTest`.
+pub(crate) fn emit_deku_ffi(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+    let prefix = to_snake_case(&ident.to_string());
+
+    let parse_ident = format_ident!("{}_parse", prefix);
+    let free_ident = format_ident!("{}_free", prefix);
+    let serialize_ident = format_ident!("{}_serialize", prefix);
+    let free_bytes_ident = format_ident!("{}_free_bytes", prefix);
+
+    let parse_doc = format!(
+        "Read a [`{}`] from `len` bytes at `data`, which must all be read\n\n\
+         Returns a handle to free with `{}`, or null on error.\n\n\
+         # Safety\n\n\
+         `data` must be valid for reads of `len` bytes.",
+        ident, free_ident
+    );
+    let free_doc = format!(
+        "Free a handle returned by `{}`, null is ignored\n\n\
+         # Safety\n\n\
+         `value` must be null or a handle returned by `{}`, not freed yet.",
+        parse_ident, parse_ident
+    );
+    let serialize_doc = format!(
+        "Write a [`{}`] to bytes, storing their length in `out_len`\n\n\
+         Returns the bytes to free with `{}`, or null on error.\n\n\
+         # Safety\n\n\
+         `value` must be a handle returned by `{}`, and `out_len` valid for writes.",
+        ident, free_bytes_ident, parse_ident
+    );
+    let free_bytes_doc = format!(
+        "Free the bytes returned by `{}`, null is ignored\n\n\
+         # Safety\n\n\
+         `data` and `len` must be returned by `{}`, not freed yet.",
+        serialize_ident, serialize_ident
+    );
+
+    let mut getters = vec![];
+    if let Data::Struct(fields) = &input.data {
+        for (i, f) in fields.iter().enumerate() {
+            if f.temp || !is_ffi_scalar(&f.ty) {
+                continue;
+            }
+
+            let field_type = &f.ty;
+            let field_ident = f.get_ident(i, false);
+            let field_name = match &f.ident {
+                Some(field_ident) => field_ident.to_string(),
+                None => i.to_string(),
+            };
+            let getter_ident = format_ident!("{}_get_{}", prefix, field_name);
+            let getter_doc = format!(
+                "Get `{}` of a [`{}`], storing it in `out`\n\n\
+                 Returns false if `value` or `out` is null.\n\n\
+                 # Safety\n\n\
+                 `value` must be null or a handle returned by `{}`, and `out` null or valid for \
+                 writes.",
+                field_name, ident, parse_ident
+            );
+
+            getters.push(quote! {
+                #[doc = #getter_doc]
+                #[no_mangle]
+                pub unsafe extern "C" fn #getter_ident(value: *const #ident, out: *mut #field_type) -> bool {
+                    if value.is_null() || out.is_null() {
+                        return false;
+                    }
+
+                    *out = (*value).#field_ident;
+                    true
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[doc = #parse_doc]
+        #[no_mangle]
+        pub unsafe extern "C" fn #parse_ident(data: *const u8, len: usize) -> *mut #ident {
+            if data.is_null() {
+                return ::std::ptr::null_mut();
+            }
+
+            let data = ::std::slice::from_raw_parts(data, len);
+            match <#ident as core::convert::TryFrom<&[u8]>>::try_from(data) {
+                Ok(value) => ::std::boxed::Box::into_raw(::std::boxed::Box::new(value)),
+                Err(_) => ::std::ptr::null_mut(),
+            }
+        }
+
+        #[doc = #free_doc]
+        #[no_mangle]
+        pub unsafe extern "C" fn #free_ident(value: *mut #ident) {
+            if !value.is_null() {
+                drop(::std::boxed::Box::from_raw(value));
+            }
+        }
+
+        #[doc = #serialize_doc]
+        #[no_mangle]
+        pub unsafe extern "C" fn #serialize_ident(value: *const #ident, out_len: *mut usize) -> *mut u8 {
+            if value.is_null() || out_len.is_null() {
+                return ::std::ptr::null_mut();
+            }
+
+            match ::deku::DekuContainerWrite::to_bytes(&*value) {
+                Ok(bytes) => {
+                    let bytes = bytes.into_boxed_slice();
+                    *out_len = bytes.len();
+                    ::std::boxed::Box::into_raw(bytes) as *mut u8
+                }
+                Err(_) => ::std::ptr::null_mut(),
+            }
+        }
+
+        #[doc = #free_bytes_doc]
+        #[no_mangle]
+        pub unsafe extern "C" fn #free_bytes_ident(data: *mut u8, len: usize) {
+            if !data.is_null() {
+                drop(::std::boxed::Box::from_raw(::std::ptr::slice_from_raw_parts_mut(data, len)));
+            }
+        }
+
+        #(#getters)*
+    })
+}
+
+/// Whether the type is a primitive with a getter
+fn is_ffi_scalar(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => ty
+            .path
+            .get_ident()
+            .is_some_and(|ident| FFI_SCALARS.contains(&ident.to_string().as_str())),
+        _ => false,
+    }
+}
+
+/// Convert an identifier in camel case to snake case, keeping acronyms together:
+/// `HTTPHeader` is `http_header`
+fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = String::with_capacity(s.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(
+        input,
+        expected,
+        case("DekuTest", "deku_test"),
+        case("HTTPHeader", "http_header"),
+        case("Ipv4Header", "ipv4_header"),
+        case("snake_case", "snake_case")
+    )]
+    fn test_to_snake_case(input: &str, expected: &str) {
+        assert_eq!(expected, to_snake_case(input));
+    }
+}
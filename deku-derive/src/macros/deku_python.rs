@@ -0,0 +1,29 @@
+use crate::DekuData;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Emit the `parse` and `build` Python methods of a `#[pyo3::pyclass]` container
+///
+/// Errors of deku are raised as a `ValueError` with the message of the error.
+pub(crate) fn emit_deku_python(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+
+    Ok(quote! {
+        #[::pyo3::pymethods]
+        impl #ident {
+            /// Read the value from bytes, which must all be read
+            #[staticmethod]
+            fn parse(data: &[u8]) -> ::pyo3::PyResult<Self> {
+                <Self as core::convert::TryFrom<&[u8]>>::try_from(data)
+                    .map_err(|e| ::pyo3::exceptions::PyValueError::new_err(e.to_string()))
+            }
+
+            /// Write the value to bytes
+            fn build(&self) -> ::pyo3::PyResult<::std::borrow::Cow<'static, [u8]>> {
+                ::deku::DekuContainerWrite::to_bytes(self)
+                    .map(::std::borrow::Cow::Owned)
+                    .map_err(|e| ::pyo3::exceptions::PyValueError::new_err(e.to_string()))
+            }
+        }
+    })
+}
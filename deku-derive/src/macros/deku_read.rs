@@ -1,6 +1,12 @@
 use crate::macros::{
-    gen_ctx_types_and_arg, gen_field_args, gen_id_args, gen_internal_field_ident,
-    gen_internal_field_idents, wrap_default_ctx,
+    deku_doc::emit_wire_doc, deku_view::emit_repr_c_view, gen_align_check, gen_align_pad_bits,
+    gen_anonymous_lifetime_generics, gen_bcd_args, gen_bit_order_read, gen_borrowed_field,
+    gen_checksum_bits_ident, gen_checksum_compute, gen_container_assert, gen_ctx_types_and_arg,
+    gen_error_msg, gen_field_args, gen_field_assert, gen_field_assert_eq, gen_fixed_point_args,
+    gen_id_args, gen_internal_field_ident, gen_internal_field_idents, gen_limit_read,
+    gen_offset_read, gen_over_compute, gen_owned_field, gen_owned_generics, gen_owned_lifetime,
+    gen_pad_bits, gen_pad_read, gen_process_read, gen_read_generics, gen_seek_read,
+    gen_sign_magnitude_args, wrap_default_ctx,
 };
 use crate::{DekuData, FieldData};
 use darling::ast::{Data, Fields};
@@ -8,16 +14,24 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 pub(crate) fn emit_deku_read(input: &DekuData) -> Result<TokenStream, syn::Error> {
-    match &input.data {
-        Data::Enum(_) => emit_enum(input),
-        Data::Struct(_) => emit_struct(input),
+    let mut tokens = match &input.data {
+        Data::Enum(_) => emit_enum(input)?,
+        Data::Struct(_) => emit_struct(input)?,
+    };
+
+    if input.wire_doc {
+        tokens.extend(emit_wire_doc(input));
     }
+
+    Ok(tokens)
 }
 
 fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let mut tokens = TokenStream::new();
 
-    let (imp, ty, wher) = input.generics.split_for_impl();
+    let (_, ty, wher) = input.generics.split_for_impl();
+    let (read_generics, lifetime) = gen_read_generics(&input.generics);
+    let (imp, _, _) = read_generics.split_for_impl();
 
     let ident = &input.ident;
     let ident = quote! { #ident #ty };
@@ -28,16 +42,44 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     // check if the first field has an ident, if not, it's a unnamed struct
     let is_named_struct = fields
         .fields
-        .get(0)
+        .first()
         .and_then(|v| v.ident.as_ref())
         .is_some();
 
-    let (field_idents, field_reads) = emit_field_reads(input, &fields)?;
+    let (field_idents, mut field_reads) = emit_field_reads(input, fields)?;
+
+    if let Some(checksum) = &input.trailer_checksum {
+        emit_checksum_read(checksum, fields, &mut field_reads);
+    }
+
+    if let Some(trailer) = &input.trailer {
+        emit_trailer_read(trailer, fields, &mut field_reads);
+    }
+
+    // Save the start of the input to compute the checksum over and to seek within
+    let checksum_start = quote! { let __deku_input = rest; };
+
+    if let Some(magic) = &input.magic {
+        field_reads.insert(0, emit_magic_read(magic));
+    }
 
     let internal_fields = gen_internal_field_idents(is_named_struct, field_idents);
 
     let initialize_struct = super::gen_struct_init(is_named_struct, internal_fields);
 
+    let read_value = gen_bit_order_read(
+        input.bit_order.as_ref(),
+        quote! {
+            {
+                #checksum_start
+
+                #(#field_reads)*
+                #initialize_struct
+            }
+        },
+    );
+    let read_value = gen_assert_read(input, read_value)?;
+
     // Implement `DekuContainerRead` for types that don't need a context
     if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
         let from_bytes_body = wrap_default_ctx(
@@ -48,10 +90,12 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
                 let mut rest = input.0.view_bits::<Msb0>();
                 rest = &rest[input.1..];
 
-                #(#field_reads)*
-                let value = #initialize_struct;
+                // read in a closure to know the position on error
+                let value = (|| -> Result<Self, DekuError> { Ok(#read_value) })();
+                let value = value
+                    .map_err(|e| e.with_context(input.0, input_bits.len() - rest.len()))?;
 
-                let pad = 8 * ((rest.len() + 7) / 8) - rest.len();
+                let pad = 8 * rest.len().div_ceil(8) - rest.len();
                 let read_idx = input_bits.len() - (rest.len() + pad);
 
                 Ok(((input_bits[read_idx..].as_slice(), pad), value))
@@ -61,20 +105,28 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
         );
 
         tokens.extend(quote! {
-        impl #imp core::convert::TryFrom<&[u8]> for #ident #wher {
+        impl #imp core::convert::TryFrom<&#lifetime [u8]> for #ident #wher {
             type Error = DekuError;
 
-            fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+            fn try_from(input: &#lifetime [u8]) -> Result<Self, Self::Error> {
                 let (rest, res) = Self::from_bytes((input, 0))?;
                 if !rest.0.is_empty() {
-                    return Err(DekuError::Parse(format!("Too much data")));
+                    return Err(DekuError::Parse(deku::error_msg!("Too much data")));
                 }
                 Ok(res)
             }
         }
 
-        impl #imp DekuContainerRead for #ident #wher {
-            fn from_bytes(input: (&[u8], usize)) -> Result<((&[u8], usize), Self), DekuError> {
+        impl #imp core::convert::TryFrom<&mut &#lifetime [u8]> for #ident #wher {
+            type Error = DekuError;
+
+            fn try_from(input: &mut &#lifetime [u8]) -> Result<Self, Self::Error> {
+                Self::from_bytes_advance(input)
+            }
+        }
+
+        impl #imp DekuContainerRead<#lifetime> for #ident #wher {
+            fn from_bytes(input: (&#lifetime [u8], usize)) -> Result<((&#lifetime [u8], usize), Self), DekuError> {
                 #from_bytes_body
             }
         }
@@ -86,16 +138,14 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let read_body = quote! {
         use core::convert::TryFrom;
         let mut rest = input;
-
-        #(#field_reads)*
-        let value = #initialize_struct;
+        let value = #read_value;
 
         Ok((rest, value))
     };
 
     tokens.extend(quote! {
-        impl #imp DekuRead<#ctx_types> for #ident #wher {
-            fn read<'a>(input: &'a BitSlice<Msb0, u8>, #ctx_arg) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        impl #imp DekuRead<#lifetime, #ctx_types> for #ident #wher {
+            fn read(input: &#lifetime BitSlice<Msb0, u8>, #ctx_arg) -> Result<(&#lifetime BitSlice<Msb0, u8>, Self), DekuError> {
                 #read_body
             }
         }
@@ -105,34 +155,318 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
         let read_body = wrap_default_ctx(read_body, &input.ctx, &input.ctx_default);
 
         tokens.extend(quote! {
-            impl #imp DekuRead for #ident #wher {
-                fn read<'a>(input: &'a BitSlice<Msb0, u8>, _: ()) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            impl #imp DekuRead<#lifetime> for #ident #wher {
+                fn read(input: &#lifetime BitSlice<Msb0, u8>, _: ()) -> Result<(&#lifetime BitSlice<Msb0, u8>, Self), DekuError> {
                     #read_body
                 }
             }
         });
     }
 
-    // println!("{}", tokens.to_string());
+    if input.owned.is_some() {
+        tokens.extend(emit_owned_struct(input)?);
+    }
+
+    if input.view {
+        tokens.extend(emit_repr_c_view(input)?);
+    }
+
     Ok(tokens)
 }
 
-fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
+/// Read and verify the magic requested with `#[deku(magic = b"...")]`
+fn emit_magic_read(magic: &syn::LitByteStr) -> TokenStream {
+    quote! {
+        {
+            let magic: &[u8] = #magic;
+            let bit_size = magic.len() * 8;
+            if rest.len() < bit_size {
+                return Err(DekuError::Incomplete(deku::error::NeedSize::new(bit_size, rest.len())));
+            }
+
+            let (magic_read, new_rest) = rest.split_at(bit_size);
+            if magic_read != magic.view_bits::<Msb0>() {
+                let mut magic_read = magic_read.to_bitvec();
+                magic_read.force_align();
+
+                return Err(DekuError::Magic(deku::error_msg!(
+                    "expected {:02x?} got {:02x?}",
+                    magic,
+                    magic_read.as_slice()
+                )));
+            }
+
+            rest = new_rest;
+        }
+    }
+}
+
+/// Verify the checksum requested with `#[deku(trailer_checksum = "...")]`
+///
+/// The checksum is computed over the bytes read before the last field, and compared with the
+/// value of the last field once read.
+fn emit_checksum_read(
+    checksum: &TokenStream,
+    fields: &Fields<&FieldData>,
+    field_reads: &mut Vec<TokenStream>,
+) {
+    // checked in `DekuData::validate`
+    let i = fields.len() - 1;
+    let f = fields.fields[i];
+    let field_type = &f.ty;
+    let field_ident = f.get_ident(i, true);
+
+    field_reads.insert(
+        i,
+        quote! {
+            let __deku_checksum: #field_type = {
+                let read_len = __deku_input.len() - rest.len();
+                if read_len % 8 != 0 {
+                    return Err(DekuError::Parse(deku::error_msg!(
+                        "checksum: {} bits read is not a multiple of 8",
+                        read_len
+                    )));
+                }
+
+                let mut data = __deku_input[..read_len].to_bitvec();
+                data.force_align();
+
+                (#checksum)(data.as_slice())
+            };
+        },
+    );
+
+    field_reads.push(quote! {
+        if *#field_ident != __deku_checksum {
+            return Err(DekuError::Parse(deku::error_msg!(
+                "checksum mismatch: expected {:?} got {:?}",
+                __deku_checksum, #field_ident
+            )));
+        }
+    });
+}
+
+/// Read the last field from the end of the input, as requested with `#[deku(trailer = "...")]`
+///
+/// The fields before it are read from the input up to the trailer, and must consume all of it.
+fn emit_trailer_read(
+    trailer: &TokenStream,
+    fields: &Fields<&FieldData>,
+    field_reads: &mut Vec<TokenStream>,
+) {
+    // checked in `DekuData::validate`
+    let i = fields.len() - 1;
+    let field_ident = fields.fields[i].get_ident(i, true);
+
+    field_reads.insert(
+        0,
+        quote! {
+            let (__deku_body, __deku_trailer) = {
+                let bit_size = <#trailer as deku::DekuSize>::BIT_SIZE;
+                if rest.len() < bit_size {
+                    return Err(DekuError::Incomplete(deku::error::NeedSize::new(bit_size, rest.len())));
+                }
+
+                rest.split_at(rest.len() - bit_size)
+            };
+            rest = __deku_body;
+        },
+    );
+
+    // the read of the last field is shifted by the split
+    field_reads.insert(
+        i + 1,
+        quote! {
+            if !rest.is_empty() {
+                return Err(DekuError::Parse(deku::error_msg!(
+                    "trailer: {} bits left between the fields and the trailer",
+                    rest.len()
+                )));
+            }
+            rest = __deku_trailer;
+        },
+    );
+
+    field_reads.push(quote! {
+        let _: &#trailer = #field_ident;
+    });
+}
+
+/// Emit the owned struct requested with `#[deku(owned = "...")]`, the conversions between the
+/// borrowed and owned struct, and a `DekuRead` implementation delegating to the borrowed struct
+fn emit_owned_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let mut tokens = TokenStream::new();
 
     let (imp, ty, wher) = input.generics.split_for_impl();
 
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let owned = input.owned.as_ref().unwrap();
+
+    let owned_generics = gen_owned_generics(&input.generics);
+    let (owned_imp, owned_ty, owned_wher) = owned_generics.split_for_impl();
+    let (owned_read_generics, read_lifetime) = gen_read_generics(&owned_generics);
+    let (owned_read_imp, _, _) = owned_read_generics.split_for_impl();
+    let owned_params = &owned_generics.params;
+    let anon_ty = gen_anonymous_lifetime_generics(&input.generics);
+    let lifetime = gen_owned_lifetime(&input.generics);
+
+    // checked in `emit_deku_read`
+    let fields = input.data.as_ref().take_struct().unwrap();
+    let is_named_struct = fields.style.is_struct();
+
+    let mut owned_field_defs = vec![];
+    let mut owned_field_inits = vec![];
+    let mut borrowed_field_inits = vec![];
+
+    for (i, f) in fields.iter().enumerate() {
+        let field_vis = &f.vis;
+        let field_ident = f.get_ident(i, false);
+
+        let (owned_field_ty, owned_field_init) =
+            gen_owned_field(&f.ty, quote! { self.#field_ident })?;
+        let borrowed_field_init = gen_borrowed_field(&f.ty, quote! { input.#field_ident });
+
+        if is_named_struct {
+            owned_field_defs.push(quote! { #field_vis #field_ident: #owned_field_ty });
+            owned_field_inits.push(quote! { #field_ident: #owned_field_init });
+            borrowed_field_inits.push(quote! { #field_ident: #borrowed_field_init });
+        } else {
+            owned_field_defs.push(quote! { #field_vis #owned_field_ty });
+            owned_field_inits.push(owned_field_init);
+            borrowed_field_inits.push(borrowed_field_init);
+        }
+    }
+
+    let owned_derive = input
+        .owned_derive
+        .as_ref()
+        .map(|derives| quote! { #[derive(#derives)] });
+
+    let owned_doc = format!(
+        "Owned version of [`{}`], see [`{}::to_owned`]",
+        ident, ident
+    );
+
+    let (owned_struct, owned_init, borrowed_init) = if is_named_struct {
+        (
+            quote! {
+                #vis struct #owned <#owned_params> #owned_wher {
+                    #(#owned_field_defs),*
+                }
+            },
+            quote! { #owned { #(#owned_field_inits),* } },
+            quote! { Self { #(#borrowed_field_inits),* } },
+        )
+    } else {
+        (
+            quote! {
+                #vis struct #owned <#owned_params> (
+                    #(#owned_field_defs),*
+                ) #owned_wher;
+            },
+            quote! { #owned ( #(#owned_field_inits),* ) },
+            quote! { Self ( #(#borrowed_field_inits),* ) },
+        )
+    };
+
+    tokens.extend(quote! {
+        #[doc = #owned_doc]
+        #owned_derive
+        #owned_struct
+
+        impl #imp #ident #ty #wher {
+            /// Copy all borrowed fields, detaching the value from the input it was read from
+            #[allow(clippy::clone_on_copy)]
+            pub fn to_owned(&self) -> #owned #owned_ty {
+                #owned_init
+            }
+        }
+
+        impl #imp From<&#ident #ty> for #owned #owned_ty #wher {
+            fn from(input: &#ident #ty) -> Self {
+                input.to_owned()
+            }
+        }
+
+        impl #imp From<&#lifetime #owned #owned_ty> for #ident #ty #wher {
+            #[allow(clippy::clone_on_copy)]
+            fn from(input: &#lifetime #owned #owned_ty) -> Self {
+                #borrowed_init
+            }
+        }
+    });
+
+    // Implement `DekuContainerRead` for types that don't need a context
+    if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
+        tokens.extend(quote! {
+            impl #owned_imp core::convert::TryFrom<&[u8]> for #owned #owned_ty #owned_wher {
+                type Error = DekuError;
+
+                fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+                    let value = <#ident #anon_ty as core::convert::TryFrom<&[u8]>>::try_from(input)?;
+                    Ok(value.to_owned())
+                }
+            }
+
+            impl #owned_read_imp DekuContainerRead<#read_lifetime> for #owned #owned_ty #owned_wher {
+                fn from_bytes(input: (&#read_lifetime [u8], usize)) -> Result<((&#read_lifetime [u8], usize), Self), DekuError> {
+                    let (rest, value) = <#ident #anon_ty as DekuContainerRead<'_>>::from_bytes(input)?;
+                    Ok((rest, value.to_owned()))
+                }
+            }
+        });
+    }
+
+    let (ctx_types, _) = gen_ctx_types_and_arg(input.ctx.as_ref())?;
+
+    tokens.extend(quote! {
+        impl #owned_read_imp DekuRead<#read_lifetime, #ctx_types> for #owned #owned_ty #owned_wher {
+            fn read(input: &#read_lifetime BitSlice<Msb0, u8>, __deku_ctx: #ctx_types) -> Result<(&#read_lifetime BitSlice<Msb0, u8>, Self), DekuError> {
+                let (rest, value) = <#ident #anon_ty as DekuRead<'_, #ctx_types>>::read(input, __deku_ctx)?;
+                Ok((rest, value.to_owned()))
+            }
+        }
+    });
+
+    if input.ctx.is_some() && input.ctx_default.is_some() {
+        tokens.extend(quote! {
+            impl #owned_read_imp DekuRead<#read_lifetime> for #owned #owned_ty #owned_wher {
+                fn read(input: &#read_lifetime BitSlice<Msb0, u8>, _: ()) -> Result<(&#read_lifetime BitSlice<Msb0, u8>, Self), DekuError> {
+                    let (rest, value) = <#ident #anon_ty as DekuRead<'_>>::read(input, ())?;
+                    Ok((rest, value.to_owned()))
+                }
+            }
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let mut tokens = TokenStream::new();
+
+    let (_, ty, wher) = input.generics.split_for_impl();
+    let (read_generics, lifetime) = gen_read_generics(&input.generics);
+    let (imp, _, _) = read_generics.split_for_impl();
+
     // checked in `emit_deku_read`
     let variants = input.data.as_ref().take_enum().unwrap();
 
     let ident = &input.ident;
     let ident = quote! { #ident #ty };
-    let ident_as_string = ident.to_string();
+    let no_match = gen_error_msg(
+        &format!(
+            "Could not match enum variant id = {{:?}} on enum `{}`",
+            ident
+        ),
+        &[quote! { variant_id }],
+    );
 
     let id = input.id.as_ref();
     let id_type = input.id_type.as_ref();
 
-    let id_args = gen_id_args(input.endian.as_ref(), input.bits)?;
+    let id_args = gen_id_args(input.endian.as_ref(), input.bits, input.bit_order.as_ref())?;
 
     let mut variant_matches = vec![];
     let mut has_default_match = false;
@@ -145,7 +479,7 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         let variant_is_named = variant
             .fields
             .fields
-            .get(0)
+            .first()
             .and_then(|v| v.ident.as_ref())
             .is_some();
 
@@ -172,7 +506,7 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
                 super::gen_enum_init(variant_is_named, variant_ident, internal_fields);
 
             // if we're consuming an id, set the rest to new_rest before reading the variant
-            let new_rest = if variant.id.is_some() {
+            let new_rest = if variant.id.is_some() || variant.fields.iter().any(|f| f.id_field) {
                 quote! {
                     rest = new_rest;
                 }
@@ -200,7 +534,7 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
     if !has_default_match {
         variant_matches.push(quote! {
             _ => {
-                return Err(DekuError::Parse(format!("Could not match enum variant id = {:?} on enum `{}`", variant_id, #ident_as_string)));
+                return Err(DekuError::Parse(#no_match));
             }
         });
     }
@@ -218,27 +552,41 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         unreachable!();
     };
 
-    let variant_read = quote! {
-        #variant_id_read
+    let magic_read = input.magic.as_ref().map(emit_magic_read);
 
-        let value = match variant_id {
-            #(#variant_matches),*
-        };
-    };
+    let read_value = gen_bit_order_read(
+        input.bit_order.as_ref(),
+        quote! {
+            {
+                let __deku_input = rest;
+
+                #magic_read
+                #variant_id_read
+
+                match variant_id {
+                    #(#variant_matches),*
+                }
+            }
+        },
+    );
+    let read_value = gen_assert_read(input, read_value)?;
 
     // Implement `DekuContainerRead` for types that don't need a context
     if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
         let from_bytes_body = wrap_default_ctx(
             quote! {
                 use core::convert::TryFrom;
-                let input_bits = input.0.bits::<Msb0>();
+                let input_bits = input.0.view_bits::<Msb0>();
 
-                let mut rest = input.0.bits::<Msb0>();
+                let mut rest = input.0.view_bits::<Msb0>();
                 rest = &rest[input.1..];
 
-                #variant_read
+                // read in a closure to know the position on error
+                let value = (|| -> Result<Self, DekuError> { Ok(#read_value) })();
+                let value = value
+                    .map_err(|e| e.with_context(input.0, input_bits.len() - rest.len()))?;
 
-                let pad = 8 * ((rest.len() + 7) / 8) - rest.len();
+                let pad = 8 * rest.len().div_ceil(8) - rest.len();
                 let read_idx = input_bits.len() - (rest.len() + pad);
 
                 Ok(((input_bits[read_idx..].as_slice(), pad), value))
@@ -248,20 +596,28 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         );
 
         tokens.extend(quote! {
-        impl #imp core::convert::TryFrom<&[u8]> for #ident #wher {
+        impl #imp core::convert::TryFrom<&#lifetime [u8]> for #ident #wher {
             type Error = DekuError;
 
-            fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+            fn try_from(input: &#lifetime [u8]) -> Result<Self, Self::Error> {
                 let (rest, res) = Self::from_bytes((input, 0))?;
                 if !rest.0.is_empty() {
-                    return Err(DekuError::Parse(format!("Too much data")));
+                    return Err(DekuError::Parse(deku::error_msg!("Too much data")));
                 }
                 Ok(res)
             }
         }
 
-        impl #imp DekuContainerRead for #ident #wher {
-            fn from_bytes(input: (&[u8], usize)) -> Result<((&[u8], usize), Self), DekuError> {
+        impl #imp core::convert::TryFrom<&mut &#lifetime [u8]> for #ident #wher {
+            type Error = DekuError;
+
+            fn try_from(input: &mut &#lifetime [u8]) -> Result<Self, Self::Error> {
+                Self::from_bytes_advance(input)
+            }
+        }
+
+        impl #imp DekuContainerRead<#lifetime> for #ident #wher {
+            fn from_bytes(input: (&#lifetime [u8], usize)) -> Result<((&#lifetime [u8], usize), Self), DekuError> {
                 #from_bytes_body
             }
         }
@@ -273,15 +629,14 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let read_body = quote! {
         use core::convert::TryFrom;
         let mut rest = input;
-
-        #variant_read
+        let value = #read_value;
 
         Ok((rest, value))
     };
 
     tokens.extend(quote! {
-        impl #imp DekuRead<#ctx_types> for #ident #wher {
-            fn read<'a>(input: &'a BitSlice<Msb0, u8>, #ctx_arg) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        impl #imp DekuRead<#lifetime, #ctx_types> for #ident #wher {
+            fn read(input: &#lifetime BitSlice<Msb0, u8>, #ctx_arg) -> Result<(&#lifetime BitSlice<Msb0, u8>, Self), DekuError> {
                 #read_body
             }
         }
@@ -291,15 +646,14 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         let read_body = wrap_default_ctx(read_body, &input.ctx, &input.ctx_default);
 
         tokens.extend(quote! {
-            impl #imp DekuRead for #ident #wher {
-                fn read<'a>(input: &'a BitSlice<Msb0, u8>, _: ()) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            impl #imp DekuRead<#lifetime> for #ident #wher {
+                fn read(input: &#lifetime BitSlice<Msb0, u8>, _: ()) -> Result<(&#lifetime BitSlice<Msb0, u8>, Self), DekuError> {
                     #read_body
                 }
             }
         });
     }
 
-    // println!("{}", tokens.to_string());
     Ok(tokens)
 }
 
@@ -312,10 +666,61 @@ fn emit_field_reads(
 
     for (i, f) in fields.iter().enumerate() {
         let (field_ident, field_read) = emit_field_read(input, i, f)?;
-        field_idents.push(field_ident);
+        // `temp` fields are only read, they aren't part of the struct
+        if !f.temp {
+            field_idents.push(field_ident);
+        }
         field_reads.push(field_read);
     }
 
+    // the checksums are verified once the container is read, they may be over the fields after
+    for (i, f) in fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.checksum.is_some())
+    {
+        let field_ident = f.get_ident(i, true);
+        let checksum = gen_checksum_compute(
+            f,
+            i,
+            quote! { __deku_input[..__deku_input.len() - rest.len()].to_bitvec() },
+            quote! { Parse },
+        );
+
+        field_reads.push(quote! {
+            let __deku_checksum = #checksum;
+            if *#field_ident != __deku_checksum {
+                return Err(DekuError::Parse(deku::error_msg!(
+                    "checksum mismatch: expected {:?} got {:?}",
+                    __deku_checksum, #field_ident
+                )));
+            }
+        });
+    }
+
+    // the signatures are verified once the container is read, they may be over the fields after
+    for (i, f) in fields.iter().enumerate() {
+        let verify = match &f.verify {
+            Some(verify) => verify,
+            None => continue,
+        };
+
+        let field_ident = f.get_ident(i, true);
+        let verify = gen_over_compute(
+            f,
+            i,
+            quote! { __deku_input[..__deku_input.len() - rest.len()].to_bitvec() },
+            quote! { Parse },
+            "signature",
+            quote! {
+                let verified: Result<(), DekuError> = (#verify)(over, #field_ident);
+                verified?;
+            },
+        );
+
+        field_reads.push(verify);
+    }
+
     Ok((field_idents, field_reads))
 }
 
@@ -326,7 +731,7 @@ fn emit_field_read(
 ) -> Result<(TokenStream, TokenStream), syn::Error> {
     let field_type = &f.ty;
 
-    let field_endian = f.endian.as_ref().or_else(|| input.endian.as_ref());
+    let field_endian = f.endian.as_ref().or(input.endian.as_ref());
 
     let field_reader = &f.reader;
 
@@ -345,10 +750,44 @@ fn emit_field_read(
     let field_read_func = if field_reader.is_some() {
         quote! { #field_reader }
     } else {
-        let read_args = gen_field_args(field_endian, f.bits, f.ctx.as_ref())?;
-
-        // Count is special, we need to generate `(count, (other, ..))` for it.
-        if let Some(field_count) = &f.count {
+        let read_args = gen_field_args(
+            field_endian,
+            f.bits.as_ref(),
+            f.bit_order.as_ref().or(input.bit_order.as_ref()),
+            f.encoding.as_ref(),
+            f.pad.as_ref(),
+            f.ctx.as_ref(),
+        )?;
+
+        if let Some(field_with) = &f.with {
+            quote! { #field_with::read(rest, (#read_args)) }
+        } else if let Some(field_bcd) = &f.bcd {
+            // #[deku(bcd)] ==> read with the `bcd` codec
+            let bcd_args = gen_bcd_args(f, field_bcd)?;
+            quote! { deku::codecs::bcd::read(rest, #bcd_args) }
+        } else if let Some(field_fixed) = &f.fixed {
+            // #[deku(fixed = "Q8.8")] ==> read with the `fixed_point` codec
+            let fixed_args = gen_fixed_point_args(field_endian, field_fixed)?;
+            quote! { deku::codecs::fixed_point::read(rest, #fixed_args) }
+        } else if f.sign_magnitude {
+            // #[deku(signed = "magnitude")] ==> read with the `sign_magnitude` codec
+            let sign_magnitude_args = gen_sign_magnitude_args(field_endian, f)?;
+            quote! { deku::codecs::sign_magnitude::read(rest, #sign_magnitude_args) }
+        } else if f.strict_bool {
+            // #[deku(strict_bool)] ==> read with the `strict_bool` codec
+            quote! { deku::codecs::strict_bool::read(rest, (#read_args)) }
+        } else if let Some(field_prefix) = &f.prefix {
+            // #[deku(prefix = "u8")] ==> read the byte length, then the field from that many bytes
+            let prefix_args = gen_field_args(field_endian, None, None, None, None, None)?;
+            quote! {
+                {
+                    let (new_rest, __deku_prefix) = <#field_prefix as DekuRead<'_, _>>::read(rest, (#prefix_args))?;
+                    rest = new_rest;
+                    DekuRead::read(rest, (deku::ctx::ByteSize(usize::try_from(__deku_prefix)?), (#read_args)))
+                }
+            }
+        } else if let Some(field_count) = &f.count {
+            // Count is special, we need to generate `(count, (other, ..))` for it.
             // The count has same problem, when it isn't a copy type, the field will be moved.
             // e.g. struct FooBar {
             //   a: Baz // a type implement `Into<usize>` but not `Copy`.
@@ -361,13 +800,44 @@ fn emit_field_read(
                     DekuRead::read(rest, (deku::ctx::Count(usize::try_from(*((#field_count).borrow()))?), (#read_args)))
                 }
             }
+        } else if let Some(field_bytes) = &f.bytes_read {
+            quote! {
+                {
+                    use core::borrow::Borrow;
+                    DekuRead::read(rest, (deku::ctx::ByteSize(usize::try_from(*((#field_bytes).borrow()))?), (#read_args)))
+                }
+            }
+        } else if let Some(field_bits) = &f.bits_read {
+            quote! {
+                {
+                    use core::borrow::Borrow;
+                    DekuRead::read(rest, (deku::ctx::BitsRead(usize::try_from(*((#field_bits).borrow()))?), (#read_args)))
+                }
+            }
+        } else if f.read_all {
+            quote! {DekuRead::read(rest, (deku::ctx::ReadAll, (#read_args)))}
         } else {
             quote! {DekuRead::read(rest, (#read_args))}
         }
     };
 
+    // #[deku(read_as = "...")] ==> read as the type, then convert it to the field
+    let field_read_as = f.read_as.as_ref().map(|read_as| {
+        let error = format!(
+            "error converting `{}` to `{}`",
+            read_as,
+            quote! { #field_type }
+        );
+        quote! {
+            let value: #read_as = value;
+            let value = <#field_type as core::convert::TryFrom<#read_as>>::try_from(value)
+                .map_err(|_| DekuError::Parse(deku::error_msg!(#error)))?;
+        }
+    });
+
     let field_read_normal = quote! {
         let (new_rest, value) = #field_read_func?;
+        #field_read_as
         let value: #field_type = #field_map(value)?;
 
         rest = new_rest;
@@ -376,7 +846,14 @@ fn emit_field_read(
     };
     let field_default = &f.default;
 
-    let field_read_tokens = match (f.skip, &f.cond) {
+    let field_read_tokens = match (f.skip || f.skip_read, &f.cond) {
+        _ if f.id_field => {
+            // #[deku(id_field)] ==> the variant id, already consumed
+            quote! {
+                let value: #field_type = variant_id;
+                value
+            }
+        }
         (true, Some(field_cond)) => {
             // #[deku(skip, cond = "...")] ==> `skip` if `cond`
             quote! {
@@ -410,12 +887,141 @@ fn emit_field_read(
         }
     };
 
+    let field_assert = f
+        .assert
+        .as_ref()
+        .map(|assert| {
+            gen_field_assert(
+                &field_ident,
+                assert,
+                &quote! { __deku_input.len() - rest.len() },
+            )
+        })
+        .transpose()?
+        .map(|(field_assert, _)| field_assert);
+    let field_assert_eq = f
+        .assert_eq
+        .as_ref()
+        .map(|expected| gen_field_assert_eq(&field_ident, expected));
+
+    let pad_before = gen_pad_read(gen_pad_bits(
+        f.pad_bits_before.as_ref(),
+        f.pad_bytes_before.as_ref(),
+    ));
+
+    let pad_after = gen_pad_read(gen_pad_bits(
+        f.pad_bits_after.as_ref(),
+        f.pad_bytes_after.as_ref(),
+    ));
+
+    let align = gen_pad_read(gen_align_pad_bits(
+        f.align_bits.as_ref(),
+        f.align_bytes.as_ref(),
+        quote! { __deku_input.len() - rest.len() },
+    ));
+
+    let seek = gen_seek_read(f);
+
+    let align_before = f.align_before.then(|| {
+        gen_align_check(
+            &field_ident,
+            quote! { __deku_input.len() - rest.len() },
+            "before",
+        )
+    });
+    let align_after = f.align_after.then(|| {
+        gen_align_check(
+            &field_ident,
+            quote! { __deku_input.len() - rest.len() },
+            "after",
+        )
+    });
+
+    let field_read_tokens = match &f.process {
+        Some(process) => gen_process_read(process, field_read_tokens),
+        None => field_read_tokens,
+    };
+
+    let field_read_tokens = match &f.limit_bytes {
+        Some(limit_bytes) => gen_limit_read(limit_bytes, field_read_tokens),
+        None => field_read_tokens,
+    };
+
+    // #[deku(peek)] ==> read, then go back to before the field
+    let field_read_tokens = if f.peek {
+        quote! {
+            let __deku_peek_rest = rest;
+            let value = {
+                #field_read_tokens
+            };
+            rest = __deku_peek_rest;
+            value
+        }
+    } else {
+        field_read_tokens
+    };
+
+    // #[deku(default_if_eof)] ==> `default` if the input ended
+    let field_read_tokens = if f.default_if_eof {
+        quote! {
+            if rest.is_empty() {
+                #field_default
+            } else {
+                #field_read_tokens
+            }
+        }
+    } else {
+        field_read_tokens
+    };
+
+    let field_read_tokens = match &f.offset {
+        Some(offset) => gen_offset_read(offset, field_read_tokens),
+        None => field_read_tokens,
+    };
+
+    // #[deku(checksum = "...")] ==> save the bits of the field, verified once the container is read
+    let (checksum_start, checksum_end) = if f.is_over_bytes() {
+        let bits_ident = gen_checksum_bits_ident(i);
+        (
+            Some(quote! { let __deku_checksum_start = __deku_input.len() - rest.len(); }),
+            Some(quote! {
+                let #bits_ident = (__deku_checksum_start, __deku_input.len() - rest.len());
+            }),
+        )
+    } else {
+        (None, None)
+    };
+
     let field_read = quote! {
+        #seek
+        #pad_before
+        #align
+        #align_before
+        #checksum_start
         let #internal_field_ident = {
             #field_read_tokens
         };
+        #checksum_end
+        #align_after
         let #field_ident = &#internal_field_ident;
+        #field_assert
+        #field_assert_eq
+        #pad_after
     };
 
     Ok((field_ident, field_read))
 }
+
+/// Wrap the read of a container with the check of the top-level `assert`
+fn gen_assert_read(input: &DekuData, read_value: TokenStream) -> Result<TokenStream, syn::Error> {
+    match gen_container_assert(input, quote! { &__deku_value })? {
+        Some(container_assert) => Ok(quote! {
+            {
+                let __deku_value = #read_value;
+                #container_assert
+                __deku_value
+            }
+        }),
+        None => Ok(read_value),
+    }
+}
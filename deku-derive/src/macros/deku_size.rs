@@ -0,0 +1,113 @@
+use crate::{DekuData, FieldData};
+use darling::ast::Data;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(crate) fn emit_deku_size(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    match &input.data {
+        Data::Enum(_) => emit_enum(input),
+        Data::Struct(_) => emit_struct(input),
+    }
+}
+
+/// Emit `DekuSize`, the size of the magic and the fields
+fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+
+    // checked in `DekuData::validate_deku_size`
+    let fields = input.data.as_ref().take_struct().unwrap();
+
+    let magic_size = gen_magic_size(input);
+    let fields_size = gen_fields_size(fields.iter().copied());
+
+    Ok(quote! {
+        impl DekuSize for #ident {
+            const BIT_SIZE: usize = #magic_size + #fields_size;
+        }
+    })
+}
+
+/// Emit `DekuEnumSize`, the smallest and largest size of the variants
+fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+
+    // checked in `DekuData::validate_deku_size`
+    let variants = input.data.as_ref().take_enum().unwrap();
+
+    let id_size = match (input.bits, &input.id_type) {
+        (Some(bits), _) => quote! { #bits },
+        (None, Some(id_type)) => quote! { <#id_type as DekuSize>::BIT_SIZE },
+        // the id is given by the top-level `id`, it isn't read
+        (None, None) => quote! { 0 },
+    };
+    let magic_size = gen_magic_size(input);
+
+    let variant_sizes = variants.iter().map(|variant| {
+        // `id_pat` and catch-all variants read the id again, unless it's held by an `id_field`
+        let consumes_id = variant.id.is_some() || variant.fields.iter().any(|f| f.id_field);
+        let variant_id_size = if consumes_id {
+            quote! { <Self as DekuEnumSize>::ID_BIT_SIZE }
+        } else {
+            quote! { #magic_size }
+        };
+
+        let fields_size = gen_fields_size(variant.fields.iter().filter(|f| !f.id_field));
+
+        quote! { #variant_id_size + #fields_size }
+    });
+    let variant_sizes = quote! { [#(#variant_sizes),*] };
+
+    Ok(quote! {
+        impl DekuEnumSize for #ident {
+            const ID_BIT_SIZE: usize = #magic_size + #id_size;
+
+            const MIN_BIT_SIZE: usize = {
+                let sizes: &[usize] = &#variant_sizes;
+                let mut min = usize::MAX;
+                let mut i = 0;
+                while i < sizes.len() {
+                    if sizes[i] < min {
+                        min = sizes[i];
+                    }
+                    i += 1;
+                }
+                min
+            };
+
+            const MAX_BIT_SIZE: usize = {
+                let sizes: &[usize] = &#variant_sizes;
+                let mut max = 0;
+                let mut i = 0;
+                while i < sizes.len() {
+                    if sizes[i] > max {
+                        max = sizes[i];
+                    }
+                    i += 1;
+                }
+                max
+            };
+        }
+    })
+}
+
+/// Generate the size of the magic requested with `#[deku(magic = b"...")]`
+fn gen_magic_size(input: &DekuData) -> TokenStream {
+    let magic_size = input
+        .magic
+        .as_ref()
+        .map_or(0, |magic| magic.value().len() * 8);
+    quote! { #magic_size }
+}
+
+/// Generate the sum of the sizes of the fields, from `bits` or the `DekuSize` of the field type
+fn gen_fields_size<'a>(fields: impl Iterator<Item = &'a FieldData>) -> TokenStream {
+    let field_sizes = fields.map(|f| {
+        let field_type = &f.ty;
+        match &f.bits {
+            Some(bits) => quote! { #bits },
+            None => quote! { <#field_type as DekuSize>::BIT_SIZE },
+        }
+    });
+
+    quote! { 0 #(+ #field_sizes)* }
+}
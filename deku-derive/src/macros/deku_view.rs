@@ -0,0 +1,196 @@
+use crate::macros::{gen_error_msg, gen_field_args};
+use crate::DekuData;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+pub(crate) fn emit_deku_view(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let (tokens, _) = emit_view(input)?;
+    Ok(tokens)
+}
+
+/// Emit a view of a `#[repr(C)]` struct, requested with `#[deku(view)]`
+///
+/// The wire layout is asserted at compile time to match the native layout: the total size of the
+/// fields must be the size of the struct, i.e. there is no padding. An inherent `view()` reading
+/// from bytes aligned as the struct is added.
+pub(crate) fn emit_repr_c_view(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let (mut tokens, field_types) = emit_view(input)?;
+
+    let ident = &input.ident;
+    let view_ident = format_ident!("{}View", ident);
+    let not_aligned = gen_error_msg(
+        &format!(
+            "input is not aligned to {{}} bytes, required by `{}`",
+            ident
+        ),
+        &[quote! { align }],
+    );
+
+    tokens.extend(quote! {
+        const _: () = {
+            #(
+                assert!(
+                    <#field_types as DekuSize>::BIT_SIZE == core::mem::size_of::<#field_types>() * 8,
+                    "wire size of field does not match the native size"
+                );
+            )*
+
+            assert!(
+                <#ident as DekuSize>::BIT_SIZE == core::mem::size_of::<#ident>() * 8,
+                "wire layout does not match the native layout"
+            );
+        };
+
+        impl #ident {
+            /// Create a view over the input, without parsing any field
+            ///
+            /// The input must be aligned to the native alignment of the struct and contain
+            /// at least enough bytes for all fields.
+            pub fn view(input: &[u8]) -> Result<#view_ident<'_>, DekuError> {
+                let align = core::mem::align_of::<Self>();
+                if input.as_ptr() as usize % align != 0 {
+                    return Err(DekuError::Parse(#not_aligned));
+                }
+
+                <Self as DekuView<'_>>::view(input.view_bits::<Msb0>())
+            }
+        }
+    });
+
+    Ok(tokens)
+}
+
+/// Emit the `DekuSize` and `DekuView` implementations, and the view type with an accessor per
+/// field. The offset of each field is the sum of the sizes of the preceding fields.
+///
+/// Returns the tokens and the type of each field
+fn emit_view(input: &DekuData) -> Result<(TokenStream, Vec<&syn::Type>), syn::Error> {
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let view_ident = format_ident!("{}View", ident);
+
+    // checked in `DekuData::validate`
+    let fields = input.data.as_ref().take_struct().unwrap();
+
+    let partial_ident = format_ident!("{}Partial", ident);
+
+    let mut field_types = vec![];
+    let mut field_idents = vec![];
+    let mut field_masks = vec![];
+    let mut field_mask_idents = vec![];
+    let mut field_accessors = vec![];
+    let mut offset = quote! { 0 };
+
+    for (i, f) in fields.iter().enumerate() {
+        let field_type = &f.ty;
+        let field_ident = f.get_ident(i, true);
+        let field_size = match &f.bits {
+            Some(bits) => quote! { #bits },
+            None => quote! { <#field_type as DekuSize>::BIT_SIZE },
+        };
+
+        let field_endian = f.endian.as_ref().or(input.endian.as_ref());
+        let read_args = gen_field_args(field_endian, f.bits.as_ref(), None, None, None, None)?;
+
+        let field_doc = format!("Read `{}` from the input", field_ident);
+
+        field_accessors.push(quote! {
+            #[doc = #field_doc]
+            pub fn #field_ident(&self) -> Result<#field_type, DekuError> {
+                const OFFSET: usize = #offset;
+                let (_, value) = <#field_type as DekuRead<'_, _>>::read(
+                    &self.input[OFFSET..OFFSET + #field_size],
+                    (#read_args),
+                )?;
+                Ok(value)
+            }
+        });
+
+        let mask_ident = format_ident!("{}", field_ident.to_string().to_uppercase());
+        let mask_doc = format!("Mask selecting `{}`", field_ident);
+        field_masks.push(quote! {
+            #[doc = #mask_doc]
+            pub const #mask_ident: u64 = 1 << #i;
+        });
+
+        field_types.push(field_type);
+        field_idents.push(field_ident);
+        field_mask_idents.push(mask_ident);
+        offset = quote! { #offset + #field_size };
+    }
+
+    let view_doc = format!(
+        "View of [`{}`], fields are read on demand from the input",
+        ident
+    );
+
+    // A field mask is a `u64`, a partial parse is only available for up to 64 fields
+    let partial = if fields.len() <= 64 {
+        let partial_doc = format!(
+            "Subset of the fields of [`{}`], read with [`{}::partial`]",
+            ident, view_ident
+        );
+
+        quote! {
+            #[doc = #partial_doc]
+            #[derive(Debug, Default, Clone, PartialEq)]
+            #vis struct #partial_ident {
+                #(pub #field_idents: Option<#field_types>,)*
+            }
+
+            impl #partial_ident {
+                #(#field_masks)*
+            }
+
+            impl<'a> #view_ident<'a> {
+                /// Read the fields selected by `mask`, other fields are skipped and left as `None`
+                pub fn partial(&self, mask: u64) -> Result<#partial_ident, DekuError> {
+                    let mut partial = #partial_ident::default();
+                    #(
+                        if mask & #partial_ident::#field_mask_idents != 0 {
+                            partial.#field_idents = Some(self.#field_idents()?);
+                        }
+                    )*
+                    Ok(partial)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let tokens = quote! {
+        impl DekuSize for #ident {
+            const BIT_SIZE: usize = #offset;
+        }
+
+        #[doc = #view_doc]
+        #[derive(Debug, Copy, Clone)]
+        #vis struct #view_ident<'a> {
+            input: &'a BitSlice<Msb0, u8>,
+        }
+
+        impl<'a> #view_ident<'a> {
+            #(#field_accessors)*
+        }
+
+        impl<'a> DekuView<'a> for #ident {
+            type View = #view_ident<'a>;
+
+            fn view(input: &'a BitSlice<Msb0, u8>) -> Result<Self::View, DekuError> {
+                let bit_size = <Self as DekuSize>::BIT_SIZE;
+                if input.len() < bit_size {
+                    return Err(DekuError::Incomplete(deku::error::NeedSize::new(bit_size, input.len())));
+                }
+
+                Ok(#view_ident {
+                    input: &input[..bit_size],
+                })
+            }
+        }
+
+        #partial
+    };
+
+    Ok((tokens, field_types))
+}
@@ -1,10 +1,17 @@
 use crate::macros::{
-    gen_ctx_types_and_arg, gen_field_args, gen_id_args, gen_struct_destruction, wrap_default_ctx,
+    gen_align_check, gen_align_pad_bits, gen_anonymous_lifetime_generics, gen_bcd_args,
+    gen_bit_order_write, gen_checksum_bits_ident, gen_checksum_compute, gen_container_assert,
+    gen_ctx_types_and_arg, gen_deferred_writes, gen_error_msg, gen_field_args, gen_field_assert,
+    gen_field_assert_eq, gen_fixed_point_args, gen_id_args, gen_limit_write, gen_offset_write,
+    gen_over_compute, gen_owned_generics, gen_pad_bits, gen_pad_write, gen_process_write,
+    gen_seek_write, gen_sign_magnitude_args, gen_struct_destruction, split_ctx_to_pats_and_types,
+    wrap_default_ctx,
 };
 use crate::{DekuData, FieldData};
 use darling::ast::{Data, Fields};
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::spanned::Spanned;
 
 pub(crate) fn emit_deku_write(input: &DekuData) -> Result<TokenStream, syn::Error> {
     match &input.data {
@@ -24,14 +31,25 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     // Checked in `emit_deku_write`.
     let fields = input.data.as_ref().take_struct().unwrap();
 
-    let field_writes = emit_field_writes(input, &fields, None)?;
-    let field_updates = emit_field_updates(&fields, Some(quote! { self. }))?;
+    let mut field_writes = emit_field_writes(input, &fields, None)?;
+    if let Some(checksum) = &input.trailer_checksum {
+        emit_checksum_write(input, checksum, &fields, &mut field_writes)?;
+    }
+    if let Some(magic) = &input.magic {
+        field_writes.insert(0, emit_magic_write(magic));
+    }
+    let field_updates = emit_field_updates(input, &fields, Some(quote! { self. }))?;
+    let (deferred_start, deferred_end) = gen_deferred_writes(fields.iter().copied());
+    let bit_order_write = gen_bit_order_write(input.bit_order.as_ref());
 
     let named = fields.style.is_struct();
+    let (auto_update, write_self) = gen_auto_update(input)?;
+    let container_assert = gen_container_assert(input, write_self.clone())?;
 
     let field_idents = fields
         .iter()
         .enumerate()
+        .filter(|(_, f)| !f.temp)
         .map(|(i, f)| f.get_ident(i, true))
         .collect::<Vec<_>>();
 
@@ -41,15 +59,20 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
         let to_bits_body = wrap_default_ctx(
             quote! {
-                match *self {
-                    #destructured => {
-                        let mut acc: BitVec<Msb0, u8> = BitVec::new();
-                        #(#field_writes)*
-
-                        Ok(acc)
-                    }
-                }
-            },
+                            #auto_update
+                            #container_assert
+                            match *#write_self {
+                                #destructured => {
+                                    let mut acc: BitVec<Msb0, u8> = BitVec::new();
+                                    #deferred_start
+                                    #(#field_writes)*
+                                    #deferred_end
+            #bit_order_write
+
+                                    Ok(acc)
+                                }
+                            }
+                        },
             &input.ctx,
             &input.ctx_default,
         );
@@ -88,26 +111,29 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let (ctx_types, ctx_arg) = gen_ctx_types_and_arg(input.ctx.as_ref())?;
 
     let write_body = quote! {
-        match *self {
-            #destructured => {
-                let mut acc: BitVec<Msb0, u8> = BitVec::new();
-                #(#field_writes)*
+            #auto_update
+            #container_assert
+            match *#write_self {
+                #destructured => {
+                    let mut acc: BitVec<Msb0, u8> = BitVec::new();
+                    #deferred_start
+                    #(#field_writes)*
+                    #deferred_end
+    #bit_order_write
 
-                Ok(acc)
+                    Ok(acc)
+                }
             }
-        }
-    };
-
-    tokens.extend(quote! {
-        impl #imp DekuUpdate for #ident #wher {
-            fn update(&mut self) -> Result<(), DekuError> {
-                use core::convert::TryInto;
-                #(#field_updates)*
+        };
 
-                Ok(())
-            }
-        }
+    tokens.extend(emit_update(
+        input,
+        quote! {
+            #(#field_updates)*
+        },
+    )?);
 
+    tokens.extend(quote! {
         impl #imp DekuWrite<#ctx_types> for #ident #wher {
             #[allow(unused_variables)]
             fn write(&self, #ctx_arg) -> Result<BitVec<Msb0, u8>, DekuError> {
@@ -129,7 +155,76 @@ fn emit_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
         });
     }
 
-    // println!("{}", tokens.to_string());
+    if input.owned.is_some() {
+        tokens.extend(emit_owned_struct(input)?);
+    }
+
+    Ok(tokens)
+}
+
+/// Emit a `DekuWrite` implementation for the owned struct requested with
+/// `#[deku(owned = "...")]`, delegating to the borrowed struct
+fn emit_owned_struct(input: &DekuData) -> Result<TokenStream, syn::Error> {
+    let mut tokens = TokenStream::new();
+
+    let ident = &input.ident;
+    let owned = input.owned.as_ref().unwrap();
+
+    let owned_generics = gen_owned_generics(&input.generics);
+    let (owned_imp, owned_ty, owned_wher) = owned_generics.split_for_impl();
+    let anon_ty = gen_anonymous_lifetime_generics(&input.generics);
+
+    // Implement `DekuContainerWrite` for types that don't need a context
+    if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
+        tokens.extend(quote! {
+            impl #owned_imp core::convert::TryFrom<#owned #owned_ty> for BitVec<Msb0, u8> #owned_wher {
+                type Error = DekuError;
+
+                fn try_from(input: #owned #owned_ty) -> Result<Self, Self::Error> {
+                    input.to_bits()
+                }
+            }
+
+            impl #owned_imp core::convert::TryFrom<#owned #owned_ty> for Vec<u8> #owned_wher {
+                type Error = DekuError;
+
+                fn try_from(input: #owned #owned_ty) -> Result<Self, Self::Error> {
+                    input.to_bytes()
+                }
+            }
+
+            impl #owned_imp DekuContainerWrite for #owned #owned_ty #owned_wher {
+                fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
+                    <#ident #anon_ty>::from(self).to_bytes()
+                }
+
+                fn to_bits(&self) -> Result<BitVec<Msb0, u8>, DekuError> {
+                    <#ident #anon_ty>::from(self).to_bits()
+                }
+            }
+        });
+    }
+
+    let (ctx_types, _) = gen_ctx_types_and_arg(input.ctx.as_ref())?;
+
+    tokens.extend(quote! {
+        impl #owned_imp DekuWrite<#ctx_types> for #owned #owned_ty #owned_wher {
+            fn write(&self, __deku_ctx: #ctx_types) -> Result<BitVec<Msb0, u8>, DekuError> {
+                <#ident #anon_ty as DekuWrite<#ctx_types>>::write(&<#ident #anon_ty>::from(self), __deku_ctx)
+            }
+        }
+    });
+
+    if input.ctx.is_some() && input.ctx_default.is_some() {
+        tokens.extend(quote! {
+            impl #owned_imp DekuWrite for #owned #owned_ty #owned_wher {
+                fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+                    <#ident #anon_ty as DekuWrite>::write(&<#ident #anon_ty>::from(self), ())
+                }
+            }
+        });
+    }
+
     Ok(tokens)
 }
 
@@ -147,7 +242,7 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let id = input.id.as_ref();
     let id_type = input.id_type.as_ref();
 
-    let id_args = gen_id_args(input.endian.as_ref(), input.bits)?;
+    let id_args = gen_id_args(input.endian.as_ref(), input.bits, input.bit_order.as_ref())?;
 
     let mut variant_writes = vec![];
     let mut variant_updates = vec![];
@@ -155,12 +250,12 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
     /*
         FIXME: The loop body is too big.
     */
-    for variant in variants {
+    for variant in &variants {
         // check if the first field has an ident, if not, it's a unnamed struct
         let variant_is_named = variant
             .fields
             .fields
-            .get(0)
+            .first()
             .and_then(|v| v.ident.as_ref())
             .is_some();
 
@@ -172,6 +267,7 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
             .as_ref()
             .iter()
             .enumerate()
+            .filter(|(_, f)| !f.temp)
             .map(|(i, f)| f.get_ident(i, true))
             .collect::<Vec<_>>();
 
@@ -190,6 +286,16 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
                     let bits = variant_id.write((#id_args))?;
                     acc.extend(bits);
                 }
+            } else if let Some((i, f)) = variant.fields.iter().enumerate().find(|(_, f)| f.id_field)
+            {
+                // the id was consumed into the `id_field`, write it back as the id
+                let field_ident = f.get_ident(i, true);
+
+                quote! {
+                    let variant_id: &#id_type = #field_ident;
+                    let bits = variant_id.write((#id_args))?;
+                    acc.extend(bits);
+                }
             } else {
                 quote! {}
             }
@@ -205,15 +311,51 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         } else {
             let field_writes = emit_field_writes(input, &variant.fields.as_ref(), None)?;
 
+            // the id of an `id_pat` variant is written by its fields, read it back to check
+            // the variant can be read again
+            let variant_id_check = match (id_type, &variant.id_pat) {
+                (Some(id_type), Some(variant_id_pat)) => {
+                    let variant_name = format!("{}::{}", input.ident, variant_ident);
+                    let read_back_failed = gen_error_msg(
+                        &format!(
+                            "Could not read back the id of enum variant `{}`",
+                            variant_name
+                        ),
+                        &[],
+                    );
+                    let id_mismatch = gen_error_msg(
+                        &format!(
+                            "Enum variant id = {{:?}} doesn't match the `id_pat` of `{}`",
+                            variant_name
+                        ),
+                        &[quote! { variant_id }],
+                    );
+
+                    Some(quote! {
+                        let (_, variant_id) = #id_type::read(&acc[__deku_variant_start..], (#id_args))
+                            .map_err(|_| DekuError::InvalidParam(#read_back_failed))?;
+                        if !matches!(variant_id, #variant_id_pat) {
+                            return Err(DekuError::InvalidParam(#id_mismatch));
+                        }
+                    })
+                }
+                _ => None,
+            };
+            let variant_start = variant_id_check
+                .as_ref()
+                .map(|_| quote! { let __deku_variant_start = acc.len(); });
+
             quote! {
                 {
+                    #variant_start
                     #variant_id_write
                     #(#field_writes)*
+                    #variant_id_check
                 }
             }
         };
 
-        let variant_field_updates = emit_field_updates(&variant.fields.as_ref(), None)?;
+        let variant_field_updates = emit_field_updates(input, &variant.fields.as_ref(), None)?;
 
         variant_writes.push(quote! {
             Self :: #variant_match => {
@@ -228,18 +370,31 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         });
     }
 
+    let magic_write = input.magic.as_ref().map(emit_magic_write);
+    let (deferred_start, deferred_end) =
+        gen_deferred_writes(variants.iter().flat_map(|variant| variant.fields.iter()));
+    let bit_order_write = gen_bit_order_write(input.bit_order.as_ref());
+    let (auto_update, write_self) = gen_auto_update(input)?;
+    let container_assert = gen_container_assert(input, write_self.clone())?;
+
     // Implement `DekuContainerWrite` for types that don't need a context
     if input.ctx.is_none() || (input.ctx.is_some() && input.ctx_default.is_some()) {
         let to_bits_body = wrap_default_ctx(
             quote! {
-                let mut acc: BitVec<Msb0, u8> = BitVec::new();
-
-                match self {
-                    #(#variant_writes),*
-                }
-
-                Ok(acc)
-            },
+                            #auto_update
+                            #container_assert
+                            let mut acc: BitVec<Msb0, u8> = BitVec::new();
+                            #deferred_start
+                            #magic_write
+
+                            match #write_self {
+                                #(#variant_writes),*
+                            }
+                            #deferred_end
+            #bit_order_write
+
+                            Ok(acc)
+                        },
             &input.ctx,
             &input.ctx_default,
         );
@@ -278,27 +433,30 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
     let (ctx_types, ctx_arg) = gen_ctx_types_and_arg(input.ctx.as_ref())?;
 
     let write_body = quote! {
-        let mut acc: BitVec<Msb0, u8> = BitVec::new();
-
-        match self {
-            #(#variant_writes),*
-        }
-
-        Ok(acc)
-    };
-    tokens.extend(quote! {
-        impl #imp DekuUpdate for #ident #wher {
-            fn update(&mut self) -> Result<(), DekuError> {
-                use core::convert::TryInto;
-
-                match self {
-                    #(#variant_updates),*
-                }
+            #auto_update
+            #container_assert
+            let mut acc: BitVec<Msb0, u8> = BitVec::new();
+            #deferred_start
+            #magic_write
+
+            match #write_self {
+                #(#variant_writes),*
+            }
+            #deferred_end
+    #bit_order_write
 
-                Ok(())
+            Ok(acc)
+        };
+    tokens.extend(emit_update(
+        input,
+        quote! {
+            match self {
+                #(#variant_updates),*
             }
-        }
+        },
+    )?);
 
+    tokens.extend(quote! {
         impl #imp DekuWrite<#ctx_types> for #ident #wher {
             #[allow(unused_variables)]
             fn write(&self, #ctx_arg) -> Result<BitVec<Msb0, u8>, DekuError> {
@@ -320,10 +478,87 @@ fn emit_enum(input: &DekuData) -> Result<TokenStream, syn::Error> {
         });
     }
 
-    // println!("{}", tokens.to_string());
     Ok(tokens)
 }
 
+/// Emit `DekuUpdateCtx` for a container with a `ctx`, and `DekuUpdate` for a container without a
+/// `ctx` or with a `ctx_default`, running `updates` with the `ctx` variables in scope
+fn emit_update(input: &DekuData, updates: TokenStream) -> Result<TokenStream, syn::Error> {
+    let (imp, ty, wher) = input.generics.split_for_impl();
+    let ident = &input.ident;
+
+    let update_body = quote! {
+        use core::convert::TryInto;
+        #updates
+
+        Ok(())
+    };
+
+    let mut tokens = TokenStream::new();
+
+    if input.ctx.is_some() {
+        let (ctx_types, ctx_arg) = gen_ctx_types_and_arg(input.ctx.as_ref())?;
+
+        tokens.extend(quote! {
+            impl #imp DekuUpdateCtx<#ctx_types> for #ident #ty #wher {
+                #[allow(unused_variables)]
+                fn update_ctx(&mut self, #ctx_arg) -> Result<(), DekuError> {
+                    #update_body
+                }
+            }
+        });
+    }
+
+    if input.ctx.is_none() || input.ctx_default.is_some() {
+        let update_body = wrap_default_ctx(update_body, &input.ctx, &input.ctx_default);
+
+        tokens.extend(quote! {
+            impl #imp DekuUpdate for #ident #ty #wher {
+                #[allow(unused_variables)]
+                fn update(&mut self) -> Result<(), DekuError> {
+                    #update_body
+                }
+            }
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// With `auto_update`, generate the update of a clone of the container before it's written,
+/// with the `ctx` it's written with. Returns the update and the reference to the container to
+/// write.
+fn gen_auto_update(input: &DekuData) -> Result<(Option<TokenStream>, TokenStream), syn::Error> {
+    if !input.auto_update {
+        return Ok((None, quote! { self }));
+    }
+
+    let update = match &input.ctx {
+        Some(ctx) => {
+            let pats = split_ctx_to_pats_and_types(ctx)?
+                .into_iter()
+                .map(|(pat, _)| pat)
+                .collect::<Vec<_>>();
+            let ctx = if pats.len() == 1 {
+                quote! { #(#pats)* }
+            } else {
+                quote! { (#(#pats),*) }
+            };
+
+            quote! { DekuUpdateCtx::update_ctx(&mut __deku_updated, #ctx)?; }
+        }
+        None => quote! { DekuUpdate::update(&mut __deku_updated)?; },
+    };
+
+    Ok((
+        Some(quote! {
+            let mut __deku_updated = self.clone();
+            #update
+        }),
+        quote! { (&__deku_updated) },
+    ))
+}
+
 fn emit_field_writes(
     input: &DekuData,
     fields: &Fields<&FieldData>,
@@ -336,17 +571,76 @@ fn emit_field_writes(
         field_writes.push(field_write);
     }
 
+    // the checksums and signatures are written once the container is, they may be over the
+    // fields after
+    for (i, f) in fields.iter().enumerate() {
+        let (checksum, what) = if f.checksum.is_some() {
+            let checksum =
+                gen_checksum_compute(f, i, quote! { acc.clone() }, quote! { InvalidParam });
+            (checksum, "checksum")
+        } else if let Some(sign) = &f.sign {
+            let field_type = &f.ty;
+            let signature = gen_over_compute(
+                f,
+                i,
+                quote! { acc.clone() },
+                quote! { InvalidParam },
+                "signature",
+                quote! {
+                    let signature: Result<#field_type, DekuError> = (#sign)(over);
+                    signature?
+                },
+            );
+            (signature, "signature")
+        } else {
+            continue;
+        };
+
+        let bits_ident = gen_checksum_bits_ident(i);
+        let size_mismatch = format!(
+            "{}: {{}} bits written in place of the {{}} bits of the field",
+            what
+        );
+
+        let field_endian = f.endian.as_ref().or(input.endian.as_ref());
+        let write_args = gen_field_args(
+            field_endian,
+            f.bits.as_ref(),
+            f.bit_order.as_ref().or(input.bit_order.as_ref()),
+            f.encoding.as_ref(),
+            f.pad.as_ref(),
+            f.ctx.as_ref(),
+        )?;
+
+        field_writes.push(quote! {
+            {
+                let __deku_checksum = #checksum;
+                let bits = __deku_checksum.write((#write_args))?;
+                let (start, end) = #bits_ident;
+                if bits.len() != end - start {
+                    return Err(DekuError::InvalidParam(deku::error_msg!(
+                        #size_mismatch,
+                        bits.len(),
+                        end - start
+                    )));
+                }
+                acc[start..end].clone_from_bitslice(&bits);
+            }
+        });
+    }
+
     Ok(field_writes)
 }
 
 fn emit_field_updates(
+    input: &DekuData,
     fields: &Fields<&FieldData>,
     object_prefix: Option<TokenStream>,
 ) -> Result<Vec<TokenStream>, syn::Error> {
     let mut field_updates = vec![];
 
     for (i, f) in fields.iter().enumerate() {
-        let new_field_updates = emit_field_update(i, f, &object_prefix)?;
+        let new_field_updates = emit_field_update(input, fields, i, f, &object_prefix)?;
         field_updates.extend(new_field_updates);
     }
 
@@ -354,6 +648,8 @@ fn emit_field_updates(
 }
 
 fn emit_field_update(
+    input: &DekuData,
+    fields: &Fields<&FieldData>,
     i: usize,
     f: &FieldData,
     object_prefix: &Option<TokenStream>,
@@ -369,20 +665,138 @@ fn emit_field_update(
 
     if let Some(field_update) = &f.update {
         field_updates.push(quote! {
-            #deref #object_prefix #field_ident = #field_update.try_into()?;
+            #deref #object_prefix #field_ident = (#field_update).try_into()?;
+        })
+    }
+
+    if let Some(len_of) = &f.len_of {
+        let (j, target) = fields
+            .iter()
+            .enumerate()
+            .find(|(_, target)| {
+                target
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| ident == &len_of.value())
+            })
+            .ok_or_else(|| syn::Error::new(len_of.span(), "`len_of` field not found"))?;
+
+        if target.temp {
+            return Err(syn::Error::new(
+                len_of.span(),
+                "`len_of` field can't be `temp`",
+            ));
+        }
+
+        let target_ident = target.get_ident(j, object_prefix.is_none());
+        let target_endian = target.endian.as_ref().or(input.endian.as_ref());
+        let write_args = gen_field_args(
+            target_endian,
+            target.bits.as_ref(),
+            target.bit_order.as_ref().or(input.bit_order.as_ref()),
+            target.encoding.as_ref(),
+            target.pad.as_ref(),
+            target.ctx.as_ref(),
+        )?;
+
+        let len_offset = f.len_offset.clone().unwrap_or_else(|| quote! { 0 });
+
+        field_updates.push(quote! {
+            #deref #object_prefix #field_ident = {
+                let bit_len = #object_prefix #target_ident.write((#write_args))?.len();
+                if bit_len % 8 != 0 {
+                    return Err(DekuError::InvalidParam(deku::error_msg!(
+                        "len_of: {} bits is not a multiple of 8",
+                        bit_len
+                    )));
+                }
+
+                let len = <i128 as core::convert::TryFrom<usize>>::try_from(bit_len / 8)?
+                    + (#len_offset) as i128;
+                len.try_into()?
+            };
         })
     }
 
     Ok(field_updates)
 }
 
+/// Write the magic requested with `#[deku(magic = b"...")]`
+fn emit_magic_write(magic: &syn::LitByteStr) -> TokenStream {
+    quote! {
+        let magic: &[u8] = #magic;
+        acc.extend(magic.view_bits::<Msb0>().to_bitvec());
+    }
+}
+
+/// Replace the write of the last field by the checksum requested with
+/// `#[deku(trailer_checksum = "...")]`, computed over the bytes written before it
+fn emit_checksum_write(
+    input: &DekuData,
+    checksum: &TokenStream,
+    fields: &Fields<&FieldData>,
+    field_writes: &mut [TokenStream],
+) -> Result<(), syn::Error> {
+    // checked in `DekuData::validate`
+    let i = fields.len() - 1;
+    let f = fields.fields[i];
+    let field_type = &f.ty;
+
+    let field_endian = f.endian.as_ref().or(input.endian.as_ref());
+    let write_args = gen_field_args(
+        field_endian,
+        f.bits.as_ref(),
+        f.bit_order.as_ref().or(input.bit_order.as_ref()),
+        f.encoding.as_ref(),
+        f.pad.as_ref(),
+        f.ctx.as_ref(),
+    )?;
+
+    let seek = gen_seek_write(f);
+
+    let pad_before = gen_pad_write(gen_pad_bits(
+        f.pad_bits_before.as_ref(),
+        f.pad_bytes_before.as_ref(),
+    ));
+
+    let pad_after = gen_pad_write(gen_pad_bits(
+        f.pad_bits_after.as_ref(),
+        f.pad_bytes_after.as_ref(),
+    ));
+
+    let align = gen_pad_write(gen_align_pad_bits(
+        f.align_bits.as_ref(),
+        f.align_bytes.as_ref(),
+        quote! { acc.len() },
+    ));
+
+    field_writes[i] = quote! {
+        if acc.len() % 8 != 0 {
+            return Err(DekuError::InvalidParam(deku::error_msg!(
+                "checksum: {} bits written is not a multiple of 8",
+                acc.len()
+            )));
+        }
+
+        let __deku_checksum: #field_type = (#checksum)(acc.as_slice());
+        #seek
+        #pad_before
+        #align
+        let bits = __deku_checksum.write((#write_args))?;
+        acc.extend(bits);
+        #pad_after
+    };
+
+    Ok(())
+}
+
 fn emit_field_write(
     input: &DekuData,
     i: usize,
     f: &FieldData,
     object_prefix: &Option<TokenStream>,
 ) -> Result<TokenStream, syn::Error> {
-    let field_endian = f.endian.as_ref().or_else(|| input.endian.as_ref());
+    let field_endian = f.endian.as_ref().or(input.endian.as_ref());
 
     let field_writer = &f.writer;
     let field_ident = f.get_ident(i, object_prefix.is_none());
@@ -390,17 +804,87 @@ fn emit_field_write(
     let field_write_func = if field_writer.is_some() {
         quote! { #field_writer }
     } else {
-        let write_args = gen_field_args(field_endian, f.bits, f.ctx.as_ref())?;
-
-        quote! { #object_prefix #field_ident.write((#write_args)) }
+        let write_args = gen_field_args(
+            field_endian,
+            f.bits.as_ref(),
+            f.bit_order.as_ref().or(input.bit_order.as_ref()),
+            f.encoding.as_ref(),
+            f.pad.as_ref(),
+            f.ctx.as_ref(),
+        )?;
+
+        if let Some(field_with) = &f.with {
+            let value = match &f.normalize {
+                Some(field_normalize) => {
+                    quote! { &(#field_normalize)(#object_prefix #field_ident) }
+                }
+                None => quote! { #object_prefix #field_ident },
+            };
+            quote! { #field_with::write(#value, (#write_args)) }
+        } else if let Some(field_bcd) = &f.bcd {
+            // #[deku(bcd)] ==> write with the `bcd` codec
+            let bcd_args = gen_bcd_args(f, field_bcd)?;
+            quote! { deku::codecs::bcd::write(#object_prefix #field_ident, #bcd_args) }
+        } else if let Some(field_fixed) = &f.fixed {
+            // #[deku(fixed = "Q8.8")] ==> write with the `fixed_point` codec
+            let fixed_args = gen_fixed_point_args(field_endian, field_fixed)?;
+            quote! { deku::codecs::fixed_point::write(#object_prefix #field_ident, #fixed_args) }
+        } else if f.sign_magnitude {
+            // #[deku(signed = "magnitude")] ==> write with the `sign_magnitude` codec
+            let sign_magnitude_args = gen_sign_magnitude_args(field_endian, f)?;
+            quote! { deku::codecs::sign_magnitude::write(#object_prefix #field_ident, #sign_magnitude_args) }
+        } else if let Some(field_map_write) = &f.map_write {
+            quote! { (#field_map_write)(#object_prefix #field_ident)?.write((#write_args)) }
+        } else if let Some(field_normalize) = &f.normalize {
+            quote! { (#field_normalize)(#object_prefix #field_ident).write((#write_args)) }
+        } else if let Some(field_read_as) = &f.read_as {
+            let field_type = &f.ty;
+            let error = format!(
+                "error converting `{}` to `{}`",
+                quote! { #field_type },
+                field_read_as
+            );
+            quote! {
+                <#field_read_as as core::convert::TryFrom<#field_type>>::try_from((#object_prefix #field_ident).clone())
+                    .map_err(|_| DekuError::InvalidParam(deku::error_msg!(#error)))?
+                    .write((#write_args))
+            }
+        } else {
+            quote! { #object_prefix #field_ident.write((#write_args)) }
+        }
     };
 
-    let field_write_normal = quote! {
-        let bits = #field_write_func ?;
-        acc.extend(bits);
+    let field_write_normal = match &f.prefix {
+        Some(field_prefix) => {
+            // #[deku(prefix = "u8")] ==> write the byte length of the field, then the field
+            let prefix_args = gen_field_args(field_endian, None, None, None, None, None)?;
+            let error = format!("prefix: length {{}} does not fit in a `{}`", field_prefix);
+            quote! {
+                let bits = #field_write_func ?;
+                if bits.len() % 8 != 0 {
+                    return Err(DekuError::InvalidParam(deku::error_msg!(
+                        "prefix: {} bits is not a multiple of 8",
+                        bits.len()
+                    )));
+                }
+
+                let __deku_prefix = <#field_prefix as core::convert::TryFrom<usize>>::try_from(bits.len() / 8)
+                    .map_err(|_| DekuError::InvalidParam(deku::error_msg!(#error, bits.len() / 8)))?;
+                acc.extend(__deku_prefix.write((#prefix_args))?);
+                acc.extend(bits);
+            }
+        }
+        None => quote! {
+            let bits = #field_write_func ?;
+            acc.extend(bits);
+        },
     };
 
-    let field_write_tokens = match (f.skip, &f.cond) {
+    let field_write_tokens = match (f.skip || f.skip_write || f.id_field, &f.cond) {
+        _ if f.peek => {
+            // #[deku(peek)] ==> written by the fields after it
+            quote! {}
+        }
         (true, Some(field_cond)) => {
             // #[deku(skip, cond = "...")] ==> `skip` if `cond`
             quote! {
@@ -424,8 +908,120 @@ fn emit_field_write(
         }
     };
 
+    // #[deku(write_cond = "...")] ==> write only if `write_cond`
+    let field_write_tokens = match &f.write_cond {
+        Some(field_write_cond) => quote! {
+            if (#field_write_cond) {
+                #field_write_tokens
+            }
+        },
+        None => field_write_tokens,
+    };
+
+    let field_assert = f
+        .assert
+        .as_ref()
+        .map(|assert| gen_field_assert(&field_ident, assert, &quote! { acc.len() }))
+        .transpose()?;
+    // an assertion over the position after the field is checked once it's written
+    let (field_assert, field_assert_after) = match field_assert {
+        Some((field_assert, true)) => (None, Some(field_assert)),
+        Some((field_assert, false)) => (Some(field_assert), None),
+        None => (None, None),
+    };
+    let field_assert_eq = f
+        .assert_eq
+        .as_ref()
+        .map(|expected| gen_field_assert_eq(&field_ident, expected));
+
+    let pad_before = gen_pad_write(gen_pad_bits(
+        f.pad_bits_before.as_ref(),
+        f.pad_bytes_before.as_ref(),
+    ));
+
+    let pad_after = gen_pad_write(gen_pad_bits(
+        f.pad_bits_after.as_ref(),
+        f.pad_bytes_after.as_ref(),
+    ));
+
+    let align = gen_pad_write(gen_align_pad_bits(
+        f.align_bits.as_ref(),
+        f.align_bytes.as_ref(),
+        quote! { acc.len() },
+    ));
+
+    let seek = gen_seek_write(f);
+
+    let align_before = f
+        .align_before
+        .then(|| gen_align_check(&field_ident, quote! { acc.len() }, "before"));
+    let align_after = f
+        .align_after
+        .then(|| gen_align_check(&field_ident, quote! { acc.len() }, "after"));
+
+    // #[deku(process_write = "...")] ==> write, then transform the bytes written
+    let field_write_tokens = match (&f.process, &f.process_write) {
+        (_, Some(process_write)) => gen_process_write(process_write, field_write_tokens),
+        (Some(process), None) => {
+            return Err(syn::Error::new(
+                process.span(),
+                "`process` field requires `process_write` to be written",
+            ))
+        }
+        (None, None) => field_write_tokens,
+    };
+
+    let field_write_tokens = match &f.limit_bytes {
+        Some(limit_bytes) => gen_limit_write(limit_bytes, field_write_tokens),
+        None => field_write_tokens,
+    };
+
+    let field_write_tokens = match &f.offset {
+        Some(offset) => gen_offset_write(offset, field_write_tokens),
+        None => field_write_tokens,
+    };
+
+    // `temp` fields aren't part of the struct, bind the value to write in their place
+    let temp_value = if f.temp {
+        let field_type = &f.ty;
+        let temp_value = f.temp_value.as_ref().ok_or_else(|| {
+            syn::Error::new(
+                field_type.span(),
+                "`temp` field requires `temp_value` to be written",
+            )
+        })?;
+
+        Some(quote! {
+            let #field_ident: &#field_type = &(#temp_value);
+        })
+    } else {
+        None
+    };
+
+    // #[deku(checksum = "...")] ==> save the bits of the field, written once the container is
+    let field_write_tokens = if f.is_over_bytes() {
+        let bits_ident = gen_checksum_bits_ident(i);
+        quote! {
+            let __deku_checksum_start = acc.len();
+            #field_write_tokens
+            let #bits_ident = (__deku_checksum_start, acc.len());
+        }
+    } else {
+        field_write_tokens
+    };
+
     let field_write = quote! {
+        #temp_value
+        #field_assert
+        #field_assert_eq
+        #seek
+        #pad_before
+        #align
+        #align_before
         #field_write_tokens
+        #align_after
+        #field_assert_after
+        #pad_after
     };
 
     Ok(field_write)
@@ -1,9 +1,17 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 
+use crate::{DekuData, FieldData};
+
+pub(crate) mod deku_codec;
+pub(crate) mod deku_doc;
+pub(crate) mod deku_ffi;
+pub(crate) mod deku_python;
 pub(crate) mod deku_read;
+pub(crate) mod deku_size;
+pub(crate) mod deku_view;
 pub(crate) mod deku_write;
 
 /// Generate enum initialization TokenStream
@@ -160,13 +168,18 @@ fn gen_ctx_types_and_arg(
 }
 
 /// Generate argument for `id`:
-/// `#deku(endian = "big", bits = "1")` -> `Endian::Big, BitSize(1)`
-fn gen_id_args(endian: Option<&syn::LitStr>, bits: Option<usize>) -> syn::Result<TokenStream> {
+/// `#deku(endian = "big", bits = "1", bit_order = "lsb")` -> `Endian::Big, BitSize(1), Order::Lsb0`
+fn gen_id_args(
+    endian: Option<&syn::LitStr>,
+    bits: Option<usize>,
+    bit_order: Option<&syn::LitStr>,
+) -> syn::Result<TokenStream> {
     let endian = endian.map(gen_endian_from_str).transpose()?;
     let bits = bits.map(|n| quote! {deku::ctx::BitSize(#n)});
+    let bit_order = bit_order.map(gen_bit_order_from_str).transpose()?;
 
     // FIXME: Should be `into_iter` here, see https://github.com/rust-lang/rust/issues/66145.
-    let id_args = [endian.as_ref(), bits.as_ref()]
+    let id_args = [endian.as_ref(), bits.as_ref(), bit_order.as_ref()]
         .iter()
         .filter_map(|i| *i)
         .collect::<Vec<_>>();
@@ -179,21 +192,35 @@ fn gen_id_args(endian: Option<&syn::LitStr>, bits: Option<usize>) -> syn::Result
 
 /// Generate argument for fields:
 ///
-/// `#deku(endian = "big", bits = "1", ctx = "a")` -> `Endian::Big, BitSize(1), a`
+/// `#deku(endian = "big", bits = "1", bit_order = "lsb", encoding = "utf8", pad = "0x20", ctx = "a")`
+/// -> `Endian::Big, BitSize(1), Order::Lsb0, Encoding::Utf8, Pad(0x20), a`
 fn gen_field_args(
     endian: Option<&syn::LitStr>,
-    bits: Option<usize>,
+    bits: Option<&TokenStream>,
+    bit_order: Option<&syn::LitStr>,
+    encoding: Option<&syn::LitStr>,
+    pad: Option<&TokenStream>,
     ctx: Option<&Punctuated<syn::Expr, syn::token::Comma>>,
 ) -> syn::Result<TokenStream> {
     let endian = endian.map(gen_endian_from_str).transpose()?;
     let bits = bits.map(|n| quote! {deku::ctx::BitSize(#n)});
+    let bit_order = bit_order.map(gen_bit_order_from_str).transpose()?;
+    let encoding = encoding.map(gen_encoding_from_str).transpose()?;
+    let pad = pad.map(|p| quote! {deku::ctx::Pad(#p)});
     let ctx = ctx.map(|c| quote! {#c});
 
     // FIXME: Should be `into_iter` here, see https://github.com/rust-lang/rust/issues/66145.
-    let field_args = [endian.as_ref(), bits.as_ref(), ctx.as_ref()]
-        .iter()
-        .filter_map(|i| *i)
-        .collect::<Vec<_>>();
+    let field_args = [
+        endian.as_ref(),
+        bits.as_ref(),
+        bit_order.as_ref(),
+        encoding.as_ref(),
+        pad.as_ref(),
+        ctx.as_ref(),
+    ]
+    .iter()
+    .filter_map(|i| *i)
+    .collect::<Vec<_>>();
 
     // Because `impl DekuRead<(T1, T2)>` but `impl DekuRead<T1>`(not tuple)
     match &field_args[..] {
@@ -215,6 +242,717 @@ fn gen_endian_from_str(s: &syn::LitStr) -> syn::Result<TokenStream> {
     }
 }
 
+/// Generate bit order tokens from string: `lsb` -> `Order::Lsb0`.
+fn gen_bit_order_from_str(s: &syn::LitStr) -> syn::Result<TokenStream> {
+    match s.value().as_str() {
+        "lsb" => Ok(quote! {deku::ctx::Order::Lsb0}),
+        "msb" => Ok(quote! {deku::ctx::Order::Msb0}),
+        _ => {
+            // treat as variable, possibly from `ctx`
+            let v: TokenStream = s.value().parse()?;
+            Ok(quote! {#v})
+        }
+    }
+}
+
+/// Generate encoding tokens from string: `utf16le` -> `Encoding::Utf16Le`.
+fn gen_encoding_from_str(s: &syn::LitStr) -> syn::Result<TokenStream> {
+    match s.value().as_str() {
+        "utf8" => Ok(quote! {deku::ctx::Encoding::Utf8}),
+        "utf16le" => Ok(quote! {deku::ctx::Encoding::Utf16Le}),
+        "utf16be" => Ok(quote! {deku::ctx::Encoding::Utf16Be}),
+        "ascii" => Ok(quote! {deku::ctx::Encoding::Ascii}),
+        "latin1" => Ok(quote! {deku::ctx::Encoding::Latin1}),
+        _ => {
+            // treat as variable, possibly from `ctx`
+            let v: TokenStream = s.value().parse()?;
+            Ok(quote! {#v})
+        }
+    }
+}
+
+/// Generate nibble order tokens from string: `little` -> `NibbleOrder::Little`.
+fn gen_nibble_order_from_str(s: &syn::LitStr) -> syn::Result<TokenStream> {
+    match s.value().as_str() {
+        "big" => Ok(quote! {deku::ctx::NibbleOrder::Big}),
+        "little" => Ok(quote! {deku::ctx::NibbleOrder::Little}),
+        _ => {
+            // treat as variable, possibly from `ctx`
+            let v: TokenStream = s.value().parse()?;
+            Ok(quote! {#v})
+        }
+    }
+}
+
+/// Generate the context of the `bcd` codec: the bit size of the field, the size of its type by
+/// default, and the nibble order
+fn gen_bcd_args(f: &FieldData, order: &syn::LitStr) -> syn::Result<TokenStream> {
+    let field_type = &f.ty;
+    let bit_size = match &f.bits {
+        Some(bits) => quote! { deku::ctx::BitSize(#bits) },
+        None => quote! { deku::ctx::BitSize::of::<#field_type>() },
+    };
+    let order = gen_nibble_order_from_str(order)?;
+
+    Ok(quote! { (#bit_size, #order) })
+}
+
+/// Generate the context of the `fixed_point` codec: the endian of the field, the default endian
+/// if not set, and the format
+fn gen_fixed_point_args(
+    endian: Option<&syn::LitStr>,
+    fixed: &TokenStream,
+) -> syn::Result<TokenStream> {
+    let endian = match endian {
+        Some(endian) => gen_endian_from_str(endian)?,
+        None => quote! { deku::ctx::Endian::default() },
+    };
+
+    Ok(quote! { (#endian, #fixed) })
+}
+
+/// Generate the context of the `sign_magnitude` codec: the endian of the field, the default
+/// endian if not set, and the bit size of the field, the size of its type by default
+fn gen_sign_magnitude_args(
+    endian: Option<&syn::LitStr>,
+    f: &FieldData,
+) -> syn::Result<TokenStream> {
+    let field_type = &f.ty;
+    let endian = match endian {
+        Some(endian) => gen_endian_from_str(endian)?,
+        None => quote! { deku::ctx::Endian::default() },
+    };
+    let bit_size = match &f.bits {
+        Some(bits) => quote! { deku::ctx::BitSize(#bits) },
+        None => quote! { deku::ctx::BitSize::of::<#field_type>() },
+    };
+
+    Ok(quote! { (#endian, #bit_size) })
+}
+
+/// Generate reading the bytes of a container with `#[deku(bit_order = "lsb")]`: `read` is run
+/// over the reordered bytes, then the bytes read are skipped in `rest`
+///
+/// The size of the container isn't known before reading it, so `read` is run over a window of
+/// the input, doubled until the read succeeds without reaching the end of the window, to only
+/// reorder about the bytes read instead of all of the input for each container
+fn gen_bit_order_read(bit_order: Option<&syn::LitStr>, read: TokenStream) -> TokenStream {
+    match bit_order {
+        Some(bit_order) if bit_order.value() == "lsb" => quote! {
+            {
+                if rest.len() % 8 != 0 {
+                    return Err(DekuError::Parse(deku::error_msg!(
+                        "bit_order: an lsb container must start on a byte boundary"
+                    )));
+                }
+
+                let mut __deku_window = rest.len().min(64);
+                loop {
+                    let __deku_reordered =
+                        deku::ctx::Order::Lsb0.reorder(&rest[..__deku_window]);
+                    let read = (|| -> Result<_, DekuError> {
+                        #[allow(unused_mut)]
+                        let mut rest = __deku_reordered.as_bitslice();
+                        let value = #read;
+                        Ok((value, rest.len()))
+                    })();
+
+                    match read {
+                        // a read reaching the end of the window may have been cut short by it
+                        Ok((value, rest_len)) if rest_len != 0 || __deku_window == rest.len() => {
+                            let read_bits = __deku_window - rest_len;
+                            rest = &rest[read_bits.div_ceil(8) * 8..];
+                            break value;
+                        }
+                        Err(e) if __deku_window == rest.len() => return Err(e),
+                        _ => __deku_window = (__deku_window * 2).min(rest.len()),
+                    }
+                }
+            }
+        },
+        _ => read,
+    }
+}
+
+/// Generate writing the bytes of a container with `#[deku(bit_order = "lsb")]`, reordering
+/// `acc` once written
+fn gen_bit_order_write(bit_order: Option<&syn::LitStr>) -> Option<TokenStream> {
+    match bit_order {
+        Some(bit_order) if bit_order.value() == "lsb" => Some(quote! {
+            let acc = deku::ctx::Order::Lsb0.reorder(&acc);
+        }),
+        _ => None,
+    }
+}
+
+/// Generate the check of the `assert` attribute, with the field in scope as a reference:
+///
+/// `#deku(assert = "*a < 3")` -> `if !(*a < 3) { deku::options::assertion_failed(..)?; }`
+///
+/// `deku::is_byte_aligned()` is replaced by the check of `pos`, the position after the field,
+/// returned with whether it's used
+fn gen_field_assert(
+    field_ident: &TokenStream,
+    assert: &syn::LitStr,
+    pos: &TokenStream,
+) -> syn::Result<(TokenStream, bool)> {
+    let (cond, uses_pos) = replace_is_byte_aligned(assert.parse()?, pos);
+    let msg = gen_error_msg(
+        &format!(
+            "field `{}` failed assertion: {}",
+            field_ident,
+            escape_fmt(&assert.value())
+        ),
+        &[],
+    );
+
+    let assert = quote! {
+        if !(#cond) {
+            deku::options::assertion_failed(#msg)?;
+        }
+    };
+
+    Ok((assert, uses_pos))
+}
+
+/// Generate the check of the top-level `assert` attribute, with `self` referring to `container`:
+///
+/// `#deku(assert = "self.a < 3")` -> `if !(__deku_self.a < 3) { deku::options::assertion_failed(..)?; }`
+fn gen_container_assert(
+    input: &DekuData,
+    container: TokenStream,
+) -> syn::Result<Option<TokenStream>> {
+    let assert = match &input.assert {
+        Some(assert) => assert,
+        None => return Ok(None),
+    };
+
+    let cond = replace_self(assert.parse()?);
+    let msg = gen_error_msg(
+        &format!(
+            "`{}` failed assertion: {}",
+            input.ident,
+            escape_fmt(&assert.value())
+        ),
+        &[],
+    );
+
+    Ok(Some(quote! {
+        {
+            let __deku_self = #container;
+            if !(#cond) {
+                deku::options::assertion_failed(#msg)?;
+            }
+        }
+    }))
+}
+
+/// Replace `self` by `__deku_self`, `self` isn't available in the generated functions
+fn replace_self(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "self" => {
+                TokenTree::Ident(syn::Ident::new("__deku_self", ident.span()))
+            }
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(group.delimiter(), replace_self(group.stream()));
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+/// Replace `deku::is_byte_aligned()` by `(#pos % 8 == 0)`, `pos` being a position in bits,
+/// returning whether it was found
+fn replace_is_byte_aligned(tokens: TokenStream, pos: &TokenStream) -> (TokenStream, bool) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut replaced = Vec::with_capacity(tokens.len());
+    let mut found = false;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let is_call = match &tokens[i..] {
+            [TokenTree::Ident(deku), TokenTree::Punct(colon_a), TokenTree::Punct(colon_b), TokenTree::Ident(func), TokenTree::Group(args), ..] => {
+                deku == "deku"
+                    && colon_a.as_char() == ':'
+                    && colon_b.as_char() == ':'
+                    && func == "is_byte_aligned"
+                    && args.delimiter() == Delimiter::Parenthesis
+                    && args.stream().is_empty()
+            }
+            _ => false,
+        };
+
+        if is_call {
+            let mut check = Group::new(Delimiter::Parenthesis, quote! { (#pos) % 8 == 0 });
+            check.set_span(tokens[i].span());
+            replaced.push(TokenTree::Group(check));
+            found = true;
+            i += 5;
+            continue;
+        }
+
+        replaced.push(match &tokens[i] {
+            TokenTree::Group(group) => {
+                let (stream, group_found) = replace_is_byte_aligned(group.stream(), pos);
+                found |= group_found;
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group)
+            }
+            token => token.clone(),
+        });
+        i += 1;
+    }
+
+    (replaced.into_iter().collect(), found)
+}
+
+/// Generate the check of `align_before` or `align_after`, `pos` being the position in bits within
+/// the container before or after the field
+fn gen_align_check(field_ident: &TokenStream, pos: TokenStream, when: &str) -> TokenStream {
+    let msg = gen_error_msg(
+        &format!(
+            "field `{}` failed assertion: not byte aligned {} the field, at bit {{}}",
+            field_ident, when
+        ),
+        &[quote! { __deku_pos }],
+    );
+
+    quote! {
+        {
+            let __deku_pos = #pos;
+            if __deku_pos % 8 != 0 {
+                deku::options::assertion_failed(#msg)?;
+            }
+        }
+    }
+}
+
+/// Generate the check of the `assert_eq` attribute, with the field in scope as a reference:
+///
+/// `#deku(assert_eq = "0x01")` -> `if *a != 0x01 { deku::options::assertion_failed(..)?; }`
+fn gen_field_assert_eq(field_ident: &TokenStream, expected: &TokenStream) -> TokenStream {
+    let msg = gen_error_msg(
+        &format!(
+            "field `{}` failed assertion: expected {{:?}} got {{:?}}",
+            field_ident
+        ),
+        &[quote! { __deku_expected }, quote! { *#field_ident }],
+    );
+
+    quote! {
+        {
+            let __deku_expected = #expected;
+            if *#field_ident != __deku_expected {
+                deku::options::assertion_failed(#msg)?;
+            }
+        }
+    }
+}
+
+/// Escape `{` and `}` in `s`, to use it in the format string of `gen_error_msg`
+fn escape_fmt(s: &str) -> String {
+    s.replace('{', "{{").replace('}', "}}")
+}
+
+/// Generate the message of an error, `format!(#fmt, #args)` as a `Cow`, which doesn't allocate
+/// with the `static-errors` feature
+fn gen_error_msg(fmt: &str, args: &[TokenStream]) -> TokenStream {
+    if args.is_empty() {
+        let msg = fmt.replace("{{", "{").replace("}}", "}");
+        quote! { deku::error_msg!(#msg) }
+    } else {
+        quote! { deku::error_msg!(#fmt, #(#args),*) }
+    }
+}
+
+/// Generate the number of bits of a number of bytes given by the attribute `what`, a number of
+/// bits which doesn't fit in a `usize` is an error
+pub(crate) fn gen_bytes_to_bits(bytes: &TokenStream, what: &str) -> TokenStream {
+    let overflow = gen_error_msg(
+        &format!("{}: {{}} bytes is too large to be counted in bits", what),
+        &[quote! { bytes }],
+    );
+
+    quote! {
+        {
+            let bytes = <usize as core::convert::TryFrom<_>>::try_from(#bytes)?;
+            bytes
+                .checked_mul(8)
+                .ok_or_else(|| DekuError::Parse(#overflow))?
+        }
+    }
+}
+
+/// Generate the number of bits of padding from the `pad_bits_*` or `pad_bytes_*` attributes
+fn gen_pad_bits(bits: Option<&TokenStream>, bytes: Option<&TokenStream>) -> Option<TokenStream> {
+    match (bits, bytes) {
+        (Some(bits), _) => Some(quote! {
+            <usize as core::convert::TryFrom<_>>::try_from(#bits)?
+        }),
+        (None, Some(bytes)) => Some(gen_bytes_to_bits(bytes, "padding")),
+        (None, None) => None,
+    }
+}
+
+/// Generate the number of bits of padding up to the alignment from the `align_bits` or
+/// `align_bytes` attributes, `pos` being the position in bits within the container
+fn gen_align_pad_bits(
+    bits: Option<&TokenStream>,
+    bytes: Option<&TokenStream>,
+    pos: TokenStream,
+) -> Option<TokenStream> {
+    gen_pad_bits(bits, bytes).map(|align| {
+        quote! {
+            {
+                let align = #align;
+                if align == 0 {
+                    return Err(DekuError::InvalidParam("align: alignment must not be 0".into()));
+                }
+                (align - (#pos) % align) % align
+            }
+        }
+    })
+}
+
+/// Generate skipping the padding when reading
+fn gen_pad_read(pad_bits: Option<TokenStream>) -> Option<TokenStream> {
+    pad_bits.map(|pad_bits| {
+        quote! {
+            {
+                let pad_bits = #pad_bits;
+                if rest.len() < pad_bits {
+                    return Err(DekuError::Incomplete(deku::error::NeedSize::new(pad_bits, rest.len())));
+                }
+                rest = &rest[pad_bits..];
+            }
+        }
+    })
+}
+
+/// Generate the padding when writing, zeros unless another fill byte is chosen by the write
+/// options
+fn gen_pad_write(pad_bits: Option<TokenStream>) -> Option<TokenStream> {
+    pad_bits.map(|pad_bits| {
+        quote! {
+            {
+                let pad_bits = #pad_bits;
+                deku::options::fill(&mut acc, pad_bits);
+            }
+        }
+    })
+}
+
+/// Generate moving the read position from the `seek_*` attributes, within the input of the
+/// container saved in `__deku_input`
+fn gen_seek_read(f: &FieldData) -> Option<TokenStream> {
+    if let Some(offset) = &f.seek_from_current {
+        Some(quote! {
+            {
+                let offset = <isize as core::convert::TryFrom<_>>::try_from(#offset)?;
+                let pos = <isize as core::convert::TryFrom<_>>::try_from(__deku_input.len() - rest.len())?;
+                let pos = offset
+                    .checked_mul(8)
+                    .and_then(|bits| pos.checked_add(bits))
+                    .ok_or_else(|| {
+                        DekuError::Parse(deku::error_msg!(
+                            "seek_from_current: can't seek by {} bytes",
+                            offset
+                        ))
+                    })?;
+                if pos < 0 || pos as usize > __deku_input.len() {
+                    return Err(DekuError::Parse(deku::error_msg!(
+                        "seek_from_current: can't seek to bit {} of {} bits",
+                        pos,
+                        __deku_input.len()
+                    )));
+                }
+                rest = &__deku_input[pos as usize..];
+            }
+        })
+    } else if f.seek_rewind {
+        Some(quote! {
+            rest = __deku_input;
+        })
+    } else {
+        None
+    }
+}
+
+/// Generate moving the write position from the `seek_*` attributes, only forward as padding
+fn gen_seek_write(f: &FieldData) -> Option<TokenStream> {
+    if let Some(offset) = &f.seek_from_current {
+        let pad_bits = gen_bytes_to_bits(&quote! { offset }, "seek_from_current");
+        Some(quote! {
+            {
+                let offset = <isize as core::convert::TryFrom<_>>::try_from(#offset)?;
+                if offset < 0 {
+                    return Err(DekuError::InvalidParam(deku::error_msg!(
+                        "seek_from_current: can't seek backwards by {} bytes when writing",
+                        -offset
+                    )));
+                }
+                let pad_bits = #pad_bits;
+                deku::options::fill(&mut acc, pad_bits);
+            }
+        })
+    } else if f.seek_rewind {
+        Some(quote! {
+            if !acc.is_empty() {
+                return Err(DekuError::InvalidParam(deku::error_msg!(
+                    "seek_rewind: can't seek backwards by {} bits when writing",
+                    acc.len()
+                )));
+            }
+        })
+    } else {
+        None
+    }
+}
+
+/// Generate reading a field with `offset` from the input of the container saved in
+/// `__deku_input`, leaving the current position unchanged
+fn gen_offset_read(offset: &TokenStream, field_read: TokenStream) -> TokenStream {
+    let offset = gen_bytes_to_bits(offset, "offset");
+
+    quote! {
+        {
+            let offset = #offset;
+            if offset > __deku_input.len() {
+                return Err(DekuError::Parse(deku::error_msg!(
+                    "offset: can't read at bit {} of {} bits",
+                    offset,
+                    __deku_input.len()
+                )));
+            }
+            #[allow(unused_mut)]
+            let mut rest = &__deku_input[offset..];
+            #field_read
+        }
+    }
+}
+
+/// Ident of the bits `(start, end)` of a field with `checksum`, `verify` or `sign` within its
+/// container
+fn gen_checksum_bits_ident(i: usize) -> syn::Ident {
+    syn::Ident::new(
+        &format!("__deku_checksum_bits_{}", i),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+/// Generate computing `compute` over the `over` bytes of the container bits `data`, with the bits
+/// of the field zeroed, bound to `over`
+///
+/// `error` is the `DekuError` variant returned when the bytes can't be taken, prefixed by `what`.
+fn gen_over_compute(
+    f: &FieldData,
+    i: usize,
+    data: TokenStream,
+    error: TokenStream,
+    what: &str,
+    compute: TokenStream,
+) -> TokenStream {
+    let bits_ident = gen_checksum_bits_ident(i);
+
+    let over = f.over.clone().unwrap_or_else(|| quote! { .. });
+    let over_str = over.to_string();
+    let not_aligned = format!(
+        "{}: {{}} bits of the container is not a multiple of 8",
+        what
+    );
+    let out_of_range = format!(
+        "{}: range `{{}}` is outside of the {{}} bytes of the container",
+        what
+    );
+
+    quote! {
+        {
+            let mut data: BitVec<Msb0, u8> = #data;
+            if data.len() % 8 != 0 {
+                return Err(DekuError::#error(deku::error_msg!(#not_aligned, data.len())));
+            }
+            data[#bits_ident.0..#bits_ident.1].set_all(false);
+            data.force_align();
+
+            let bytes = data.as_slice();
+            let over = bytes.get(#over).ok_or_else(|| {
+                DekuError::#error(deku::error_msg!(#out_of_range, #over_str, bytes.len()))
+            })?;
+            #compute
+        }
+    }
+}
+
+/// Generate computing the checksum of a field with `checksum` over the `over` bytes of the
+/// container bits `data`, with the bits of the field zeroed
+///
+/// `error` is the `DekuError` variant returned when the checksum can't be computed.
+fn gen_checksum_compute(
+    f: &FieldData,
+    i: usize,
+    data: TokenStream,
+    error: TokenStream,
+) -> TokenStream {
+    // checked by the caller
+    let checksum = f.checksum.as_ref().unwrap();
+    let field_type = &f.ty;
+
+    gen_over_compute(
+        f,
+        i,
+        data,
+        error,
+        "checksum",
+        quote! {
+            let checksum: #field_type = (#checksum)(over);
+            checksum
+        },
+    )
+}
+
+/// Generate reading a field with `limit_bytes` from a window of that many bytes, skipping what
+/// the field leaves of the window
+fn gen_limit_read(limit_bytes: &TokenStream, field_read: TokenStream) -> TokenStream {
+    let limit = gen_bytes_to_bits(limit_bytes, "limit_bytes");
+
+    quote! {
+        {
+            let limit = #limit;
+            if rest.len() < limit {
+                return Err(DekuError::Incomplete(deku::error::NeedSize::new(limit, rest.len())));
+            }
+            let (__deku_window, __deku_rest) = rest.split_at(limit);
+            let value = {
+                #[allow(unused_mut)]
+                let mut rest = __deku_window;
+                #field_read
+            };
+            rest = __deku_rest;
+            value
+        }
+    }
+}
+
+/// Generate writing a field with `limit_bytes`, padded up to the limit
+fn gen_limit_write(limit_bytes: &TokenStream, field_write: TokenStream) -> TokenStream {
+    let limit = gen_bytes_to_bits(limit_bytes, "limit_bytes");
+
+    quote! {
+        {
+            let limit = #limit;
+            let __deku_start = acc.len();
+            #field_write
+            let written = acc.len() - __deku_start;
+            if written > limit {
+                return Err(DekuError::InvalidParam(deku::error_msg!(
+                    "limit_bytes: {} bits written, more than the limit of {} bits",
+                    written, limit
+                )));
+            }
+            let pad_bits = __deku_start + limit - acc.len();
+            deku::options::fill(&mut acc, pad_bits);
+        }
+    }
+}
+
+/// Generate reading a field with `process` from the rest of the input transformed by the
+/// function, consuming the rest of the input
+fn gen_process_read(process: &TokenStream, field_read: TokenStream) -> TokenStream {
+    quote! {
+        {
+            let mut __deku_window = rest.to_bitvec();
+            if __deku_window.len() % 8 != 0 {
+                return Err(DekuError::Parse(deku::error_msg!(
+                    "process: {} bits to process is not a multiple of 8",
+                    __deku_window.len()
+                )));
+            }
+            __deku_window.force_align();
+            let __deku_processed: Vec<u8> = (#process)(__deku_window.as_slice())?;
+            let value = {
+                #[allow(unused_mut)]
+                let mut rest = __deku_processed.view_bits::<Msb0>();
+                #field_read
+            };
+            rest = &rest[rest.len()..];
+            value
+        }
+    }
+}
+
+/// Generate writing a field with `process_write` to its own `acc`, transformed by the function
+fn gen_process_write(process_write: &TokenStream, field_write: TokenStream) -> TokenStream {
+    quote! {
+        {
+            let __deku_processed: Vec<u8> = {
+                #[allow(unused_mut)]
+                let mut acc: BitVec<Msb0, u8> = BitVec::new();
+                #field_write
+                if acc.len() % 8 != 0 {
+                    return Err(DekuError::InvalidParam(deku::error_msg!(
+                        "process_write: {} bits written is not a multiple of 8",
+                        acc.len()
+                    )));
+                }
+                (#process_write)(acc.as_slice())?
+            };
+            acc.extend(BitVec::<Msb0, u8>::from_vec(__deku_processed));
+        }
+    }
+}
+
+/// Generate writing a field with `offset` to its own `acc`, placed by [`gen_deferred_writes`]
+/// once the container is written
+fn gen_offset_write(offset: &TokenStream, field_write: TokenStream) -> TokenStream {
+    let offset = gen_bytes_to_bits(offset, "offset");
+
+    quote! {
+        {
+            let offset = #offset;
+            #[allow(unused_mut)]
+            let mut acc: BitVec<Msb0, u8> = BitVec::new();
+            #field_write
+            __deku_deferred.push((offset, acc));
+        }
+    }
+}
+
+/// Generate the start and end of a container write placing the fields with `offset`, which
+/// must be after all the other data written
+fn gen_deferred_writes<'a>(
+    mut fields: impl Iterator<Item = &'a FieldData>,
+) -> (Option<TokenStream>, Option<TokenStream>) {
+    if !fields.any(|f| f.offset.is_some()) {
+        return (None, None);
+    }
+
+    let start = quote! {
+        let mut __deku_deferred: Vec<(usize, BitVec<Msb0, u8>)> = Vec::new();
+    };
+    let end = quote! {
+        __deku_deferred.sort_by_key(|(offset, _)| *offset);
+        for (offset, bits) in __deku_deferred {
+            if offset < acc.len() {
+                return Err(DekuError::InvalidParam(deku::error_msg!(
+                    "offset: field at bit {} overlaps the {} bits written before it",
+                    offset,
+                    acc.len()
+                )));
+            }
+            let pad_bits = offset - acc.len();
+            deku::options::fill(&mut acc, pad_bits);
+            acc.extend(bits);
+        }
+    };
+
+    (Some(start), Some(end))
+}
+
 /// Wraps a TokenStream with a closure providing access to `ctx` variables when
 /// `ctx_default` is provided
 fn wrap_default_ctx(
@@ -233,3 +971,133 @@ fn wrap_default_ctx(
         body
     }
 }
+
+/// Generate the owned equivalent of a field type and the conversion from the borrowed field:
+///
+/// - `&'a [T]` -> `Vec<T>`
+/// - `&'a str` -> `String`
+/// - `&'a T` -> `T`
+/// - `T` -> `T` (cloned)
+///
+/// A borrow nested in the type, such as `Option<&'a [u8]>` or `Inner<'a>`, has no owned
+/// equivalent and is an error
+fn gen_owned_field(ty: &syn::Type, field: TokenStream) -> syn::Result<(TokenStream, TokenStream)> {
+    let (owned_ty, owned_field) = if let syn::Type::Reference(reference) = ty {
+        let elem = &reference.elem;
+        let owned_ty = match elem.as_ref() {
+            syn::Type::Slice(slice) => {
+                let slice_elem = &slice.elem;
+                quote! { Vec<#slice_elem> }
+            }
+            syn::Type::Path(path) if path.path.is_ident("str") => quote! { String },
+            _ => quote! { #elem },
+        };
+
+        (owned_ty, quote! { ToOwned::to_owned(#field) })
+    } else {
+        (quote! { #ty }, quote! { Clone::clone(&#field) })
+    };
+
+    if has_lifetime(owned_ty.clone()) {
+        return Err(syn::Error::new(
+            ty.span(),
+            "`owned` only supports borrows at the top of a field type, such as `&'a [u8]`, not nested borrows such as `Option<&'a [u8]>` or `Inner<'a>`",
+        ));
+    }
+
+    Ok((owned_ty, owned_field))
+}
+
+/// Whether a lifetime other than `'static` appears in `tokens`
+fn has_lifetime(tokens: TokenStream) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+            !matches!(tokens.get(i + 1), Some(TokenTree::Ident(ident)) if ident == "static")
+        }
+        TokenTree::Group(group) => has_lifetime(group.stream()),
+        _ => false,
+    })
+}
+
+/// Generate the conversion from an owned field back to the borrowed field
+fn gen_borrowed_field(ty: &syn::Type, field: TokenStream) -> TokenStream {
+    if let syn::Type::Reference(_) = ty {
+        quote! { core::borrow::Borrow::borrow(&#field) }
+    } else {
+        quote! { Clone::clone(&#field) }
+    }
+}
+
+/// Remove all lifetimes from generics, used for the generated owned struct
+fn gen_owned_generics(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+
+    generics.params = generics
+        .params
+        .into_iter()
+        .filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+        .collect();
+
+    if let Some(where_clause) = generics.where_clause.as_mut() {
+        where_clause.predicates = where_clause
+            .predicates
+            .clone()
+            .into_iter()
+            .filter(|predicate| !matches!(predicate, syn::WherePredicate::Lifetime(_)))
+            .collect();
+    }
+
+    generics
+}
+
+/// Generate the type generics with each lifetime replaced by `'_`
+///
+/// `Foo<'a, T>` -> `<'_, T>`
+fn gen_anonymous_lifetime_generics(generics: &syn::Generics) -> TokenStream {
+    if generics.params.is_empty() {
+        return quote! {};
+    }
+
+    let params = generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(_) => quote! { '_ },
+        syn::GenericParam::Type(ty) => {
+            let ident = &ty.ident;
+            quote! { #ident }
+        }
+        syn::GenericParam::Const(c) => {
+            let ident = &c.ident;
+            quote! { #ident }
+        }
+    });
+
+    quote! { <#(#params),*> }
+}
+
+/// Generate the generics for a `DekuRead` impl and the lifetime of the input
+///
+/// The first lifetime of the type is used as the input lifetime, if there is none, a
+/// `'__deku_input` lifetime is added to the generics.
+fn gen_read_generics(generics: &syn::Generics) -> (syn::Generics, syn::Lifetime) {
+    if let Some(l) = generics.lifetimes().next() {
+        return (generics.clone(), l.lifetime.clone());
+    }
+
+    let lifetime = syn::Lifetime::new("'__deku_input", proc_macro2::Span::call_site());
+
+    let mut generics = generics.clone();
+    generics.params.insert(
+        0,
+        syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime.clone())),
+    );
+
+    (generics, lifetime)
+}
+
+/// Lifetime used to borrow from the owned struct, the first lifetime of the borrowed struct
+fn gen_owned_lifetime(generics: &syn::Generics) -> TokenStream {
+    generics.lifetimes().next().map_or(quote! { '_ }, |l| {
+        let lifetime = &l.lifetime;
+        quote! { #lifetime }
+    })
+}
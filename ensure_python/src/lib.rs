@@ -0,0 +1,25 @@
+use deku::prelude::*;
+use pyo3::prelude::*;
+
+#[pyclass(get_all, set_all)]
+#[derive(Debug, PartialEq, DekuRead, DekuWrite, DekuPython)]
+#[deku(endian = "big")]
+pub struct Header {
+    pub version: u8,
+    pub length: u16,
+}
+
+#[pyclass(get_all, set_all)]
+#[derive(Debug, PartialEq, DekuRead, DekuWrite, DekuPython)]
+#[deku(magic = b"dk")]
+pub struct Packet {
+    pub count: u8,
+    #[deku(count = "count", assert = "data.len() == *count as usize")]
+    pub data: Vec<u8>,
+}
+
+#[pymodule]
+fn ensure_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Header>()?;
+    m.add_class::<Packet>()
+}
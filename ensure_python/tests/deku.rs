@@ -0,0 +1,62 @@
+use ensure_python::{Header, Packet};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+fn run(code: &str) -> PyResult<()> {
+    Python::with_gil(|py| {
+        let locals = PyDict::new_bound(py);
+        locals.set_item("Header", py.get_type_bound::<Header>())?;
+        locals.set_item("Packet", py.get_type_bound::<Packet>())?;
+        py.run_bound(code, None, Some(&locals))
+    })
+}
+
+#[test]
+fn test_parse_build() {
+    run(r#"
+header = Header.parse(b"\x01\x00\x10")
+assert header.version == 1
+assert header.length == 16
+header.length = 32
+assert header.build() == b"\x01\x00\x20"
+
+packet = Packet.parse(b"dk\x02\xAA\xBB")
+assert packet.data == [0xAA, 0xBB]
+packet.count = 1
+packet.data = [0xCC]
+assert packet.build() == b"dk\x01\xCC"
+"#)
+    .unwrap();
+}
+
+#[test]
+fn test_parse_error() {
+    run(r#"
+try:
+    Header.parse(b"\x01\x00")
+    assert False
+except ValueError as e:
+    assert "not enough data" in str(e).lower(), str(e)
+
+try:
+    Packet.parse(b"xx\x00")
+    assert False
+except ValueError as e:
+    assert "magic" in str(e).lower(), str(e)
+"#)
+    .unwrap();
+}
+
+#[test]
+fn test_build_error() {
+    run(r#"
+packet = Packet.parse(b"dk\x00")
+packet.count = 2
+try:
+    packet.build()
+    assert False
+except ValueError:
+    pass
+"#)
+    .unwrap();
+}
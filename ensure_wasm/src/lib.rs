@@ -33,13 +33,11 @@ pub struct DekuTest {
 }
 
 #[wasm_bindgen]
-pub fn deku_read(input: &[u8]) -> DekuTest {
-    let (_rest, val) = DekuTest::from_bytes((input, 0)).unwrap();
-
-    val
+pub fn deku_read(input: &[u8]) -> Result<DekuTest, JsValue> {
+    deku::wasm::from_bytes(input).map_err(JsValue::from)
 }
 
 #[wasm_bindgen]
-pub fn deku_write(input: &DekuTest) -> Vec<u8> {
-    input.to_bytes().unwrap()
+pub fn deku_write(input: &DekuTest) -> Result<Vec<u8>, JsValue> {
+    deku::wasm::to_bytes(input).map_err(JsValue::from)
 }
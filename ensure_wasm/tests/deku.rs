@@ -15,7 +15,17 @@ fn test_read() {
             field_b: 0b101,
             field_c: 0xBE
         },
-        deku_read([0b10101_101, 0xBE].as_ref())
+        deku_read([0b10101_101, 0xBE].as_ref()).unwrap()
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_read_error() {
+    assert_eq!(
+        Some("Parse error: Too much data".to_string()),
+        deku_read([0b10101_101, 0xBE, 0xEF].as_ref())
+            .unwrap_err()
+            .as_string()
     )
 }
 
@@ -30,5 +40,14 @@ fn test_write() {
         }
         .to_bytes()
         .unwrap()
+    );
+    assert_eq!(
+        vec![0b10101_101, 0xBE],
+        deku_write(&DekuTest {
+            field_a: 0b10101,
+            field_b: 0b101,
+            field_c: 0xBE
+        })
+        .unwrap()
     )
 }
@@ -0,0 +1,403 @@
+//! An `Archive` container for a header, records, and an index located by the header
+//!
+//! Many file formats share the same shape: a header holding the offset of an index, the records
+//! between the header and the index, then the index. `Archive<H, R, I>` reads the header `H`,
+//! the records `R` until the offset given by the header, then the index `I` at that offset.
+//!
+//! The header provides the offset of the index with [`ArchiveHeader`]. Offsets are in bytes,
+//! from the start of the archive.
+//!
+//! When writing, the offset of the index is computed from the size of the header and of the
+//! records, and set in the header with [`ArchiveHeader::set_index_offset`] before it's written.
+//! The written header must keep the same size whatever the offset.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::archive::{Archive, ArchiveHeader};
+//! # use std::convert::TryFrom;
+//! #[derive(Debug, Clone, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(magic = b"AR")]
+//! struct Header {
+//!     index_offset: u8,
+//! }
+//!
+//! impl ArchiveHeader for Header {
+//!     fn index_offset(&self) -> usize {
+//!         self.index_offset as usize
+//!     }
+//!
+//!     fn set_index_offset(&mut self, offset: usize) -> Result<(), DekuError> {
+//!         self.index_offset = u8::try_from(offset)
+//!             .map_err(|_| DekuError::InvalidParam(format!("index offset {} too large", offset).into()))?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Record {
+//!     len: u8,
+//!     #[deku(count = "len")]
+//!     data: Vec<u8>,
+//! }
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Index {
+//!     count: u8,
+//!     #[deku(count = "count")]
+//!     offsets: Vec<u8>,
+//! }
+//!
+//! let data: Vec<u8> = vec![
+//!     b'A', b'R', 0x08, // header, the index is at byte 8
+//!     0x01, 0xAA, // record
+//!     0x02, 0xBB, 0xCC, // record
+//!     0x02, 0x03, 0x05, // index
+//! ];
+//!
+//! let (_, archive) = Archive::<Header, Record, Index>::read(data.view_bits(), ()).unwrap();
+//! assert_eq!(2, archive.records.len());
+//! assert_eq!(vec![0x03, 0x05], archive.index.offsets);
+//!
+//! // the offset of the index is computed when writing
+//! let mut archive = archive;
+//! archive.records.push(Record { len: 1, data: vec![0xDD] });
+//! archive.index = Index { count: 3, offsets: vec![0x03, 0x05, 0x08] };
+//!
+//! let value = archive.write(()).unwrap().into_vec();
+//! assert_eq!(
+//!     vec![
+//!         b'A', b'R', 0x0A,
+//!         0x01, 0xAA,
+//!         0x02, 0xBB, 0xCC,
+//!         0x01, 0xDD,
+//!         0x03, 0x03, 0x05, 0x08,
+//!     ],
+//!     value
+//! );
+//! ```
+
+use crate::error::NeedSize;
+use crate::{DekuError, DekuRead, DekuWrite};
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+
+/// Header of an [`Archive`], holding the offset of its index
+pub trait ArchiveHeader {
+    /// Offset of the index in bytes, from the start of the archive
+    fn index_offset(&self) -> usize;
+
+    /// Set the offset of the index in bytes before the header is written, an offset which the
+    /// header can't hold is an error
+    fn set_index_offset(&mut self, offset: usize) -> Result<(), DekuError>;
+}
+
+/// A header, records and an index, see the [module documentation](index.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Archive<H, R, I> {
+    /// The header, holding the offset of the index
+    pub header: H,
+    /// The records between the header and the index
+    pub records: Vec<R>,
+    /// The index
+    pub index: I,
+}
+
+impl<H, R, I> Archive<H, R, I> {
+    /// Create an `Archive` from its parts
+    pub fn new(header: H, records: Vec<R>, index: I) -> Self {
+        Self {
+            header,
+            records,
+            index,
+        }
+    }
+}
+
+impl<'a, H, R, I, Ctx> DekuRead<'a, Ctx> for Archive<H, R, I>
+where
+    H: DekuRead<'a, Ctx> + ArchiveHeader,
+    R: DekuRead<'a, Ctx>,
+    I: DekuRead<'a, Ctx>,
+    Ctx: Copy,
+{
+    /// Read the header, the records until the offset of the index, then the index
+    /// * `ctx` - The context required by `H`, `R` and `I`
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let (rest, header) = H::read(input, ctx)?;
+        let header_size = input.len() - rest.len();
+
+        let index_offset = header.index_offset().checked_mul(8).ok_or_else(|| {
+            DekuError::Parse(error_msg!(
+                "archive: index offset {} is too large to be counted in bits",
+                header.index_offset()
+            ))
+        })?;
+        if index_offset < header_size {
+            return Err(DekuError::Parse(error_msg!(
+                "archive: index offset {} is within the header of {} bits",
+                header.index_offset(),
+                header_size
+            )));
+        }
+        if index_offset > input.len() {
+            return Err(DekuError::Incomplete(NeedSize::new(
+                index_offset,
+                input.len(),
+            )));
+        }
+
+        // records can't read past the index
+        let mut records = Vec::new();
+        let mut record_rest = &input[header_size..index_offset];
+        while !record_rest.is_empty() {
+            #[cfg(feature = "limit")]
+            crate::limit::record_element()?;
+
+            let (new_rest, record) = R::read(record_rest, ctx)?;
+            // a record which consumes no bits would be read forever
+            if new_rest.len() == record_rest.len() {
+                return Err(DekuError::Parse(error_msg!(
+                    "archive: record consumed no bits {} bits before the index",
+                    record_rest.len()
+                )));
+            }
+
+            records.push(record);
+            record_rest = new_rest;
+        }
+
+        let (rest, index) = I::read(&input[index_offset..], ctx)?;
+
+        Ok((rest, Self::new(header, records, index)))
+    }
+}
+
+impl<H, R, I, Ctx> DekuWrite<Ctx> for Archive<H, R, I>
+where
+    H: DekuWrite<Ctx> + ArchiveHeader + Clone,
+    R: DekuWrite<Ctx>,
+    I: DekuWrite<Ctx>,
+    Ctx: Copy,
+{
+    /// Write the header with the offset of the index set, the records, then the index
+    /// * `ctx` - The context required by `H`, `R` and `I`
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let records = self.records.write(ctx)?;
+
+        // the size of the header is needed to compute the offset it holds
+        let mut header = self.header.clone();
+        header.set_index_offset(0)?;
+        let header_size = header.write(ctx)?.len();
+
+        let index_offset = header_size + records.len();
+        if index_offset % 8 != 0 {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "archive: index at bit {} isn't on a byte boundary",
+                index_offset
+            )));
+        }
+
+        header.set_index_offset(index_offset / 8)?;
+        let mut acc = header.write(ctx)?;
+        if acc.len() != header_size {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "archive: header size changed from {} to {} bits with the index offset",
+                header_size,
+                acc.len()
+            )));
+        }
+
+        acc.extend(records);
+        acc.extend(self.index.write(ctx)?);
+
+        Ok(acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Header(u16);
+
+    impl<'a> DekuRead<'a, Endian> for Header {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            endian: Endian,
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, offset) = u16::read(input, endian)?;
+            Ok((rest, Header(offset)))
+        }
+    }
+
+    impl DekuWrite<Endian> for Header {
+        fn write(&self, endian: Endian) -> Result<BitVec<Msb0, u8>, DekuError> {
+            self.0.write(endian)
+        }
+    }
+
+    impl ArchiveHeader for Header {
+        fn index_offset(&self) -> usize {
+            self.0 as usize
+        }
+
+        fn set_index_offset(&mut self, offset: usize) -> Result<(), DekuError> {
+            self.0 = offset as u16;
+            Ok(())
+        }
+    }
+
+    #[rstest(input, endian, expected, expected_rest,
+        case::big(
+            [0x00, 0x06, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x11].as_ref(),
+            Endian::Big,
+            Archive::new(Header(6), vec![0xAABB, 0xCCDD], 0xEEFF),
+            bits![Msb0, u8; 0, 0, 0, 1, 0, 0, 0, 1],
+        ),
+        case::little(
+            [0x04, 0x00, 0xAA, 0xBB, 0xEE, 0xFF].as_ref(),
+            Endian::Little,
+            Archive::new(Header(4), vec![0xBBAA], 0xFFEE),
+            bits![Msb0, u8;],
+        ),
+        case::no_records(
+            [0x00, 0x02, 0xEE, 0xFF].as_ref(),
+            Endian::Big,
+            Archive::new(Header(2), vec![], 0xEEFF),
+            bits![Msb0, u8;],
+        ),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"archive: index offset 1 is within the header of 16 bits\")")]
+        case::offset_in_header(
+            [0x00, 0x01, 0xEE, 0xFF].as_ref(),
+            Endian::Big,
+            Archive::new(Header(0), vec![], 0),
+            bits![Msb0, u8;],
+        ),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 64, got: 32 })")]
+        case::offset_past_input(
+            [0x00, 0x08, 0xEE, 0xFF].as_ref(),
+            Endian::Big,
+            Archive::new(Header(0), vec![], 0),
+            bits![Msb0, u8;],
+        ),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::record_past_index(
+            [0x00, 0x03, 0xAA, 0xEE, 0xFF].as_ref(),
+            Endian::Big,
+            Archive::new(Header(0), vec![], 0),
+            bits![Msb0, u8;],
+        ),
+    )]
+    fn test_archive_read(
+        input: &[u8],
+        endian: Endian,
+        expected: Archive<Header, u16, u16>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = Archive::<Header, u16, u16>::read(bit_slice, endian).unwrap();
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[rstest(input, endian, expected,
+        case::big(
+            Archive::new(Header(0), vec![0xAABB, 0xCCDD], 0xEEFF),
+            Endian::Big,
+            vec![0x00, 0x06, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
+        ),
+        case::little(
+            Archive::new(Header(0xFFFF), vec![0xBBAA], 0xFFEE),
+            Endian::Little,
+            vec![0x04, 0x00, 0xAA, 0xBB, 0xEE, 0xFF],
+        ),
+        case::no_records(
+            Archive::new(Header(0), vec![], 0xEEFF),
+            Endian::Big,
+            vec![0x00, 0x02, 0xEE, 0xFF],
+        ),
+    )]
+    fn test_archive_write(input: Archive<Header, u16, u16>, endian: Endian, expected: Vec<u8>) {
+        let res_write = input.write(endian).unwrap().into_vec();
+        assert_eq!(expected, res_write);
+
+        // the offset written is the one read back
+        let (_, res_read) =
+            Archive::<Header, u16, u16>::read(res_write.view_bits(), endian).unwrap();
+        assert_eq!(input.records, res_read.records);
+        assert_eq!(input.index, res_read.index);
+    }
+
+    /// A header with the index out of reach of any input
+    #[derive(Debug, PartialEq)]
+    struct FarHeader;
+
+    impl<'a> DekuRead<'a, Endian> for FarHeader {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: Endian,
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            Ok((input, FarHeader))
+        }
+    }
+
+    impl ArchiveHeader for FarHeader {
+        fn index_offset(&self) -> usize {
+            usize::MAX
+        }
+
+        fn set_index_offset(&mut self, _: usize) -> Result<(), DekuError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_archive_read_offset_overflow() {
+        let input = [0xEE, 0xFF];
+        assert_eq!(
+            DekuError::Parse(error_msg!(
+                "archive: index offset {} is too large to be counted in bits",
+                usize::MAX
+            )),
+            Archive::<FarHeader, u16, u16>::read(input.view_bits(), Endian::Big).unwrap_err()
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Bit;
+
+    impl DekuWrite<Endian> for Bit {
+        fn write(&self, _: Endian) -> Result<BitVec<Msb0, u8>, DekuError> {
+            Ok(bitvec![Msb0, u8; 1])
+        }
+    }
+
+    #[test]
+    fn test_archive_write_unaligned() {
+        let archive = Archive::new(Header(0), vec![Bit], 0xEEFFu16);
+        let err = archive.write(Endian::Big).unwrap_err();
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            DekuError::InvalidParam(error_msg!(
+                "archive: index at bit 17 isn't on a byte boundary"
+            )),
+            err
+        );
+        #[cfg(feature = "static-errors")]
+        assert!(matches!(err, DekuError::InvalidParam(_)));
+    }
+}
@@ -6,20 +6,74 @@ A documentation-only module for #[deku] attributes
 | Attribute | Scope | Description
 |-----------|------------------|------------
 | [endian](#endian) | top-level, field | Set the endianness
+| [explicit_endian](#explicit_endian) | top-level | Require an endian on multi-byte fields
+| [bit_order](#bit_order) | top-level, field | Set the bit order
+| [encoding](#encoding) | field | Set the text encoding of a `String` field
 | [bits](#bits) | field | Set the bit-size of the field
 | [bytes](#bytes) | field | Set the byte-size of the field
+| [pad](#pad) | field | Set the code unit padding a fixed-width `String` field
 | [count](#count) | field | Set the field representing the element count of a container
+| [bytes_read](#bytes_read) | field | Set the field representing the number of bytes of a container
+| [bits_read](#bits_read) | field | Set the field representing the number of bits of a container
+| [read_all](#read_all) | field | Read the elements of a container until the end of the input, or borrow the rest of the input
+| [prefix](#prefix) | field | Read and write the byte length of the field before it, as an integer type
+| [bcd](#bcd) | field | Read and write an unsigned integer field as packed BCD digits
+| [fixed](#fixed) | field | Read and write a float field as a fixed-point number `Qm.n`
+| [signed](#signed) | field | Set the representation of a signed integer field, two's complement or sign-magnitude
+| [strict_bool](#strict_bool) | field | Read a `bool` field as `0` or `1` only
 | [update](#update) | field | Apply code over the field when `.update()` is called
+| [auto_update](#auto_update) | top-level | Call `.update()` on a clone of the struct/enum before writing it
+| [len_of](#len_of) | field | Set the field to the byte length of another field when `.update()` is called
+| [len_offset](#len_of) | field | Adjust the length set by `len_of`
+| [pad_bits_before](#pad_bits_before) | field | Skip bits before reading, write zeros before writing
+| [pad_bytes_before](#pad_bytes_before) | field | Skip bytes before reading, write zeros before writing
+| [pad_bits_after](#pad_bits_after) | field | Skip bits after reading, write zeros after writing
+| [pad_bytes_after](#pad_bytes_after) | field | Skip bytes after reading, write zeros after writing
+| [align_bits](#align_bits) | field | Skip bits up to a multiple of a number of bits from the start of the container before the field
+| [align_bytes](#align_bytes) | field | Skip bytes up to a multiple of a number of bytes from the start of the container before the field
+| [align_before](#align_before) | field | Check the field starts on a byte boundary from the start of the container
+| [align_after](#align_after) | field | Check the field ends on a byte boundary from the start of the container
+| [seek_from_current](#seek_from_current) | field | Move the read position by a number of bytes before the field
+| [seek_rewind](#seek_rewind) | field | Move the read position to the start of the container before the field
+| [offset](#offset) | field | Read the field at a byte offset from the start of the container
+| [limit_bytes](#limit_bytes) | field | Read the field from a window of a number of bytes, skipping what it leaves
+| [process](#process) | field | Transform the bytes the field is read from, such as a compressed or encrypted region
+| [process_write](#process) | field | Transform the bytes the field is written to, the inverse of `process`
+| [temp](#temp) | field | Read the field without storing it in the struct
+| [peek](#peek) | field | Read the field without consuming its bits
+| [temp_value](#temp) | field | Value written for a `temp` field
 | [skip](#skip) | field | Skip the reading/writing of a field
+| [skip_read](#skip_read) | field | Skip the reading of a field
+| [skip_write](#skip_write) | field | Skip the writing of a field
 | [cond](#cond) | field | Conditional expression for the field
+| [write_cond](#write_cond) | field | Conditional expression for writing the field
 | [default](#default) | field | Custom defaulting code when `skip` is true
+| [default_if_eof](#default_if_eof) | top-level, field | Set the field to `default` if the input ends before it
 | [map](#map) | field | Apply a function over the result of reading
+| [map_write](#map_write) | field | Apply a function over the field before writing, the inverse of `map`
+| [normalize](#normalize) | field | Apply a function over the field before writing
+| [read_as](#read_as) | field | Read and write the field as another type, converted with `TryFrom`
+| [assert](#assert) | top-level, field | Condition the container or field must satisfy when reading and writing
+| [assert_eq](#assert_eq) | field | Value the field must equal when reading and writing
 | [reader](#readerwriter) | variant, field | Custom reader code
 | [writer](#readerwriter) | variant, field | Custom writer code
+| [with](#with) | field | Module providing the reader and writer, e.g. a [codec](../codecs/index.html)
 | [ctx](#ctx) | top-level, field| Context list for context sensitive parsing
 | [ctx_default](#ctx_default) | top-level, field| Default context values
+| [owned](#owned) | top-level | Generate an owned version of a borrowing struct
+| [magic](#magic) | top-level | Fixed bytes read and verified before the first field
+| [trailer_checksum](#trailer_checksum) | top-level | Verify and write a checksum held by the last field
+| [trailer](#trailer) | top-level | Read the last field from the end of the input
+| [checksum](#checksum) | field | Verify and write a checksum over a range of bytes of the container
+| [over](#checksum) | field | Range of bytes of the container covered by `checksum`, `verify` or `sign`
+| [verify](#verify-and-sign) | field | Verify a signature over a range of bytes of the container when reading
+| [sign](#verify-and-sign) | field | Compute a signature over a range of bytes of the container when writing
+| [view](#view) | top-level | Generate an accessor-based view of a `#[repr(C)]` struct
+| [debug](#debug) | top-level | Dump the generated reader and writer at compile time
+| [wire_doc](#wire_doc) | top-level | Generate a Markdown table of the wire format of the fields
 | enum: [id](#id) | top-level, variant | enum or variant id value
 | enum: [id_pat](#id_pat) | variant | variant id match pattern
+| enum: [id_field](#id_field) | field | Field of an `id_pat` or catch-all variant holding the variant id
 | enum: [type](#type) | top-level | Set the type of the variant `id`
 | enum: [bits](#bits) | top-level | Set the bit-size of the variant `id`
 | enum: [bytes](#bytes) | top-level | Set the byte-size of the variant `id`
@@ -32,6 +86,9 @@ Values: `big`, `little` or an expression which returns a [`Endian`](../ctx/enum.
 
 Precedence: field > top-level > system endianness (default)
 
+With the top-level [explicit_endian](#explicit_endian) attribute, there is no system endianness
+default: omitting the endian of a multi-byte field is a compile error.
+
 Example:
 ```rust
 # use deku::prelude::*;
@@ -101,6 +158,117 @@ let value: Vec<u8> = value.try_into().unwrap();
 assert_eq!(data, value);
 ```
 
+# explicit_endian
+
+Require an `endian`, at top-level or on the field, for the fields reading multi-byte integers or
+floats and the enum `type`, instead of the system endianness: omitting it is a compile error.
+
+The attribute only applies to the container it's set on. Fields with `ctx`, `map`, `reader` or
+`with` aren't checked, the endian is up to them.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::TryFrom;
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(explicit_endian)]
+struct DekuTest {
+    field_a: u8,
+    #[deku(endian = "big")]
+    field_b: u16,
+    // field_c: u16, // compile error: `explicit_endian`: multi-byte field without an `endian`
+}
+
+let value = DekuTest::try_from([0x01, 0xAB, 0xCD].as_ref()).unwrap();
+
+assert_eq!(DekuTest { field_a: 0x01, field_b: 0xABCD }, value);
+```
+
+# bit_order
+
+Set to read/write bits in a specific bit order, for protocols and C bitfields packing bits least
+significant bit first.
+
+Values: `lsb`, `msb` or, at field-level, an expression which returns a
+[`Order`](../ctx/enum.Order.html)
+
+Precedence: field > top-level > `msb` (default)
+
+At top-level, `lsb` reads the bits of each byte of the container starting from the least
+significant bit, and fields are read least significant bit first. The container starts and ends on
+a byte boundary, the last byte is padded with zeros, reading it from the middle of a byte is an
+error. It can't hold fields borrowing from the input.
+
+At field-level, `lsb` reads the bits of the field least significant bit first. The `endian` only
+applies to a field of the size of its type.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(bit_order = "lsb")]
+struct DekuTest {
+    #[deku(bits = "3")]
+    field_a: u8,
+    #[deku(bits = "13", endian = "little")]
+    field_b: u16,
+}
+
+let data: Vec<u8> = vec![0b1010_1101, 0b0000_0001];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       field_a: 0b101,
+       field_b: 0b0_0000_0011_0101,
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+**Note**: Like `endian`, the top-level `bit_order` is passed as a context argument to sub-types,
+which apply it at field-level. `bit_order` shouldn't be set at top-level on a sub-type, its bytes
+are already reordered.
+
+# encoding
+
+Set the text encoding of a `String` field, one of `utf8`, `utf16le`, `utf16be`, `ascii` and
+`latin1`, or an [Encoding](../ctx/enum.Encoding.html) variable, possibly from `ctx`.
+
+The string is read with [count](#count), its number of code units, e.g. two bytes each in UTF-16,
+[bytes_read](#bytes_read) or [read_all](#read_all). Invalid data is an error on read, and a
+character which can't be encoded is an error on write.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(encoding = "utf16le", count = "len")]
+    name: String,
+    #[deku(encoding = "latin1", read_all)]
+    label: String,
+}
+
+let data: Vec<u8> = vec![0x02, b'h', 0x00, b'i', 0x00, b'f', 0xE9, b'e'];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 2, name: "hi".to_string(), label: "fée".to_string() },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
 # bits
 
 Set the bit-size of the field
@@ -137,11 +305,19 @@ let value: Vec<u8> = value.try_into().unwrap();
 assert_eq!(data, value);
 ```
 
-# bytes
+## Fields across bytes
 
-Set the byte-size of the field
+A field of `bits` can start anywhere in a byte and span several bytes. The bits of the field,
+in input order, hold the value as follows:
+- `endian = "big"`: most significant bit first, e.g. 12 bits `1010 1011 1100` are `0xABC`
+- `endian = "little"`: the bytes of the value least significant first, the last, partial byte
+  holding the most significant bits, e.g. 12 bits `1010 1011 1100` are `0xCAB`
+- [bit_order](#bit_order) `"lsb"`: least significant bit first, e.g. 12 bits
+  `1010 1011 1100` are `0x3D5`. Combined with a container `bit_order = "lsb"`, fields are packed
+  from the least significant bit of each byte, as in C bit-fields on little-endian targets or
+  IEEE 802.11 headers.
 
-**Note**: Cannot be used in combination with [bits](#bits)
+The same rules are used when writing.
 
 Example:
 ```rust
@@ -149,19 +325,26 @@ Example:
 # use std::convert::{TryInto, TryFrom};
 # #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
 struct DekuTest {
-    #[deku(bytes = 2)]
-    field_a: u32,
-    field_b: u8, // defaults to size_of<u8>
+    #[deku(bits = 4)]
+    field_a: u8,
+    #[deku(bits = 12, endian = "big")]
+    field_b: u16,
+    #[deku(bits = 12, endian = "little")]
+    field_c: u16,
+    #[deku(bits = 4)]
+    field_d: u8,
 }
 
-let data: Vec<u8> = vec![0xAB, 0xCD, 0xFF];
+let data: Vec<u8> = vec![0x1A, 0xBC, 0xAB, 0xC2];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
     DekuTest {
-       field_a: 0xCDAB,
-       field_b: 0xFF,
+       field_a: 0x1,
+       field_b: 0xABC,
+       field_c: 0xCAB,
+       field_d: 0x2,
     },
     value
 );
@@ -170,9 +353,20 @@ let value: Vec<u8> = value.try_into().unwrap();
 assert_eq!(data, value);
 ```
 
-# count
+## Expressions
 
-Specify the field representing the length of the container, i.e. a Vec
+The size can also be an expression, computed at runtime from previously read fields or `ctx`
+arguments. It is converted to `usize`, a negative or too large value is an error.
+
+A size larger than the field type is an error when reading and writing. When writing, the
+value is truncated to the size, or with the `strict` [write option](../options/index.html) a
+value which doesn't fit is an error.
+
+A value read into a larger integer type is zero-extended for unsigned types and sign-extended
+(two's complement) for signed types, e.g. 2 bytes `FF FE` read into an `i64` are `-2`. With
+`bytes = "len"`, integers of a variable number of bytes can be read into a `u64` or `i64`.
+
+**Note**: `DekuView` only supports integer sizes
 
 Example:
 ```rust
@@ -180,20 +374,20 @@ Example:
 # use std::convert::{TryInto, TryFrom};
 # #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
 struct DekuTest {
-    #[deku(update = "self.items.len()")]
-    count: u8,
-    #[deku(count = "count")]
-    items: Vec<u8>,
+    #[deku(bits = 4)]
+    width: u8,
+    #[deku(endian = "big", bits = "*width")]
+    value: u16,
 }
 
-let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
+let data: Vec<u8> = vec![0b1100_1010, 0b1111_0000];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
     DekuTest {
-       count: 0x02,
-       items: vec![0xAB, 0xCD],
+       width: 12,
+       value: 0b1010_1111_0000,
     },
     value
 );
@@ -202,184 +396,1935 @@ let value: Vec<u8> = value.try_into().unwrap();
 assert_eq!(data, value);
 ```
 
-**Note**: See [update](#update) for more information on the attribute!
-
-# update
+## Raw bits
 
-Specify custom code to run on the field when `.update()` is called on the struct/enum
+A `BitVec<Msb0, u8>` field of `bits`, such as a variable-width region which isn't interpreted,
+is read as the bits of the input and written back as they are. The length of the `BitVec` must
+be the size of the field when writing.
 
 Example:
 ```rust
-use deku::prelude::*;
-use std::convert::{TryInto, TryFrom};
-#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+# use deku::prelude::*;
+# use bitvec::bitvec;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
 struct DekuTest {
-    #[deku(update = "self.items.len()")]
-    count: u8,
-    #[deku(count = "count")]
-    items: Vec<u8>,
+    #[deku(bits = 4)]
+    len: u8,
+    #[deku(bits = "*len as usize")]
+    opaque: BitVec<Msb0, u8>,
+    #[deku(bits = 6)]
+    field_b: u8,
 }
 
-let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
+let data: Vec<u8> = vec![0b0110_1011, 0b00_111111];
 
-// `mut` so it can be updated
-let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { count: 0x02, items: vec![0xAB, 0xCD] },
+    DekuTest {
+       len: 6,
+       opaque: bitvec![Msb0, u8; 1, 0, 1, 1, 0, 0],
+       field_b: 0b111111,
+    },
     value
 );
 
-// push a new item to the vec
-value.items.push(0xFF);
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
 
-// update it, this will update the `count` field
-value.update().unwrap();
+## Wide fields
+
+`u128` and `i128` fields can be read and written with up to 128 `bits`, or 16 `bytes`, such as
+a 96-bit identifier or a 72-bit timestamp, at any bit position.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct DekuTest {
+    #[deku(bits = 4)]
+    version: u8,
+    #[deku(bits = 76)]
+    id: u128,
+    #[deku(bytes = 9)]
+    timestamp: i128,
+}
+
+let data: Vec<u8> = vec![
+    0x1A, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF, 0x00, 0x11, 0x22, 0x33,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { count: 0x03, items: vec![0xAB, 0xCD, 0xFF] },
+    DekuTest {
+       version: 0x1,
+       id: 0xA_BBCC_DDEE_FF00_1122_33,
+       timestamp: -2,
+    },
     value
 );
 
 let value: Vec<u8> = value.try_into().unwrap();
-assert_eq!(vec![0x03, 0xAB, 0xCD, 0xFF], value);
+assert_eq!(data, value);
 ```
 
-# skip
-
-Skip the reading/writing of a field.
+# bytes
 
-Defaults value to [default](#default)
+Set the byte-size of the field
 
-**Note**: Can be paired with [cond](#cond) to have conditional skipping
+**Note**: Cannot be used in combination with [bits](#bits)
 
 Example:
-
 ```rust
 # use deku::prelude::*;
 # use std::convert::{TryInto, TryFrom};
-#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-pub struct DekuTest {
-    pub field_a: u8,
-    #[deku(skip)]
-    pub field_b: Option<u8>,
-    pub field_c: u8,
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bytes = 2)]
+    field_a: u32,
+    field_b: u8, // defaults to size_of<u8>
 }
 
-let data: Vec<u8> = vec![0x01, 0x02];
+let data: Vec<u8> = vec![0xAB, 0xCD, 0xFF];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { field_a: 0x01, field_b: None, field_c: 0x02 },
+    DekuTest {
+       field_a: 0xCDAB,
+       field_b: 0xFF,
+    },
     value
 );
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
 ```
 
-# cond
+As with [bits](#bits), the size can be an expression, for example
+`#[deku(bytes = "if *wide != 0 { 4 } else { 2 }")]` to vary the width of a number with a flag
+read before it.
 
-Specify a condition to parse or skip a field
+# pad
 
-**Note**: Can be paired with [default](#default)
+Set the code unit padding a `String` field of a number of [bits](#bits) or [bytes](#bytes), such
+as the space padded names of tar headers and FAT directory entries. The trailing pad code units
+are trimmed when reading, and added up to the size when writing. A string longer than the size is
+an error.
+
+Without `pad`, a fixed-width string is padded with nul bytes. The string is UTF-8, or in its
+[encoding](#encoding), where the pad code unit of UTF-16 is the byte zero-extended.
 
 Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bytes = "8", pad = "b' '")]
+    name: String,
+    #[deku(bytes = "3", encoding = "ascii", pad = "b' '")]
+    ext: String,
+}
+
+let data: Vec<u8> = b"README  MD ".to_vec();
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { name: "README".to_string(), ext: "MD".to_string() },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# count
+
+Specify the field representing the length of the container, i.e. a Vec
 
+Example:
 ```rust
 # use deku::prelude::*;
 # use std::convert::{TryInto, TryFrom};
-#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-pub struct DekuTest {
-    field_a: u8,
-    #[deku(cond = "*field_a == 0x01")]
-    field_b: Option<u8>,
-    #[deku(cond = "*field_b == Some(0xFF)", default = "Some(0x05)")]
-    field_c: Option<u8>,
-    #[deku(skip, cond = "*field_a == 0x01", default = "Some(0x06)")]
-    field_d: Option<u8>,
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(update = "self.items.len()")]
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u8>,
 }
 
-let data: Vec<u8> = vec![0x01, 0x02];
+let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { field_a: 0x01, field_b: Some(0x02), field_c: Some(0x05), field_d: Some(0x06)},
+    DekuTest {
+       count: 0x02,
+       items: vec![0xAB, 0xCD],
+    },
     value
 );
 
-assert_eq!(
-    vec![0x01, 0x02, 0x05],
-    value.to_bytes().unwrap(),
-)
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
 ```
 
-# default
+**Note**: See [update](#update) for more information on the attribute!
 
-Default code tokens used with [skip](#skip) or [cond](#cond)
+## Expressions
 
-Defaults to `Default::default()`
+The count can be any expression converting to `usize`, in which the previous fields and the
+`ctx` arguments are in scope. The previous fields are references, dereference them to compute
+with their value, i.e. `*header_len as usize - 4`.
 
 Example:
-
 ```rust
 # use deku::prelude::*;
 # use std::convert::{TryInto, TryFrom};
-#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-pub struct DekuTest {
-    pub field_a: u8,
-    #[deku(skip, default = "Some(*field_a)")]
-    pub field_b: Option<u8>,
-    pub field_c: u8,
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(ctx = "pairs: bool", ctx_default = "true")]
+struct DekuTest {
+    header_len: u8,
+    #[deku(count = "*header_len as usize - 1")]
+    header: Vec<u8>,
+    #[deku(count = "if pairs { 2 } else { 1 }")]
+    items: Vec<u8>,
 }
 
-let data: Vec<u8> = vec![0x01, 0x02];
+let data: Vec<u8> = vec![0x02, 0xAB, 0xCD, 0xEF];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { field_a: 0x01, field_b: Some(0x01), field_c: 0x02 },
+    DekuTest {
+       header_len: 0x02,
+       header: vec![0xAB],
+       items: vec![0xCD, 0xEF],
+    },
     value
 );
-```
 
-# map
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
 
-Specify a function or lambda to apply to the result of the read
+## Nested containers
 
-Example:
+The count and the ctx of a field are propagated through wrapper types to the container:
 
-Read a `u8` and apply a function to convert it to a `String`.
+- `Option<T>` passes the count and ctx to `T`, i.e. `Option<Vec<T>>` with `cond` and `count`
+- `Vec<T>` consumes the count and passes the remaining ctx, such as `endian`, to every element,
+  i.e. `Vec<Option<T>>` or `Vec<[u16; 2]>`
 
+Example:
 ```rust
 # use deku::prelude::*;
 # use std::convert::{TryInto, TryFrom};
-#[derive(PartialEq, Debug, DekuRead)]
-pub struct DekuTest {
-    #[deku(map = "|field: u8| -> Result<_, DekuError> { Ok(field.to_string()) }")]
-    pub field_a: String,
-    #[deku(map = "DekuTest::map_field_b")]
-    pub field_b: String,
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    count: u8,
+    #[deku(endian = "big", cond = "*count != 0", count = "count")]
+    items: Option<Vec<u16>>,
+}
+
+let data: Vec<u8> = vec![0x01, 0xAB, 0xCD];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       count: 0x01,
+       items: Some(vec![0xABCD]),
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# bytes_read
+
+Specify the field representing the number of bytes of the container, i.e. a Vec
+
+Elements are read until exactly that many bytes are consumed, an element which doesn't fit in
+the remaining bytes is an error. An element which consumes no bits, such as an empty struct, is
+also an error, as it would be read forever: use [count](#count) for these. Cannot be used with [count](#count), [bits_read](#bits_read)
+or [read_all](#read_all).
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(update = "self.items.len() * 2")]
+    len: u8,
+    #[deku(endian = "big", bytes_read = "len")]
+    items: Vec<u16>,
+}
+
+let data: Vec<u8> = vec![0x04, 0xAB, 0xCD, 0xEF, 0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       len: 0x04,
+       items: vec![0xABCD, 0xEF01],
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# bits_read
+
+Specify the field representing the number of bits of the container, i.e. a Vec
+
+Same as [bytes_read](#bytes_read), but in bits, for bit-packed repeated structures.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = 4)]
+    len: u8,
+    #[deku(bits = 2, bits_read = "len")]
+    items: Vec<u8>,
+}
+
+let data: Vec<u8> = vec![0b0100_0110];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       len: 4,
+       items: vec![0b01, 0b10],
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# read_all
+
+Read the elements of the container, i.e. a Vec, until the end of the input, or borrow the rest of
+the input
+
+This is meant for the last field, as no input is left for the fields after it. An element which
+doesn't fit in the remaining input is an error, as is an element which consumes no bits.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    header: u8,
+    #[deku(endian = "big", read_all)]
+    items: Vec<u16>,
+}
+
+let data: Vec<u8> = vec![0x01, 0xAB, 0xCD, 0xEF, 0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       header: 0x01,
+       items: vec![0xABCD, 0xEF01],
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+A borrowed `&[u8]` field borrows the rest of the input, i.e. the opaque payload after a header.
+The rest of the input must be a whole number of bytes.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest<'a> {
+    header: u8,
+    #[deku(read_all)]
+    payload: &'a [u8],
+}
+
+let data: Vec<u8> = vec![0x01, 0xAB, 0xCD, 0xEF];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       header: 0x01,
+       payload: &[0xAB, 0xCD, 0xEF],
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# prefix
+
+Read and write the byte length of the field before it, as an integer of the given type, i.e.
+a Pascal string
+
+When reading, the length is read with the `endian` of the field, then the field is read from
+that many bytes, as with [bytes_read](#bytes_read). When writing, the field is written, then its
+byte length before it: no length field or [update](#update) is needed. A length which doesn't fit
+in the type is an error.
+
+A `String` is in UTF-8 without [encoding](#encoding). Cannot be used with [bits](#bits),
+[bytes](#bytes), a container size, [reader](#readerwriter), [writer](#readerwriter) or
+[with](#with).
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct DekuTest {
+    #[deku(prefix = "u8")]
+    name: String,
+    #[deku(prefix = "u16")]
+    items: Vec<u16>,
+}
+
+let data: Vec<u8> = vec![0x04, b'd', b'e', b'k', b'u', 0x00, 0x04, 0xAB, 0xCD, 0xEF, 0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest {
+       name: "deku".to_string(),
+       items: vec![0xABCD, 0xEF01],
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# bcd
+
+Read and write an unsigned integer field as packed BCD (binary-coded decimal), two decimal digits
+per byte, most significant first, with the [bcd](../codecs/bcd/index.html) codec. The number of
+bytes is set with `bits` or `bytes`, by default the size of the field type. The `endian` of the
+field isn't used.
+
+The nibble order of the two digits of a byte can be specified:
+- `bcd` or `bcd = "big"`: the first digit is in the high nibble, i.e. `1234` is `0x12 0x34`
+- `bcd = "little"`: the first digit is in the low nibble, i.e. `1234` is `0x21 0x43`, as in TBCD
+  (telephony BCD)
+
+A variable, possibly from `ctx`, of type `deku::ctx::NibbleOrder` can be used in place of the
+string.
+
+A nibble greater than 9, or a value too large for the field type, is an error when reading. A
+value with more digits than the field is an error when writing.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bcd, bytes = "3")]
+    amount: u32,
+    #[deku(bcd = "little", bytes = "2")]
+    code: u16,
+}
+
+let data: Vec<u8> = vec![0x01, 0x23, 0x45, 0x21, 0x43];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { amount: 12345, code: 1234 }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+
+let value = DekuTest::try_from([0x01, 0x23, 0x4A, 0x21, 0x43].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::Parse("bcd: invalid digit 0xA at nibble 5".into())),
+    value
+);
+```
+
+# fixed
+
+Read and write a float field as a fixed-point number of format `Qm.n` (signed) or `UQm.n`
+(unsigned), with the [fixed_point](../codecs/fixed_point/index.html) codec. The raw integer has
+`m + n` bits, including the sign bit of a signed format, and is read and written with the
+`endian` of the field. Its value is the integer divided by `2^n`, e.g. `Q8.8` is a 16-bit signed
+integer in units of `1/256`.
+
+The field can be an `f32` or `f64`, or another type implementing
+`deku::codecs::fixed_point::FixedPointFloat`. When writing, the value is rounded to the nearest
+raw integer, and a value out of the range of the format is an error.
+
+A variable, possibly from `ctx`, of type `deku::ctx::FixedPoint` can be used in place of the
+string.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct DekuTest {
+    #[deku(fixed = "Q8.8")]
+    temperature: f32,
+    #[deku(fixed = "UQ0.16")]
+    gain: f64,
+}
+
+let data: Vec<u8> = vec![0xFF, 0x40, 0xC0, 0x00];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { temperature: -0.75, gain: 0.75 }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+
+let value: Result<Vec<u8>, _> = DekuTest { temperature: 128.0, gain: 0.0 }.try_into();
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::InvalidParam("fixed_point: value 128 out of range of Q8.8".into())),
+    value
+);
+```
+
+# signed
+
+Set the representation of a signed integer field:
+- `signed = "twos_complement"`, the default: the most significant of the bits read is the sign
+  bit, extended over the whole type, e.g. `#[deku(bits = 5)]` bits `11101` are `-3` in an `i8`.
+  When writing, a value which doesn't fit in the bits is truncated like any other field, or with
+  the `strict` [write option](../options/index.html) is an error.
+- `signed = "magnitude"`: the most significant bit is the sign, set if negative, and the other
+  bits are the magnitude, e.g. 5 bits `10011` are `-3`. A negative zero is read as zero. The
+  field is read and written with the [sign_magnitude](../codecs/sign_magnitude/index.html) codec,
+  converting to the field type: a value which doesn't fit in the type or the bits is an error.
+
+The size of the field is set with [bits](#bits) or [bytes](#bytes), the size of its type by
+default, and its `endian` is used.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = 5)]
+    field_a: i8,
+    #[deku(bits = 5, signed = "magnitude")]
+    field_b: i8,
+    #[deku(bits = 6, signed = "magnitude", endian = "big")]
+    field_c: i16,
+}
+
+let data: Vec<u8> = vec![0b11101_100, 0b11_100001];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { field_a: -3, field_b: -3, field_c: -1 }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# strict_bool
+
+A `bool` field is read and written as a `u8`, of 1 to 8 [bits](#bits): any non-zero value is
+read as `true`. With `strict_bool`, a value other than `0` or `1` is an error instead, e.g. to
+validate the reserved bits of a flag byte. The field is read with the
+[strict_bool](../codecs/strict_bool/index.html) codec.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(strict_bool)]
+    enabled: bool,
+    #[deku(bits = 4, strict_bool)]
+    field_b: bool,
+    #[deku(bits = 4)]
+    field_c: bool,
+}
+
+let data: Vec<u8> = vec![0x01, 0x01];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { enabled: true, field_b: false, field_c: true }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+
+let value = DekuTest::try_from([0x01, 0x21].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::Parse("strict_bool: invalid value 0x2, expected 0 or 1".into())),
+    value
+);
+```
+
+# update
+
+Specify custom code to run on the field when `.update()` is called on the struct/enum
+
+The variables of the top-level [ctx](#ctx) are in scope when updating with `.update_ctx(ctx)`, see
+[`DekuUpdateCtx`](../trait.DekuUpdateCtx.html).
+
+Example:
+```rust
+use deku::prelude::*;
+use std::convert::{TryInto, TryFrom};
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(update = "self.items.len()")]
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u8>,
+}
+
+let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
+
+// `mut` so it can be updated
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { count: 0x02, items: vec![0xAB, 0xCD] },
+    value
+);
+
+// push a new item to the vec
+value.items.push(0xFF);
+
+// update it, this will update the `count` field
+value.update().unwrap();
+
+assert_eq!(
+    DekuTest { count: 0x03, items: vec![0xAB, 0xCD, 0xFF] },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x03, 0xAB, 0xCD, 0xFF], value);
+```
+
+On an enum, the updates of the current variant are run. The fields of the variant are in scope by
+name, or as `field_0`, `field_1`, ... for unnamed fields.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(type = "u8")]
+enum DekuTest {
+    #[deku(id = "0x01")]
+    VarA(#[deku(update = "field_1.len()")] u8, #[deku(count = "field_0")] Vec<u8>),
+    #[deku(id = "0x02")]
+    VarB(u8),
+}
+
+let data: Vec<u8> = vec![0x01, 0x01, 0xAB];
+
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+if let DekuTest::VarA(_, items) = &mut value {
+    items.push(0xCD);
+}
+
+value.update().unwrap();
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01, 0x02, 0xAB, 0xCD], value);
+```
+
+# auto_update
+
+Call `.update()` before writing the struct/enum, so the [update](#update) and [len_of](#len_of)
+fields are always written up to date. With a [ctx](#ctx), `.update_ctx(ctx)` is called with the
+`ctx` it's written with.
+
+As writing takes the struct/enum by reference, a clone is updated and written, the struct/enum
+must implement `Clone`. The value itself isn't updated.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, Clone, DekuRead, DekuWrite)]
+#[deku(auto_update)]
+struct DekuTest {
+    #[deku(update = "self.items.len()")]
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u8>,
+}
+
+let value = DekuTest { count: 0, items: vec![0xAB, 0xCD] };
+
+let bytes = value.to_bytes().unwrap();
+assert_eq!(vec![0x02, 0xAB, 0xCD], bytes);
+```
+
+# len_of
+
+Set the field to the byte length of another field when `.update()` is called, as the number of
+bytes the other field is written as.
+
+The length can be adjusted with `len_offset`, an expression added to it, i.e. the size of a header
+included in the length, or a negative value for bytes of the other field excluded from the length.
+The other field must be a named field and must be writable without the top-level `ctx`.
+
+`len_of` only applies to `.update()`, the other field is read with its own attribute such as
+[bytes_read](#bytes_read). A length read from the input can be shorter than the `len_offset`,
+removing the offset must be checked.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+/// The length of the items, `len` includes the 3 bytes of the header
+fn items_len(len: u16) -> Result<u16, DekuError> {
+    len.checked_sub(3)
+        .ok_or_else(|| DekuError::Parse(format!("len: {} is shorter than the header", len).into()))
+}
+
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct DekuTest {
+    // the length includes the header: `len` and `tag`
+    #[deku(len_of = "items", len_offset = "3")]
+    len: u16,
+    tag: u8,
+    #[deku(bytes_read = "items_len(*len)?")]
+    items: Vec<u16>,
+}
+
+let data: Vec<u8> = vec![0x00, 0x05, 0xFF, 0xAB, 0xCD];
+
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 0x05, tag: 0xFF, items: vec![0xABCD] },
+    value
+);
+
+// push a new item to the vec
+value.items.push(0xEF01);
+
+// update it, this will update the `len` field
+value.update().unwrap();
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x00, 0x07, 0xFF, 0xAB, 0xCD, 0xEF, 0x01], value);
+
+// a length shorter than the header is an error
+let data: Vec<u8> = vec![0x00, 0x02, 0xFF];
+assert!(DekuTest::try_from(data.as_ref()).is_err());
+```
+
+# pad_bits_before
+
+Specify a number of bits of padding before the field. The padding is skipped when reading,
+and zeros are written when writing.
+
+The number can be an expression of the previous fields, which are in scope as references.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "4")]
+    field_a: u8,
+    #[deku(pad_bits_before = "4")]
+    field_b: u8,
+}
+
+let data: Vec<u8> = vec![0b1010_1111, 0xBB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0b1010, field_b: 0xBB },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0b1010_0000, 0xBB], value);
+```
+
+# pad_bytes_before
+
+Specify a number of bytes of padding before the field, see [pad_bits_before](#pad_bits_before).
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    field_a: u8,
+    #[deku(pad_bytes_before = "2")]
+    field_b: u8,
+}
+
+let data: Vec<u8> = vec![0xAA, 0xFF, 0xFF, 0xBB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0xAA, field_b: 0xBB },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0xAA, 0x00, 0x00, 0xBB], value);
+```
+
+# pad_bits_after
+
+Specify a number of bits of padding after the field. The padding is skipped when reading,
+and zeros are written when writing.
+
+The number can be an expression of the field and the previous fields, which are in scope as
+references.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "4", pad_bits_after = "4")]
+    field_a: u8,
+    field_b: u8,
+}
+
+let data: Vec<u8> = vec![0b1010_1111, 0xBB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0b1010, field_b: 0xBB },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0b1010_0000, 0xBB], value);
+```
+
+# pad_bytes_after
+
+Specify a number of bytes of padding after the field, see [pad_bits_after](#pad_bits_after).
+
+Example, padding the data to a 4-byte boundary:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(count = "len", pad_bytes_after = "(4 - (1 + *len as usize) % 4) % 4")]
+    data: Vec<u8>,
+}
+
+let data: Vec<u8> = vec![0x01, 0xAA, 0xFF, 0xFF];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 1, data: vec![0xAA] },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01, 0xAA, 0x00, 0x00], value);
+```
+
+# align_bits
+
+Align the field to a multiple of a number of bits from the start of the container. The padding
+up to the alignment is skipped when reading, and zeros are written when writing. The alignment is
+applied after the `pad_*_before` padding.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "3")]
+    field_a: u8,
+    #[deku(align_bits = "4", bits = "4")]
+    field_b: u8,
+}
+
+let data: Vec<u8> = vec![0b101_1_0110];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0b101, field_b: 0b0110 },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0b101_0_0110], value);
+```
+
+# align_bytes
+
+Align the field to a multiple of a number of bytes from the start of the container, see
+[align_bits](#align_bits).
+
+Example, the data following a list is on a 4-byte boundary:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(count = "len")]
+    items: Vec<u8>,
+    #[deku(align_bytes = "4")]
+    data: u8,
+}
+
+let data: Vec<u8> = vec![0x01, 0xAA, 0xFF, 0xFF, 0xBB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 1, items: vec![0xAA], data: 0xBB },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01, 0xAA, 0x00, 0x00, 0xBB], value);
+```
+
+# align_before
+
+Check the field starts on a byte boundary from the start of the container, when reading and
+writing. Unlike [align_bits](#align_bits), no bits are skipped: an unaligned field is an error,
+such as a run of bit fields of the wrong total size before a byte field.
+
+When the check fails, a `DekuError::Assertion` is returned naming the field and the bit position.
+In the `lenient` mode of [options](../options/index.html), it's recorded as a warning instead.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "3")]
+    flags: u8,
+    #[deku(bits = "4")]
+    kind: u8,
+    #[deku(align_before)]
+    data: u8,
+}
+
+let value = DekuTest::try_from([0b001_0010_1, 0xFF].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::Assertion(
+        "field `data` failed assertion: not byte aligned before the field, at bit 7".into()
+    )),
+    value
+);
+```
+
+# align_after
+
+Check the field ends on a byte boundary from the start of the container, when reading and
+writing, see [align_before](#align_before).
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "3")]
+    flags: u8,
+    #[deku(bits = "5", align_after)]
+    kind: u8,
+    data: u8,
+}
+
+let value = DekuTest::try_from([0b001_00101, 0xFF].as_ref()).unwrap();
+assert_eq!(DekuTest { flags: 0b001, kind: 0b00101, data: 0xFF }, value);
+```
+
+# seek_from_current
+
+Move the read position by a number of bytes before reading the field, relative to the current
+position. The offset may be negative, to read bytes again, as long as the position stays within
+the input of the container.
+
+When writing, a positive offset writes zeros and a negative offset is an error, as the bytes read
+again can't be written twice.
+
+Example, skipping a gap given by a prior field:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    gap: u8,
+    #[deku(seek_from_current = "*gap")]
+    field_a: u8,
+}
+
+let data: Vec<u8> = vec![0x02, 0xFF, 0xFF, 0xAA];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { gap: 2, field_a: 0xAA },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x02, 0x00, 0x00, 0xAA], value);
+```
+
+Example, reading a byte again:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "4")]
+    version: u8,
+    #[deku(bits = "4")]
+    length: u8,
+    #[deku(seek_from_current = "-1")]
+    header: u8,
+}
+
+let data: Vec<u8> = vec![0x45];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { version: 4, length: 5, header: 0x45 },
+    value
+);
+
+let value: Result<Vec<u8>, _> = value.try_into();
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::InvalidParam(
+        "seek_from_current: can't seek backwards by 1 bytes when writing".into()
+    )),
+    value
+);
+```
+
+# seek_rewind
+
+Move the read position to the start of the input of the container before reading the field.
+
+When writing, this is an error unless nothing was written yet.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    field_a: u8,
+    #[deku(seek_rewind)]
+    field_b: u16,
+}
+
+let data: Vec<u8> = vec![0xAA, 0xBB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0xAA, field_b: u16::from_ne_bytes([0xAA, 0xBB]) },
+    value
+);
+```
+
+# offset
+
+Read the field at an absolute byte offset from the start of the input of the container, such as
+the offset tables of ZIP or ELF files. The position is restored after the field, the following
+fields are read from where the previous field ended.
+
+When writing, the field is placed at the offset once all the other fields are written, with zeros
+before it. The field must not overlap the data written before it.
+
+`offset` can't be combined with the `seek_*`, `pad_*` or `align_*` attributes.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    data_offset: u8,
+    #[deku(offset = "*data_offset")]
+    data: u8,
+    field_a: u8,
+}
+
+let data: Vec<u8> = vec![0x03, 0xAA, 0xFF, 0xBB];
+let (rest, value) = DekuTest::from_bytes((data.as_ref(), 0)).unwrap();
+
+assert_eq!(
+    DekuTest { data_offset: 3, data: 0xBB, field_a: 0xAA },
+    value
+);
+assert_eq!((&[0xFF, 0xBB][..], 0), rest);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x03, 0xAA, 0x00, 0xBB], value);
+```
+
+# limit_bytes
+
+Read the field from a window of a number of bytes, such as the body of a chunk or box whose
+length is given by a header field. The field can't read past the window, as with
+[read_all](#read_all) which reads until the end of the window, and the bytes of the window it
+doesn't read are skipped.
+
+When writing, zeros are written after the field up to the number of bytes, and a field written
+in more bytes is an error.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(limit_bytes = "*len", read_all)]
+    body: Vec<u8>,
+    #[deku(limit_bytes = "2")]
+    field_a: u8,
+}
+
+let data: Vec<u8> = vec![0x02, 0xAA, 0xBB, 0xCC, 0xFF];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 2, body: vec![0xAA, 0xBB], field_a: 0xCC },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x02, 0xAA, 0xBB, 0xCC, 0x00], value);
+```
+
+# process
+
+Transform the bytes of the rest of the input with a function before reading the field from
+them, such as decompressing or decrypting a region. With [limit_bytes](#limit_bytes), the bytes
+transformed are those of the window. The field can't borrow from the transformed bytes.
+
+When writing, the bytes written by the field are transformed by the `process_write` function, the
+inverse of `process`.
+
+Both functions take the bytes as `&[u8]` and return `Result<Vec<u8>, DekuError>`, the bytes
+must be a whole number of bytes.
+
+The [escape](../escape/index.html) module provides such functions removing and inserting escape
+sequences, such as the emulation prevention bytes of H.264.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+fn xor(bytes: &[u8]) -> Result<Vec<u8>, DekuError> {
+    Ok(bytes.iter().map(|b| b ^ 0xFF).collect())
+}
+
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(limit_bytes = "*len", process = "xor", process_write = "xor", endian = "big")]
+    field_a: u16,
+    field_b: u8,
+}
+
+let data: Vec<u8> = vec![0x02, 0x55, 0x44, 0xCC];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 2, field_a: 0xAABB, field_b: 0xCC },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x02, 0x55, 0x44, 0xCC], value);
+```
+
+# temp
+
+A field which is read, and usable in the attributes of the following fields, but removed from the
+struct. This is useful for a length or flags read only to drive the following fields.
+
+`temp` fields require `#[deku_derive(DekuRead, DekuWrite)]` in place of
+`#[derive(DekuRead, DekuWrite)]`, placed before any `#[derive(..)]`, which removes the fields
+from the emitted struct.
+
+When writing, the value of a `temp` field is given by the `temp_value` expression, in which the
+fields of the struct are in scope as references.
+
+A `temp` field with a constant `temp_value`, e.g. `temp_value = "0u16"`, writes a reserved word or
+separator which has no place in the struct.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[deku_derive(DekuRead, DekuWrite)]
+#[derive(Debug, PartialEq)]
+struct DekuTest {
+    #[deku(temp, temp_value = "items.len() as u8")]
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u8>,
+}
+
+let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { items: vec![0xAB, 0xCD] },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# peek
+
+Read the field without consuming its bits: the following field is read from the same position.
+This is useful for a version or a tag at the start of the data, which decides how the data is
+read but is also part of it.
+
+A `peek` field isn't written, its bits are written by the fields which consume them. To read the
+elements of a container while they match, see [TakeWhile](crate::take_while).
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(peek, bits = "4")]
+    version: u8,
+    #[deku(cond = "*version == 2")]
+    header: Option<u8>,
+    data: u8,
+}
+
+let data: Vec<u8> = vec![0x2A, 0xBC];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { version: 2, header: Some(0x2A), data: 0xBC },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+A peeked tag can also choose the variant of an enum which reads the tag again as part of its
+payload, passing it with [ctx](#ctx) to an enum with a top-level [id](#id):
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(peek)]
+    tag: u8,
+    #[deku(ctx = "*tag")]
+    payload: Payload,
+}
+
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(ctx = "tag: u8", id = "tag")]
+enum Payload {
+    #[deku(id = "0x01")]
+    Short(u8),
+    #[deku(id_pat = "_")]
+    Long(u8, u8),
+}
+
+let data: Vec<u8> = vec![0x05, 0xAA];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { tag: 0x05, payload: Payload::Long(0x05, 0xAA) },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# skip
+
+Skip the reading/writing of a field.
+
+Defaults value to [default](#default)
+
+**Note**: Can be paired with [cond](#cond) to have conditional skipping
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    pub field_a: u8,
+    #[deku(skip)]
+    pub field_b: Option<u8>,
+    pub field_c: u8,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: None, field_c: 0x02 },
+    value
+);
+```
+
+# skip_read
+
+Skip the reading of a field, it is still written.
+
+Defaults value to [default](#default), for a field held in memory and written, such as a value
+computed by the application.
+
+**Note**: Can be paired with [cond](#cond) to have conditional skipping
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    pub field_a: u8,
+    #[deku(skip_read, default = "0xFF")]
+    pub field_b: u8,
+}
+
+let data: Vec<u8> = vec![0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: 0xFF },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01, 0xFF], value);
+```
+
+# skip_write
+
+Skip the writing of a field, it is still read.
+
+For a field which is read but dropped when writing, such as a deprecated field.
+
+**Note**: Can be paired with [cond](#cond) to have conditional skipping
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    pub field_a: u8,
+    #[deku(skip_write)]
+    pub field_b: u8,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: 0x02 },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01], value);
+```
+
+# cond
+
+Specify a condition to parse or skip a field
+
+**Note**: Can be paired with [default](#default)
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    field_a: u8,
+    #[deku(cond = "*field_a == 0x01")]
+    field_b: Option<u8>,
+    #[deku(cond = "*field_b == Some(0xFF)", default = "Some(0x05)")]
+    field_c: Option<u8>,
+    #[deku(skip, cond = "*field_a == 0x01", default = "Some(0x06)")]
+    field_d: Option<u8>,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: Some(0x02), field_c: Some(0x05), field_d: Some(0x06)},
+    value
+);
+
+assert_eq!(
+    vec![0x01, 0x02, 0x05],
+    value.to_bytes().unwrap(),
+)
+```
+
+# write_cond
+
+Specify a condition to write a field, independently of the condition to read it
+
+The field is written only if the condition is true, e.g. an extension gated by a flag which may
+change after the field is read.
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    flags: u8,
+    #[deku(write_cond = "*flags & 0x01 != 0")]
+    ext: u8,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { flags: 0x01, ext: 0x02 }, value);
+assert_eq!(data, value.to_bytes().unwrap());
+
+value.flags = 0x00;
+assert_eq!(vec![0x00], value.to_bytes().unwrap());
+```
+
+# default
+
+Default code tokens used with [skip](#skip), [cond](#cond) or [default_if_eof](#default_if_eof)
+
+Defaults to `Default::default()`
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    pub field_a: u8,
+    #[deku(skip, default = "Some(*field_a)")]
+    pub field_b: Option<u8>,
+    pub field_c: u8,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: Some(0x01), field_c: 0x02 },
+    value
+);
+```
+
+# default_if_eof
+
+Set the field to its [default](#default) if the input ends before the field, instead of failing
+with an error. On a struct or enum, this applies to all the fields.
+
+Useful for protocols which append fields in newer versions, reading the shorter data of older
+versions. Only the end of the input is checked, a field which is partially in the input is still an
+error. The fields are always written.
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    pub field_a: u8,
+    #[deku(default_if_eof, default = "0xFF")]
+    pub field_b: u8,
+}
+
+let data: Vec<u8> = vec![0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: 0xFF },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x01, 0xFF], value);
+```
+
+# map
+
+Specify a function or lambda to apply to the result of the read
+
+Example:
+
+Read a `u8` and apply a function to convert it to a `String`.
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead)]
+pub struct DekuTest {
+    #[deku(map = "|field: u8| -> Result<_, DekuError> { Ok(field.to_string()) }")]
+    pub field_a: String,
+    #[deku(map = "DekuTest::map_field_b")]
+    pub field_b: String,
+}
+
+impl DekuTest {
+    fn map_field_b(field_b: u8) -> Result<String, DekuError> {
+        Ok(field_b.to_string())
+    }
+}
+
+let data: Vec<u8> = vec![0x01, 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: "1".to_string(), field_b: "2".to_string() },
+    value
+);
+```
+
+# map_write
+
+Specify a function or lambda to apply to the field before it's written, the inverse of
+[map](#map), so a mapped field can be written back
+
+The function takes a reference to the field and returns a `Result` of the value written.
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    #[deku(
+        map = "|field: u8| -> Result<_, DekuError> { Ok(field.to_string()) }",
+        map_write = "DekuTest::unmap_field_a"
+    )]
+    pub field_a: String,
+}
+
+impl DekuTest {
+    fn unmap_field_a(field_a: &str) -> Result<u8, DekuError> {
+        field_a
+            .parse()
+            .map_err(|_| DekuError::InvalidParam(format!("not a u8: {}", field_a).into()))
+    }
+}
+
+let data: Vec<u8> = vec![0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { field_a: "1".to_string() }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# read_as
+
+Read the field as another type, then convert it to the type of the field with `TryFrom`. Before
+writing, the field is cloned and converted back to the other type with `TryFrom`.
+
+Useful for enums or newtypes with invariants, or third-party types, which have conversions from and
+to a type implementing `DekuRead` and `DekuWrite`. As `TryFrom` is implemented for all `From`
+conversions, either can be used. A failed conversion is a `DekuError::Parse` error when reading, and
+a `DekuError::InvalidParam` error when writing.
+
+Example:
+
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Mode {
+    Off,
+    On,
+}
+
+impl TryFrom<u8> for Mode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Mode::Off),
+            1 => Ok(Mode::On),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Mode> for u8 {
+    fn from(mode: Mode) -> Self {
+        mode as u8
+    }
+}
+
+#[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+pub struct DekuTest {
+    #[deku(read_as = "u8")]
+    pub mode: Mode,
+}
+
+let data: Vec<u8> = vec![0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+assert_eq!(DekuTest { mode: Mode::On }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+
+let data: Vec<u8> = vec![0x02];
+assert!(DekuTest::try_from(data.as_ref()).is_err());
+```
+
+# normalize
+
+Specify a function applied to the field before it's written, returning the value written
+instead of the field. Reading is not affected.
+
+This is useful to make the output canonical, i.e. for hashing or signing, when the value in
+memory wasn't constructed as such.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    count: u8,
+    #[deku(count = "count", normalize = "DekuTest::sorted")]
+    items: Vec<u8>,
+}
+
+impl DekuTest {
+    fn sorted(items: &[u8]) -> Vec<u8> {
+        let mut items = items.to_vec();
+        items.sort_unstable();
+        items
+    }
+}
+
+let value = DekuTest { count: 2, items: vec![0xCD, 0xAB] };
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x02, 0xAB, 0xCD], value);
+```
+
+# assert
+
+Specify a condition the field must satisfy, checked after the field is read and before it is
+written. The field, and the fields before it, are in scope as references.
+
+When the condition is false, a `DekuError::Assertion` is returned naming the field and the
+condition. In the `lenient` mode of [options](../options/index.html), it's recorded as a warning
+instead.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(assert = "*version <= 3")]
+    version: u8,
+}
+
+let value = DekuTest::try_from([0x04].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+assert_eq!(
+    Err(DekuError::Assertion("field `version` failed assertion: *version <= 3".into())),
+    value
+);
+
+let value: Result<Vec<u8>, _> = DekuTest { version: 0x04 }.try_into();
+assert!(value.is_err());
+```
+
+A field condition can use the `deku::is_byte_aligned()` token, true when the position after the
+field is a multiple of 8 bits from the start of the container. A condition using it is checked
+after the field is written, rather than before.
+
+Example, the variable-size field must end on a byte boundary:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(bits = "4")]
+    len: u8,
+    #[deku(bits = "*len as usize", assert = "deku::is_byte_aligned()")]
+    value: u8,
+}
+
+let value = DekuTest::try_from([0x4A].as_ref()).unwrap();
+assert_eq!(DekuTest { len: 4, value: 0xA }, value);
+
+let value = DekuTest::try_from([0x3A].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+assert_eq!(
+    Err(DekuError::Assertion(
+        "field `value` failed assertion: deku::is_byte_aligned()".into()
+    )),
+    value
+);
+```
+
+As a top-level attribute, the condition is checked once the whole container is read, and before
+it's written. The container is in scope as `self`, so conditions between fields can be checked in
+one place.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(assert = "self.header_len as usize == self.options.len() + 2")]
+struct DekuTest {
+    header_len: u8,
+    count: u8,
+    #[deku(count = "count")]
+    options: Vec<u8>,
+}
+
+let value = DekuTest::try_from([0x03, 0x02, 0xAA, 0xBB].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+assert_eq!(
+    Err(DekuError::Assertion(
+        "`DekuTest` failed assertion: self.header_len as usize == self.options.len() + 2".into()
+    )),
+    value
+);
+```
+
+# assert_eq
+
+Specify a value the field must equal, checked after the field is read and before it is
+written. The fields before it are in scope as references.
+
+When the field doesn't equal the value, a `DekuError::Assertion` is returned with the expected
+and actual values, the field must implement `PartialEq` and `Debug`.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(endian = "big", assert_eq = "0xCAFE")]
+    signature: u16,
+    len: u8,
+    #[deku(assert_eq = "*len")]
+    len_copy: u8,
+}
+
+let value = DekuTest::try_from([0xCA, 0xFF, 0x01, 0x01].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::Assertion(
+        "field `signature` failed assertion: expected 51966 got 51967".into()
+    )),
+    value
+);
+
+let value = DekuTest::try_from([0xCA, 0xFE, 0x01, 0x02].as_ref());
+# let value = value.map_err(DekuError::into_inner);
+# #[cfg(not(feature = "static-errors"))]
+assert_eq!(
+    Err(DekuError::Assertion("field `len_copy` failed assertion: expected 1 got 2".into())),
+    value
+);
+```
+
+# reader/writer
+
+Specify custom reader or writer tokens for reading a field or variant
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(
+        reader = "DekuTest::read(rest)",
+        writer = "DekuTest::write(&self.field_a)"
+    )]
+    field_a: String,
+}
+
+impl DekuTest {
+    /// Read and convert to String
+    fn read(
+        rest: &BitSlice<Msb0, u8>,
+    ) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
+        let (rest, value) = u8::read(rest, ())?;
+        Ok((rest, value.to_string()))
+    }
+
+    /// Parse from String to u8 and write
+    fn write(field_a: &str) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let value = field_a.parse::<u8>().unwrap();
+        value.write(())
+    }
+}
+
+let data: Vec<u8> = vec![0x01];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: "1".to_string() },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+## Codec functions
+
+The `#[deku_reader(..)]` and `#[deku_writer(..)]` attribute macros generate a reader or writer
+from a function converting a value, so a custom codec can be defined once and used by many
+fields.
+
+The `type` of the value is read or written with the `endian`, `bit_order`, `bits`, `bytes` and
+`ctx` given in the attribute, as for a field. The function takes the value as first argument,
+followed by any extra arguments, and returns a `Result<T, DekuError>`:
+- `#[deku_reader(..)] fn f(value: V, args..) -> Result<T, DekuError>` is called as
+  `f(rest, args..)`
+- `#[deku_writer(..)] fn f(value: &T, args..) -> Result<V, DekuError>` is called as
+  `f(value, args..)`
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+/// Read a big-endian u16 in tenths of a unit
+#[deku_reader(type = "u16", endian = "big")]
+fn tenths_read(value: u16) -> Result<f32, DekuError> {
+    Ok(f32::from(value) / 10.0)
 }
 
-impl DekuTest {
-    fn map_field_b(field_b: u8) -> Result<String, DekuError> {
-        Ok(field_b.to_string())
-    }
+#[deku_writer(type = "u16", endian = "big")]
+fn tenths_write(value: &f32) -> Result<u16, DekuError> {
+    Ok((value * 10.0).round() as u16)
 }
 
-let data: Vec<u8> = vec![0x01, 0x02];
+# #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+struct DekuTest {
+    #[deku(reader = "tenths_read(rest)", writer = "tenths_write(field_a)")]
+    field_a: f32,
+    #[deku(reader = "tenths_read(rest)", writer = "tenths_write(field_b)")]
+    field_b: f32,
+}
+
+let data: Vec<u8> = vec![0x00, 0x7B, 0x01, 0xC8];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { field_a: "1".to_string(), field_b: "2".to_string() },
+    DekuTest { field_a: 12.3, field_b: 45.6 },
     value
 );
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
 ```
 
-# reader/writer
+# with
 
-Specify custom reader or writer tokens for reading a field or variant
+Specify a module providing the `read` and `write` functions of a field, called as
+`module::read(rest, ctx)` and `module::write(field, ctx)` with the context of the field, i.e. its
+`endian`, `bits`, `bytes` and `ctx`.
+
+Codecs of common field types are provided in the [`codecs`](../codecs/index.html) module.
+
+**Note**: Cannot be used in combination with `reader`, `writer`, `count`, `bytes_read`,
+`bits_read` or `read_all`
 
 Example:
 ```rust
@@ -387,35 +2332,18 @@ Example:
 # use std::convert::{TryInto, TryFrom};
 # #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
 struct DekuTest {
-    #[deku(
-        reader = "DekuTest::read(rest)",
-        writer = "DekuTest::write(&self.field_a)"
-    )]
-    field_a: String,
-}
-
-impl DekuTest {
-    /// Read and convert to String
-    fn read(
-        rest: &BitSlice<Msb0, u8>,
-    ) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
-        let (rest, value) = u8::read(rest, ())?;
-        Ok((rest, value.to_string()))
-    }
-
-    /// Parse from String to u8 and write
-    fn write(field_a: &str) -> Result<BitVec<Msb0, u8>, DekuError> {
-        let value = field_a.parse::<u8>().unwrap();
-        value.write(())
-    }
+    #[deku(with = "deku::codecs::cstring")]
+    name: String,
+    #[deku(with = "deku::codecs::varint")]
+    id: u64,
 }
 
-let data: Vec<u8> = vec![0x01];
+let data: Vec<u8> = vec![b'd', b'e', b'k', b'u', 0x00, 0xAC, 0x02];
 
 let value = DekuTest::try_from(data.as_ref()).unwrap();
 
 assert_eq!(
-    DekuTest { field_a: "1".to_string() },
+    DekuTest { name: "deku".to_string(), id: 300 },
     value
 );
 
@@ -537,6 +2465,375 @@ let (rest, value) = Subtype::from_bytes((&data[..], 0)).unwrap();
 assert_eq!(value.b, 0x01 + 0x02)
 ```
 
+**Note**: `ctx_default` is only available when deriving. For types implementing
+`DekuRead`/`DekuWrite` by hand, implement the [`DekuCtxDefault`](../trait.DekuCtxDefault.html)
+trait to read and write the type without a ctx.
+
+# owned
+
+Generate a parallel struct with the given name, where borrowed fields are replaced by
+their owned equivalent, along with a `to_owned()` method converting to it.
+
+This allows a value to be detached from the input it was parsed from.
+
+- `&'a [T]` becomes `Vec<T>`
+- `&'a str` becomes `String`
+- `&'a T` becomes `T`
+- all other fields are cloned
+
+A borrow nested in a field type, such as `Option<&'a [u8]>`, `Vec<&'a str>` or a struct
+`Inner<'a>` borrowing from the input, has no owned equivalent and is a compile error: such a
+field needs its own owned type, written by hand.
+
+The owned struct implements the same `DekuRead`/`DekuWrite` traits as the original,
+by converting from/to the original struct.
+
+Use `owned_derive` to specify derives for the owned struct.
+
+**Note**: `owned` requires deriving `DekuRead`
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(owned = "DekuTestOwned", owned_derive = "Debug, PartialEq")]
+struct DekuTest<'a> {
+    count: u8,
+    #[deku(count = "count")]
+    items: &'a [u8],
+}
+
+let data: Vec<u8> = vec![0x02, 0xAB, 0xCD];
+
+let value = DekuTest::try_from(data.as_slice()).unwrap();
+
+assert_eq!(
+    DekuTestOwned { count: 0x02, items: vec![0xAB, 0xCD] },
+    value.to_owned()
+);
+
+let value = DekuTestOwned::try_from(data.as_slice()).unwrap();
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
+# debug
+
+Dump the code generated by `DekuRead` and `DekuWrite` at compile time, to inspect the parsing
+logic produced by the attributes.
+
+The code is printed to stderr, or written to `<ident>.DekuRead.rs` and `<ident>.DekuWrite.rs`
+in the directory given by the `DEKU_DEBUG_DIR` environment variable if it is set. The
+generated code is not affected.
+
+Example:
+```rust
+# use deku::prelude::*;
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(debug, endian = "big")]
+struct DekuTest {
+    field_a: u8,
+    field_b: u16,
+}
+```
+
+# wire_doc
+
+Generate `WIRE_FORMAT`, a Markdown table of the wire format of the fields: the name, type, bit
+size, endian and doc comment of each field, and the magic and id of the container. The table is
+also the doc comment of the constant, so it shows in the generated documentation of the type, and
+is derived from the same attributes as the reader so it doesn't drift from it.
+
+A size which depends on the input is `variable`, and `skip` fields aren't listed. Generated with
+`DekuRead`.
+
+Example:
+```rust
+# use deku::prelude::*;
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big", wire_doc)]
+struct DekuTest {
+    /// Number of items
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u16>,
+}
+
+assert_eq!(
+    "| Field | Type | Bits | Endian | Description |\n\
+     |---|---|---|---|---|\n\
+     | count | `u8` | 8 | big | Number of items |\n\
+     | items | `Vec<u16>` | variable | big |  |\n",
+    DekuTest::WIRE_FORMAT
+);
+```
+
+# view
+
+Generate a view type named `<Struct>View` with an accessor method per field, reading the field
+on demand from the input instead of parsing the whole struct, and a `view()` constructor.
+
+The struct must be `#[repr(C)]` and its wire layout must match its native layout, this is
+asserted at compile time: there is no padding and each field has the same size on the wire as
+in memory. The fields can only use the [endian](#endian) attribute, and the struct can't use
+[magic](#magic), [trailer](#trailer) or [trailer_checksum](#trailer_checksum), which the
+accessors would ignore.
+
+The input of `view()` must be aligned as the struct.
+
+The size of the struct is available through the [`DekuSize`](../trait.DekuSize.html) trait.
+This is a stricter version of [`DekuView`](../trait.DekuView.html), which is also implemented.
+
+Example:
+```rust
+# use deku::prelude::*;
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(view, endian = "big")]
+#[repr(C)]
+struct DekuTest {
+    field_a: u32,
+    field_b: u16,
+    field_c: [u8; 2],
+}
+
+#[repr(C, align(4))]
+struct Aligned([u8; 8]);
+
+let data = Aligned([0x01, 0x02, 0x03, 0x04, 0xAB, 0xCD, 0xEF, 0xFF]);
+
+let view = DekuTest::view(&data.0).unwrap();
+assert_eq!(0xABCD, view.field_b().unwrap());
+assert_eq!([0xEF, 0xFF], view.field_c().unwrap());
+
+assert_eq!(64, DekuTest::BIT_SIZE);
+```
+
+# magic
+
+Specify a byte string read before the first field, or the `id` of an enum, and written back.
+
+When reading, a magic which doesn't match is a [`DekuError::Magic`](../error/enum.DekuError.html#variant.Magic) error.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(magic = b"\x7FELF")]
+struct DekuTest {
+    class: u8,
+}
+
+let data: Vec<u8> = vec![0x7F, b'E', b'L', b'F', 0x02];
+
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(DekuTest { class: 0x02 }, value);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+
+let data: Vec<u8> = vec![0x7F, b'E', b'L', b'G', 0x02];
+let value = DekuTest::try_from(data.as_ref());
+# let value = value.map_err(DekuError::into_inner);
+assert!(matches!(value, Err(DekuError::Magic(_))));
+```
+
+# checksum
+
+Specify a function computing the checksum held by the field, over the byte range `over` of the
+container, by default all of it
+
+The range is relative to the start of the container, e.g. `over = "2.."`, and may cover fields
+after the checksum. The bits of the checksum field are zeroed when computing the checksum. When
+reading, the checksum is verified once the container is read, a mismatch is an error. When
+writing, the checksum is computed once the container is written and written in place of the value
+of the field. The container must be a whole number of bytes.
+
+Checksum functions are provided in the [`checksum`](../checksum/index.html) module.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct DekuTest {
+    len: u8,
+    #[deku(checksum = "deku::checksum::sum8", over = "2..")]
+    sum: u8,
+    #[deku(count = "len")]
+    data: Vec<u8>,
+}
+
+let data: Vec<u8> = vec![0x02, 0x03, 0x01, 0x02];
+
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { len: 2, sum: 0x03, data: vec![0x01, 0x02] },
+    value
+);
+
+// the checksum is computed when writing
+value.data[0] = 0xFF;
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0x02, 0x01, 0xFF, 0x02], value);
+
+// a mismatch is an error
+let data: Vec<u8> = vec![0x02, 0x00, 0x01, 0x02];
+assert!(DekuTest::try_from(data.as_ref()).is_err());
+```
+
+# verify and sign
+
+Specify functions verifying and computing the signature held by the field, such as an HMAC or an
+Ed25519 signature, over the byte range `over` of the container, by default all of it
+
+The range and the bytes are the same as for a [checksum](#checksum): the bits of the signature
+field are zeroed. When reading, `verify` is called once the container is read with the bytes and
+the signature read, and returns a `Result<(), DekuError>`, an error fails the read. When writing,
+`sign` is called once the container is written with the bytes, and returns a
+`Result<T, DekuError>` of the signature written in place of the value of the field.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+const KEY: u8 = 0x5A;
+
+// a keyed hash, stands for a cryptographic MAC
+fn mac(key: u8, data: &[u8]) -> u16 {
+    data.iter().fold(u16::from(key), |acc, b| acc.rotate_left(3) ^ u16::from(*b))
+}
+
+fn verify(key: u8, data: &[u8], tag: &u16) -> Result<(), DekuError> {
+    if mac(key, data) != *tag {
+        return Err(DekuError::Parse("invalid signature".into()));
+    }
+    Ok(())
+}
+
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct Message {
+    len: u8,
+    #[deku(count = "len")]
+    payload: Vec<u8>,
+    #[deku(
+        verify = "|data, tag| verify(KEY, data, tag)",
+        sign = "|data| Ok(mac(KEY, data))",
+        over = "..",
+        endian = "big"
+    )]
+    tag: u16,
+}
+
+// the signature is computed when writing
+let value = Message { len: 2, payload: vec![0x01, 0x02], tag: 0 };
+let data: Vec<u8> = value.try_into().unwrap();
+assert_eq!(mac(KEY, &[0x02, 0x01, 0x02, 0x00, 0x00]).to_be_bytes(), data[3..]);
+
+let value = Message::try_from(data.as_ref()).unwrap();
+assert_eq!(vec![0x01, 0x02], value.payload);
+
+// a bad signature is an error
+let mut data = data;
+data[1] = 0xFF;
+let value = Message::try_from(data.as_ref());
+# let value = value.map_err(DekuError::into_inner);
+assert_eq!(Err(DekuError::Parse("invalid signature".into())), value);
+```
+
+# trailer_checksum
+
+Specify a function computing the checksum held by the last field of a struct
+
+The function takes the bytes of all fields before the checksum and returns the checksum, of the
+type of the last field. When reading, the checksum read is compared with the one computed, a
+mismatch is an error. When writing, the checksum is computed and written in place of the value
+of the last field. The fields before the checksum must be a whole number of bytes.
+
+Checksum functions are provided in the [`checksum`](../checksum/index.html) module.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(trailer_checksum = "deku::checksum::crc16_ccitt", endian = "big")]
+struct DekuTest {
+    field_a: u8,
+    field_b: u16,
+    crc: u16,
+}
+
+let data: Vec<u8> = vec![0x01, 0x02, 0x03, 0xAD, 0xAD];
+
+let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    DekuTest { field_a: 0x01, field_b: 0x0203, crc: 0xADAD },
+    value
+);
+
+// the checksum is computed when writing
+value.field_a = 0xFF;
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(vec![0xFF, 0x02, 0x03, 0x55, 0xFE], value);
+
+// a mismatch is an error
+let data: Vec<u8> = vec![0x01, 0x02, 0x03, 0x00, 0x00];
+assert!(DekuTest::try_from(data.as_ref()).is_err());
+```
+
+# trailer
+
+Specify the type of a trailer held by the last field of a struct, read from the end of the input
+
+The trailer is read from its last `BIT_SIZE` bits, as given by [DekuSize](../trait.DekuSize.html),
+instead of where the fields before it end. The fields before the trailer are read from the input
+up to the trailer and must consume all of it, e.g. with a payload field using
+[read_all](#read_all). The fields are written in order, the trailer last.
+
+The last field must be of the type of the trailer.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite, DekuSize)]
+#[deku(endian = "big")]
+struct Signature {
+    key_id: u8,
+    signature: u16,
+}
+
+# #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(trailer = "Signature")]
+struct Image {
+    version: u8,
+    #[deku(read_all)]
+    payload: Vec<u8>,
+    signature: Signature,
+}
+
+let data: Vec<u8> = vec![0x01, 0xAA, 0xBB, 0xCC, 0x07, 0x12, 0x34];
+
+let value = Image::try_from(data.as_ref()).unwrap();
+
+assert_eq!(
+    Image {
+        version: 0x01,
+        payload: vec![0xAA, 0xBB, 0xCC],
+        signature: Signature { key_id: 0x07, signature: 0x1234 },
+    },
+    value
+);
+
+let value: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, value);
+```
+
 # id
 
 ## id (top-level)
@@ -646,7 +2943,9 @@ assert_eq!(vec![0xFF], variant_bytes);
 
 Specify the identifier in the form of a match pattern for the enum variant.
 
-The enum variant must have space to store the identifier for proper writing.
+The enum variant must have space to store the identifier for proper writing. The identifier written
+by the variant is checked against the pattern, writing a variant which couldn't be read back
+returns a `DekuError::InvalidParam` error.
 
 Example:
 ```rust
@@ -672,6 +2971,52 @@ assert_eq!(
     value
 );
 
+let variant_bytes: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, variant_bytes);
+
+let value = DekuTest::VariantB { id: 0x07 };
+assert!(value.to_bytes().is_err());
+```
+
+# id_field
+
+Set the field of an [id_pat](#id_pat) or catch-all variant to the variant id which was read.
+
+The id is consumed instead of being read again by the variant, and the field is written as the
+variant id. The field must be of the [type](#type) of the id, and can be used by the expressions of
+the fields after it. With a top-level [id](#id-top-level), the field is set to the id and isn't
+written.
+
+Example:
+```rust
+# use deku::prelude::*;
+# use std::convert::{TryInto, TryFrom};
+# #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+#[deku(type = "u8")]
+enum DekuTest {
+    #[deku(id = "0x01")]
+    VariantA(u8),
+    #[deku(id_pat = "0x02..=0x03")]
+    VariantB {
+        #[deku(id_field)]
+        id: u8,
+        #[deku(cond = "*id == 0x03")]
+        ext: Option<u8>,
+    },
+    VariantUnknown(#[deku(id_field)] u8),
+}
+
+let data: Vec<u8> = vec![0x03, 0xAB];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+assert_eq!(DekuTest::VariantB { id: 0x03, ext: Some(0xAB) }, value);
+
+let variant_bytes: Vec<u8> = value.try_into().unwrap();
+assert_eq!(data, variant_bytes);
+
+let data: Vec<u8> = vec![0xFF];
+let value = DekuTest::try_from(data.as_ref()).unwrap();
+assert_eq!(DekuTest::VariantUnknown(0xFF), value);
+
 let variant_bytes: Vec<u8> = value.try_into().unwrap();
 assert_eq!(data, variant_bytes);
 ```
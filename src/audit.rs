@@ -0,0 +1,240 @@
+//! Audit that writing a value is deterministic, for reproducible output
+//!
+//! Enabled with the `audit` feature. [`audit`] writes a value and checks that the output is a
+//! pure function of the value:
+//! - No multi-byte value is written with the system endianness, the default when no `endian`
+//!   is specified, which differs between platforms
+//! - Writing the value twice gives the same output
+//! - Writing the value read back from the output gives the same output, which detects an order
+//!   not defined by the value, i.e. iterating a `HashMap`
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::audit::{audit, AuditError};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(endian = "big")]
+//! struct Deterministic {
+//!     field_a: u16,
+//! }
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct SystemEndian {
+//!     field_a: u16,
+//! }
+//!
+//! let output = audit(&Deterministic { field_a: 0xABCD }).unwrap();
+//! assert_eq!(vec![0xAB, 0xCD], output);
+//!
+//! assert_eq!(
+//!     Err(AuditError::DefaultEndian(vec!["u16"])),
+//!     audit(&SystemEndian { field_a: 0xABCD })
+//! );
+//! ```
+
+use crate::{DekuContainerRead, DekuContainerWrite, DekuError};
+use std::cell::RefCell;
+use std::vec::Vec;
+
+std::thread_local! {
+    /// Types written with the system endianness, `Some` while auditing
+    static DEFAULT_ENDIAN: RefCell<Option<Vec<&'static str>>> = const { RefCell::new(None) };
+}
+
+/// Record a use of the system endianness, called when a multi-byte value is read or written
+/// without an endian
+pub(crate) fn record_default_endian(type_name: &'static str) {
+    DEFAULT_ENDIAN.with(|types| {
+        if let Some(types) = types.borrow_mut().as_mut() {
+            types.push(type_name);
+        }
+    });
+}
+
+/// Reason a write isn't deterministic
+#[derive(Debug, PartialEq)]
+pub enum AuditError {
+    /// Error reading or writing the value
+    Deku(DekuError),
+    /// Types written with the system endianness
+    DefaultEndian(Vec<&'static str>),
+    /// Writing the value twice gives different outputs
+    NotPure,
+    /// Writing the value read back from the output gives a different output
+    NotCanonical,
+}
+
+impl From<DekuError> for AuditError {
+    fn from(e: DekuError) -> AuditError {
+        AuditError::Deku(e)
+    }
+}
+
+impl core::fmt::Display for AuditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            AuditError::Deku(ref err) => write!(f, "{}", err),
+            AuditError::DefaultEndian(ref types) => {
+                write!(f, "written with the system endianness: {:?}", types)
+            }
+            AuditError::NotPure => write!(f, "writing twice gives different outputs"),
+            AuditError::NotCanonical => {
+                write!(f, "writing the value read back gives a different output")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+/// Write `value`, checking the output is deterministic, see the
+/// [module documentation](index.html)
+pub fn audit<T>(value: &T) -> Result<Vec<u8>, AuditError>
+where
+    T: DekuContainerWrite + for<'a> DekuContainerRead<'a>,
+{
+    DEFAULT_ENDIAN.with(|types| *types.borrow_mut() = Some(Vec::new()));
+    let output = value.to_bytes();
+    let default_endian = DEFAULT_ENDIAN.with(|types| types.borrow_mut().take());
+    let output = output?;
+
+    match default_endian {
+        Some(types) if !types.is_empty() => return Err(AuditError::DefaultEndian(types)),
+        _ => {}
+    }
+
+    if value.to_bytes()? != output {
+        return Err(AuditError::NotPure);
+    }
+
+    let (_, read_back) = T::from_bytes((&output, 0))?;
+    if read_back.to_bytes()? != output {
+        return Err(AuditError::NotCanonical);
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::{BitSize, Count, Endian};
+    use crate::{DekuRead, DekuWrite};
+    use bitvec::prelude::*;
+    use core::cell::Cell;
+    use rstest::rstest;
+
+    /// Items written in an order not defined by the value, like a `HashMap`
+    #[derive(Debug, PartialEq)]
+    struct Unordered(Vec<u8>);
+
+    impl<'a> DekuRead<'a> for Unordered {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, items) = Vec::<u8>::read(input, Count(2))?;
+            Ok((rest, Unordered(items)))
+        }
+    }
+
+    impl DekuWrite for Unordered {
+        fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+            let mut items = self.0.clone();
+            items.reverse();
+            items.write(())
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Unordered {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (_, value) = Self::read(input.0.view_bits(), ())?;
+            Ok(((&[], 0), value))
+        }
+    }
+
+    impl DekuContainerWrite for Unordered {
+        fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
+            Ok(self.to_bits()?.into_vec())
+        }
+
+        fn to_bits(&self) -> Result<BitVec<Msb0, u8>, DekuError> {
+            self.write(())
+        }
+    }
+
+    std::thread_local! {
+        static WRITES: Cell<u8> = const { Cell::new(0) };
+    }
+
+    /// Writes a different value on each write
+    #[derive(Debug, PartialEq)]
+    struct Impure;
+
+    impl<'a> DekuRead<'a> for Impure {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, _) = u8::read(input, ())?;
+            Ok((rest, Impure))
+        }
+    }
+
+    impl DekuWrite for Impure {
+        fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+            let writes = WRITES.with(|writes| {
+                writes.set(writes.get() + 1);
+                writes.get()
+            });
+            writes.write(())
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Impure {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (_, value) = Self::read(input.0.view_bits(), ())?;
+            Ok(((&[], 0), value))
+        }
+    }
+
+    impl DekuContainerWrite for Impure {
+        fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
+            Ok(self.to_bits()?.into_vec())
+        }
+
+        fn to_bits(&self) -> Result<BitVec<Msb0, u8>, DekuError> {
+            self.write(())
+        }
+    }
+
+    #[test]
+    fn test_audit_not_canonical() {
+        assert_eq!(
+            Err(AuditError::NotCanonical),
+            audit(&Unordered(vec![0x01, 0x02]))
+        );
+    }
+
+    #[test]
+    fn test_audit_not_pure() {
+        assert_eq!(Err(AuditError::NotPure), audit(&Impure));
+    }
+
+    #[rstest(bit_size, expected,
+        case::byte(8, vec![]),
+        case::wide(9, vec!["u16"]),
+    )]
+    fn test_record_default_endian(bit_size: usize, expected: Vec<&'static str>) {
+        DEFAULT_ENDIAN.with(|types| *types.borrow_mut() = Some(Vec::new()));
+
+        0xABu16.write(BitSize(bit_size)).unwrap();
+        0xABu16.write((Endian::Big, BitSize(bit_size))).unwrap();
+        0xABu8.write(()).unwrap();
+
+        let types = DEFAULT_ENDIAN.with(|types| types.borrow_mut().take());
+        assert_eq!(Some(expected), types);
+    }
+}
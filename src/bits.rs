@@ -0,0 +1,208 @@
+//! Opaque bit fields of a fixed width: [`Bits`](struct.Bits.html)
+//!
+//! A `Bits<N>` holds exactly `N` bits which are read and written untouched, for reserved or
+//! not-yet-understood fields whose width must be preserved.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::bits::Bits;
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(endian = "big")]
+//! struct DekuTest {
+//!     #[deku(bits = 4)]
+//!     version: u8,
+//!     reserved: Bits<12>,
+//!     length: u16,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x1A, 0xBC, 0x00, 0x10];
+//! let value = DekuTest::try_from(data.as_ref()).unwrap();
+//! assert_eq!(0x10, value.length);
+//! assert_eq!(&[0xAB, 0xC0], value.reserved.as_bytes());
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(data, value);
+//! ```
+
+use crate::ctx::Endian;
+use crate::error::NeedSize;
+use crate::{DekuError, DekuRead, DekuSize, DekuWrite};
+use bitvec::prelude::*;
+use core::fmt;
+
+/// Bytes backing a [`Bits`], the maximum width is 8 times as many bits
+const MAX_BYTES: usize = 32;
+
+/// `N` raw bits, read and written untouched
+///
+/// The bits are stored in order from the most significant bit of the first byte, the bits after
+/// the `N`th are zero. `N` is at most 256.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use deku::bits::Bits;
+/// let data = [0b1010_1100];
+/// let (rest, value) = Bits::<5>::read(data.view_bits(), ()).unwrap();
+/// assert_eq!(3, rest.len());
+/// assert_eq!(&[0b1010_1000], value.as_bytes());
+/// assert_eq!("Bits<5>(0b10101)", format!("{:?}", value));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bits<const N: usize> {
+    bytes: [u8; MAX_BYTES],
+}
+
+impl<const N: usize> Bits<N> {
+    /// Width in bits
+    pub const BITS: usize = N;
+
+    /// Fails to compile when `N` exceeds the capacity
+    const CHECK_WIDTH: () = assert!(N <= MAX_BYTES * 8, "`Bits<N>` holds at most 256 bits");
+
+    /// Bits all zero
+    pub const fn zero() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CHECK_WIDTH;
+        Bits {
+            bytes: [0; MAX_BYTES],
+        }
+    }
+
+    /// Copy the bits of `bits`, which must be `N` bits long
+    pub fn from_bitslice<O: BitOrder, T: BitStore>(
+        bits: &BitSlice<O, T>,
+    ) -> Result<Self, DekuError> {
+        if bits.len() != N {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "bits: expected {} bits, got {}",
+                N,
+                bits.len()
+            )));
+        }
+
+        let mut value = Self::zero();
+        value.bytes.view_bits_mut::<Msb0>()[..N].clone_from_bitslice(bits);
+        Ok(value)
+    }
+
+    /// The `N` bits
+    pub fn as_bitslice(&self) -> &BitSlice<Msb0, u8> {
+        &self.bytes.view_bits()[..N]
+    }
+
+    /// The `N` bits, mutable
+    pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<Msb0, u8> {
+        &mut self.bytes.view_bits_mut()[..N]
+    }
+
+    /// The bytes holding the bits, the last byte is padded with zero bits
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..N.div_ceil(8)]
+    }
+}
+
+impl<const N: usize> Default for Bits<N> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const N: usize> fmt::Debug for Bits<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bits<{}>(0b", N)?;
+        for bit in self.as_bitslice() {
+            f.write_str(if *bit { "1" } else { "0" })?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<const N: usize> DekuSize for Bits<N> {
+    const BIT_SIZE: usize = N;
+}
+
+macro_rules! ImplDekuBits {
+    ($ctx_arg:tt: $ctx_type:ty) => {
+        impl<'a, const N: usize> DekuRead<'a, $ctx_type> for Bits<N> {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                $ctx_arg: $ctx_type,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                if input.len() < N {
+                    return Err(DekuError::Incomplete(NeedSize::new(N, input.len())));
+                }
+
+                let (bits, rest) = input.split_at(N);
+                let value = Self::from_bitslice(bits)?;
+                Ok((rest, value))
+            }
+        }
+
+        impl<const N: usize> DekuWrite<$ctx_type> for Bits<N> {
+            fn write(&self, $ctx_arg: $ctx_type) -> Result<BitVec<Msb0, u8>, DekuError> {
+                Ok(self.as_bitslice().to_bitvec())
+            }
+        }
+    };
+}
+
+// The bits are untouched: the endian of the container is ignored
+ImplDekuBits!(_endian: Endian);
+ImplDekuBits!(_ctx: ());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(input, expected_rest,
+        case::aligned(&[0xAB, 0xCD, 0xEF], bits![Msb0, u8; 1, 1, 0, 1, 1, 1, 1, 0, 1, 1, 1, 1]),
+        case::exact(&[0xAB, 0xC0], bits![Msb0, u8; 0, 0, 0, 0]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 12, got: 8 })")]
+        case::not_enough_data(&[0xAB], bits![Msb0, u8;]),
+    )]
+    fn test_bits_read(input: &[u8], expected_rest: &BitSlice<Msb0, u8>) {
+        let (rest, value) = Bits::<12>::read(input.view_bits(), ()).unwrap();
+        assert_eq!(expected_rest, rest);
+        assert_eq!(&[0xAB, 0xC0], value.as_bytes());
+        assert_eq!(&input.view_bits::<Msb0>()[..12], value.write(()).unwrap());
+    }
+
+    #[test]
+    fn test_bits_unaligned() {
+        let input = [0b1011_0110, 0b1100_0000];
+        let (rest, value) = Bits::<7>::read(&input.view_bits()[3..], Endian::Big).unwrap();
+        assert_eq!(6, rest.len());
+        assert_eq!(&[0b1011_0110], value.as_bytes());
+        assert_eq!("Bits<7>(0b1011011)", format!("{:?}", value));
+        assert_eq!(7, Bits::<7>::BIT_SIZE);
+    }
+
+    #[test]
+    fn test_bits_from_bitslice() {
+        let mut value = Bits::<3>::from_bitslice(bits![Msb0, u8; 1, 0, 1]).unwrap();
+        value.as_mut_bitslice().set(1, true);
+        assert_eq!(&[0b1110_0000], value.as_bytes());
+        assert_eq!(Bits::<0>::default().as_bytes(), &[] as &[u8]);
+
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "bits: expected 3 bits, got 2"
+            ))),
+            Bits::<3>::from_bitslice(bits![Msb0, u8; 1, 0])
+        );
+    }
+
+    #[test]
+    fn test_bits_wide() {
+        let input = [0xFF; 32];
+        let (rest, value) = Bits::<256>::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(&input, value.as_bytes());
+    }
+}
@@ -0,0 +1,132 @@
+//! Checksum functions to use with the [checksum](../attributes/index.html#checksum) and
+//! [trailer_checksum](../attributes/index.html#trailer_checksum) attributes
+//!
+//! A checksum function takes the bytes to compute the checksum over and returns the checksum,
+//! of the type of the field holding it.
+
+/// Additive checksum: the wrapping sum of the bytes
+///
+/// # Examples
+/// ```rust
+/// # use deku::checksum::sum8;
+/// assert_eq!(0xDD, sum8(b"123456789"));
+/// ```
+pub fn sum8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// CRC-8/SMBUS: polynomial `0x07`, initial value `0x00`, no reflection
+///
+/// # Examples
+/// ```rust
+/// # use deku::checksum::crc8;
+/// assert_eq!(0xF4, crc8(b"123456789"));
+/// ```
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0x00;
+
+    for byte in data {
+        crc ^= *byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// CRC-16/CCITT-FALSE: polynomial `0x1021`, initial value `0xFFFF`, no reflection
+///
+/// # Examples
+/// ```rust
+/// # use deku::checksum::crc16_ccitt;
+/// assert_eq!(0x29B1, crc16_ccitt(b"123456789"));
+/// ```
+pub fn crc16_ccitt(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
+
+/// CRC-32/ISO-HDLC, as used by Ethernet and zip: polynomial `0x04C11DB7`, initial value
+/// `0xFFFFFFFF`, reflected, final XOR `0xFFFFFFFF`
+///
+/// # Examples
+/// ```rust
+/// # use deku::checksum::crc32;
+/// assert_eq!(0xCBF43926, crc32(b"123456789"));
+/// ```
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest(input, expected,
+        case::empty(&[], 0x00),
+        case::check(b"123456789", 0xDD),
+        case::wrap(&[0xFF, 0x02], 0x01),
+    )]
+    fn test_sum8(input: &[u8], expected: u8) {
+        assert_eq!(expected, sum8(input));
+    }
+
+    #[rstest(input, expected,
+        case::empty(&[], 0x00),
+        case::check(b"123456789", 0xF4),
+        case::one(&[0x01], 0x07),
+    )]
+    fn test_crc8(input: &[u8], expected: u8) {
+        assert_eq!(expected, crc8(input));
+    }
+
+    #[rstest(input, expected,
+        case::empty(&[], 0xFFFF),
+        case::check(b"123456789", 0x29B1),
+        case::zero(&[0x00], 0xE1F0),
+    )]
+    fn test_crc16_ccitt(input: &[u8], expected: u16) {
+        assert_eq!(expected, crc16_ccitt(input));
+    }
+
+    #[rstest(input, expected,
+        case::empty(&[], 0x0000_0000),
+        case::check(b"123456789", 0xCBF4_3926),
+        case::zero(&[0x00], 0xD202_EF8D),
+    )]
+    fn test_crc32(input: &[u8], expected: u32) {
+        assert_eq!(expected, crc32(input));
+    }
+}
@@ -0,0 +1,2589 @@
+//! Codecs of common field types to use with the [with](../attributes/index.html#with) attribute
+//!
+//! A codec is a module providing the functions:
+//! - `read(input: &BitSlice<Msb0, u8>, ctx: Ctx) -> Result<(&BitSlice<Msb0, u8>, T), DekuError>`
+//! - `write(value: &T, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>`
+//!
+//! where `ctx` is the context of the field, i.e. its `endian`, `bits`, `bytes` and `ctx`.
+//! Codecs without a use for the context ignore it.
+//!
+//! Checksums held by a trailing field are supported by the
+//! [trailer_checksum](../attributes/index.html#trailer_checksum) attribute.
+
+use crate::error::NeedSize;
+use crate::{DekuError, DekuRead};
+use alloc::{format, string::String, vec::Vec};
+use bitvec::prelude::*;
+
+/// Read a byte at a time until `done` returns true for the last byte read
+fn read_bytes_until(
+    input: &BitSlice<Msb0, u8>,
+    mut done: impl FnMut(&[u8]) -> Result<bool, DekuError>,
+) -> Result<(&BitSlice<Msb0, u8>, Vec<u8>), DekuError> {
+    let mut rest = input;
+    let mut bytes = Vec::new();
+
+    loop {
+        let (new_rest, byte) = u8::read(rest, ())?;
+        rest = new_rest;
+        bytes.push(byte);
+
+        if done(&bytes)? {
+            return Ok((rest, bytes));
+        }
+    }
+}
+
+/// Null-terminated UTF-8 string, as a `String`
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::cstring")]
+///     name: String,
+/// }
+///
+/// let data: &[u8] = b"deku\0";
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { name: "deku".to_string() }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod cstring {
+    use super::*;
+
+    /// Read bytes up to and including a nul byte
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
+        let (rest, mut bytes) = read_bytes_until(input, |bytes| Ok(bytes.last() == Some(&0)))?;
+        bytes.pop();
+
+        let value =
+            String::from_utf8(bytes).map_err(|e| DekuError::Parse(error_msg!("cstring: {}", e)))?;
+        Ok((rest, value))
+    }
+
+    /// Write the bytes followed by a nul byte
+    pub fn write<Ctx>(value: &str, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        if value.contains('\0') {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "cstring: string contains a nul byte"
+            )));
+        }
+
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        Ok(BitVec::from_vec(bytes))
+    }
+}
+
+/// UTF-8 string prefixed by its byte length as a `u8`, as a `String`
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::pascal_string")]
+///     name: String,
+/// }
+///
+/// let data: &[u8] = b"\x04deku";
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { name: "deku".to_string() }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod pascal_string {
+    use super::*;
+    use core::convert::TryFrom;
+
+    /// Read the length, then the bytes
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
+        let (rest, len) = u8::read(input, ())?;
+        let (rest, bytes) = Vec::<u8>::read(rest, crate::ctx::Count(usize::from(len)))?;
+
+        let value = String::from_utf8(bytes)
+            .map_err(|e| DekuError::Parse(error_msg!("pascal_string: {}", e)))?;
+        Ok((rest, value))
+    }
+
+    /// Write the length, then the bytes
+    pub fn write<Ctx>(value: &str, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let len = u8::try_from(value.len()).map_err(|_| {
+            DekuError::InvalidParam(error_msg!(
+                "pascal_string: length {} does not fit in a u8",
+                value.len()
+            ))
+        })?;
+
+        let mut bytes = Vec::with_capacity(value.len() + 1);
+        bytes.push(len);
+        bytes.extend_from_slice(value.as_bytes());
+        Ok(BitVec::from_vec(bytes))
+    }
+}
+
+/// Unsigned LEB128 variable-length integer, as a `u64`
+///
+/// Each byte holds 7 bits of the value, least significant first, and has its high bit set if
+/// more bytes follow.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::varint")]
+///     value: u64,
+/// }
+///
+/// let data: &[u8] = &[0xE5, 0x8E, 0x26];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: 624485 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod varint {
+    use super::*;
+
+    /// Maximum number of bytes of a `u64`
+    const MAX_BYTES: usize = 10;
+
+    /// Read bytes until one without the continuation bit
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, u64), DekuError> {
+        let (rest, bytes) = read_bytes_until(input, |bytes| {
+            if bytes.len() > MAX_BYTES {
+                return Err(DekuError::Parse(error_msg!(
+                    "varint: value does not fit in a u64"
+                )));
+            }
+            Ok(matches!(bytes.last(), Some(byte) if byte & 0x80 == 0))
+        })?;
+
+        let mut value: u64 = 0;
+        for (i, byte) in bytes.iter().enumerate() {
+            let bits = u64::from(byte & 0x7F);
+            let shifted = bits << (7 * i);
+            if shifted >> (7 * i) != bits {
+                return Err(DekuError::Parse(error_msg!(
+                    "varint: value does not fit in a u64"
+                )));
+            }
+            value |= shifted;
+        }
+
+        Ok((rest, value))
+    }
+
+    /// Write the value 7 bits at a time
+    pub fn write<Ctx>(value: &u64, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut value = *value;
+        let mut bytes = Vec::with_capacity(MAX_BYTES);
+
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return Ok(BitVec::from_vec(bytes));
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+}
+
+/// Maximum number of leading zero bits of an exp-Golomb code, of a value up to `2^33 - 2`
+const EXP_GOLOMB_MAX_ZEROS: usize = 32;
+
+/// Read an exp-Golomb code: `n` zero bits, a one bit, then the `n` low bits of the value plus one
+fn read_exp_golomb<'a>(
+    input: &'a BitSlice<Msb0, u8>,
+    codec: &str,
+) -> Result<(&'a BitSlice<Msb0, u8>, u64), DekuError> {
+    let zeros = input.iter().take_while(|bit| !**bit).count();
+    if zeros > EXP_GOLOMB_MAX_ZEROS {
+        return Err(DekuError::Parse(error_msg!(
+            "{}: more than {} leading zero bits",
+            codec,
+            EXP_GOLOMB_MAX_ZEROS
+        )));
+    }
+
+    let len = 2 * zeros + 1;
+    if input.len() < len {
+        return Err(DekuError::Incomplete(NeedSize::new(len, input.len())));
+    }
+
+    let (bits, rest) = input.split_at(len);
+    let value = bits[zeros..]
+        .iter()
+        .fold(0u64, |value, bit| (value << 1) | u64::from(*bit));
+
+    Ok((rest, value - 1))
+}
+
+/// Write an exp-Golomb code, of a value up to `2^33 - 2`
+fn write_exp_golomb(value: u64) -> BitVec<Msb0, u8> {
+    let value = value + 1;
+    let len = 64 - value.leading_zeros() as usize;
+
+    let mut bits = BitVec::with_capacity(2 * len - 1);
+    bits.resize(len - 1, false);
+    bits.extend(value.view_bits::<Msb0>()[64 - len..].iter().copied());
+    bits
+}
+
+/// Unsigned exp-Golomb code, `ue(v)` of H.264 and H.265, as a `u32`
+///
+/// The value plus one is written in the least number of bits, preceded by as many zero bits
+/// less one, e.g. `0` is `1`, `1` is `010` and `3` is `00100`. The code is read bit by bit, it
+/// doesn't need to be byte aligned.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::exp_golomb")]
+///     field_a: u32,
+///     #[deku(with = "deku::codecs::exp_golomb")]
+///     field_b: u32,
+///     #[deku(bits = 2)]
+///     flags: u8,
+/// }
+///
+/// let data: &[u8] = &[0b1_00100_10];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { field_a: 0, field_b: 3, flags: 0b10 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod exp_golomb {
+    use super::*;
+    use core::convert::TryFrom;
+
+    /// Read the leading zero bits, then the value
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, u32), DekuError> {
+        let (rest, value) = read_exp_golomb(input, "exp_golomb")?;
+        let value = u32::try_from(value)
+            .map_err(|_| DekuError::Parse(error_msg!("exp_golomb: value does not fit in a u32")))?;
+
+        Ok((rest, value))
+    }
+
+    /// Write the leading zero bits, then the value
+    pub fn write<Ctx>(value: &u32, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        Ok(write_exp_golomb(u64::from(*value)))
+    }
+}
+
+/// Signed exp-Golomb code, `se(v)` of H.264 and H.265, as an `i32`
+///
+/// The positive values `k` are the [exp_golomb](exp_golomb/index.html) code of `2k - 1` and the
+/// others of `-2k`, e.g. `1` is `010`, `-1` is `011` and `2` is `00100`.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::signed_exp_golomb")]
+///     field_a: i32,
+///     #[deku(with = "deku::codecs::signed_exp_golomb")]
+///     field_b: i32,
+/// }
+///
+/// let data: &[u8] = &[0b011_00100];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { field_a: -1, field_b: 2 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod signed_exp_golomb {
+    use super::*;
+    use core::convert::TryFrom;
+
+    /// Read the unsigned code, then map it to the signed value
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, i32), DekuError> {
+        let (rest, code) = read_exp_golomb(input, "signed_exp_golomb")?;
+        let value = if code % 2 == 1 {
+            (code / 2 + 1) as i64
+        } else {
+            -((code / 2) as i64)
+        };
+        let value = i32::try_from(value).map_err(|_| {
+            DekuError::Parse(error_msg!(
+                "signed_exp_golomb: value does not fit in an i32"
+            ))
+        })?;
+
+        Ok((rest, value))
+    }
+
+    /// Map the value to the unsigned code, then write it
+    pub fn write<Ctx>(value: &i32, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let value = i64::from(*value);
+        let code = if value > 0 { 2 * value - 1 } else { -2 * value };
+
+        Ok(write_exp_golomb(code as u64))
+    }
+}
+
+/// Packed BCD (binary-coded decimal) of the number of bytes given by `bits` or `bytes`, two digits
+/// per byte, as an unsigned integer
+///
+/// The digits are read and written most significant first. The context is the bit size of the
+/// field and the [`NibbleOrder`](../../ctx/enum.NibbleOrder.html) of the digits of a byte, see the
+/// [bcd](../../attributes/index.html#bcd) attribute. A nibble greater than 9 is an error.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::bcd", bytes = "2", ctx = "deku::ctx::NibbleOrder::Little")]
+///     value: u16,
+/// }
+///
+/// let data: &[u8] = &[0x21, 0x43];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: 1234 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod bcd {
+    use super::*;
+    use crate::ctx::{BitSize, NibbleOrder};
+    use core::convert::TryFrom;
+
+    /// Read the digits, then convert the value to the field type
+    pub fn read<T: TryFrom<u128>>(
+        input: &BitSlice<Msb0, u8>,
+        (bit_size, order): (BitSize, NibbleOrder),
+    ) -> Result<(&BitSlice<Msb0, u8>, T), DekuError> {
+        let bit_size: usize = bit_size.into();
+        if !bit_size.is_multiple_of(8) {
+            return Err(DekuError::Parse(error_msg!(
+                "bcd: {} bits is not a whole number of bytes",
+                bit_size
+            )));
+        }
+        if input.len() < bit_size {
+            return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+        }
+
+        let type_name = core::any::type_name::<T>();
+        let overflow =
+            || DekuError::Parse(error_msg!("bcd: value does not fit in a `{}`", type_name));
+
+        let mut value: u128 = 0;
+        let mut rest = input;
+        for i in 0..bit_size / 8 {
+            let (new_rest, byte) = u8::read(rest, ())?;
+            rest = new_rest;
+
+            let digits = match order {
+                NibbleOrder::Big => [byte >> 4, byte & 0x0F],
+                NibbleOrder::Little => [byte & 0x0F, byte >> 4],
+            };
+            for (j, digit) in digits.iter().enumerate() {
+                if *digit > 9 {
+                    return Err(DekuError::Parse(error_msg!(
+                        "bcd: invalid digit 0x{:X} at nibble {}",
+                        digit,
+                        2 * i + j
+                    )));
+                }
+                value = value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(u128::from(*digit)))
+                    .ok_or_else(overflow)?;
+            }
+        }
+
+        let value = T::try_from(value).map_err(|_| overflow())?;
+        Ok((rest, value))
+    }
+
+    /// Write the digits of the value, padded with leading zeros
+    pub fn write<T: Copy + Into<u128>>(
+        value: &T,
+        (bit_size, order): (BitSize, NibbleOrder),
+    ) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let bit_size: usize = bit_size.into();
+        if !bit_size.is_multiple_of(8) {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "bcd: {} bits is not a whole number of bytes",
+                bit_size
+            )));
+        }
+
+        let value: u128 = (*value).into();
+        let mut rest = value;
+        let mut bytes = alloc::vec![0u8; bit_size / 8];
+        for byte in bytes.iter_mut().rev() {
+            let low = (rest % 10) as u8;
+            let high = (rest / 10 % 10) as u8;
+            rest /= 100;
+
+            *byte = match order {
+                NibbleOrder::Big => high << 4 | low,
+                NibbleOrder::Little => low << 4 | high,
+            };
+        }
+        if rest != 0 {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "bcd: value {} doesn't fit in {} digits",
+                value,
+                bit_size / 4
+            )));
+        }
+
+        Ok(BitVec::from_vec(bytes))
+    }
+}
+
+/// Fixed-point number of a [`FixedPoint`](../../ctx/struct.FixedPoint.html) format `Qm.n` or
+/// `UQm.n`, as a float
+///
+/// The raw integer is read and written with the endian of the field, and is the value multiplied
+/// by `2^n`. When writing, the value is rounded to the nearest raw integer, and a value out of
+/// the range of the format is an error. The context is the endian and the format, see the
+/// [fixed](../../attributes/index.html#fixed) attribute.
+///
+/// Other types, such as those of the `fixed` crate, can implement
+/// [`FixedPointFloat`](fixed_point/trait.FixedPointFloat.html) to be used as the field type.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(fixed = "Q8.8", endian = "big")]
+///     value: f32,
+/// }
+///
+/// let data: &[u8] = &[0xFE, 0x80];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: -1.5 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod fixed_point {
+    use super::*;
+    use crate::ctx::{Endian, FixedPoint};
+    use crate::DekuWrite;
+
+    /// A type holding the value of a fixed-point number, converted from and to `f64`
+    pub trait FixedPointFloat: Copy {
+        /// Convert the value of the fixed-point number
+        fn from_f64(value: f64) -> Self;
+        /// Convert to the value of the fixed-point number to write
+        fn to_f64(self) -> f64;
+    }
+
+    impl FixedPointFloat for f32 {
+        fn from_f64(value: f64) -> Self {
+            value as f32
+        }
+
+        fn to_f64(self) -> f64 {
+            f64::from(self)
+        }
+    }
+
+    impl FixedPointFloat for f64 {
+        fn from_f64(value: f64) -> Self {
+            value
+        }
+
+        fn to_f64(self) -> f64 {
+            self
+        }
+    }
+
+    /// `2^n`, the scale of the raw integer of a format
+    fn scale(format: FixedPoint) -> f64 {
+        (1u128 << format.frac_bits) as f64
+    }
+
+    /// Read the raw integer, then divide it by the scale
+    pub fn read<T: FixedPointFloat>(
+        input: &BitSlice<Msb0, u8>,
+        (endian, format): (Endian, FixedPoint),
+    ) -> Result<(&BitSlice<Msb0, u8>, T), DekuError> {
+        let (rest, raw) = if format.signed {
+            let (rest, raw) = i64::read(input, (endian, format.bit_size()))?;
+            (rest, raw as f64)
+        } else {
+            let (rest, raw) = u64::read(input, (endian, format.bit_size()))?;
+            (rest, raw as f64)
+        };
+
+        Ok((rest, T::from_f64(raw / scale(format))))
+    }
+
+    /// Multiply the value by the scale, then write the rounded raw integer
+    pub fn write<T: FixedPointFloat>(
+        value: &T,
+        (endian, format): (Endian, FixedPoint),
+    ) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let value = value.to_f64();
+        let scaled = value * scale(format);
+
+        // rounded half away from zero, `as` saturates out of the range of `i128`
+        let raw = if scaled >= 0.0 {
+            (scaled + 0.5) as i128
+        } else {
+            (scaled - 0.5) as i128
+        };
+
+        let bit_size = format.bit_size().0;
+        let (min, max) = if format.signed {
+            (-(1i128 << (bit_size - 1)), (1i128 << (bit_size - 1)) - 1)
+        } else {
+            (0, (1i128 << bit_size) - 1)
+        };
+        if !scaled.is_finite() || raw < min || raw > max {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "fixed_point: value {} out of range of {}",
+                value,
+                format
+            )));
+        }
+
+        if format.signed {
+            (raw as i64).write((endian, format.bit_size()))
+        } else {
+            (raw as u64).write((endian, format.bit_size()))
+        }
+    }
+}
+
+/// Conversions between `f64` and the legacy float formats, as their raw bits
+#[cfg(feature = "legacy-float")]
+mod legacy_float {
+    use super::*;
+    use crate::ctx::Endian;
+
+    /// Split a finite, non-zero `f64` into an integer and a power of two: `|value| = m * 2^exp`
+    fn decompose(value: f64) -> (u64, i32) {
+        let bits = value.to_bits();
+        let exp_bits = ((bits >> 52) & 0x7FF) as i32;
+        let mantissa = bits & ((1 << 52) - 1);
+        if exp_bits == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | (1 << 52), exp_bits - 1075)
+        }
+    }
+
+    /// Shift `m` by `shift` bits, left if positive, rounding to nearest if right
+    fn shift_round(m: u64, shift: i32) -> u64 {
+        if shift >= 0 {
+            m << shift
+        } else if -shift >= 64 {
+            0
+        } else {
+            let shift = -shift as u32;
+            let half = 1u64 << (shift - 1);
+            (m >> shift) + u64::from(m & ((half << 1) - 1) >= half)
+        }
+    }
+
+    /// IBM hexadecimal float of `frac_bits` bits of fraction: sign, 7 bits of excess-64
+    /// exponent of 16, fraction `0.F`
+    pub(super) fn ibm_to_f64(raw: u64, frac_bits: u32) -> f64 {
+        let sign = raw >> (frac_bits + 7) & 1 == 1;
+        let exponent = (raw >> frac_bits & 0x7F) as i32;
+        let fraction = raw & ((1 << frac_bits) - 1);
+
+        let value = fraction as f64 * 2f64.powi(4 * (exponent - 64) - frac_bits as i32);
+        if sign {
+            -value
+        } else {
+            value
+        }
+    }
+
+    pub(super) fn f64_to_ibm(value: f64, frac_bits: u32) -> Result<u64, DekuError> {
+        if !value.is_finite() {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "ibm_float: {:e} can't be represented",
+                value
+            )));
+        }
+        let sign = u64::from(value.is_sign_negative());
+        if value == 0.0 {
+            return Ok(0);
+        }
+
+        // |value| = m * 2^exp, in [2^(b - 1), 2^b)
+        let (m, exp) = decompose(value);
+        let len = 64 - m.leading_zeros() as i32;
+        let b = exp + len;
+
+        // the fraction is in [1/16, 1): |value| = 0.F * 16^e
+        let mut e = b.div_euclid(4) + i32::from(b.rem_euclid(4) != 0);
+        let mut fraction = shift_round(m, exp + frac_bits as i32 - 4 * e);
+        if fraction >> frac_bits != 0 {
+            fraction >>= 4;
+            e += 1;
+        }
+
+        let mut biased = e + 64;
+        if biased > 0x7F {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "ibm_float: {:e} out of range",
+                value
+            )));
+        }
+        if biased < 0 {
+            // below the smallest exponent, the fraction is unnormalized
+            fraction = shift_round(fraction, 4 * biased);
+            biased = 0;
+        }
+        if fraction == 0 {
+            return Ok(0);
+        }
+
+        Ok(sign << (frac_bits + 7) | (biased as u64) << frac_bits | fraction)
+    }
+
+    /// VAX float of `frac_bits` bits of fraction: sign, 8 bits of excess-128 exponent, fraction
+    /// `0.1F` with a hidden bit
+    pub(super) fn vax_to_f64(raw: u64, frac_bits: u32) -> Result<f64, DekuError> {
+        let sign = raw >> (frac_bits + 8) & 1 == 1;
+        let exponent = (raw >> frac_bits & 0xFF) as i32;
+        let fraction = raw & ((1 << frac_bits) - 1);
+
+        if exponent == 0 {
+            return if sign {
+                Err(DekuError::Parse(error_msg!(
+                    "vax_float: reserved operand, negative zero exponent"
+                )))
+            } else {
+                Ok(0.0)
+            };
+        }
+
+        let mantissa = (1 << frac_bits) | fraction;
+        let value = mantissa as f64 * 2f64.powi(exponent - 128 - (frac_bits as i32 + 1));
+        Ok(if sign { -value } else { value })
+    }
+
+    pub(super) fn f64_to_vax(value: f64, frac_bits: u32) -> Result<u64, DekuError> {
+        if !value.is_finite() {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "vax_float: {:e} can't be represented",
+                value
+            )));
+        }
+        if value == 0.0 {
+            return Ok(0);
+        }
+        let sign = u64::from(value.is_sign_negative());
+
+        // |value| = m * 2^exp, in [2^(b - 1), 2^b): |value| = 0.1F * 2^b
+        let (m, exp) = decompose(value);
+        let len = 64 - m.leading_zeros() as i32;
+        let mut b = exp + len;
+
+        let mut mantissa = shift_round(m, frac_bits as i32 + 1 - len);
+        if mantissa >> (frac_bits + 1) != 0 {
+            mantissa >>= 1;
+            b += 1;
+        }
+
+        let biased = b + 128;
+        if biased > 0xFF {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "vax_float: {:e} out of range",
+                value
+            )));
+        }
+        if biased < 1 {
+            // no denormals: flushed to zero
+            return Ok(0);
+        }
+
+        let fraction = mantissa & ((1 << frac_bits) - 1);
+        Ok(sign << (frac_bits + 8) | (biased as u64) << frac_bits | fraction)
+    }
+
+    /// Read the 16-bit little-endian words of a VAX float, most significant first
+    pub(super) fn read_vax_words(
+        input: &BitSlice<Msb0, u8>,
+        words: usize,
+    ) -> Result<(&BitSlice<Msb0, u8>, u64), DekuError> {
+        if input.len() < words * 16 {
+            return Err(DekuError::Incomplete(NeedSize::new(
+                words * 16,
+                input.len(),
+            )));
+        }
+
+        let mut raw = 0;
+        let mut rest = input;
+        for _ in 0..words {
+            let (new_rest, word) = u16::read(rest, Endian::Little)?;
+            rest = new_rest;
+            raw = raw << 16 | u64::from(word);
+        }
+        Ok((rest, raw))
+    }
+
+    /// Write the 16-bit little-endian words of a VAX float, most significant first
+    pub(super) fn write_vax_words(raw: u64, words: usize) -> BitVec<Msb0, u8> {
+        let mut bytes = Vec::with_capacity(words * 2);
+        for i in (0..words).rev() {
+            let word = (raw >> (16 * i)) as u16;
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        BitVec::from_vec(bytes)
+    }
+}
+
+/// IBM System/360 single precision hexadecimal float, as an `f64`
+///
+/// The 32 bits are a sign bit, a 7-bit exponent of 16 in excess-64, and a 24-bit fraction. They
+/// are read and written as a `u32` with the context of the field: IBM data is
+/// `endian = "big"`. There is no infinity or NaN, which are an error when writing, as is a value
+/// out of range. When writing, the fraction is rounded to nearest.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::ibm_float32", endian = "big")]
+///     value: f64,
+/// }
+///
+/// let data: &[u8] = &[0xC2, 0x76, 0xA0, 0x00];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: -118.625 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+#[cfg(feature = "legacy-float")]
+pub mod ibm_float32 {
+    use super::*;
+    use crate::DekuWrite;
+
+    /// Read a `u32`, then convert it to an `f64`
+    pub fn read<'a, Ctx>(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, f64), DekuError>
+    where
+        u32: DekuRead<'a, Ctx>,
+    {
+        let (rest, raw) = u32::read(input, ctx)?;
+        Ok((rest, legacy_float::ibm_to_f64(raw.into(), 24)))
+    }
+
+    /// Convert the `f64`, then write a `u32`
+    pub fn write<Ctx>(value: &f64, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+    where
+        u32: DekuWrite<Ctx>,
+    {
+        let raw = legacy_float::f64_to_ibm(*value, 24)? as u32;
+        raw.write(ctx)
+    }
+}
+
+/// IBM System/360 double precision hexadecimal float, as an `f64`
+///
+/// The 64 bits are a sign bit, a 7-bit exponent of 16 in excess-64, and a 56-bit fraction, which
+/// is rounded to the 53 bits of an `f64` when reading. See
+/// [ibm_float32](../ibm_float32/index.html).
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::ibm_float64", endian = "big")]
+///     value: f64,
+/// }
+///
+/// let data: &[u8] = &[0x40, 0x19, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: 0.1 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+#[cfg(feature = "legacy-float")]
+pub mod ibm_float64 {
+    use super::*;
+    use crate::DekuWrite;
+
+    /// Read a `u64`, then convert it to an `f64`
+    pub fn read<'a, Ctx>(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, f64), DekuError>
+    where
+        u64: DekuRead<'a, Ctx>,
+    {
+        let (rest, raw) = u64::read(input, ctx)?;
+        Ok((rest, legacy_float::ibm_to_f64(raw, 56)))
+    }
+
+    /// Convert the `f64`, then write a `u64`
+    pub fn write<Ctx>(value: &f64, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+    where
+        u64: DekuWrite<Ctx>,
+    {
+        legacy_float::f64_to_ibm(*value, 56)?.write(ctx)
+    }
+}
+
+/// VAX F_floating single precision float, as an `f64`
+///
+/// The 32 bits are a sign bit, an 8-bit exponent in excess-128, and a 23-bit fraction with a
+/// hidden bit, stored as two 16-bit little-endian words, most significant first. The layout is
+/// fixed, the context of the field is ignored. There is no infinity, NaN or denormal: a
+/// negative zero exponent is a reserved operand, which is an error when reading, and when
+/// writing, infinity, NaN and values out of range are an error, and values too small are zero.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::vax_f_float")]
+///     value: f64,
+/// }
+///
+/// let data: &[u8] = &[0x80, 0x40, 0x00, 0x00];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: 1.0 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+#[cfg(feature = "legacy-float")]
+pub mod vax_f_float {
+    use super::*;
+
+    /// Read the words, then convert them to an `f64`
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, f64), DekuError> {
+        let (rest, raw) = legacy_float::read_vax_words(input, 2)?;
+        Ok((rest, legacy_float::vax_to_f64(raw, 23)?))
+    }
+
+    /// Convert the `f64`, then write the words
+    pub fn write<Ctx>(value: &f64, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let raw = legacy_float::f64_to_vax(*value, 23)?;
+        Ok(legacy_float::write_vax_words(raw, 2))
+    }
+}
+
+/// VAX D_floating double precision float, as an `f64`
+///
+/// The 64 bits are a sign bit, an 8-bit exponent in excess-128, and a 55-bit fraction with a
+/// hidden bit, which is rounded to the 53 bits of an `f64` when reading, stored as four 16-bit
+/// little-endian words, most significant first. See [vax_f_float](../vax_f_float/index.html).
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::vax_d_float")]
+///     value: f64,
+/// }
+///
+/// let data: &[u8] = &[0x80, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { value: 1.0 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+#[cfg(feature = "legacy-float")]
+pub mod vax_d_float {
+    use super::*;
+
+    /// Read the words, then convert them to an `f64`
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, f64), DekuError> {
+        let (rest, raw) = legacy_float::read_vax_words(input, 4)?;
+        Ok((rest, legacy_float::vax_to_f64(raw, 55)?))
+    }
+
+    /// Convert the `f64`, then write the words
+    pub fn write<Ctx>(value: &f64, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let raw = legacy_float::f64_to_vax(*value, 55)?;
+        Ok(legacy_float::write_vax_words(raw, 4))
+    }
+}
+
+/// Sign-magnitude integer, as a signed integer
+///
+/// The most significant of the bits is the sign, set if negative, and the other bits are the
+/// magnitude. A negative zero is read as zero, and zero is written as a positive zero. The
+/// context is the endian and the bit size, see the [signed](../../attributes/index.html#signed)
+/// attribute.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(bits = 5, signed = "magnitude")]
+///     offset: i8,
+///     #[deku(bits = 3)]
+///     rest: u8,
+/// }
+///
+/// let data: &[u8] = &[0b10011_000];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { offset: -3, rest: 0 }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod sign_magnitude {
+    use super::*;
+    use crate::ctx::{BitSize, Endian};
+    use crate::DekuWrite;
+    use core::convert::TryFrom;
+
+    /// Read the sign and the magnitude, then convert the value to the field type
+    pub fn read<T: TryFrom<i128>>(
+        input: &BitSlice<Msb0, u8>,
+        (endian, bit_size): (Endian, BitSize),
+    ) -> Result<(&BitSlice<Msb0, u8>, T), DekuError> {
+        let bit_size: usize = bit_size.into();
+        if bit_size < 2 {
+            return Err(DekuError::Parse(error_msg!(
+                "sign_magnitude: {} bits leaves no bits for the magnitude",
+                bit_size
+            )));
+        }
+
+        let (rest, raw) = u128::read(input, (endian, BitSize(bit_size)))?;
+
+        let magnitude_bits = bit_size - 1;
+        let magnitude = raw & (u128::MAX >> (128 - magnitude_bits));
+        let negative = (raw >> magnitude_bits) & 1 == 1;
+
+        let type_name = core::any::type_name::<T>();
+        let overflow = || {
+            DekuError::Parse(error_msg!(
+                "sign_magnitude: value does not fit in a `{}`",
+                type_name
+            ))
+        };
+
+        let value = i128::try_from(magnitude).map_err(|_| overflow())?;
+        let value = if negative { -value } else { value };
+        let value = T::try_from(value).map_err(|_| overflow())?;
+        Ok((rest, value))
+    }
+
+    /// Write the sign and the magnitude of the value
+    pub fn write<T: Copy + Into<i128>>(
+        value: &T,
+        (endian, bit_size): (Endian, BitSize),
+    ) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let bit_size: usize = bit_size.into();
+        if bit_size < 2 {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "sign_magnitude: {} bits leaves no bits for the magnitude",
+                bit_size
+            )));
+        }
+
+        let value: i128 = (*value).into();
+        let magnitude_bits = bit_size - 1;
+        let magnitude = value.unsigned_abs();
+        if magnitude > u128::MAX >> (128 - magnitude_bits) {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "sign_magnitude: value {} doesn't fit in {} bits",
+                value,
+                bit_size
+            )));
+        }
+
+        let sign = u128::from(value < 0) << magnitude_bits;
+        (sign | magnitude).write((endian, BitSize(bit_size)))
+    }
+}
+
+/// `0` or `1`, as a `bool`
+///
+/// The value is read as a `u8` with the context of the field, e.g. `bits`, and any other value
+/// than `0` or `1` is an error, instead of `true`. See the
+/// [strict_bool](../../attributes/index.html#strict_bool) attribute.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::strict_bool")]
+///     flag: bool,
+/// }
+///
+/// let value = DekuTest::try_from(&[0x01][..]).unwrap();
+/// assert_eq!(DekuTest { flag: true }, value);
+///
+/// let value = DekuTest::try_from(&[0x02][..]);
+/// # let value = value.map_err(DekuError::into_inner);
+/// # #[cfg(not(feature = "static-errors"))]
+/// assert_eq!(
+///     Err(DekuError::Parse("strict_bool: invalid value 0x2, expected 0 or 1".into())),
+///     value
+/// );
+/// ```
+pub mod strict_bool {
+    use super::*;
+    use crate::DekuWrite;
+
+    /// Read a `u8`, which must be `0` or `1`
+    pub fn read<'a, Ctx>(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, bool), DekuError>
+    where
+        u8: DekuRead<'a, Ctx>,
+    {
+        let (rest, value) = u8::read(input, ctx)?;
+        match value {
+            0 => Ok((rest, false)),
+            1 => Ok((rest, true)),
+            _ => Err(DekuError::Parse(error_msg!(
+                "strict_bool: invalid value 0x{:X}, expected 0 or 1",
+                value
+            ))),
+        }
+    }
+
+    /// Write `1` if `true`, `0` otherwise, as a `u8`
+    pub fn write<Ctx>(value: &bool, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+    where
+        u8: DekuWrite<Ctx>,
+    {
+        value.write(ctx)
+    }
+}
+
+/// Duration of `units` units of `1 / per_sec` second
+#[cfg(feature = "std")]
+fn duration_from_units(units: u64, per_sec: u64) -> std::time::Duration {
+    let nanos = (units % per_sec) * (1_000_000_000 / per_sec);
+    std::time::Duration::new(units / per_sec, nanos as u32)
+}
+
+/// Whole units of `1 / per_sec` second of `duration`, if they fit in a `u64`
+#[cfg(feature = "std")]
+fn duration_to_units(duration: std::time::Duration, per_sec: u64) -> Option<u64> {
+    use core::convert::TryFrom;
+
+    u64::try_from(duration.as_nanos() / u128::from(1_000_000_000 / per_sec)).ok()
+}
+
+// Codec of a `SystemTime` as a `u64` of units since the Unix epoch
+macro_rules! unix_timestamp_codec {
+    ($(#[$attr:meta])* $name:ident, $per_sec:expr, $unit:literal) => {
+        $(#[$attr])*
+        #[cfg(feature = "std")]
+        pub mod $name {
+            use super::*;
+            use crate::DekuWrite;
+            use std::time::SystemTime;
+
+            #[doc = concat!("Read the ", $unit, " as a `u64` with the context of the field")]
+            pub fn read<'a, Ctx>(
+                input: &'a BitSlice<Msb0, u8>,
+                ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, SystemTime), DekuError>
+            where
+                u64: DekuRead<'a, Ctx>,
+            {
+                let (rest, units) = u64::read(input, ctx)?;
+                let value = SystemTime::UNIX_EPOCH
+                    .checked_add(duration_from_units(units, $per_sec))
+                    .ok_or_else(|| {
+                        DekuError::Parse(error_msg!(
+                            concat!(stringify!($name), ": {} ", $unit, " is out of range"),
+                            units
+                        ))
+                    })?;
+
+                Ok((rest, value))
+            }
+
+            #[doc = concat!("Write the whole ", $unit, " as a `u64` with the context of the field")]
+            pub fn write<Ctx>(value: &SystemTime, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+            where
+                u64: DekuWrite<Ctx>,
+            {
+                let duration = value.duration_since(SystemTime::UNIX_EPOCH).map_err(|_| {
+                    DekuError::InvalidParam(
+                        error_msg!(concat!(stringify!($name), ": time is before the epoch")),
+                    )
+                })?;
+                let units = duration_to_units(duration, $per_sec).ok_or_else(|| {
+                    DekuError::InvalidParam(
+                        error_msg!(concat!(stringify!($name), ": time doesn't fit in a u64 of ", $unit)),
+                    )
+                })?;
+
+                units.write(ctx)
+            }
+        }
+    };
+}
+
+// Codec of a `Duration` as a `u64` of units
+macro_rules! duration_codec {
+    ($(#[$attr:meta])* $name:ident, $per_sec:expr, $unit:literal) => {
+        $(#[$attr])*
+        #[cfg(feature = "std")]
+        pub mod $name {
+            use super::*;
+            use crate::DekuWrite;
+            use std::time::Duration;
+
+            #[doc = concat!("Read the ", $unit, " as a `u64` with the context of the field")]
+            pub fn read<'a, Ctx>(
+                input: &'a BitSlice<Msb0, u8>,
+                ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Duration), DekuError>
+            where
+                u64: DekuRead<'a, Ctx>,
+            {
+                let (rest, units) = u64::read(input, ctx)?;
+                Ok((rest, duration_from_units(units, $per_sec)))
+            }
+
+            #[doc = concat!("Write the whole ", $unit, " as a `u64` with the context of the field")]
+            pub fn write<Ctx>(value: &Duration, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+            where
+                u64: DekuWrite<Ctx>,
+            {
+                let units = duration_to_units(*value, $per_sec).ok_or_else(|| {
+                    DekuError::InvalidParam(
+                        error_msg!(concat!(stringify!($name), ": duration doesn't fit in a u64 of ", $unit)),
+                    )
+                })?;
+
+                units.write(ctx)
+            }
+        }
+    };
+}
+
+unix_timestamp_codec!(
+    /// Seconds since the Unix epoch as a `u64`, as a `std::time::SystemTime`
+    ///
+    /// The context of the field is used to read and write the `u64`, e.g. `endian` or `bytes`.
+    /// The fraction of a second is truncated when writing. The codecs `unix_timestamp_millis`,
+    /// `unix_timestamp_micros` and `unix_timestamp_nanos` count smaller units.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::prelude::*;
+    /// # use std::convert::TryFrom;
+    /// # use std::time::{Duration, SystemTime};
+    /// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    /// struct DekuTest {
+    ///     #[deku(with = "deku::codecs::unix_timestamp", endian = "big", bytes = 4)]
+    ///     time: SystemTime,
+    /// }
+    ///
+    /// let data: &[u8] = &[0x5F, 0x5E, 0x10, 0x00];
+    /// let value = DekuTest::try_from(data).unwrap();
+    /// assert_eq!(
+    ///     DekuTest { time: SystemTime::UNIX_EPOCH + Duration::from_secs(0x5F5E_1000) },
+    ///     value
+    /// );
+    /// assert_eq!(data, &*value.to_bytes().unwrap());
+    /// ```
+    unix_timestamp,
+    1,
+    "seconds"
+);
+
+unix_timestamp_codec!(
+    /// Milliseconds since the Unix epoch as a `u64`, as a `std::time::SystemTime`
+    ///
+    /// See [unix_timestamp](unix_timestamp/index.html).
+    unix_timestamp_millis,
+    1_000,
+    "milliseconds"
+);
+
+unix_timestamp_codec!(
+    /// Microseconds since the Unix epoch as a `u64`, as a `std::time::SystemTime`
+    ///
+    /// See [unix_timestamp](unix_timestamp/index.html).
+    unix_timestamp_micros,
+    1_000_000,
+    "microseconds"
+);
+
+unix_timestamp_codec!(
+    /// Nanoseconds since the Unix epoch as a `u64`, as a `std::time::SystemTime`
+    ///
+    /// See [unix_timestamp](unix_timestamp/index.html).
+    unix_timestamp_nanos,
+    1_000_000_000,
+    "nanoseconds"
+);
+
+duration_codec!(
+    /// Seconds as a `u64`, as a `std::time::Duration`
+    ///
+    /// The context of the field is used to read and write the `u64`, e.g. `endian` or `bytes`.
+    /// The fraction of a second is truncated when writing. The codecs `duration_millis`,
+    /// `duration_micros` and `duration_nanos` count smaller units.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::prelude::*;
+    /// # use std::convert::TryFrom;
+    /// # use std::time::Duration;
+    /// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    /// struct DekuTest {
+    ///     #[deku(with = "deku::codecs::duration_millis", endian = "little", bytes = 4)]
+    ///     elapsed: Duration,
+    /// }
+    ///
+    /// let data: &[u8] = &[0xE8, 0x03, 0x00, 0x00];
+    /// let value = DekuTest::try_from(data).unwrap();
+    /// assert_eq!(DekuTest { elapsed: Duration::from_secs(1) }, value);
+    /// assert_eq!(data, &*value.to_bytes().unwrap());
+    /// ```
+    duration_secs,
+    1,
+    "seconds"
+);
+
+duration_codec!(
+    /// Milliseconds as a `u64`, as a `std::time::Duration`
+    ///
+    /// See [duration_secs](duration_secs/index.html).
+    duration_millis,
+    1_000,
+    "milliseconds"
+);
+
+duration_codec!(
+    /// Microseconds as a `u64`, as a `std::time::Duration`
+    ///
+    /// See [duration_secs](duration_secs/index.html).
+    duration_micros,
+    1_000_000,
+    "microseconds"
+);
+
+duration_codec!(
+    /// Nanoseconds as a `u64`, as a `std::time::Duration`
+    ///
+    /// See [duration_secs](duration_secs/index.html).
+    duration_nanos,
+    1_000_000_000,
+    "nanoseconds"
+);
+
+/// Date and times of the `chrono` and `time` crates, enabled by the features of the same name
+///
+/// The codecs of this module read and write any [`DateTimeValue`](trait.DateTimeValue.html):
+/// `chrono::DateTime<Utc>` and `time::OffsetDateTime`, written in UTC. The context of the field
+/// is used to read and write the integers, e.g. `endian` or `bytes`.
+///
+/// - [unix_secs](unix_secs/index.html), [unix_millis](unix_millis/index.html) and
+///   [unix_nanos](unix_nanos/index.html): an `i64` of units since the Unix epoch, which is also
+///   how the types are read and written without a codec
+/// - [dos](dos/index.html): an MS-DOS time then date, see
+///   [DosDateTime](../../datetime/struct.DosDateTime.html)
+/// - [ntp](ntp/index.html): an NTP timestamp, a `u32` of seconds since 1900 then a `u32` of
+///   fraction of a second
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::{TryInto, TryFrom};
+/// use chrono::{DateTime, Utc};
+///
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// #[deku(endian = "big")]
+/// struct Packet {
+///     #[deku(bytes = 4)]
+///     created: DateTime<Utc>,
+///     #[deku(with = "deku::codecs::date_time::ntp")]
+///     sent: DateTime<Utc>,
+/// }
+///
+/// let data: Vec<u8> = vec![
+///     0x5F, 0x5E, 0x10, 0x00, 0xE3, 0x08, 0x8E, 0x80, 0x80, 0x00, 0x00, 0x00,
+/// ];
+/// let value = Packet::try_from(data.as_ref()).unwrap();
+/// assert_eq!(DateTime::from_timestamp(0x5F5E_1000, 0), Some(value.created));
+/// assert_eq!(DateTime::from_timestamp(0x5F5E_1000, 500_000_000), Some(value.sent));
+///
+/// let value: Vec<u8> = value.try_into().unwrap();
+/// assert_eq!(data, value);
+/// ```
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod date_time {
+    use super::*;
+    use crate::datetime::{civil_from_days, days_from_civil, DosDate, DosDateTime, DosTime};
+    use crate::DekuWrite;
+    use core::convert::TryFrom;
+
+    /// Days from 1970-01-01 to 1978-01-01, the epoch of the date conversions
+    const UNIX_DAYS_1978: i64 = 2_922;
+
+    /// Seconds from 1900-01-01 to the Unix epoch
+    const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+
+    /// A date and time read and written by the codecs of [`date_time`](index.html)
+    pub trait DateTimeValue: Sized {
+        /// The date and time `secs` seconds and `nanos` nanoseconds after the Unix epoch, if it's
+        /// representable
+        fn from_unix(secs: i64, nanos: u32) -> Option<Self>;
+
+        /// Seconds and nanoseconds after the Unix epoch, the nanoseconds are less than a second
+        fn to_unix(&self) -> (i64, u32);
+    }
+
+    #[cfg(feature = "chrono")]
+    impl DateTimeValue for chrono::DateTime<chrono::Utc> {
+        fn from_unix(secs: i64, nanos: u32) -> Option<Self> {
+            chrono::DateTime::from_timestamp(secs, nanos)
+        }
+
+        fn to_unix(&self) -> (i64, u32) {
+            (self.timestamp(), self.timestamp_subsec_nanos())
+        }
+    }
+
+    #[cfg(feature = "time")]
+    impl DateTimeValue for time::OffsetDateTime {
+        fn from_unix(secs: i64, nanos: u32) -> Option<Self> {
+            time::OffsetDateTime::from_unix_timestamp(secs)
+                .and_then(|value| value.replace_nanosecond(nanos))
+                .ok()
+        }
+
+        fn to_unix(&self) -> (i64, u32) {
+            (self.unix_timestamp(), self.nanosecond())
+        }
+    }
+
+    /// Error of a date and time out of the range of the type
+    fn out_of_range(codec: &str, secs: i64) -> DekuError {
+        DekuError::Parse(error_msg!(
+            "{}: {} seconds from the unix epoch is out of range",
+            codec,
+            secs
+        ))
+    }
+
+    // Codec of a date and time as an `i64` of units since the Unix epoch
+    macro_rules! unix_codec {
+        ($(#[$attr:meta])* $name:ident, $per_sec:expr) => {
+            $(#[$attr])*
+            pub mod $name {
+                use super::*;
+
+                /// Read the units as an `i64` with the context of the field
+                pub fn read<'a, T, Ctx>(
+                    input: &'a BitSlice<Msb0, u8>,
+                    ctx: Ctx,
+                ) -> Result<(&'a BitSlice<Msb0, u8>, T), DekuError>
+                where
+                    T: DateTimeValue,
+                    i64: DekuRead<'a, Ctx>,
+                {
+                    let (rest, units) = i64::read(input, ctx)?;
+                    let secs = units.div_euclid($per_sec);
+                    let nanos = units.rem_euclid($per_sec) * (1_000_000_000 / $per_sec);
+                    let value = T::from_unix(secs, nanos as u32)
+                        .ok_or_else(|| out_of_range(stringify!($name), secs))?;
+
+                    Ok((rest, value))
+                }
+
+                /// Write the whole units as an `i64` with the context of the field
+                pub fn write<T, Ctx>(value: &T, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+                where
+                    T: DateTimeValue,
+                    i64: DekuWrite<Ctx>,
+                {
+                    let (secs, nanos) = value.to_unix();
+                    let units = secs
+                        .checked_mul($per_sec)
+                        .and_then(|units| {
+                            units.checked_add(i64::from(nanos) / (1_000_000_000 / $per_sec))
+                        })
+                        .ok_or_else(|| {
+                            DekuError::InvalidParam(
+                                error_msg!(concat!(stringify!($name), ": time doesn't fit in an i64")),
+                            )
+                        })?;
+
+                    units.write(ctx)
+                }
+            }
+        };
+    }
+
+    unix_codec!(
+        /// Seconds since the Unix epoch as an `i64`, the fraction of a second is truncated when
+        /// writing
+        unix_secs,
+        1
+    );
+
+    unix_codec!(
+        /// Milliseconds since the Unix epoch as an `i64`, the fraction of a millisecond is
+        /// truncated when writing
+        unix_millis,
+        1_000
+    );
+
+    unix_codec!(
+        /// Nanoseconds since the Unix epoch as an `i64`, from 1677 to 2262
+        unix_nanos,
+        1_000_000_000
+    );
+
+    /// MS-DOS time then date, see [DosDateTime](../../../datetime/struct.DosDateTime.html)
+    ///
+    /// An odd second is written as the even second before it, and the fraction of a second is
+    /// truncated.
+    pub mod dos {
+        use super::*;
+
+        /// Read a `DosDateTime` with the context of the field, which must be a valid date
+        pub fn read<'a, T, Ctx>(
+            input: &'a BitSlice<Msb0, u8>,
+            ctx: Ctx,
+        ) -> Result<(&'a BitSlice<Msb0, u8>, T), DekuError>
+        where
+            T: DateTimeValue,
+            DosDateTime: DekuRead<'a, Ctx>,
+        {
+            let (rest, DosDateTime { date, time }) = DosDateTime::read(input, ctx)?;
+
+            let days = days_from_civil(i64::from(date.year), date.month, date.day);
+            if civil_from_days(days) != (i64::from(date.year), date.month, date.day)
+                || time.hour > 23
+                || time.minute > 59
+                || time.second > 59
+            {
+                return Err(DekuError::Parse(error_msg!(
+                    "dos: invalid date and time {:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                    date.year,
+                    date.month,
+                    date.day,
+                    time.hour,
+                    time.minute,
+                    time.second
+                )));
+            }
+
+            let secs = (days + UNIX_DAYS_1978) * 86_400
+                + i64::from(time.hour) * 3_600
+                + i64::from(time.minute) * 60
+                + i64::from(time.second);
+            let value = T::from_unix(secs, 0).ok_or_else(|| out_of_range("dos", secs))?;
+
+            Ok((rest, value))
+        }
+
+        /// Write a `DosDateTime` with the context of the field, from 1980 to 2107
+        pub fn write<T, Ctx>(value: &T, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+        where
+            T: DateTimeValue,
+            DosDateTime: DekuWrite<Ctx>,
+        {
+            let (secs, _) = value.to_unix();
+            let (year, month, day) = civil_from_days(secs.div_euclid(86_400) - UNIX_DAYS_1978);
+            let secs_of_day = secs.rem_euclid(86_400);
+
+            let year = u16::try_from(year).map_err(|_| {
+                DekuError::InvalidParam(error_msg!("dos: year {} doesn't fit", year))
+            })?;
+            let value = DosDateTime {
+                date: DosDate { year, month, day },
+                time: DosTime {
+                    hour: (secs_of_day / 3_600) as u8,
+                    minute: (secs_of_day / 60 % 60) as u8,
+                    second: (secs_of_day % 60) as u8,
+                },
+            };
+
+            value.write(ctx)
+        }
+    }
+
+    /// NTP timestamp: a `u32` of seconds since 1900-01-01 then a `u32` of fraction of a second, in
+    /// units of 2^-32 seconds
+    ///
+    /// The seconds are of the NTP era 0, from 1900 to 2036.
+    pub mod ntp {
+        use super::*;
+
+        /// Read the seconds and the fraction as `u32` with the context of the field
+        pub fn read<'a, T, Ctx: Copy>(
+            input: &'a BitSlice<Msb0, u8>,
+            ctx: Ctx,
+        ) -> Result<(&'a BitSlice<Msb0, u8>, T), DekuError>
+        where
+            T: DateTimeValue,
+            u32: DekuRead<'a, Ctx>,
+        {
+            let (rest, secs) = u32::read(input, ctx)?;
+            let (rest, fraction) = u32::read(rest, ctx)?;
+
+            let secs = i64::from(secs) - NTP_UNIX_OFFSET;
+            // round to the nearest nanosecond
+            let nanos = ((u64::from(fraction) * 1_000_000_000 + (1 << 31)) >> 32) as u32;
+            let value = T::from_unix(secs, nanos).ok_or_else(|| out_of_range("ntp", secs))?;
+
+            Ok((rest, value))
+        }
+
+        /// Write the seconds and the fraction as `u32` with the context of the field
+        pub fn write<T, Ctx: Copy>(value: &T, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>
+        where
+            T: DateTimeValue,
+            u32: DekuWrite<Ctx>,
+        {
+            let (secs, nanos) = value.to_unix();
+            let ntp_secs = u32::try_from(secs + NTP_UNIX_OFFSET).map_err(|_| {
+                DekuError::InvalidParam(error_msg!(
+                    "ntp: {} seconds from the unix epoch is out of the era 0",
+                    secs
+                ))
+            })?;
+            // round to the nearest fraction
+            let fraction = ((u64::from(nanos) << 32) + 500_000_000) / 1_000_000_000;
+
+            let mut acc = ntp_secs.write(ctx)?;
+            acc.extend((fraction as u32).write(ctx)?);
+            Ok(acc)
+        }
+    }
+}
+
+/// 6-byte MAC address, as a `String` of colon-separated hex bytes, e.g. `01:23:45:67:89:ab`
+///
+/// To hold the address as bytes, use the [`MacAddr`](../../net/struct.MacAddr.html) type instead.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::mac_address")]
+///     mac: String,
+/// }
+///
+/// let data: &[u8] = &[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB];
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { mac: "01:23:45:67:89:ab".to_string() }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod mac_address {
+    use super::*;
+
+    /// Read 6 bytes and format them
+    pub fn read<Ctx>(
+        input: &BitSlice<Msb0, u8>,
+        _ctx: Ctx,
+    ) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
+        let (rest, bytes) = <[u8; 6]>::read(input, ())?;
+
+        let value = bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(":");
+        Ok((rest, value))
+    }
+
+    /// Parse the 6 bytes and write them
+    pub fn write<Ctx>(value: &str, _ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let error =
+            || DekuError::InvalidParam(error_msg!("mac_address: invalid address {:?}", value));
+
+        let bytes = value
+            .split(':')
+            .map(|byte| {
+                if byte.len() == 2 {
+                    u8::from_str_radix(byte, 16).map_err(|_| error())
+                } else {
+                    Err(error())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if bytes.len() != 6 {
+            return Err(error());
+        }
+
+        Ok(BitVec::from_vec(bytes))
+    }
+}
+
+/// UTF-8 string of the number of bytes given by `bytes`, padded with nul bytes, as any
+/// `Default + core::fmt::Write` string
+///
+/// Reading doesn't allocate, so the field can be a fixed-capacity string such as
+/// `heapless::String<N>` or `arrayvec::ArrayString<N>`. A string longer than the capacity is an
+/// error. The bytes after the first nul byte are padding.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use std::convert::TryFrom;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct DekuTest {
+///     #[deku(with = "deku::codecs::fixed_string", bytes = "6")]
+///     name: String,
+/// }
+///
+/// let data: &[u8] = b"deku\0\0";
+/// let value = DekuTest::try_from(data).unwrap();
+/// assert_eq!(DekuTest { name: "deku".to_string() }, value);
+/// assert_eq!(data, &*value.to_bytes().unwrap());
+/// ```
+pub mod fixed_string {
+    use super::*;
+    use crate::ctx::BitSize;
+    use core::fmt::Write;
+
+    /// Read the bytes, decoding the characters up to the first nul byte
+    pub fn read<T: Default + Write>(
+        input: &BitSlice<Msb0, u8>,
+        bit_size: BitSize,
+    ) -> Result<(&BitSlice<Msb0, u8>, T), DekuError> {
+        let bit_size: usize = bit_size.into();
+        if !bit_size.is_multiple_of(8) {
+            return Err(DekuError::Parse(error_msg!(
+                "fixed_string: {} bits is not a whole number of bytes",
+                bit_size
+            )));
+        }
+        if input.len() < bit_size {
+            return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+        }
+
+        let mut value = T::default();
+        let mut rest = input;
+
+        // the bytes of the current character, decoded once complete
+        let mut char_bytes = [0u8; 4];
+        let mut char_len = 0;
+        let mut padding = false;
+
+        for i in 0..bit_size / 8 {
+            let (new_rest, byte) = u8::read(rest, ())?;
+            rest = new_rest;
+
+            padding |= byte == 0;
+            if padding {
+                continue;
+            }
+
+            char_bytes[char_len] = byte;
+            char_len += 1;
+            match core::str::from_utf8(&char_bytes[..char_len]) {
+                Ok(c) => {
+                    value.write_str(c).map_err(|_| {
+                        DekuError::Parse(error_msg!(
+                            "fixed_string: string longer than the capacity, at byte {}",
+                            i
+                        ))
+                    })?;
+                    char_len = 0;
+                }
+                // the character continues in the next byte
+                Err(e) if e.error_len().is_none() => {}
+                Err(_) => {
+                    return Err(DekuError::Parse(error_msg!(
+                        "fixed_string: invalid utf-8 at byte {}",
+                        i + 1 - char_len
+                    )))
+                }
+            }
+        }
+
+        if char_len != 0 {
+            return Err(DekuError::Parse(error_msg!(
+                "fixed_string: invalid utf-8 at byte {}",
+                bit_size / 8 - char_len
+            )));
+        }
+
+        Ok((rest, value))
+    }
+
+    /// Write the bytes, padded with nul bytes
+    pub fn write(value: &str, bit_size: BitSize) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let bit_size: usize = bit_size.into();
+        if !bit_size.is_multiple_of(8) {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "fixed_string: {} bits is not a whole number of bytes",
+                bit_size
+            )));
+        }
+        if value.len() > bit_size / 8 {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "fixed_string: {} bytes don't fit in {} bytes",
+                value.len(),
+                bit_size / 8
+            )));
+        }
+        if value.contains('\0') {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "fixed_string: string contains a nul byte"
+            )));
+        }
+
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.resize(bit_size / 8, 0);
+        Ok(BitVec::from_vec(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::{BitSize, Endian, NibbleOrder};
+    use rstest::rstest;
+    #[cfg(feature = "std")]
+    use std::time::Duration;
+
+    #[rstest(input, expected, expected_rest,
+        case::empty(&[0x00], Ok(""), bits![Msb0, u8;]),
+        case::rest(b"ab\0c", Ok("ab"), b"c".view_bits::<Msb0>()),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
+        case::no_nul(b"ab", Ok(""), bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_utf8(&[0xFF, 0x00], Err(DekuError::Parse(error_msg!("cstring: invalid utf-8 sequence of 1 bytes from index 0"))), bits![Msb0, u8;]),
+    )]
+    fn test_cstring_read(
+        input: &[u8],
+        expected: Result<&str, DekuError>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        match cstring::read(input.view_bits(), ()) {
+            Ok((rest, value)) => {
+                assert_eq!(expected, Ok(value.as_str()));
+                assert_eq!(expected_rest, rest);
+            }
+            Err(e) => match expected {
+                Err(expected) => assert_eq!(expected, e),
+                Ok(_) => panic!("{:?}", e),
+            },
+        }
+    }
+
+    #[rstest(input, expected,
+        case::empty("", Ok(vec![0x00])),
+        case::text("ab", Ok(vec![b'a', b'b', 0x00])),
+        case::nul("a\0b", Err(DekuError::InvalidParam(error_msg!("cstring: string contains a nul byte")))),
+    )]
+    fn test_cstring_write(input: &str, expected: Result<Vec<u8>, DekuError>) {
+        assert_eq!(
+            expected,
+            cstring::write(input, ()).map(|bits| bits.into_vec())
+        );
+    }
+
+    #[rstest(input, expected,
+        case::empty(&[0x00], Ok("")),
+        case::text(b"\x02ab", Ok("ab")),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
+        case::not_enough_data(b"\x03ab", Ok("")),
+    )]
+    fn test_pascal_string_read(input: &[u8], expected: Result<&str, DekuError>) {
+        let (rest, value) = pascal_string::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, Ok(value.as_str()));
+    }
+
+    #[test]
+    fn test_pascal_string_write() {
+        assert_eq!(
+            b"\x02ab".to_vec(),
+            pascal_string::write("ab", ()).unwrap().into_vec()
+        );
+
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let long = "a".repeat(256);
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "pascal_string: length 256 does not fit in a u8"
+                ))),
+                pascal_string::write(&long, ())
+            );
+        }
+    }
+
+    /// Fixed-capacity string of up to 4 bytes
+    #[derive(Debug, Default)]
+    struct SmallString {
+        bytes: [u8; 4],
+        len: usize,
+    }
+
+    impl core::fmt::Write for SmallString {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            if end > self.bytes.len() {
+                return Err(core::fmt::Error);
+            }
+            self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    impl core::ops::Deref for SmallString {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    #[rstest(input, bytes, expected,
+        case::empty(&[0x00, 0x00], 2, Ok("")),
+        case::full(b"abcd", 4, Ok("abcd")),
+        case::padding(b"ab\0c\0", 5, Ok("ab")),
+        case::multibyte("é\0".as_bytes(), 3, Ok("é")),
+        #[cfg(not(feature = "static-errors"))]
+        case::capacity(b"abcde", 5, Err(DekuError::Parse(error_msg!("fixed_string: string longer than the capacity, at byte 4")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_utf8(&[b'a', 0xFF], 2, Err(DekuError::Parse(error_msg!("fixed_string: invalid utf-8 at byte 1")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::truncated_char(&[b'a', 0xC3], 2, Err(DekuError::Parse(error_msg!("fixed_string: invalid utf-8 at byte 1")))),
+        case::not_enough_data(b"ab", 3, Err(DekuError::Incomplete(NeedSize::new(24, 16)))),
+    )]
+    fn test_fixed_string_read(input: &[u8], bytes: usize, expected: Result<&str, DekuError>) {
+        let res_read =
+            fixed_string::read::<SmallString>(input.view_bits(), BitSize::with_byte_size(bytes));
+        match res_read {
+            Ok((rest, value)) => {
+                assert!(rest.is_empty());
+                assert_eq!(expected, Ok(&*value));
+            }
+            Err(e) => assert_eq!(expected, Err(e)),
+        }
+    }
+
+    #[rstest(input, bytes, expected,
+        case::padding("ab", 4, Ok(b"ab\0\0".to_vec())),
+        case::full("abcd", 4, Ok(b"abcd".to_vec())),
+        #[cfg(not(feature = "static-errors"))]
+        case::too_long("abcde", 4, Err(DekuError::InvalidParam(error_msg!("fixed_string: 5 bytes don't fit in 4 bytes")))),
+        case::nul("a\0", 4, Err(DekuError::InvalidParam(error_msg!("fixed_string: string contains a nul byte")))),
+    )]
+    fn test_fixed_string_write(input: &str, bytes: usize, expected: Result<Vec<u8>, DekuError>) {
+        assert_eq!(
+            expected,
+            fixed_string::write(input, BitSize::with_byte_size(bytes)).map(|bits| bits.into_vec())
+        );
+    }
+
+    #[rstest(input, expected,
+        case::zero(&[0x00], 0),
+        case::one_byte(&[0x7F], 0x7F),
+        case::two_bytes(&[0x80, 0x01], 0x80),
+        case::wiki(&[0xE5, 0x8E, 0x26], 624485),
+        case::max(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01], u64::MAX),
+        #[should_panic(expected = "Parse(\"varint: value does not fit in a u64\")")]
+        case::overflow(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02], 0),
+        #[should_panic(expected = "Parse(\"varint: value does not fit in a u64\")")]
+        case::too_long(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00], 0),
+    )]
+    fn test_varint(input: &[u8], expected: u64) {
+        let (rest, value) = varint::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.to_vec(),
+            varint::write(&value, ()).unwrap().into_vec()
+        );
+    }
+
+    #[rstest(input, expected,
+        case::zero(bits![Msb0, u8; 1], 0),
+        case::one(bits![Msb0, u8; 0, 1, 0], 1),
+        case::two(bits![Msb0, u8; 0, 1, 1], 2),
+        case::three(bits![Msb0, u8; 0, 0, 1, 0, 0], 3),
+        case::eight(bits![Msb0, u8; 0, 0, 0, 1, 0, 0, 1], 8),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 5, got: 4 })")]
+        case::not_enough_data(bits![Msb0, u8; 0, 0, 1, 0], 0),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 5, got: 2 })")]
+        case::only_zeros(bits![Msb0, u8; 0, 0], 0),
+    )]
+    fn test_exp_golomb(input: &BitSlice<Msb0, u8>, expected: u32) {
+        let (rest, value) = exp_golomb::read(input, ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(input, exp_golomb::write(&value, ()).unwrap().as_bitslice());
+    }
+
+    #[test]
+    fn test_exp_golomb_max() {
+        let bits = exp_golomb::write(&u32::MAX, ()).unwrap();
+        assert_eq!(65, bits.len());
+        assert_eq!(
+            Ok((bits![Msb0, u8;], u32::MAX)),
+            exp_golomb::read(&bits, ())
+        );
+
+        // `2^32`, one more than `u32::MAX`
+        let mut bits: BitVec<Msb0, u8> = BitVec::repeat(false, 32);
+        bits.push(true);
+        bits.extend(BitVec::<Msb0, u8>::repeat(false, 31));
+        bits.push(true);
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "exp_golomb: value does not fit in a u32"
+            ))),
+            exp_golomb::read(&bits, ())
+        );
+
+        let mut bits: BitVec<Msb0, u8> = BitVec::repeat(false, 33);
+        bits.push(true);
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "exp_golomb: more than 32 leading zero bits"
+            ))),
+            exp_golomb::read(&bits, ())
+        );
+    }
+
+    #[rstest(input, expected,
+        case::zero(bits![Msb0, u8; 1], 0),
+        case::one(bits![Msb0, u8; 0, 1, 0], 1),
+        case::minus_one(bits![Msb0, u8; 0, 1, 1], -1),
+        case::two(bits![Msb0, u8; 0, 0, 1, 0, 0], 2),
+        case::minus_two(bits![Msb0, u8; 0, 0, 1, 0, 1], -2),
+    )]
+    fn test_signed_exp_golomb(input: &BitSlice<Msb0, u8>, expected: i32) {
+        let (rest, value) = signed_exp_golomb::read(input, ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input,
+            signed_exp_golomb::write(&value, ()).unwrap().as_bitslice()
+        );
+    }
+
+    #[rstest(value, case(i32::MIN), case(i32::MAX))]
+    fn test_signed_exp_golomb_limits(value: i32) {
+        let bits = signed_exp_golomb::write(&value, ()).unwrap();
+        assert_eq!(
+            Ok((bits![Msb0, u8;], value)),
+            signed_exp_golomb::read(&bits, ())
+        );
+    }
+
+    #[rstest(input, order, expected,
+        case::zero(&[0x00, 0x00], NibbleOrder::Big, 0),
+        case::big(&[0x12, 0x34], NibbleOrder::Big, 1234),
+        case::little(&[0x21, 0x43], NibbleOrder::Little, 1234),
+        case::leading_zeros(&[0x00, 0x09], NibbleOrder::Big, 9),
+        case::max(&[0x99, 0x99], NibbleOrder::Little, 9999),
+    )]
+    fn test_bcd(input: &[u8], order: NibbleOrder, expected: u32) {
+        let bit_size = BitSize::with_byte_size(input.len());
+        let (rest, value) = bcd::read::<u32>(input.view_bits(), (bit_size, order)).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.to_vec(),
+            bcd::write(&value, (bit_size, order)).unwrap().into_vec()
+        );
+    }
+
+    #[rstest(input, bit_size, expected,
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_digit(&[0x12, 0x3A], 16, DekuError::Parse(error_msg!("bcd: invalid digit 0xA at nibble 3"))),
+        #[cfg(not(feature = "static-errors"))]
+        case::filler(&[0xF1], 8, DekuError::Parse(error_msg!("bcd: invalid digit 0xF at nibble 0"))),
+        #[cfg(not(feature = "static-errors"))]
+        case::overflow(&[0x02, 0x56], 16, DekuError::Parse(error_msg!("bcd: value does not fit in a `u8`"))),
+        #[cfg(not(feature = "static-errors"))]
+        case::partial_byte(&[0x12], 4, DekuError::Parse(error_msg!("bcd: 4 bits is not a whole number of bytes"))),
+        case::incomplete(&[0x12], 16, DekuError::Incomplete(NeedSize::new(16, 8))),
+    )]
+    fn test_bcd_read_error(input: &[u8], bit_size: usize, expected: DekuError) {
+        assert_eq!(
+            Err(expected),
+            bcd::read::<u8>(input.view_bits(), (BitSize(bit_size), NibbleOrder::Big))
+        );
+    }
+
+    #[cfg(not(feature = "static-errors"))]
+    #[test]
+    fn test_bcd_write_too_large() {
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "bcd: value 12345 doesn't fit in 4 digits"
+            ))),
+            bcd::write(&12345u32, (BitSize(16), NibbleOrder::Big)).map(|bits| bits.into_vec())
+        );
+    }
+
+    #[rstest(input, format, expected,
+        case::q8_8(&[0x01, 0x80], "Q8.8", 1.5),
+        case::q8_8_negative(&[0xFE, 0x80], "Q8.8", -1.5),
+        case::q1_15(&[0x80, 0x00], "Q1.15", -1.0),
+        case::uq0_8(&[0xC0], "UQ0.8", 0.75),
+        case::uq4_4(&[0xFF], "UQ4.4", 15.9375),
+        case::q16_16(&[0x00, 0x01, 0x40, 0x00], "Q16.16", 1.25),
+    )]
+    fn test_fixed_point(input: &[u8], format: &str, expected: f64) {
+        let ctx = (Endian::Big, format.parse().unwrap());
+        let (rest, value) = fixed_point::read::<f64>(input.view_bits(), ctx).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.to_vec(),
+            fixed_point::write(&value, ctx).unwrap().into_vec()
+        );
+    }
+
+    #[test]
+    fn test_fixed_point_little_endian() {
+        let ctx = (Endian::Little, "Q8.8".parse().unwrap());
+        let (_, value) = fixed_point::read::<f32>([0x80, 0xFE].view_bits(), ctx).unwrap();
+        assert_eq!(-1.5, value);
+
+        // rounded to the nearest raw integer
+        assert_eq!(
+            vec![0x01, 0x00],
+            fixed_point::write(&(1.0f32 / 512.0 + 0.0001), ctx)
+                .unwrap()
+                .into_vec()
+        );
+    }
+
+    #[rstest(value, format,
+        case(128.0, "Q8.8"),
+        case(-128.01, "Q8.8"),
+        case(-0.1, "UQ8.8"),
+        case(f64::NAN, "Q8.8"),
+        case(f64::INFINITY, "UQ16.16"),
+    )]
+    fn test_fixed_point_out_of_range(value: f64, format: &str) {
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "fixed_point: value {} out of range of {}",
+                value,
+                format
+            ))),
+            fixed_point::write(&value, (Endian::Big, format.parse().unwrap()))
+                .map(|bits| bits.into_vec())
+        );
+    }
+
+    #[cfg(feature = "legacy-float")]
+    #[rstest(input, expected,
+        case::one(&[0x41, 0x10, 0x00, 0x00], 1.0),
+        case::hundred(&[0x42, 0x64, 0x00, 0x00], 100.0),
+        case::negative(&[0xC2, 0x76, 0xA0, 0x00], -118.625),
+        case::small(&[0x40, 0x28, 0x00, 0x00], 0.15625),
+        case::zero(&[0x00, 0x00, 0x00, 0x00], 0.0),
+    )]
+    fn test_ibm_float32(input: &[u8], expected: f64) {
+        let (rest, value) = ibm_float32::read(input.view_bits(), Endian::Big).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.to_vec(),
+            ibm_float32::write(&value, Endian::Big).unwrap().into_vec()
+        );
+    }
+
+    #[cfg(feature = "legacy-float")]
+    #[test]
+    fn test_ibm_float() {
+        // rounded to nearest
+        assert_eq!(
+            vec![0x40, 0x19, 0x99, 0x9A],
+            ibm_float32::write(&0.1, Endian::Big).unwrap().into_vec()
+        );
+        assert_eq!(
+            vec![0xC1, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            ibm_float64::write(&-1.0, Endian::Big).unwrap().into_vec()
+        );
+        // too small for the smallest exponent, even unnormalized
+        assert_eq!(
+            vec![0x00, 0x00, 0x00, 0x00],
+            ibm_float32::write(&1e-100, Endian::Big).unwrap().into_vec()
+        );
+        // unnormalized fraction
+        let (_, value) =
+            ibm_float32::read([0x41, 0x01, 0x00, 0x00].view_bits(), Endian::Big).unwrap();
+        assert_eq!(0.0625, value);
+
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "ibm_float: 1e80 out of range"
+            ))),
+            ibm_float32::write(&1e80, Endian::Big).map(|bits| bits.into_vec())
+        );
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "ibm_float: NaN can't be represented"
+            ))),
+            ibm_float64::write(&f64::NAN, Endian::Big).map(|bits| bits.into_vec())
+        );
+    }
+
+    #[cfg(feature = "legacy-float")]
+    #[rstest(input, expected,
+        case::one(&[0x80, 0x40, 0x00, 0x00], 1.0),
+        case::negative_one(&[0x80, 0xC0, 0x00, 0x00], -1.0),
+        case::half(&[0x00, 0x40, 0x00, 0x00], 0.5),
+        case::three(&[0x40, 0x41, 0x00, 0x00], 3.0),
+        case::low_word(&[0x80, 0x40, 0x01, 0x00], 1.0 + 2f64.powi(-23)),
+        case::zero(&[0x00, 0x00, 0x00, 0x00], 0.0),
+    )]
+    fn test_vax_f_float(input: &[u8], expected: f64) {
+        let (rest, value) = vax_f_float::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.to_vec(),
+            vax_f_float::write(&value, ()).unwrap().into_vec()
+        );
+    }
+
+    #[cfg(feature = "legacy-float")]
+    #[test]
+    fn test_vax_float() {
+        let input = [0x80, 0x40, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00];
+        let (rest, value) = vax_d_float::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(1.0 + 2f64.powi(-52), value);
+        assert_eq!(
+            input.to_vec(),
+            vax_d_float::write(&value, ()).unwrap().into_vec()
+        );
+
+        // flushed to zero
+        assert_eq!(
+            vec![0x00, 0x00, 0x00, 0x00],
+            vax_f_float::write(&1e-40, ()).unwrap().into_vec()
+        );
+
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "vax_float: reserved operand, negative zero exponent"
+            ))),
+            vax_f_float::read([0x00, 0x80, 0x00, 0x00].view_bits(), ()).map(|(_, value)| value)
+        );
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "vax_float: 1e39 out of range"
+            ))),
+            vax_f_float::write(&1e39, ()).map(|bits| bits.into_vec())
+        );
+        assert_eq!(
+            Err(DekuError::Incomplete(NeedSize::new(64, 32))),
+            vax_d_float::read([0x80, 0x40, 0x00, 0x00].view_bits(), ()).map(|(_, value)| value)
+        );
+    }
+
+    #[rstest(input, endian, bit_size, expected,
+        case::positive(&[0b0011_0000], Endian::Big, 4, 3),
+        case::negative(&[0b1011_0000], Endian::Big, 4, -3),
+        case::negative_min(&[0xFF], Endian::Big, 8, -127),
+        case::negative_little(&[0x34, 0x92], Endian::Little, 16, -0x1234),
+        case::negative_big(&[0x92, 0x34], Endian::Big, 16, -0x1234),
+        case::wide(&[0xFF; 16], Endian::Big, 128, -(i128::MAX)),
+    )]
+    fn test_sign_magnitude(input: &[u8], endian: Endian, bit_size: usize, expected: i128) {
+        let ctx = (endian, BitSize(bit_size));
+        let (rest, value) = sign_magnitude::read::<i128>(input.view_bits(), ctx).unwrap();
+        assert_eq!(input.len() * 8 - bit_size, rest.len());
+        assert_eq!(expected, value);
+
+        assert_eq!(
+            input.view_bits::<Msb0>()[..bit_size],
+            sign_magnitude::write(&value, ctx).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sign_magnitude_zero() {
+        let ctx = (Endian::Big, BitSize(8));
+        // negative zero is read as zero, and written as a positive zero
+        let (_, value) = sign_magnitude::read::<i8>([0x80].view_bits(), ctx).unwrap();
+        assert_eq!(0, value);
+        assert_eq!(
+            vec![0x00],
+            sign_magnitude::write(&value, ctx).unwrap().into_vec()
+        );
+    }
+
+    #[cfg(not(feature = "static-errors"))]
+    #[test]
+    fn test_sign_magnitude_errors() {
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "sign_magnitude: value -8 doesn't fit in 4 bits"
+            ))),
+            sign_magnitude::write(&-8i8, (Endian::Big, BitSize(4))).map(|bits| bits.into_vec())
+        );
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "sign_magnitude: value does not fit in a `i8`"
+            ))),
+            sign_magnitude::read::<i8>([0x00, 0xFF].view_bits(), (Endian::Big, BitSize(16)))
+                .map(|(_, value)| value)
+        );
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "sign_magnitude: 1 bits leaves no bits for the magnitude"
+            ))),
+            sign_magnitude::read::<i8>([0x00].view_bits(), (Endian::Big, BitSize(1)))
+                .map(|(_, value)| value)
+        );
+    }
+
+    #[rstest(input, ctx, expected,
+        case::byte_false(&[0x00], BitSize(8), Ok(false)),
+        case::byte_true(&[0x01], BitSize(8), Ok(true)),
+        case::bit_true(&[0x80], BitSize(1), Ok(true)),
+        #[cfg(not(feature = "static-errors"))]
+        case::byte_invalid(&[0xFF], BitSize(8), Err(DekuError::Parse(error_msg!("strict_bool: invalid value 0xFF, expected 0 or 1")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::bits_invalid(&[0x80], BitSize(2), Err(DekuError::Parse(error_msg!("strict_bool: invalid value 0x2, expected 0 or 1")))),
+    )]
+    fn test_strict_bool(input: &[u8], ctx: BitSize, expected: Result<bool, DekuError>) {
+        let ret = strict_bool::read(input.view_bits(), ctx).map(|(_, value)| value);
+        assert_eq!(expected, ret);
+
+        if let Ok(value) = ret {
+            assert_eq!(
+                input.view_bits::<Msb0>()[..ctx.0],
+                strict_bool::write(&value, ctx).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unix_timestamp() {
+        use crate::ctx::Endian;
+        use std::time::{Duration, SystemTime};
+
+        let input = [0x00, 0x00, 0x00, 0x00, 0x5F, 0x5E, 0x10, 0x00];
+        let (rest, value) = unix_timestamp::read(input.view_bits(), Endian::Big).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::from_secs(0x5F5E_1000),
+            value
+        );
+        assert_eq!(
+            input.to_vec(),
+            unix_timestamp::write(&value, Endian::Big)
+                .unwrap()
+                .into_vec()
+        );
+
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "unix_timestamp: time is before the epoch"
+            ))),
+            unix_timestamp::write(
+                &(SystemTime::UNIX_EPOCH - Duration::from_secs(1)),
+                Endian::Big
+            )
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unix_timestamp_units() {
+        use std::time::SystemTime;
+
+        let time = SystemTime::UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_789);
+        let ctx = Endian::Little;
+
+        let millis = unix_timestamp_millis::write(&time, ctx).unwrap();
+        assert_eq!(
+            1_600_000_000_123u64.to_le_bytes().to_vec(),
+            millis.as_raw_slice()
+        );
+        let (_, value) = unix_timestamp_millis::read(&millis, ctx).unwrap();
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::new(1_600_000_000, 123_000_000),
+            value
+        );
+
+        let micros = unix_timestamp_micros::write(&time, ctx).unwrap();
+        let (_, value) = unix_timestamp_micros::read(&micros, ctx).unwrap();
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::new(1_600_000_000, 123_456_000),
+            value
+        );
+
+        let nanos = unix_timestamp_nanos::write(&time, ctx).unwrap();
+        let (_, value) = unix_timestamp_nanos::read(&nanos, ctx).unwrap();
+        assert_eq!(time, value);
+
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "unix_timestamp_millis: time is before the epoch"
+            ))),
+            unix_timestamp_millis::write(&(SystemTime::UNIX_EPOCH - Duration::from_millis(1)), ctx)
+        );
+
+        // A narrower field holds fewer units
+        let input = [0xFF, 0xFF, 0xFF, 0xFF];
+        let (_, value) =
+            unix_timestamp_millis::read(input.view_bits(), (ctx, BitSize(32))).unwrap();
+        assert_eq!(
+            SystemTime::UNIX_EPOCH + Duration::from_millis(0xFFFF_FFFF),
+            value
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_duration_read() {
+        let input = 1_000u64.to_be_bytes();
+        let input = input.view_bits();
+        let ctx = Endian::Big;
+
+        let (rest, value) = duration_secs::read(input, ctx).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(Duration::from_secs(1_000), value);
+        assert_eq!(
+            Duration::from_millis(1_000),
+            duration_millis::read(input, ctx).unwrap().1
+        );
+        assert_eq!(
+            Duration::from_micros(1_000),
+            duration_micros::read(input, ctx).unwrap().1
+        );
+        assert_eq!(
+            Duration::from_nanos(1_000),
+            duration_nanos::read(input, ctx).unwrap().1
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_duration_write() {
+        let ctx = Endian::Big;
+        let value = Duration::new(2, 500_000_000);
+        assert_eq!(
+            2u64.to_be_bytes().to_vec(),
+            duration_secs::write(&value, ctx).unwrap().into_vec()
+        );
+        assert_eq!(
+            2_500u64.to_be_bytes().to_vec(),
+            duration_millis::write(&value, ctx).unwrap().into_vec()
+        );
+        assert_eq!(
+            2_500_000_000u64.to_be_bytes().to_vec(),
+            duration_nanos::write(&value, ctx).unwrap().into_vec()
+        );
+
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "duration_nanos: duration doesn't fit in a u64 of nanoseconds"
+            ))),
+            duration_nanos::write(&Duration::from_secs(u64::MAX), ctx)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[rstest(input, expected,
+        case::epoch(0, (0, 0)),
+        case::before_epoch(-1_500, (-2, 500_000_000)),
+        case::after_epoch(1_600_000_000_123, (1_600_000_000, 123_000_000)),
+    )]
+    fn test_date_time_unix_millis(input: i64, expected: (i64, u32)) {
+        use chrono::{DateTime, Utc};
+        use date_time::DateTimeValue;
+
+        let data = input.to_be_bytes();
+        let (rest, value): (_, DateTime<Utc>) =
+            date_time::unix_millis::read(data.view_bits(), Endian::Big).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value.to_unix());
+        assert_eq!(
+            data.to_vec(),
+            date_time::unix_millis::write(&value, Endian::Big)
+                .unwrap()
+                .into_vec()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_date_time_unix_range() {
+        use chrono::{DateTime, Utc};
+
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let data = i64::MAX.to_be_bytes();
+            assert_eq!(
+                Err(DekuError::Parse(error_msg!(
+                    "unix_secs: 9223372036854775807 seconds from the unix epoch is out of range"
+                ))),
+                date_time::unix_secs::read::<DateTime<Utc>, _>(data.view_bits(), Endian::Big)
+                    .map(|(_, value)| value)
+            );
+        }
+
+        let value = DateTime::<Utc>::MAX_UTC;
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "unix_nanos: time doesn't fit in an i64"
+            ))),
+            date_time::unix_nanos::write(&value, Endian::Big)
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[rstest(input, expected,
+        case::zip([0x3B, 0x7A, 0x21, 0x51].as_ref(), Ok(1_598_973_474)),
+        case::epoch([0x00, 0x00, 0x21, 0x00].as_ref(), Ok(315_532_800)),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_day([0x00, 0x00, 0x5D, 0x02].as_ref(), Err(DekuError::Parse(error_msg!("dos: invalid date and time 1981-02-29 00:00:00")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_date([0x00, 0x00, 0x00, 0x00].as_ref(), Err(DekuError::Parse(error_msg!("dos: invalid date and time 1980-00-00 00:00:00")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_time([0x00, 0xC0, 0x21, 0x00].as_ref(), Err(DekuError::Parse(error_msg!("dos: invalid date and time 1980-01-01 24:00:00")))),
+    )]
+    fn test_date_time_dos(input: &[u8], expected: Result<i64, DekuError>) {
+        use chrono::{DateTime, Utc};
+        use date_time::DateTimeValue;
+
+        let res_read = date_time::dos::read::<DateTime<Utc>, _>(input.view_bits(), Endian::Little)
+            .map(|(_, value)| value);
+
+        match (expected, res_read) {
+            (Ok(expected), Ok(value)) => {
+                assert_eq!(expected, value.to_unix().0);
+                assert_eq!(
+                    input.to_vec(),
+                    date_time::dos::write(&value, Endian::Little)
+                        .unwrap()
+                        .into_vec()
+                );
+            }
+            (expected, res_read) => {
+                assert_eq!(expected, res_read.map(|value| value.to_unix().0))
+            }
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_date_time_ntp() {
+        use time::OffsetDateTime;
+
+        let input = [0xE3, 0x08, 0x8E, 0x80, 0x40, 0x00, 0x00, 0x00];
+        let (rest, value): (_, OffsetDateTime) =
+            date_time::ntp::read(input.view_bits(), Endian::Big).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(1_600_000_000, value.unix_timestamp());
+        assert_eq!(250_000_000, value.nanosecond());
+        assert_eq!(
+            input.to_vec(),
+            date_time::ntp::write(&value, Endian::Big)
+                .unwrap()
+                .into_vec()
+        );
+
+        // nanoseconds round trip through the finer fraction
+        let value = OffsetDateTime::from_unix_timestamp_nanos(1_600_000_000_123_456_789).unwrap();
+        let data = date_time::ntp::write(&value, Endian::Big).unwrap();
+        let (_, res_read): (_, OffsetDateTime) = date_time::ntp::read(&data, Endian::Big).unwrap();
+        assert_eq!(value, res_read);
+
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let value = OffsetDateTime::from_unix_timestamp(-2_208_988_801).unwrap();
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "ntp: -2208988801 seconds from the unix epoch is out of the era 0"
+                ))),
+                date_time::ntp::write(&value, Endian::Big)
+            );
+        }
+    }
+
+    #[rstest(input, expected,
+        case::valid("01:23:45:67:89:ab", Ok(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB])),
+        case::upper("01:23:45:67:89:AB", Ok(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB])),
+        #[cfg(not(feature = "static-errors"))]
+        case::short("01:23:45:67:89", Err(DekuError::InvalidParam(error_msg!("mac_address: invalid address \"01:23:45:67:89\"")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::digits("01:23:45:67:89:a", Err(DekuError::InvalidParam(error_msg!("mac_address: invalid address \"01:23:45:67:89:a\"")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::not_hex("01:23:45:67:89:zz", Err(DekuError::InvalidParam(error_msg!("mac_address: invalid address \"01:23:45:67:89:zz\"")))),
+    )]
+    fn test_mac_address_write(input: &str, expected: Result<Vec<u8>, DekuError>) {
+        assert_eq!(
+            expected,
+            mac_address::write(input, ()).map(|bits| bits.into_vec())
+        );
+    }
+
+    #[test]
+    fn test_mac_address_read() {
+        let input = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB];
+        let (rest, value) = mac_address::read(input.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!("01:23:45:67:89:ab", value);
+    }
+}
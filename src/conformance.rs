@@ -0,0 +1,202 @@
+//! Conformance report of captured input, for interoperability testing
+//!
+//! [`check`] reads a value from a captured byte stream and reports how the input conforms to the
+//! type, instead of stopping at the first problem:
+//! - The value read, or the error which stopped the read
+//! - The number of bits read
+//! - The failed `assert` and `assert_eq`, which are read in
+//!   [lenient](../options/index.html) mode. Reserved bits are checked with `assert_eq = "0"`
+//! - The trailing bytes left after the value
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::conformance::check;
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     #[deku(assert = "*version <= 3")]
+//!     version: u8,
+//!     #[deku(bits = "4", assert_eq = "0")]
+//!     reserved: u8,
+//!     #[deku(bits = "4")]
+//!     flags: u8,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x04, 0x1F, 0xAA];
+//! let report = check::<DekuTest>(&data);
+//!
+//! assert!(!report.is_conformant());
+//! assert_eq!(Some(DekuTest { version: 4, reserved: 1, flags: 0xF }), report.value);
+//! assert_eq!(16, report.bits_read);
+//! # #[cfg(not(feature = "static-errors"))]
+//! assert_eq!(
+//!     vec![
+//!         "field `version` failed assertion: *version <= 3".to_string(),
+//!         "field `reserved` failed assertion: expected 0 got 1".to_string(),
+//!     ],
+//!     report.assertion_failures
+//! );
+//! assert_eq!(vec![0xAA], report.trailing_bytes);
+//! ```
+
+use crate::options::{from_bytes_with_options, ParseOptions};
+use crate::{DekuContainerRead, DekuError};
+use core::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+/// Conformance of captured input to a type, see the [module documentation](index.html)
+#[derive(Debug, PartialEq)]
+pub struct ConformanceReport<T> {
+    /// The value read, `None` if the read failed
+    pub value: Option<T>,
+    /// The error which stopped the read
+    pub error: Option<DekuError>,
+    /// Number of bits read
+    pub bits_read: usize,
+    /// Failed `assert` and `assert_eq`
+    pub assertion_failures: Vec<String>,
+    /// Bytes left after the value
+    pub trailing_bytes: Vec<u8>,
+}
+
+impl<T> ConformanceReport<T> {
+    /// The value was read without error, failed assertion or trailing bytes
+    pub fn is_conformant(&self) -> bool {
+        self.error.is_none() && self.assertion_failures.is_empty() && self.trailing_bytes.is_empty()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for ConformanceReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = if self.is_conformant() {
+            "conformant"
+        } else {
+            "not conformant"
+        };
+        writeln!(f, "{}", status)?;
+
+        if let Some(value) = &self.value {
+            writeln!(f, "value: {:?}", value)?;
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "error: {}", error)?;
+        }
+        writeln!(f, "bits read: {}", self.bits_read)?;
+        for failure in &self.assertion_failures {
+            writeln!(f, "assertion failure: {}", failure)?;
+        }
+        if !self.trailing_bytes.is_empty() {
+            writeln!(f, "trailing bytes: {:02x?}", self.trailing_bytes)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Read a `T` from `input` and report its conformance, see the
+/// [module documentation](index.html)
+pub fn check<'a, T>(input: &'a [u8]) -> ConformanceReport<T>
+where
+    T: DekuContainerRead<'a>,
+{
+    let options = ParseOptions::new().lenient(true).collect_warnings(true);
+
+    match from_bytes_with_options::<T>((input, 0), options) {
+        Ok(output) => {
+            let (rest, bit_offset) = output.rest;
+            let bits_read = (input.len() - rest.len()) * 8 + bit_offset;
+
+            // a partially read byte isn't trailing
+            let trailing_bytes = if bit_offset == 0 { rest } else { &rest[1..] };
+
+            ConformanceReport {
+                value: Some(output.value),
+                error: None,
+                bits_read,
+                assertion_failures: output.warnings,
+                trailing_bytes: trailing_bytes.to_vec(),
+            }
+        }
+        Err(error) => ConformanceReport {
+            value: None,
+            error: Some(error),
+            bits_read: 0,
+            assertion_failures: Vec::new(),
+            trailing_bytes: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use crate::error::NeedSize;
+    use crate::DekuRead;
+    use bitvec::prelude::*;
+
+    /// A big endian `u16`
+    #[derive(Debug, PartialEq)]
+    struct Word(u16);
+
+    impl<'a> DekuRead<'a> for Word {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, value) = u16::read(input, Endian::Big)?;
+            Ok((rest, Word(value)))
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Word {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (rest, value) = Self::read(&input.0.view_bits()[input.1..], ())?;
+            let pad = 8 * rest.len().div_ceil(8) - rest.len();
+            Ok((
+                (&input.0[input.0.len() - (rest.len() + pad) / 8..], pad),
+                value,
+            ))
+        }
+    }
+
+    #[test]
+    fn test_check_conformant() {
+        let report = check::<Word>(&[0xAA, 0xBB]);
+
+        assert!(report.is_conformant());
+        assert_eq!(Some(Word(0xAABB)), report.value);
+        assert_eq!(16, report.bits_read);
+        assert_eq!(
+            "conformant\nvalue: Word(43707)\nbits read: 16\n",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_trailing() {
+        let report = check::<Word>(&[0xAA, 0xBB, 0xCC]);
+
+        assert!(!report.is_conformant());
+        assert_eq!(vec![0xCC], report.trailing_bytes);
+        assert_eq!(
+            "not conformant\nvalue: Word(43707)\nbits read: 16\ntrailing bytes: [cc]\n",
+            report.to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_error() {
+        let report = check::<Word>(&[0xAA]);
+
+        assert!(!report.is_conformant());
+        assert_eq!(None, report.value);
+        assert_eq!(
+            Some(DekuError::Incomplete(NeedSize::new(16, 8))),
+            report.error
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! This module provides types for context representation could be used in context-sensitive parsing.
 //! See [ctx attribute](../attributes/index.html#ctx) for more information.
 
+use bitvec::prelude::*;
 use core::ops::{Deref, DerefMut};
 use core::str::FromStr;
 
@@ -70,13 +71,289 @@ impl FromStr for Endian {
     }
 }
 
+/// A bit order, the order in which the bits of a value are read
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Order {
+    /// Most significant bit first
+    Msb0,
+    /// Least significant bit first
+    Lsb0,
+}
+
+/// Error returned when parsing a `Order` using [`from_str`]
+///
+/// [`from_str`]: enum.Order.html#method.from_str
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOrderError {}
+
+impl Order {
+    /// Reorder the bits of each byte from the bit order to `Msb0`, or back, padding the last
+    /// byte with zeros
+    ///
+    /// With `Lsb0` the first bit of each byte is its least significant bit, so the bits of each
+    /// byte are reversed.
+    /// # Examples
+    /// ```rust
+    /// use deku::ctx::Order;
+    /// use deku::prelude::*;
+    ///
+    /// let bits = Order::Lsb0.reorder([0b1100_0000, 0b01].view_bits::<Msb0>());
+    /// assert_eq!(vec![0b0000_0011, 0b1000_0000], bits.into_vec());
+    /// ```
+    pub fn reorder(self, bits: &BitSlice<Msb0, u8>) -> BitVec<Msb0, u8> {
+        let mut res = BitVec::with_capacity(bits.len().div_ceil(8) * 8);
+        for chunk in bits.chunks(8) {
+            let start = res.len();
+            res.extend(chunk.iter().copied());
+            res.resize(start + 8, false);
+            if self == Order::Lsb0 {
+                res[start..].reverse();
+            }
+        }
+
+        res
+    }
+}
+
+impl Default for Order {
+    /// Return `Msb0`, the order of the bits read and written by deku
+    fn default() -> Self {
+        Order::Msb0
+    }
+}
+
+impl FromStr for Order {
+    type Err = ParseOrderError;
+
+    /// Parse a `Order` from a string.
+    /// # Examples
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use deku::ctx::Order;
+    /// assert_eq!(FromStr::from_str("msb"), Ok(Order::Msb0));
+    /// assert_eq!(FromStr::from_str("lsb"), Ok(Order::Lsb0));
+    /// assert!(<Order as FromStr>::from_str("not an order").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "msb" => Ok(Order::Msb0),
+            "lsb" => Ok(Order::Lsb0),
+            _ => Err(ParseOrderError {}),
+        }
+    }
+}
+
+/// A text encoding of a `String`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Encoding {
+    /// UTF-8, code units of one byte
+    Utf8,
+    /// UTF-16 little endian, code units of two bytes
+    Utf16Le,
+    /// UTF-16 big endian, code units of two bytes
+    Utf16Be,
+    /// 7-bit ASCII, one byte per character
+    Ascii,
+    /// ISO 8859-1, one byte per character of `U+0000` to `U+00FF`
+    Latin1,
+}
+
+/// Error returned when parsing a `Encoding` using [`from_str`]
+///
+/// [`from_str`]: enum.Encoding.html#method.from_str
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEncodingError {}
+
+impl Encoding {
+    /// Number of bytes of a code unit, the unit of the `count` of a string
+    pub fn code_unit_bytes(self) -> usize {
+        match self {
+            Encoding::Utf16Le | Encoding::Utf16Be => 2,
+            Encoding::Utf8 | Encoding::Ascii | Encoding::Latin1 => 1,
+        }
+    }
+}
+
+impl Default for Encoding {
+    /// Return `Utf8`
+    fn default() -> Self {
+        Encoding::Utf8
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = ParseEncodingError;
+
+    /// Parse a `Encoding` from a string.
+    /// # Examples
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use deku::ctx::Encoding;
+    /// assert_eq!(FromStr::from_str("utf8"), Ok(Encoding::Utf8));
+    /// assert_eq!(FromStr::from_str("utf16le"), Ok(Encoding::Utf16Le));
+    /// assert_eq!(FromStr::from_str("utf16be"), Ok(Encoding::Utf16Be));
+    /// assert_eq!(FromStr::from_str("ascii"), Ok(Encoding::Ascii));
+    /// assert_eq!(FromStr::from_str("latin1"), Ok(Encoding::Latin1));
+    /// assert!(<Encoding as FromStr>::from_str("not an encoding").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "utf8" => Ok(Encoding::Utf8),
+            "utf16le" => Ok(Encoding::Utf16Le),
+            "utf16be" => Ok(Encoding::Utf16Be),
+            "ascii" => Ok(Encoding::Ascii),
+            "latin1" => Ok(Encoding::Latin1),
+            _ => Err(ParseEncodingError {}),
+        }
+    }
+}
+
+/// The code unit padding a fixed-width string up to its size, zero-extended in UTF-16
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Pad(pub u8);
+
+impl Deref for Pad {
+    type Target = u8;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The order of the two digits of a byte of packed BCD, see [bcd](../attributes/index.html#bcd)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NibbleOrder {
+    /// The first digit is in the high nibble, i.e. `12` is `0x12`
+    Big,
+    /// The first digit is in the low nibble, i.e. `12` is `0x21`, as in TBCD
+    Little,
+}
+
+/// Error returned when parsing a `NibbleOrder` using [`from_str`]
+///
+/// [`from_str`]: enum.NibbleOrder.html#method.from_str
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNibbleOrderError {}
+
+impl Default for NibbleOrder {
+    /// Return `Big`, the order of packed BCD
+    fn default() -> Self {
+        NibbleOrder::Big
+    }
+}
+
+impl FromStr for NibbleOrder {
+    type Err = ParseNibbleOrderError;
+
+    /// Parse a `NibbleOrder` from a string.
+    /// # Examples
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use deku::ctx::NibbleOrder;
+    /// assert_eq!(FromStr::from_str("big"), Ok(NibbleOrder::Big));
+    /// assert_eq!(FromStr::from_str("little"), Ok(NibbleOrder::Little));
+    /// assert!(<NibbleOrder as FromStr>::from_str("not an order").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "big" => Ok(NibbleOrder::Big),
+            "little" => Ok(NibbleOrder::Little),
+            _ => Err(ParseNibbleOrderError {}),
+        }
+    }
+}
+
+/// A fixed-point format `Qm.n` or `UQm.n`, see [fixed](../attributes/index.html#fixed)
+///
+/// The raw integer has `int_bits + frac_bits` bits, including the sign bit of a signed format,
+/// and the value is the integer divided by `2^frac_bits`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FixedPoint {
+    /// Whether the raw integer is signed, two's complement
+    pub signed: bool,
+    /// The number of integer bits, including the sign bit
+    pub int_bits: usize,
+    /// The number of fractional bits
+    pub frac_bits: usize,
+}
+
+/// Error returned when parsing a `FixedPoint` using [`from_str`]
+///
+/// [`from_str`]: struct.FixedPoint.html#method.from_str
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixedPointError {}
+
+impl FixedPoint {
+    /// The number of bits of the raw integer
+    pub fn bit_size(self) -> BitSize {
+        BitSize(self.int_bits + self.frac_bits)
+    }
+}
+
+impl core::fmt::Display for FixedPoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let prefix = if self.signed { "Q" } else { "UQ" };
+        write!(f, "{}{}.{}", prefix, self.int_bits, self.frac_bits)
+    }
+}
+
+impl FromStr for FixedPoint {
+    type Err = ParseFixedPointError;
+
+    /// Parse a `FixedPoint` from a string, of 1 to 64 bits with a sign bit if signed
+    /// # Examples
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use deku::ctx::FixedPoint;
+    /// assert_eq!(
+    ///     FromStr::from_str("Q8.8"),
+    ///     Ok(FixedPoint { signed: true, int_bits: 8, frac_bits: 8 })
+    /// );
+    /// assert_eq!(
+    ///     FromStr::from_str("UQ0.16"),
+    ///     Ok(FixedPoint { signed: false, int_bits: 0, frac_bits: 16 })
+    /// );
+    /// assert!(<FixedPoint as FromStr>::from_str("Q0.16").is_err());
+    /// assert!(<FixedPoint as FromStr>::from_str("Q32.40").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (signed, format) = match (s.strip_prefix("UQ"), s.strip_prefix('Q')) {
+            (Some(format), _) => (false, format),
+            (None, Some(format)) => (true, format),
+            (None, None) => return Err(ParseFixedPointError {}),
+        };
+        let mut parts = format.splitn(2, '.');
+        let mut next_part = || -> Result<usize, Self::Err> {
+            parts
+                .next()
+                .and_then(|part| part.parse().ok())
+                .ok_or(ParseFixedPointError {})
+        };
+        let int_bits = next_part()?;
+        let frac_bits = next_part()?;
+
+        let bit_size = int_bits
+            .checked_add(frac_bits)
+            .ok_or(ParseFixedPointError {})?;
+        if bit_size == 0 || bit_size > 64 || (signed && int_bits == 0) {
+            return Err(ParseFixedPointError {});
+        }
+
+        Ok(FixedPoint {
+            signed,
+            int_bits,
+            frac_bits,
+        })
+    }
+}
+
 /// The count of a container's elements
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Count(pub usize);
 
-impl Into<usize> for Count {
-    fn into(self) -> usize {
-        self.0
+impl From<Count> for usize {
+    fn from(v: Count) -> Self {
+        v.0
     }
 }
 
@@ -100,7 +377,7 @@ impl DerefMut for Count {
     }
 }
 
-/// The number bits in a field
+/// The number bits in a field, or to consume when reading a container's elements
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct BitSize(pub usize);
 
@@ -139,9 +416,9 @@ impl BitSize {
     }
 }
 
-impl Into<usize> for BitSize {
-    fn into(self) -> usize {
-        self.0
+impl From<BitSize> for usize {
+    fn from(v: BitSize) -> Self {
+        v.0
     }
 }
 
@@ -164,3 +441,69 @@ impl DerefMut for BitSize {
         &mut self.0
     }
 }
+
+/// The number of bytes to consume when reading a container's elements
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ByteSize(pub usize);
+
+impl From<ByteSize> for usize {
+    fn from(v: ByteSize) -> Self {
+        v.0
+    }
+}
+
+impl From<usize> for ByteSize {
+    fn from(n: usize) -> Self {
+        Self(n)
+    }
+}
+
+impl Deref for ByteSize {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ByteSize {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// The number of bits to consume when reading a container's elements
+///
+/// Unlike [`BitSize`], which is the size of a single value, this bounds all the elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct BitsRead(pub usize);
+
+impl From<BitsRead> for usize {
+    fn from(v: BitsRead) -> Self {
+        v.0
+    }
+}
+
+impl From<usize> for BitsRead {
+    fn from(n: usize) -> Self {
+        Self(n)
+    }
+}
+
+impl Deref for BitsRead {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for BitsRead {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Read a container's elements until the end of the input
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ReadAll;
@@ -0,0 +1,575 @@
+//! Packed date and time types of file systems and archives
+//!
+//! - [`DosDate`](struct.DosDate.html), [`DosTime`](struct.DosTime.html) and
+//!   [`DosDateTime`](struct.DosDateTime.html): the 16-bit date and time of FAT, ZIP and cabinet
+//!   files
+//! - [`CpmTimestamp`](struct.CpmTimestamp.html): the date stamp of CP/M 3 directory labels
+//! - [`AmigaDateStamp`](struct.AmigaDateStamp.html): the `DateStamp` of AmigaDOS
+//!
+//! The integers holding the dates are read and written with the context of the field, e.g.
+//! `endian = "little"` for FAT and ZIP, or `endian = "big"` for AmigaDOS. The fields are read as
+//! they are, without checking the date is valid, and writing a field out of the range of its bits
+//! is an error.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::datetime::{DosDate, DosDateTime, DosTime};
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(endian = "little")]
+//! struct ZipEntry {
+//!     version: u16,
+//!     flags: u16,
+//!     compression: u16,
+//!     modified: DosDateTime,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x14, 0x00, 0x00, 0x00, 0x08, 0x00, 0x3B, 0x7A, 0x21, 0x51];
+//! let value = ZipEntry::try_from(data.as_ref()).unwrap();
+//! assert_eq!(
+//!     DosDateTime {
+//!         date: DosDate { year: 2020, month: 9, day: 1 },
+//!         time: DosTime { hour: 15, minute: 17, second: 54 },
+//!     },
+//!     value.modified
+//! );
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(data, value);
+//! ```
+
+use crate::{DekuError, DekuRead, DekuSize, DekuWrite};
+use bitvec::prelude::*;
+use core::convert::TryFrom;
+
+/// Check `value` fits in `bits` bits once `offset` is subtracted
+fn pack(what: &str, value: u32, offset: u32, bits: u32) -> Result<u32, DekuError> {
+    value
+        .checked_sub(offset)
+        .filter(|packed| packed >> bits == 0)
+        .ok_or_else(|| {
+            DekuError::InvalidParam(error_msg!(
+                "{}: {} doesn't fit, expected {} to {}",
+                what,
+                value,
+                offset,
+                offset + (1 << bits) - 1
+            ))
+        })
+}
+
+/// Convert a number of days since 1978-01-01 to the date `(year, month, day)`
+pub(crate) fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    // days since 0000-03-01, in eras of 400 years
+    let days = days + 2_922 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u8;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Convert the date `(year, month, day)` to a number of days since 1978-01-01
+pub(crate) fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468 - 2_922
+}
+
+/// MS-DOS date: 7 bits of year since 1980, 4 bits of month, 5 bits of day, from the most
+/// significant bit of a `u16`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DosDate {
+    /// Year, 1980 to 2107
+    pub year: u16,
+    /// Month, 1 to 12
+    pub month: u8,
+    /// Day of the month, 1 to 31
+    pub day: u8,
+}
+
+impl DosDate {
+    fn from_raw(raw: u16) -> Self {
+        DosDate {
+            year: (raw >> 9) + 1980,
+            month: (raw >> 5 & 0x0F) as u8,
+            day: (raw & 0x1F) as u8,
+        }
+    }
+
+    fn to_raw(self) -> Result<u16, DekuError> {
+        let year = pack("dos date year", self.year.into(), 1980, 7)?;
+        let month = pack("dos date month", self.month.into(), 0, 4)?;
+        let day = pack("dos date day", self.day.into(), 0, 5)?;
+        Ok((year << 9 | month << 5 | day) as u16)
+    }
+}
+
+/// MS-DOS time: 5 bits of hour, 6 bits of minute, 5 bits of second divided by 2, from the most
+/// significant bit of a `u16`
+///
+/// An odd second is written as the even second before it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DosTime {
+    /// Hour, 0 to 23
+    pub hour: u8,
+    /// Minute, 0 to 59
+    pub minute: u8,
+    /// Second, 0 to 58 in steps of 2
+    pub second: u8,
+}
+
+impl DosTime {
+    fn from_raw(raw: u16) -> Self {
+        DosTime {
+            hour: (raw >> 11) as u8,
+            minute: (raw >> 5 & 0x3F) as u8,
+            second: (raw & 0x1F) as u8 * 2,
+        }
+    }
+
+    fn to_raw(self) -> Result<u16, DekuError> {
+        let hour = pack("dos time hour", self.hour.into(), 0, 5)?;
+        let minute = pack("dos time minute", self.minute.into(), 0, 6)?;
+        let second = pack("dos time second", self.second.into(), 0, 6)?;
+        Ok((hour << 11 | minute << 5 | (second / 2)) as u16)
+    }
+}
+
+/// MS-DOS time then date, as in FAT directory entries and ZIP headers
+///
+/// Cabinet files hold the date before the time, as two fields.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DosDateTime {
+    /// Date
+    pub date: DosDate,
+    /// Time
+    pub time: DosTime,
+}
+
+/// CP/M 3 date stamp: a `u16` of days, day 1 being 1978-01-01, then the hour and the minute as
+/// one packed BCD byte each
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CpmTimestamp {
+    /// Days, day 1 is 1978-01-01
+    pub days: u16,
+    /// Hour, 0 to 23
+    pub hour: u8,
+    /// Minute, 0 to 59
+    pub minute: u8,
+}
+
+impl CpmTimestamp {
+    /// The date `(year, month, day)` of `days`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::datetime::CpmTimestamp;
+    /// let stamp = CpmTimestamp { days: 1, hour: 0, minute: 0 };
+    /// assert_eq!((1978, 1, 1), stamp.date());
+    /// ```
+    pub fn date(&self) -> (i64, u8, u8) {
+        civil_from_days(i64::from(self.days) - 1)
+    }
+
+    /// The timestamp of the date `(year, month, day)`, if it's representable
+    pub fn from_date(year: i64, month: u8, day: u8, hour: u8, minute: u8) -> Option<Self> {
+        let days = u16::try_from(days_from_civil(year, month, day) + 1).ok()?;
+        Some(CpmTimestamp { days, hour, minute })
+    }
+}
+
+/// AmigaDOS `DateStamp`: `u32` days since 1978-01-01, minutes since midnight, and ticks of 1/50
+/// second since the minute
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AmigaDateStamp {
+    /// Days since 1978-01-01
+    pub days: u32,
+    /// Minutes since midnight
+    pub minutes: u32,
+    /// Ticks of 1/50 second since the minute
+    pub ticks: u32,
+}
+
+impl AmigaDateStamp {
+    /// Ticks per second
+    pub const TICKS_PER_SECOND: u32 = 50;
+
+    /// The date `(year, month, day)` of `days`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::datetime::AmigaDateStamp;
+    /// let stamp = AmigaDateStamp { days: 8035, minutes: 0, ticks: 0 };
+    /// assert_eq!((2000, 1, 1), stamp.date());
+    /// ```
+    pub fn date(&self) -> (i64, u8, u8) {
+        civil_from_days(i64::from(self.days))
+    }
+
+    /// The date stamp of the date `(year, month, day)` at midnight, if it's representable
+    pub fn from_date(year: i64, month: u8, day: u8) -> Option<Self> {
+        let days = u32::try_from(days_from_civil(year, month, day)).ok()?;
+        Some(AmigaDateStamp {
+            days,
+            minutes: 0,
+            ticks: 0,
+        })
+    }
+}
+
+/// Convert a packed BCD byte to its value
+fn from_bcd(what: &str, byte: u8) -> Result<u8, DekuError> {
+    if byte >> 4 > 9 || byte & 0x0F > 9 {
+        return Err(DekuError::Parse(error_msg!(
+            "{}: invalid BCD byte 0x{:02X}",
+            what,
+            byte
+        )));
+    }
+    Ok((byte >> 4) * 10 + (byte & 0x0F))
+}
+
+/// Convert a value of 0 to 99 to a packed BCD byte
+fn to_bcd(what: &str, value: u8) -> Result<u8, DekuError> {
+    if value > 99 {
+        return Err(DekuError::InvalidParam(error_msg!(
+            "{}: {} doesn't fit, expected 0 to 99",
+            what,
+            value
+        )));
+    }
+    Ok(((value / 10) << 4) | (value % 10))
+}
+
+impl<'a, Ctx> DekuRead<'a, Ctx> for DosDate
+where
+    u16: DekuRead<'a, Ctx>,
+{
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, raw) = u16::read(input, ctx)?;
+        Ok((rest, DosDate::from_raw(raw)))
+    }
+}
+
+impl<Ctx> DekuWrite<Ctx> for DosDate
+where
+    u16: DekuWrite<Ctx>,
+{
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.to_raw()?.write(ctx)
+    }
+}
+
+impl<'a, Ctx> DekuRead<'a, Ctx> for DosTime
+where
+    u16: DekuRead<'a, Ctx>,
+{
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, raw) = u16::read(input, ctx)?;
+        Ok((rest, DosTime::from_raw(raw)))
+    }
+}
+
+impl<Ctx> DekuWrite<Ctx> for DosTime
+where
+    u16: DekuWrite<Ctx>,
+{
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.to_raw()?.write(ctx)
+    }
+}
+
+impl<'a, Ctx: Copy> DekuRead<'a, Ctx> for DosDateTime
+where
+    u16: DekuRead<'a, Ctx>,
+{
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, time) = DosTime::read(input, ctx)?;
+        let (rest, date) = DosDate::read(rest, ctx)?;
+        Ok((rest, DosDateTime { date, time }))
+    }
+}
+
+impl<Ctx: Copy> DekuWrite<Ctx> for DosDateTime
+where
+    u16: DekuWrite<Ctx>,
+{
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut acc = self.time.write(ctx)?;
+        acc.extend(self.date.write(ctx)?);
+        Ok(acc)
+    }
+}
+
+impl<'a, Ctx> DekuRead<'a, Ctx> for CpmTimestamp
+where
+    u16: DekuRead<'a, Ctx>,
+{
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, days) = u16::read(input, ctx)?;
+        let (rest, hour) = u8::read(rest, ())?;
+        let (rest, minute) = u8::read(rest, ())?;
+
+        let value = CpmTimestamp {
+            days,
+            hour: from_bcd("cp/m hour", hour)?,
+            minute: from_bcd("cp/m minute", minute)?,
+        };
+        Ok((rest, value))
+    }
+}
+
+impl<Ctx> DekuWrite<Ctx> for CpmTimestamp
+where
+    u16: DekuWrite<Ctx>,
+{
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut acc = self.days.write(ctx)?;
+        acc.extend(to_bcd("cp/m hour", self.hour)?.write(())?);
+        acc.extend(to_bcd("cp/m minute", self.minute)?.write(())?);
+        Ok(acc)
+    }
+}
+
+impl<'a, Ctx: Copy> DekuRead<'a, Ctx> for AmigaDateStamp
+where
+    u32: DekuRead<'a, Ctx>,
+{
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+        let (rest, days) = u32::read(input, ctx)?;
+        let (rest, minutes) = u32::read(rest, ctx)?;
+        let (rest, ticks) = u32::read(rest, ctx)?;
+        Ok((
+            rest,
+            AmigaDateStamp {
+                days,
+                minutes,
+                ticks,
+            },
+        ))
+    }
+}
+
+impl<Ctx: Copy> DekuWrite<Ctx> for AmigaDateStamp
+where
+    u32: DekuWrite<Ctx>,
+{
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut acc = self.days.write(ctx)?;
+        acc.extend(self.minutes.write(ctx)?);
+        acc.extend(self.ticks.write(ctx)?);
+        Ok(acc)
+    }
+}
+
+impl DekuSize for DosDate {
+    const BIT_SIZE: usize = 16;
+}
+
+impl DekuSize for DosTime {
+    const BIT_SIZE: usize = 16;
+}
+
+impl DekuSize for DosDateTime {
+    const BIT_SIZE: usize = 32;
+}
+
+impl DekuSize for CpmTimestamp {
+    const BIT_SIZE: usize = 32;
+}
+
+impl DekuSize for AmigaDateStamp {
+    const BIT_SIZE: usize = 96;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    #[rstest(input, expected,
+        case::zip(&[0x3B, 0x7A, 0x21, 0x51], DosDateTime {
+            date: DosDate { year: 2020, month: 9, day: 1 },
+            time: DosTime { hour: 15, minute: 17, second: 54 },
+        }),
+        case::epoch(&[0x00, 0x00, 0x21, 0x00], DosDateTime {
+            date: DosDate { year: 1980, month: 1, day: 1 },
+            time: DosTime { hour: 0, minute: 0, second: 0 },
+        }),
+        case::last(&[0x7D, 0xBF, 0x9F, 0xFF], DosDateTime {
+            date: DosDate { year: 2107, month: 12, day: 31 },
+            time: DosTime { hour: 23, minute: 59, second: 58 },
+        }),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::not_enough_data(&[0x3B, 0x7A, 0x21], DosDateTime::default()),
+    )]
+    fn test_dos_date_time(input: &[u8], expected: DosDateTime) {
+        let (rest, value) = DosDateTime::read(input.view_bits(), Endian::Little).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, value);
+        assert_eq!(
+            input.to_vec(),
+            value.write(Endian::Little).unwrap().into_vec()
+        );
+    }
+
+    #[test]
+    fn test_dos_write_error() {
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let date = DosDate {
+                year: 1979,
+                month: 1,
+                day: 1,
+            };
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "dos date year: 1979 doesn't fit, expected 1980 to 2107"
+                ))),
+                date.write(Endian::Little)
+            );
+
+            let time = DosTime {
+                hour: 32,
+                minute: 0,
+                second: 0,
+            };
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "dos time hour: 32 doesn't fit, expected 0 to 31"
+                ))),
+                time.write(Endian::Little)
+            );
+        }
+
+        // Odd seconds are truncated
+        let time = DosTime {
+            hour: 0,
+            minute: 0,
+            second: 59,
+        };
+        assert_eq!(
+            vec![0x1D, 0x00],
+            time.write(Endian::Little).unwrap().into_vec()
+        );
+    }
+
+    #[test]
+    fn test_cpm_timestamp() {
+        let input = [0x01, 0x00, 0x23, 0x59];
+        let (rest, value) = CpmTimestamp::read(input.view_bits(), Endian::Little).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            CpmTimestamp {
+                days: 1,
+                hour: 23,
+                minute: 59
+            },
+            value
+        );
+        assert_eq!((1978, 1, 1), value.date());
+        assert_eq!(
+            input.to_vec(),
+            value.write(Endian::Little).unwrap().into_vec()
+        );
+
+        assert_eq!(
+            Some(CpmTimestamp {
+                days: 8036,
+                hour: 12,
+                minute: 30
+            }),
+            CpmTimestamp::from_date(2000, 1, 1, 12, 30)
+        );
+        assert_eq!(None, CpmTimestamp::from_date(1977, 12, 30, 0, 0));
+
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "cp/m hour: invalid BCD byte 0x2A"
+            ))),
+            CpmTimestamp::read([0x01, 0x00, 0x2A, 0x00].view_bits(), Endian::Little)
+        );
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let value = CpmTimestamp {
+                days: 1,
+                hour: 100,
+                minute: 0,
+            };
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "cp/m hour: 100 doesn't fit, expected 0 to 99"
+                ))),
+                value.write(Endian::Little)
+            );
+        }
+    }
+
+    #[test]
+    fn test_amiga_date_stamp() {
+        let input = [
+            0x00, 0x00, 0x1F, 0x63, 0x00, 0x00, 0x02, 0xD0, 0x00, 0x00, 0x00, 0x19,
+        ];
+        let (rest, value) = AmigaDateStamp::read(input.view_bits(), Endian::Big).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            AmigaDateStamp {
+                days: 8035,
+                minutes: 720,
+                ticks: 25
+            },
+            value
+        );
+        assert_eq!((2000, 1, 1), value.date());
+        assert_eq!(input.to_vec(), value.write(Endian::Big).unwrap().into_vec());
+        assert_eq!(96, AmigaDateStamp::BIT_SIZE);
+    }
+
+    #[rstest(days, expected,
+        case(0, (1978, 1, 1)),
+        case(59, (1978, 3, 1)),
+        case(789, (1980, 2, 29)),
+        case(8094, (2000, 2, 29)),
+        case(-1, (1977, 12, 31)),
+    )]
+    fn test_civil_days(days: i64, expected: (i64, u8, u8)) {
+        assert_eq!(expected, civil_from_days(days));
+        assert_eq!(days, days_from_civil(expected.0, expected.1, expected.2));
+    }
+}
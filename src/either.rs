@@ -0,0 +1,172 @@
+//! An `Either` type to read one of two types, selected at runtime
+//!
+//! Lighter-weight alternative to an enum with an `id` for a simple structural alternation.
+//! The side is selected by a `bool` passed as ctx: `Left` if `true`, `Right` otherwise.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::either::Either;
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     is_short: u8,
+//!     #[deku(ctx = "*is_short == 1, deku::ctx::Endian::Big")]
+//!     value: Either<u8, u16>,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x00, 0xAB, 0xCD];
+//!
+//! let value = DekuTest::try_from(data.as_ref()).unwrap();
+//!
+//! assert_eq!(
+//!     DekuTest {
+//!         is_short: 0x00,
+//!         value: Either::Right(0xABCD),
+//!     },
+//!     value
+//! );
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(data, value);
+//! ```
+
+use crate::{DekuError, DekuRead, DekuWrite};
+use bitvec::prelude::*;
+
+/// One of two types, see the [module documentation](index.html)
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Either<L, R> {
+    /// Read when the ctx is `true`
+    Left(L),
+    /// Read when the ctx is `false`
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Is it `Left`
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    /// Is it `Right`
+    pub fn is_right(&self) -> bool {
+        !self.is_left()
+    }
+}
+
+impl<'a, L, R, Ctx> DekuRead<'a, (bool, Ctx)> for Either<L, R>
+where
+    L: DekuRead<'a, Ctx>,
+    R: DekuRead<'a, Ctx>,
+{
+    /// Read `L` if `is_left`, `R` otherwise
+    /// * `is_left` - select the side to read
+    /// * `inner_ctx` - The context required by `L` and `R`
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (is_left, inner_ctx): (bool, Ctx),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        if is_left {
+            let (rest, value) = L::read(input, inner_ctx)?;
+            Ok((rest, Either::Left(value)))
+        } else {
+            let (rest, value) = R::read(input, inner_ctx)?;
+            Ok((rest, Either::Right(value)))
+        }
+    }
+}
+
+impl<'a, L, R> DekuRead<'a, bool> for Either<L, R>
+where
+    L: DekuRead<'a>,
+    R: DekuRead<'a>,
+{
+    /// Read `L` if `is_left`, `R` otherwise, for types which don't require context.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        is_left: bool,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Self::read(input, (is_left, ()))
+    }
+}
+
+impl<L, R, Ctx> DekuWrite<(bool, Ctx)> for Either<L, R>
+where
+    L: DekuWrite<Ctx>,
+    R: DekuWrite<Ctx>,
+{
+    /// Write the value, which must be on the side selected by `is_left`
+    /// * `is_left` - the expected side
+    /// * `inner_ctx` - The context required by `L` and `R`
+    fn write(&self, (is_left, inner_ctx): (bool, Ctx)) -> Result<BitVec<Msb0, u8>, DekuError> {
+        if self.is_left() != is_left {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "Either side does not match, expected is_left = {}",
+                is_left
+            )));
+        }
+
+        match self {
+            Either::Left(value) => value.write(inner_ctx),
+            Either::Right(value) => value.write(inner_ctx),
+        }
+    }
+}
+
+impl<L, R> DekuWrite<bool> for Either<L, R>
+where
+    L: DekuWrite,
+    R: DekuWrite,
+{
+    /// Write the value, for types which don't require context.
+    fn write(&self, is_left: bool) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.write((is_left, ()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    #[rstest(input, is_left, expected, expected_rest,
+        case::left([0xAA, 0xBB].as_ref(), true, Either::Left(0xAA), bits![Msb0, u8; 1, 0, 1, 1, 1, 0, 1, 1]),
+        case::right([0xAA, 0xBB].as_ref(), false, Either::Right(0xAABB), bits![Msb0, u8;]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::not_enough_data([0xAA].as_ref(), false, Either::Right(0xAA), bits![Msb0, u8;]),
+    )]
+    fn test_either_read(
+        input: &[u8],
+        is_left: bool,
+        expected: Either<u8, u16>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = Either::<u8, u16>::read(bit_slice, (is_left, Endian::Big)).unwrap();
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[rstest(input, is_left, expected,
+        case::left(Either::Left(0xAA), true, vec![0xAA]),
+        case::right(Either::Right(0xAABB), false, vec![0xAA, 0xBB]),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "InvalidParam(\"Either side does not match, expected is_left = true\")")]
+        case::mismatch(Either::Right(0xAABB), true, vec![]),
+    )]
+    fn test_either_write(input: Either<u8, u16>, is_left: bool, expected: Vec<u8>) {
+        let res_write = input.write((is_left, Endian::Big)).unwrap().into_vec();
+        assert_eq!(expected, res_write);
+    }
+}
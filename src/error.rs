@@ -1,26 +1,111 @@
 #![cfg(feature = "alloc")]
-use alloc::{format, string::String, string::ToString};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
+/// Number of bits a read needed, and the number of bits left in the input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeedSize {
+    /// Bits needed
+    pub expected: usize,
+    /// Bits left in the input
+    pub got: usize,
+}
+
+impl NeedSize {
+    /// Create a `NeedSize` of `expected` bits, with `got` bits left
+    pub fn new(expected: usize, got: usize) -> Self {
+        NeedSize { expected, got }
+    }
+}
 
 /// Deku errors
+///
+/// `Incomplete` doesn't allocate, so reading input which ends too early doesn't use the heap on
+/// the failure path. The other errors hold a message, which is a `&'static str` when it has no
+/// values in it. With the `static-errors` feature every message is static, the values being left
+/// out of it, so no error allocates.
 #[derive(Debug, PartialEq)]
 pub enum DekuError {
+    /// The input ended before the bits needed by a read
+    Incomplete(NeedSize),
     /// Parsing error when reading
-    Parse(String),
+    Parse(Cow<'static, str>),
     /// Invalid parameter
-    InvalidParam(String),
+    InvalidParam(Cow<'static, str>),
     /// Unexpected error
-    Unexpected(String),
+    Unexpected(Cow<'static, str>),
+    /// The magic read doesn't match the `magic` attribute
+    Magic(Cow<'static, str>),
+    /// A field doesn't satisfy its `assert` attribute
+    Assertion(Cow<'static, str>),
+    /// Error with the position in the input at which it occurred, added by `from_bytes` with the
+    /// `pretty-errors` feature
+    Context {
+        /// Bit offset in the input of the field which failed
+        bit_offset: usize,
+        /// Byte offset in the input of the first byte of `window`
+        window_start: usize,
+        /// Bytes of the input around `bit_offset`
+        window: Vec<u8>,
+        /// The error which occurred
+        error: Box<DekuError>,
+    },
+}
+
+/// Number of bytes of input shown on each side of the position of an error
+#[cfg(feature = "pretty-errors")]
+const CONTEXT_BYTES: usize = 8;
+
+impl DekuError {
+    /// Attach the position `bit_offset` in `input` at which the error occurred, rendered as a hex
+    /// window with a caret by `Display`
+    ///
+    /// Returns the error unchanged without the `pretty-errors` feature, or if it already has a
+    /// position.
+    pub fn with_context(self, input: &[u8], bit_offset: usize) -> DekuError {
+        #[cfg(feature = "pretty-errors")]
+        {
+            if let DekuError::Context { .. } = self {
+                return self;
+            }
+
+            let byte = bit_offset / 8;
+            let window_start = byte.saturating_sub(CONTEXT_BYTES).min(input.len());
+            let window_end = (byte + CONTEXT_BYTES).min(input.len());
+
+            DekuError::Context {
+                bit_offset,
+                window_start,
+                window: input[window_start..window_end].to_vec(),
+                error: Box::new(self),
+            }
+        }
+
+        #[cfg(not(feature = "pretty-errors"))]
+        {
+            let _ = (input, bit_offset);
+            self
+        }
+    }
+
+    /// Returns the error without the position added by
+    /// [with_context](DekuError::with_context), to match on the error itself
+    pub fn into_inner(self) -> DekuError {
+        match self {
+            DekuError::Context { error, .. } => *error,
+            error => error,
+        }
+    }
 }
 
 impl From<core::num::TryFromIntError> for DekuError {
     fn from(e: core::num::TryFromIntError) -> DekuError {
-        DekuError::Parse(format!("error parsing int: {}", e.to_string()))
+        DekuError::Parse(error_msg!("error parsing int: {}", e))
     }
 }
 
 impl From<core::array::TryFromSliceError> for DekuError {
     fn from(e: core::array::TryFromSliceError) -> DekuError {
-        DekuError::Parse(format!("error parsing from slice: {}", e.to_string()))
+        DekuError::Parse(error_msg!("error parsing from slice: {}", e))
     }
 }
 
@@ -33,9 +118,36 @@ impl From<core::convert::Infallible> for DekuError {
 impl core::fmt::Display for DekuError {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match *self {
+            DekuError::Incomplete(NeedSize { expected, got }) => write!(
+                f,
+                "Incomplete error: not enough data: expected {} bits got {} bits",
+                expected, got
+            ),
             DekuError::Parse(ref err) => write!(f, "Parse error: {}", err),
             DekuError::InvalidParam(ref err) => write!(f, "Invalid param error: {}", err),
             DekuError::Unexpected(ref err) => write!(f, "Unexpected error: {}", err),
+            DekuError::Magic(ref err) => write!(f, "Magic error: {}", err),
+            DekuError::Assertion(ref err) => write!(f, "Assertion error: {}", err),
+            DekuError::Context {
+                bit_offset,
+                window_start,
+                ref window,
+                ref error,
+            } => {
+                write!(f, "{} at byte {}", error, bit_offset / 8)?;
+                if bit_offset % 8 != 0 {
+                    write!(f, " bit {}", bit_offset % 8)?;
+                }
+
+                write!(f, "\n{:08x}:", window_start)?;
+                for byte in window {
+                    write!(f, " {:02x}", byte)?;
+                }
+
+                // `XXXXXXXX:` then ` XX` per byte
+                let caret = 9 + 3 * (bit_offset / 8 - window_start) + 1;
+                write!(f, "\n{:>width$}", "^^", width = caret + 2)
+            }
         }
     }
 }
@@ -0,0 +1,299 @@
+//! Escape sequences stripped before reading and re-inserted after writing
+//!
+//! Some formats escape the bytes of a payload which would otherwise be mistaken for a marker,
+//! such as the emulation prevention bytes of H.264 and H.265, or the byte stuffing of HDLC and
+//! PPP. The functions of this module remove and insert the escapes, to use with the
+//! [process](../attributes/index.html#process) and `process_write` attributes: the field is read
+//! from the unescaped bytes, and written escaped.
+//!
+//! [`unescape_emulation_prevention`] and [`unescape_bytes`] return an [`Unescaped`], which tracks
+//! the offsets of the escapes removed, mapping an offset in the unescaped bytes back to the
+//! original input, e.g. to report the position of an error.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::escape::{insert_emulation_prevention, strip_emulation_prevention};
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Nal {
+//!     header: u8,
+//!     #[deku(
+//!         process = "strip_emulation_prevention",
+//!         process_write = "insert_emulation_prevention",
+//!         read_all
+//!     )]
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x67, 0x00, 0x00, 0x03, 0x01, 0xAA];
+//! let value = Nal::try_from(data.as_ref()).unwrap();
+//! assert_eq!(Nal { header: 0x67, payload: vec![0x00, 0x00, 0x01, 0xAA] }, value);
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(data, value);
+//! ```
+
+use crate::DekuError;
+use alloc::vec::Vec;
+
+/// Bytes with their escapes removed, and the offsets of the escapes in the original input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unescaped {
+    bytes: Vec<u8>,
+    escapes: Vec<usize>,
+}
+
+impl Unescaped {
+    /// The unescaped bytes
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Take the unescaped bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The offsets in the original input of the escape bytes removed, in increasing order
+    pub fn escapes(&self) -> &[usize] {
+        &self.escapes
+    }
+
+    /// Map an offset in the unescaped bytes to the offset of the same byte in the original input
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::escape::unescape_emulation_prevention;
+    /// let unescaped = unescape_emulation_prevention(&[0x00, 0x00, 0x03, 0x01, 0xAA]).unwrap();
+    /// assert_eq!(&[0x00, 0x00, 0x01, 0xAA], unescaped.bytes());
+    /// assert_eq!(1, unescaped.original_offset(1));
+    /// assert_eq!(4, unescaped.original_offset(3));
+    /// ```
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let mut original = offset;
+        for escape in &self.escapes {
+            if *escape > original {
+                break;
+            }
+            original += 1;
+        }
+
+        original
+    }
+}
+
+/// Remove the emulation prevention bytes of H.264 and H.265, tracking their offsets
+///
+/// The `0x03` of each `0x00 0x00 0x03` sequence is removed. A `0x00 0x00` followed by `0x00`,
+/// `0x01` or `0x02` can't appear in an escaped payload, and is an error.
+pub fn unescape_emulation_prevention(input: &[u8]) -> Result<Unescaped, DekuError> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut escapes = Vec::new();
+    let mut zeros = 0;
+
+    for (i, byte) in input.iter().enumerate() {
+        if zeros >= 2 {
+            match byte {
+                0x03 => {
+                    escapes.push(i);
+                    zeros = 0;
+                    continue;
+                }
+                0x00..=0x02 => {
+                    return Err(DekuError::Parse(error_msg!(
+                        "emulation_prevention: forbidden sequence 00 00 {:02X} at byte {}",
+                        byte,
+                        i - 2
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        zeros = if *byte == 0x00 { zeros + 1 } else { 0 };
+        bytes.push(*byte);
+    }
+
+    Ok(Unescaped { bytes, escapes })
+}
+
+/// Remove the emulation prevention bytes of H.264 and H.265, to use with `process`, see
+/// [`unescape_emulation_prevention`]
+pub fn strip_emulation_prevention(input: &[u8]) -> Result<Vec<u8>, DekuError> {
+    unescape_emulation_prevention(input).map(Unescaped::into_bytes)
+}
+
+/// Insert the emulation prevention bytes of H.264 and H.265, to use with `process_write`
+///
+/// A `0x03` is inserted after each `0x00 0x00` followed by `0x00` to `0x03`, and after a final
+/// `0x00 0x00`.
+///
+/// # Examples
+/// ```rust
+/// # use deku::escape::insert_emulation_prevention;
+/// assert_eq!(
+///     Ok(vec![0x00, 0x00, 0x03, 0x01, 0x00, 0x00, 0x03]),
+///     insert_emulation_prevention(&[0x00, 0x00, 0x01, 0x00, 0x00])
+/// );
+/// ```
+pub fn insert_emulation_prevention(input: &[u8]) -> Result<Vec<u8>, DekuError> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut zeros = 0;
+
+    for byte in input {
+        if zeros >= 2 && *byte <= 0x03 {
+            bytes.push(0x03);
+            zeros = 0;
+        }
+
+        zeros = if *byte == 0x00 { zeros + 1 } else { 0 };
+        bytes.push(*byte);
+    }
+    if zeros >= 2 {
+        bytes.push(0x03);
+    }
+
+    Ok(bytes)
+}
+
+/// Remove the escapes of byte stuffing, tracking their offsets: each `escape` byte is removed,
+/// and the byte following it is XOR-ed with `xor`
+///
+/// An `escape` byte at the end of the input is an error.
+///
+/// # Examples
+/// HDLC, escaping `0x7E` and `0x7D` with `0x7D` and XOR `0x20`:
+/// ```rust
+/// # use deku::escape::unescape_bytes;
+/// let unescaped = unescape_bytes(&[0x01, 0x7D, 0x5E, 0x02], 0x7D, 0x20).unwrap();
+/// assert_eq!(&[0x01, 0x7E, 0x02], unescaped.bytes());
+/// assert_eq!(&[1], unescaped.escapes());
+/// ```
+pub fn unescape_bytes(input: &[u8], escape: u8, xor: u8) -> Result<Unescaped, DekuError> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut escapes = Vec::new();
+    let mut iter = input.iter().enumerate();
+
+    while let Some((i, byte)) = iter.next() {
+        if *byte != escape {
+            bytes.push(*byte);
+            continue;
+        }
+
+        let (_, escaped) = iter.next().ok_or_else(|| {
+            DekuError::Parse(error_msg!(
+                "byte stuffing: escape byte {:02X} at the end of the input, at byte {}",
+                escape,
+                i
+            ))
+        })?;
+        escapes.push(i);
+        bytes.push(escaped ^ xor);
+    }
+
+    Ok(Unescaped { bytes, escapes })
+}
+
+/// Escape the bytes of `special` with byte stuffing: each is written as the `escape` byte, then
+/// the byte XOR-ed with `xor`
+///
+/// `special` must include the `escape` byte for the stuffing to be reversible.
+///
+/// # Examples
+/// ```rust
+/// # use deku::escape::escape_bytes;
+/// assert_eq!(
+///     vec![0x01, 0x7D, 0x5E, 0x7D, 0x5D],
+///     escape_bytes(&[0x01, 0x7E, 0x7D], 0x7D, &[0x7E, 0x7D], 0x20)
+/// );
+/// ```
+pub fn escape_bytes(input: &[u8], escape: u8, special: &[u8], xor: u8) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(input.len());
+
+    for byte in input {
+        if special.contains(byte) {
+            bytes.push(escape);
+            bytes.push(byte ^ xor);
+        } else {
+            bytes.push(*byte);
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rstest::rstest;
+
+    #[rstest(input, expected, escapes,
+        case::empty(&[], &[], &[]),
+        case::no_escape(&[0x00, 0x01, 0x00, 0x04], &[0x00, 0x01, 0x00, 0x04], &[]),
+        case::start_code(&[0x00, 0x00, 0x03, 0x01], &[0x00, 0x00, 0x01], &[2]),
+        case::zeros(&[0x00, 0x00, 0x03, 0x00, 0x00, 0x03, 0x00], &[0x00, 0x00, 0x00, 0x00, 0x00], &[2, 5]),
+        case::three(&[0xAA, 0x00, 0x00, 0x03, 0x03], &[0xAA, 0x00, 0x00, 0x03], &[3]),
+        case::trailing(&[0x00, 0x00, 0x03], &[0x00, 0x00], &[2]),
+    )]
+    fn test_emulation_prevention(input: &[u8], expected: &[u8], escapes: &[usize]) {
+        let unescaped = unescape_emulation_prevention(input).unwrap();
+        assert_eq!(expected, unescaped.bytes());
+        assert_eq!(escapes, unescaped.escapes());
+
+        assert_eq!(
+            input.to_vec(),
+            insert_emulation_prevention(expected).unwrap()
+        );
+    }
+
+    #[cfg(not(feature = "static-errors"))]
+    #[test]
+    fn test_emulation_prevention_forbidden() {
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "emulation_prevention: forbidden sequence 00 00 01 at byte 1"
+            ))),
+            strip_emulation_prevention(&[0xAA, 0x00, 0x00, 0x01])
+        );
+    }
+
+    #[rstest(offset, expected, case(0, 0), case(2, 3), case(3, 4), case(4, 6))]
+    fn test_original_offset(offset: usize, expected: usize) {
+        // escapes at offsets 2 and 5 of the original input
+        let unescaped =
+            unescape_emulation_prevention(&[0x00, 0x00, 0x03, 0x00, 0x00, 0x03, 0x00]).unwrap();
+        assert_eq!(expected, unescaped.original_offset(offset));
+    }
+
+    #[test]
+    fn test_byte_stuffing() {
+        let input = [0x7D, 0x5D, 0x01, 0x7D, 0x5E];
+        let unescaped = unescape_bytes(&input, 0x7D, 0x20).unwrap();
+        assert_eq!(&[0x7D, 0x01, 0x7E], unescaped.bytes());
+        assert_eq!(&[0, 3], unescaped.escapes());
+        assert_eq!(2, unescaped.original_offset(1));
+
+        assert_eq!(
+            input.to_vec(),
+            escape_bytes(unescaped.bytes(), 0x7D, &[0x7E, 0x7D], 0x20)
+        );
+
+        #[cfg(not(feature = "static-errors"))]
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "byte stuffing: escape byte 7D at the end of the input, at byte 1"
+            ))),
+            unescape_bytes(&[0x01, 0x7D], 0x7D, 0x20)
+        );
+    }
+
+    #[test]
+    fn test_emulation_prevention_roundtrip() {
+        let input = vec![0x00, 0x00, 0x00, 0x01, 0x02, 0x00, 0x00, 0x03, 0x00];
+        let escaped = insert_emulation_prevention(&input).unwrap();
+        assert_eq!(input, strip_emulation_prevention(&escaped).unwrap());
+    }
+}
@@ -0,0 +1,87 @@
+//! Read values from hex strings, for tests and debugging
+//!
+//! Enabled with the `hex` feature. [DekuContainerReadHex] is implemented for every container
+//! which doesn't borrow from its input, and is part of the prelude.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(endian = "big")]
+//! struct Record(u8, u16);
+//!
+//! assert_eq!(Record(0x01, 0xFFAB), Record::from_hex_str("01 ff ab").unwrap());
+//! ```
+
+use crate::error::DekuError;
+use crate::DekuContainerRead;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Parse a string of hex digit pairs, ignoring whitespace, e.g. `"01 ff AB"`
+pub(crate) fn decode(input: &str) -> Result<Vec<u8>, DekuError> {
+    let digits = input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| {
+            c.to_digit(16)
+                .ok_or_else(|| DekuError::Parse(error_msg!("invalid hex digit: {:?}", c)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if digits.len() % 2 != 0 {
+        return Err(DekuError::Parse(error_msg!(
+            "invalid hex string: {} digits is not a multiple of 2",
+            digits.len()
+        )));
+    }
+
+    Ok(digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4 | pair[1]) as u8)
+        .collect())
+}
+
+/// Read a container from a string of hex digit pairs
+pub trait DekuContainerReadHex: Sized {
+    /// Read a string of hex digit pairs and construct type
+    ///
+    /// Whitespace between the digits is ignored, and the whole input must be read.
+    fn from_hex_str(input: &str) -> Result<Self, DekuError>;
+}
+
+impl<T> DekuContainerReadHex for T
+where
+    T: for<'a> DekuContainerRead<'a>,
+{
+    fn from_hex_str(input: &str) -> Result<Self, DekuError> {
+        let bytes = decode(input)?;
+        let ((rest, bit_offset), value) = T::from_bytes((&bytes, 0))?;
+        if !rest.is_empty() || bit_offset != 0 {
+            return Err(DekuError::Parse(error_msg!("Too much data")));
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest(input, expected,
+        case::empty("", Ok(vec![])),
+        case::spaced("01 ff AB", Ok(vec![0x01, 0xFF, 0xAB])),
+        case::packed("01ffab", Ok(vec![0x01, 0xFF, 0xAB])),
+        case::multiline("01 02\n\t03", Ok(vec![0x01, 0x02, 0x03])),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_digit("01 fg", Err(DekuError::Parse(error_msg!("invalid hex digit: 'g'")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::odd("01 f", Err(DekuError::Parse(error_msg!("invalid hex string: 3 digits is not a multiple of 2")))),
+    )]
+    fn test_decode(input: &str, expected: Result<Vec<u8>, DekuError>) {
+        assert_eq!(expected, decode(input));
+    }
+}
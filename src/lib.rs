@@ -23,6 +23,58 @@ For more examples, see the
 
 For use in `no_std` environments, `alloc` is the single feature which is required on deku.
 
+## hex
+
+The `hex` feature enables the [hex](hex/index.html) module, reading a value from a string such as
+`"01 ff ab"`, to write tests and experiment without converting hex by hand.
+
+## pretty-errors
+
+The `pretty-errors` feature adds the position to the errors of `from_bytes`, as a
+[DekuError::Context](error/enum.DekuError.html#variant.Context) displayed with the bytes around
+the field which failed:
+
+```text
+Incomplete error: not enough data: expected 32 bits got 16 bits at byte 2
+00000000: 01 02 ab cd
+                ^^
+```
+
+## static-errors
+
+Errors without values in their message, such as a failed `assert`, hold a `&'static str`. The
+`static-errors` feature leaves the values out of the other messages too, so no error allocates,
+for decoders which can't use the heap when the input is invalid:
+
+```text
+Magic error: expected {:02x?} got {:02x?}
+```
+
+## audit
+
+The `audit` feature enables the [audit](audit/index.html) module, checking that writing a value
+is a pure function of the value, for reproducible output.
+
+## legacy-float
+
+The `legacy-float` feature enables the codecs of legacy float formats as an `f64`, still used by
+scientific archive formats: the IBM System/360 hexadecimal floats
+[ibm_float32](codecs/ibm_float32/index.html) and [ibm_float64](codecs/ibm_float64/index.html),
+and the VAX floats [vax_f_float](codecs/vax_f_float/index.html) and
+[vax_d_float](codecs/vax_d_float/index.html).
+
+## chrono and time
+
+The `chrono` and `time` features implement `DekuRead` and `DekuWrite` for
+`chrono::DateTime<Utc>` and `time::OffsetDateTime`, as an `i64` of seconds since the Unix epoch
+of the width of the field. The codecs of [date_time](codecs/date_time/index.html) read and write
+them as milliseconds or nanoseconds since the epoch, an MS-DOS date and time or an NTP timestamp.
+
+## limit
+
+The `limit` feature enables the [limit](limit/index.html) module, limiting the number of elements
+and bits read from untrusted input.
+
 # Example
 
 Let's read big-endian data into a struct, with fields containing different sizes,
@@ -202,6 +254,101 @@ assert_eq!(value.a, 0x01);
 assert_eq!(value.sub.b, 0x01 + 0x02)
 ```
 
+# Python bindings
+
+`#[derive(DekuPython)]` generates the methods of a [PyO3](https://pyo3.rs) class reading and
+writing the container, so Python scripts can use the same wire definitions:
+- `parse(data: bytes)`, a static method reading the value, all the bytes must be read
+- `build()`, returning the bytes written
+
+Errors are raised as a `ValueError` with the message of the `DekuError`. The crate must depend on
+`pyo3`, and the container must also be a `#[pyo3::pyclass]` without generics. If it has other
+`#[pymethods]`, the `multiple-pymethods` feature of `pyo3` is required.
+
+Example:
+
+```rust,ignore
+use deku::prelude::*;
+use pyo3::prelude::*;
+
+#[pyclass(get_all, set_all)]
+#[derive(DekuRead, DekuWrite, DekuPython)]
+#[deku(endian = "big")]
+struct Header {
+    version: u8,
+    length: u16,
+}
+
+#[pymodule]
+fn protocol(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Header>()
+}
+```
+
+```python
+from protocol import Header
+
+header = Header.parse(b"\x01\x00\x10")
+assert header.length == 16
+header.length = 32
+assert header.build() == b"\x01\x00\x20"
+```
+
+# C bindings
+
+`#[derive(DekuFfi)]` generates `extern "C"` functions, so existing C tooling can read and write
+the container during a migration. The functions are prefixed with the name of the container in
+snake case, `header` for `Header`:
+- `header_parse(data, len)`, reading a handle from bytes which must all be read
+- `header_free(value)`, freeing a handle
+- `header_serialize(value, out_len)`, writing the bytes, to free with `header_free_bytes(data, len)`
+- `header_get_<field>(value, out)`, storing the field in `out`, for each field of a primitive
+  integer, float or `bool` type
+
+The functions returning a pointer return null on error, the getters return false if a pointer is
+null. The container must not be generic, and
+the crate is built as a `cdylib` or `staticlib` to link from C.
+
+Example:
+
+```rust
+use deku::prelude::*;
+
+#[derive(DekuRead, DekuWrite, DekuFfi)]
+#[deku(endian = "big")]
+pub struct Header {
+    version: u8,
+    length: u16,
+}
+
+let data = [0x01, 0x00, 0x10];
+unsafe {
+    let header = header_parse(data.as_ptr(), data.len());
+    let mut length = 0;
+    assert!(header_get_length(header, &mut length));
+    assert_eq!(16, length);
+    assert!(!header_get_length(std::ptr::null(), &mut length));
+
+    let mut len = 0;
+    let bytes = header_serialize(header, &mut len);
+    assert_eq!(&data, std::slice::from_raw_parts(bytes, len));
+
+    header_free_bytes(bytes, len);
+    header_free(header);
+}
+```
+
+```c
+struct Header;
+
+struct Header *header_parse(const uint8_t *data, size_t len);
+void header_free(struct Header *value);
+uint8_t *header_serialize(const struct Header *value, size_t *out_len);
+void header_free_bytes(uint8_t *data, size_t len);
+bool header_get_version(const struct Header *value, uint8_t *out);
+bool header_get_length(const struct Header *value, uint16_t *out);
+```
+
 */
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -209,52 +356,170 @@ assert_eq!(value.sub.b, 0x01 + 0x02)
 extern crate alloc;
 
 #[cfg(feature = "alloc")]
-use alloc::{format, string::ToString, vec::Vec};
+use alloc::vec::Vec;
 
 #[cfg(feature = "std")]
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-use crate::ctx::{BitSize, Count, Endian};
+use crate::ctx::{BitSize, BitsRead, ByteSize, Count, Endian, Order, ReadAll};
 use bitvec::prelude::*;
 use core::convert::TryInto;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
 pub use deku_derive::*;
 
+/// Message of a `DekuError`, formatted like `format!`, used by the generated code
+///
+/// A message without values is borrowed. With the `static-errors` feature the values are left
+/// out and the format string is used as is, so the message never allocates.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! error_msg {
+    ($msg:expr) => {
+        $crate::export::Cow::Borrowed($msg)
+    };
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {
+        $crate::__error_msg_values!($fmt, $($arg),+)
+    };
+}
+
+#[cfg(all(feature = "alloc", feature = "static-errors"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error_msg_values {
+    ($fmt:expr, $($arg:expr),+) => {{
+        let _ = ($(&$arg,)+);
+        $crate::export::Cow::Borrowed($fmt)
+    }};
+}
+
+#[cfg(all(feature = "alloc", not(feature = "static-errors")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error_msg_values {
+    ($fmt:expr, $($arg:expr),+) => {
+        $crate::export::Cow::Owned($crate::export::format!($fmt, $($arg),+))
+    };
+}
+
+#[cfg(feature = "alloc")]
+pub mod archive;
 pub mod attributes;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "alloc")]
+pub mod bits;
+pub mod checksum;
+#[cfg(feature = "alloc")]
+pub mod codecs;
+#[cfg(feature = "std")]
+pub mod conformance;
 pub mod ctx;
+#[cfg(feature = "alloc")]
+pub mod datetime;
+pub mod either;
 pub mod error;
+#[cfg(feature = "alloc")]
+pub mod escape;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod export {
+    pub use alloc::{borrow::Cow, format};
+}
+#[cfg(feature = "hex")]
+pub mod hex;
+#[cfg(feature = "limit")]
+pub mod limit;
+pub mod net;
+#[cfg(feature = "alloc")]
+pub mod options;
+pub mod overlay;
 pub mod prelude;
+#[cfg(feature = "std")]
+pub mod roundtrip;
+pub mod scan;
 mod slice_impls;
+#[cfg(feature = "alloc")]
+mod string_impls;
+#[cfg(feature = "alloc")]
+pub mod take_while;
+mod tuple_impls;
+#[cfg(feature = "alloc")]
+pub mod wasm;
 
-use crate::error::DekuError;
+use crate::error::{DekuError, NeedSize};
 
 /// "Reader" trait: read bits and construct type
-pub trait DekuRead<Ctx = ()> {
+///
+/// The lifetime `'a` is the lifetime of the input, allowing the constructed type to borrow from
+/// it, e.g. a `&'a [u8]` field.
+pub trait DekuRead<'a, Ctx = ()> {
     /// Read bits and construct type
     /// * **input** - Input as bits
     /// * **ctx** - A context required by context-sensitive reading. A unit type `()` means no context
-    /// needed.
-    fn read(input: &BitSlice<Msb0, u8>, ctx: Ctx) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+    ///   needed.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized;
 }
 
 /// "Reader" trait: implemented on DekuRead struct and enum containers. A `container` is a type which
 /// doesn't need any context information.
-pub trait DekuContainerRead: DekuRead<()> {
+pub trait DekuContainerRead<'a>: DekuRead<'a, ()> {
     /// Read bytes and construct type
     /// * **input** - Input as a tuple of (bytes, bit_offset)
     ///
     /// Returns a tuple of the remaining data as (bytes, bit_offset) and a constructed value
-    fn from_bytes(input: (&[u8], usize)) -> Result<((&[u8], usize), Self), DekuError>
+    fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError>
     where
         Self: Sized;
+
+    /// Read bytes and construct type, advancing the input past the bytes read
+    /// * **input** - Input bytes, the type must end on a byte boundary
+    ///
+    /// This is also available as `TryFrom<&mut &[u8]>`
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::prelude::*;
+    /// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    /// struct Record(u8);
+    ///
+    /// let data: Vec<u8> = vec![0x01, 0x02, 0x03];
+    /// let mut input = data.as_ref();
+    ///
+    /// assert_eq!(Record(0x01), Record::from_bytes_advance(&mut input).unwrap());
+    /// assert_eq!(Record(0x02), Record::from_bytes_advance(&mut input).unwrap());
+    /// assert_eq!(&[0x03], input);
+    /// ```
+    fn from_bytes_advance(input: &mut &'a [u8]) -> Result<Self, DekuError>
+    where
+        Self: Sized,
+    {
+        let ((rest, bit_offset), value) = Self::from_bytes((input, 0))?;
+        if bit_offset != 0 {
+            return Err(DekuError::Parse(error_msg!(
+                "not byte aligned: read ends {} bits into a byte",
+                bit_offset
+            )));
+        }
+
+        *input = rest;
+        Ok(value)
+    }
 }
 
 /// "Writer" trait: write from type to bits
 pub trait DekuWrite<Ctx = ()> {
     /// Write type to bits
     /// * **ctx** - A context required by context-sensitive reading. A unit type `()` means no context
-    /// needed.
+    ///   needed.
     fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError>;
 }
 
@@ -266,6 +531,35 @@ pub trait DekuContainerWrite: DekuWrite<()> {
 
     /// Write struct/enum to BitVec
     fn to_bits(&self) -> Result<BitVec<Msb0, u8>, DekuError>;
+
+    /// Write struct/enum into the start of `output`, returning the number of bytes written
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use deku::prelude::*;
+    /// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    /// #[deku(endian = "big")]
+    /// struct Record(u16);
+    ///
+    /// let mut output = [0u8; 4];
+    /// let written = Record(0xABCD).to_slice(&mut output[1..]).unwrap();
+    ///
+    /// assert_eq!(2, written);
+    /// assert_eq!([0x00, 0xAB, 0xCD, 0x00], output);
+    /// ```
+    fn to_slice(&self, output: &mut [u8]) -> Result<usize, DekuError> {
+        let bytes = self.to_bytes()?;
+        if output.len() < bytes.len() {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "output too small: expected {} bytes got {} bytes",
+                bytes.len(),
+                output.len()
+            )));
+        }
+
+        output[..bytes.len()].copy_from_slice(&bytes);
+        Ok(bytes.len())
+    }
 }
 
 /// "Updater" trait: apply mutations to a type
@@ -274,38 +568,270 @@ pub trait DekuUpdate {
     fn update(&mut self) -> Result<(), DekuError>;
 }
 
+/// "Updater" trait with context: apply mutations to a type, with the `ctx` it's read and written
+/// with in scope of the `update` expressions
+///
+/// Implemented for structs and enums with a `ctx`. Only those with a `ctx_default` implement
+/// [`DekuUpdate`] too, updating with the default `ctx`.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// #[deku(ctx = "version: u8")]
+/// struct DekuTest {
+///     #[deku(update = "if version >= 2 { self.items.len() * 2 } else { self.items.len() }")]
+///     len: u8,
+///     #[deku(bytes_read = "if version >= 2 { *len } else { *len * 2 }")]
+///     items: Vec<u16>,
+/// }
+///
+/// let mut value = DekuTest { len: 0, items: vec![0xABCD] };
+///
+/// value.update_ctx(2).unwrap();
+/// assert_eq!(2, value.len);
+///
+/// value.update_ctx(1).unwrap();
+/// assert_eq!(1, value.len);
+/// ```
+pub trait DekuUpdateCtx<Ctx> {
+    /// Apply updates with the context `ctx`
+    fn update_ctx(&mut self, ctx: Ctx) -> Result<(), DekuError>;
+}
+
+/// "Size" trait: the size of a type on the wire, known at compile time
+///
+/// This is the layout metadata used by views to compute the offset of each field without
+/// reading the preceding fields. Implemented with `#[derive(DekuSize)]` on a struct, the fields
+/// must have a size known at compile time, or with `#[derive(DekuView)]`.
+pub trait DekuSize {
+    /// Size in bits
+    const BIT_SIZE: usize;
+}
+
+/// "Enum size" trait: the sizes of the variants of an enum on the wire, known at compile time
+///
+/// Implemented with `#[derive(DekuSize)]` on an enum, the fields of all variants must have a size
+/// known at compile time. The size of a variant includes its id, `MAX_BIT_SIZE` is the size of a
+/// buffer holding any variant.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// #[derive(DekuSize)]
+/// #[deku(type = "u8")]
+/// enum DekuTest {
+///     #[deku(id = "0x01")]
+///     VariantA(u8),
+///     #[deku(id = "0x02")]
+///     VariantB(u16, u32),
+/// }
+///
+/// assert_eq!(8, DekuTest::ID_BIT_SIZE);
+/// assert_eq!(16, DekuTest::MIN_BIT_SIZE);
+/// assert_eq!(56, DekuTest::MAX_BIT_SIZE);
+/// assert_eq!(48, DekuTest::MAX_VARIANT_BIT_SIZE);
+/// ```
+pub trait DekuEnumSize {
+    /// Size in bits of the `magic` and the id, read before the variant
+    const ID_BIT_SIZE: usize;
+
+    /// Size in bits of the smallest variant, including the id
+    const MIN_BIT_SIZE: usize;
+
+    /// Size in bits of the largest variant, including the id
+    const MAX_BIT_SIZE: usize;
+
+    /// Size in bits of the smallest variant, without the id
+    const MIN_VARIANT_BIT_SIZE: usize = Self::MIN_BIT_SIZE - Self::ID_BIT_SIZE;
+
+    /// Size in bits of the largest variant, without the id
+    const MAX_VARIANT_BIT_SIZE: usize = Self::MAX_BIT_SIZE - Self::ID_BIT_SIZE;
+}
+
+/// "View" trait: access the fields of a type on demand from the input, without reading it
+///
+/// Implemented with `#[derive(DekuView)]`, which generates a `<Struct>View` type with an accessor
+/// method per field. The offset of each field is computed from the [`DekuSize`] of the preceding
+/// fields, all fields must have a size known at compile time. The struct can't have a `magic`,
+/// `trailer` or `trailer_checksum`.
+///
+/// A subset of the fields can be read at once with `partial(mask)`, returning a
+/// `<Struct>Partial` with an `Option` per field, `None` for the fields which were skipped.
+/// The mask is built from the constants of `<Struct>Partial`, one per field, named after the
+/// field in uppercase. This is only generated for structs of up to 64 fields.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// #[derive(DekuView)]
+/// #[deku(endian = "big")]
+/// struct Record {
+///     #[deku(bits = 4)]
+///     kind: u8,
+///     #[deku(bits = 4)]
+///     flags: u8,
+///     len: u16,
+///     id: u32,
+/// }
+///
+/// let data: Vec<u8> = vec![0x12, 0x00, 0x03, 0xAA, 0xBB, 0xCC, 0xDD];
+///
+/// let view = Record::view(data.view_bits()).unwrap();
+/// assert_eq!(0x02, view.flags().unwrap());
+/// assert_eq!(0xAABBCCDD, view.id().unwrap());
+///
+/// let partial = view.partial(RecordPartial::KIND | RecordPartial::ID).unwrap();
+/// assert_eq!(Some(0x01), partial.kind);
+/// assert_eq!(None, partial.len);
+/// assert_eq!(Some(0xAABBCCDD), partial.id);
+/// ```
+pub trait DekuView<'a>: DekuSize {
+    /// Type providing an accessor per field
+    type View;
+
+    /// Create a view over the input, without reading any field
+    /// * **input** - Input as bits, must contain at least `BIT_SIZE` bits
+    fn view(input: &'a BitSlice<Msb0, u8>) -> Result<Self::View, DekuError>;
+}
+
+/// "Ctx default" trait: the ctx used to read and write a type when no ctx is provided
+///
+/// This is the trait equivalent of the `ctx_default` attribute, useful for types which don't
+/// derive `DekuRead`/`DekuWrite`. A type implementing `DekuCtxDefault` along with
+/// `DekuRead<'a, Self::Ctx>` and `DekuWrite<Self::Ctx>` can be read and written without a ctx,
+/// i.e. embedded in a struct without a `ctx` attribute on the field.
+///
+/// # Examples
+/// ```rust
+/// # use deku::prelude::*;
+/// # use deku::ctx::Endian;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// #[deku(ctx = "endian: Endian", endian = "endian")]
+/// struct Field(u16);
+///
+/// impl DekuCtxDefault for Field {
+///     type Ctx = Endian;
+///
+///     fn ctx_default() -> Self::Ctx {
+///         Endian::Big
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// struct Root {
+///     field: Field,
+/// }
+///
+/// let (_, value) = Root::from_bytes((&[0xAB, 0xCD], 0)).unwrap();
+/// assert_eq!(Root { field: Field(0xABCD) }, value);
+/// ```
+pub trait DekuCtxDefault {
+    /// Type of the ctx
+    type Ctx;
+
+    /// The ctx used when none is provided
+    fn ctx_default() -> Self::Ctx;
+}
+
+impl<'a, T> DekuRead<'a> for T
+where
+    T: DekuCtxDefault + DekuRead<'a, <T as DekuCtxDefault>::Ctx>,
+{
+    /// Read with the ctx provided by `DekuCtxDefault`
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        _: (),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        <T as DekuRead<'a, T::Ctx>>::read(input, T::ctx_default())
+    }
+}
+
+impl<T> DekuWrite for T
+where
+    T: DekuCtxDefault + DekuWrite<<T as DekuCtxDefault>::Ctx>,
+{
+    /// Write with the ctx provided by `DekuCtxDefault`
+    fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+        <T as DekuWrite<T::Ctx>>::write(self, T::ctx_default())
+    }
+}
+
+/// Two's complement sign extension of a signed integer read with fewer bits than its type
+trait SignExtend: Sized {
+    /// The type is a signed integer
+    const SIGNED: bool = false;
+
+    /// Extend the most significant of the low `bit_size` bits over the whole value
+    fn sign_extend(self, _bit_size: usize) -> Self {
+        self
+    }
+}
+
+macro_rules! ImplSignExtend {
+    (unsigned: $($typ:ty),*) => {
+        $(impl SignExtend for $typ {})*
+    };
+    (signed: $($typ:ty),*) => {
+        $(
+            impl SignExtend for $typ {
+                const SIGNED: bool = true;
+
+                fn sign_extend(self, bit_size: usize) -> Self {
+                    let shift = BitSize::of::<$typ>().0.saturating_sub(bit_size);
+                    if bit_size == 0 || shift == 0 {
+                        self
+                    } else {
+                        (self << shift) >> shift
+                    }
+                }
+            }
+        )*
+    };
+}
+
+ImplSignExtend!(unsigned: u8, u16, u32, u64, u128, usize, f32, f64);
+ImplSignExtend!(signed: i8, i16, i32, i64, i128, isize);
+
 macro_rules! ImplDekuTraits {
     ($typ:ty) => {
-        impl DekuRead<(Endian, BitSize)> for $typ {
+        impl<'a> DekuRead<'a, (Endian, BitSize)> for $typ {
             fn read(
-                input: &BitSlice<Msb0, u8>,
+                input: &'a BitSlice<Msb0, u8>,
                 (endian, bit_size): (Endian, BitSize),
-            ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError> {
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
                 let max_type_bits: usize = BitSize::of::<$typ>().into();
                 let bit_size: usize = bit_size.into();
 
                 let input_is_le = endian.is_le();
 
                 if bit_size > max_type_bits {
-                    return Err(DekuError::Parse(format!(
+                    return Err(DekuError::Parse(error_msg!(
                         "too much data: container of {} bits cannot hold {} bits",
-                        max_type_bits, bit_size
+                        max_type_bits,
+                        bit_size
                     )));
                 }
 
                 if input.len() < bit_size {
-                    return Err(DekuError::Parse(format!(
-                        "not enough data: expected {} bits got {} bits",
-                        bit_size,
-                        input.len()
-                    )));
+                    return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
                 }
 
+                #[cfg(feature = "limit")]
+                crate::limit::record_bits(bit_size)?;
+
                 let (bit_slice, rest) = input.split_at(bit_size);
 
-                let pad = 8 * ((bit_slice.len() + 7) / 8) - bit_slice.len();
+                let pad = 8 * bit_slice.len().div_ceil(8) - bit_slice.len();
 
-                let value = if pad == 0 && bit_slice.len() == max_type_bits {
+                // `as_slice` holds the partial bytes at each end of an unaligned slice
+                let value = if pad == 0
+                    && bit_slice.len() == max_type_bits
+                    && bit_slice.as_slice().len() * 8 == max_type_bits
+                {
                     // if everything is aligned, just read the value
 
                     let bytes: &[u8] = bit_slice.as_slice();
@@ -358,11 +884,14 @@ macro_rules! ImplDekuTraits {
                     let bytes: &[u8] = bits.as_slice();
 
                     // Read value
-                    if input_is_le {
+                    let value = if input_is_le {
                         <$typ>::from_le_bytes(bytes.try_into()?)
                     } else {
                         <$typ>::from_be_bytes(bytes.try_into()?)
-                    }
+                    };
+
+                    // signed integers are two's complement of `bit_size` bits
+                    value.sign_extend(bit_size)
                 };
 
                 Ok((rest, value))
@@ -370,38 +899,67 @@ macro_rules! ImplDekuTraits {
         }
 
         // Only have `endian`, set `bit_size` to `BitSize::of::<Type>()`
-        impl DekuRead<Endian> for $typ {
+        impl<'a> DekuRead<'a, Endian> for $typ {
             fn read(
-                input: &BitSlice<Msb0, u8>,
+                input: &'a BitSlice<Msb0, u8>,
                 endian: Endian,
-            ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError> {
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
                 let max_type_bits = BitSize::of::<$typ>();
 
                 <$typ>::read(input, (endian, max_type_bits))
             }
         }
 
-        // Only have `bit_size`, set `endian` to `Endian::default`.
-        impl DekuRead<BitSize> for $typ {
+        // With `Order::Lsb0` the bits of the value are read least significant first, in the byte
+        // order of `endian` for a value of the size of the type
+        impl<'a> DekuRead<'a, (Endian, BitSize, Order)> for $typ {
             fn read(
-                input: &BitSlice<Msb0, u8>,
-                bit_size: BitSize,
-            ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError> {
-                let endian = Endian::default();
+                input: &'a BitSlice<Msb0, u8>,
+                (endian, bit_size, order): (Endian, BitSize, Order),
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                if order == Order::Msb0 {
+                    return <$typ>::read(input, (endian, bit_size));
+                }
 
-                <$typ>::read(input, (endian, bit_size))
+                let max_type_bits: usize = BitSize::of::<$typ>().into();
+                let bit_size: usize = bit_size.into();
+
+                if input.len() < bit_size {
+                    return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+                }
+
+                let (bit_slice, rest) = input.split_at(bit_size);
+
+                let bits: BitVec<Msb0, u8> = bit_slice.iter().rev().copied().collect();
+                let (_, value) =
+                    <$typ>::read(bits.as_bitslice(), (Endian::Big, BitSize(bit_size)))?;
+
+                let value = if endian.is_be() && bit_size == max_type_bits {
+                    <$typ>::from_le_bytes(value.to_be_bytes())
+                } else {
+                    value
+                };
+
+                Ok((rest, value))
             }
         }
 
-        impl DekuRead for $typ {
+        // Only have `endian` and `order`, set `bit_size` to `BitSize::of::<Type>()`
+        impl<'a> DekuRead<'a, (Endian, Order)> for $typ {
             fn read(
-                input: &BitSlice<Msb0, u8>,
-                _: (),
-            ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError> {
-                <$typ>::read(input, Endian::default())
+                input: &'a BitSlice<Msb0, u8>,
+                (endian, order): (Endian, Order),
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                let max_type_bits = BitSize::of::<$typ>();
+
+                <$typ>::read(input, (endian, max_type_bits, order))
             }
         }
 
+        impl DekuSize for $typ {
+            const BIT_SIZE: usize = core::mem::size_of::<$typ>() * 8;
+        }
+
         impl DekuWrite<(Endian, BitSize)> for $typ {
             fn write(
                 &self,
@@ -419,17 +977,38 @@ macro_rules! ImplDekuTraits {
                 let input_bits: BitVec<Msb0, u8> = input
                     .to_vec()
                     .try_into()
-                    .map_err(|_e| DekuError::Unexpected("Converting Vec to BitVec".to_string()))?;
+                    .map_err(|_e| DekuError::Unexpected(error_msg!("Converting Vec to BitVec")))?;
 
                 let res_bits: BitVec<Msb0, u8> = {
                     if bit_size > input_bits.len() {
-                        return Err(DekuError::InvalidParam(format!(
+                        return Err(DekuError::InvalidParam(error_msg!(
                             "bit size {} is larger then input {}",
                             bit_size,
                             input_bits.len()
                         )));
                     }
 
+                    // the bits above `bit_size` are dropped, in strict mode they must be zeros,
+                    // or copies of the sign bit for signed integers
+                    if bit_size < input_bits.len() && crate::options::current_write().strict {
+                        let fits = if <$typ as SignExtend>::SIGNED {
+                            (bit_size != 0 || *self == <$typ>::default())
+                                && self.sign_extend(bit_size) == *self
+                        } else {
+                            self.to_le_bytes().iter().enumerate().all(|(i, byte)| {
+                                let kept = bit_size.saturating_sub(8 * i);
+                                kept >= 8 || byte >> kept == 0
+                            })
+                        };
+                        if !fits {
+                            return Err(DekuError::InvalidParam(error_msg!(
+                                "value {} doesn't fit in {} bits",
+                                self,
+                                bit_size
+                            )));
+                        }
+                    }
+
                     if output_is_le {
                         // Example read 10 bits u32 [0xAB, 0b11_000000]
                         // => [10101011, 00000011, 00000000, 00000000]
@@ -466,6 +1045,41 @@ macro_rules! ImplDekuTraits {
             }
         }
 
+        // With `Order::Lsb0` the bits of the value are written least significant first, in the
+        // byte order of `endian` for a value of the size of the type
+        impl DekuWrite<(Endian, BitSize, Order)> for $typ {
+            fn write(
+                &self,
+                (endian, bit_size, order): (Endian, BitSize, Order),
+            ) -> Result<BitVec<Msb0, u8>, DekuError> {
+                if order == Order::Msb0 {
+                    return self.write((endian, bit_size));
+                }
+
+                let max_type_bits: usize = BitSize::of::<$typ>().into();
+
+                let value = if endian.is_be() && *bit_size == max_type_bits {
+                    <$typ>::from_le_bytes(self.to_be_bytes())
+                } else {
+                    *self
+                };
+
+                let bits = value.write((Endian::Big, bit_size))?;
+
+                Ok(bits.iter().rev().copied().collect())
+            }
+        }
+
+        // Only have `endian` and `order`, return all input
+        impl DekuWrite<(Endian, Order)> for $typ {
+            fn write(
+                &self,
+                (endian, order): (Endian, Order),
+            ) -> Result<BitVec<Msb0, u8>, DekuError> {
+                self.write((endian, BitSize::of::<$typ>(), order))
+            }
+        }
+
         // Only have `endian`, return all input
         impl DekuWrite<Endian> for $typ {
             fn write(&self, endian: Endian) -> Result<BitVec<Msb0, u8>, DekuError> {
@@ -475,29 +1089,16 @@ macro_rules! ImplDekuTraits {
                     self.to_be_bytes()
                 };
 
-                Ok(input
+                input
                     .to_vec()
                     .try_into()
-                    .map_err(|_e| DekuError::Unexpected("Converting Vec to BitVec".to_string()))?)
-            }
-        }
-
-        // Only have `bit_size`, set `endian` to `Endian::default`.
-        impl DekuWrite<BitSize> for $typ {
-            fn write(&self, bit_size: BitSize) -> Result<BitVec<Msb0, u8>, DekuError> {
-                <$typ>::write(self, (Endian::default(), bit_size))
-            }
-        }
-
-        impl DekuWrite for $typ {
-            fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
-                <$typ>::write(self, Endian::default())
+                    .map_err(|_e| DekuError::Unexpected(error_msg!("Converting Vec to BitVec")))
             }
         }
     };
 }
 
-impl<T: DekuRead<Ctx>, Ctx: Copy> DekuRead<(Count, Ctx)> for Vec<T> {
+impl<'a, T: DekuRead<'a, Ctx>, Ctx: Copy> DekuRead<'a, (Count, Ctx)> for Vec<T> {
     /// Read the specified number of `T`s from input.
     /// * `count` - the number of `T`s you want to read.
     /// * `inner_ctx` - The context required by `T`. It will be passed to every `T`s when constructing.
@@ -507,22 +1108,26 @@ impl<T: DekuRead<Ctx>, Ctx: Copy> DekuRead<(Count, Ctx)> for Vec<T> {
     /// # use deku::DekuRead;
     /// # use bitvec::view::BitView;
     /// let input = vec![1u8, 2, 3, 4];
-    /// let (rest, v) = Vec::<u32>::read(input.view_bits(), (1.into(), Endian::Little)).unwrap();
+    /// let (rest, v) = Vec::<u32>::read(input.view_bits(), (Count(1), Endian::Little)).unwrap();
     /// assert!(rest.is_empty());
     /// assert_eq!(v, vec![0x04030201])
     /// ```
     fn read(
-        input: &BitSlice<Msb0, u8>,
+        input: &'a BitSlice<Msb0, u8>,
         (count, inner_ctx): (Count, Ctx),
-    ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized,
     {
         let count: usize = count.into();
 
-        let mut res = Vec::with_capacity(count);
+        // the count comes from the input, don't preallocate more elements than there are bits
+        let mut res = Vec::with_capacity(count.min(input.len()));
         let mut rest = input;
         for _i in 0..count {
+            #[cfg(feature = "limit")]
+            crate::limit::record_element()?;
+
             let (new_rest, val) = <T>::read(rest, inner_ctx)?;
             res.push(val);
             rest = new_rest;
@@ -532,12 +1137,12 @@ impl<T: DekuRead<Ctx>, Ctx: Copy> DekuRead<(Count, Ctx)> for Vec<T> {
     }
 }
 
-impl<T: DekuRead> DekuRead<Count> for Vec<T> {
+impl<'a, T: DekuRead<'a>> DekuRead<'a, Count> for Vec<T> {
     /// Read the specified number of `T`s from input for types which don't require context.
     fn read(
-        input: &BitSlice<Msb0, u8>,
+        input: &'a BitSlice<Msb0, u8>,
         count: Count,
-    ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized,
     {
@@ -545,64 +1150,545 @@ impl<T: DekuRead> DekuRead<Count> for Vec<T> {
     }
 }
 
-impl<T: DekuWrite<Ctx>, Ctx: Copy> DekuWrite<Ctx> for Vec<T> {
-    /// Write all `T`s in a `Vec` to bits.
-    /// * **inner_ctx** - The context required by `T`.
+impl<'a, T: DekuRead<'a, Ctx>, Ctx: Copy> DekuRead<'a, (BitsRead, Ctx)> for Vec<T> {
+    /// Read `T`s from input until the specified number of bits are consumed.
+    /// * `bit_size` - the number of bits to consume.
+    /// * `inner_ctx` - The context required by `T`. It will be passed to every `T`s when constructing.
+    ///
+    /// An element which doesn't fit in the remaining bits is an error, as is an element which
+    /// consumes no bits.
     /// # Examples
     /// ```rust
-    /// # use deku::{ctx::Endian, DekuWrite, prelude::Lsb0};
-    /// # use bitvec::bitvec;
-    /// let data = vec![1u8];
-    /// let output = data.write(Endian::Big).unwrap();
-    /// assert_eq!(output, bitvec![0, 0, 0, 0, 0, 0, 0, 1])
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![0b0110_1001, 0b1110_1001];
+    /// let (rest, v) = Vec::<u8>::read(input.view_bits(), (BitsRead(12), BitSize(4))).unwrap();
+    /// assert_eq!(rest.len(), 4);
+    /// assert_eq!(v, vec![0b0110, 0b1001, 0b1110])
     /// ```
-    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
-        let mut acc = BitVec::new();
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (bit_size, inner_ctx): (BitsRead, Ctx),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let bit_size: usize = bit_size.into();
 
-        for v in self {
-            let r = v.write(inner_ctx)?;
-            acc.extend(r);
+        if input.len() < bit_size {
+            return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
         }
 
-        Ok(acc)
+        let (mut window, rest) = input.split_at(bit_size);
+
+        let mut res = Vec::new();
+        while !window.is_empty() {
+            #[cfg(feature = "limit")]
+            crate::limit::record_element()?;
+
+            let (new_window, val) = <T>::read(window, inner_ctx)?;
+            // an element which consumes no bits would be read forever
+            if new_window.len() == window.len() {
+                return Err(DekuError::Parse(error_msg!(
+                    "element consumed no bits: can't read elements until {} bits are consumed, use `count`",
+                    bit_size
+                )));
+            }
+            res.push(val);
+            window = new_window;
+        }
+
+        Ok((rest, res))
     }
 }
 
-impl<T: DekuRead<Ctx>, Ctx: Copy> DekuRead<Ctx> for Option<T> {
-    /// Read a T from input and store as Some(T)
+impl<'a, T: DekuRead<'a>> DekuRead<'a, BitsRead> for Vec<T> {
+    /// Read `T`s from input until the specified number of bits are consumed, for types which
+    /// don't require context.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        bit_size: BitsRead,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Vec::read(input, (bit_size, ()))
+    }
+}
+
+impl<'a, T: DekuRead<'a, Ctx>, Ctx: Copy> DekuRead<'a, (ByteSize, Ctx)> for Vec<T> {
+    /// Read `T`s from input until the specified number of bytes are consumed.
+    /// * `byte_size` - the number of bytes to consume.
     /// * `inner_ctx` - The context required by `T`. It will be passed to every `T`s when constructing.
+    ///
+    /// An element which doesn't fit in the remaining bytes is an error.
     /// # Examples
     /// ```rust
     /// # use deku::ctx::*;
     /// # use deku::DekuRead;
     /// # use bitvec::view::BitView;
-    /// let input = vec![1u8, 2, 3, 4];
-    /// let (rest, v) = Option::<u32>::read(input.view_bits(), Endian::Little).unwrap();
-    /// assert!(rest.is_empty());
-    /// assert_eq!(v, Some(0x04030201))
+    /// let input = vec![1u8, 2, 3, 4, 5];
+    /// let (rest, v) = Vec::<u16>::read(input.view_bits(), (ByteSize(4), Endian::Little)).unwrap();
+    /// assert_eq!(rest.len(), 8);
+    /// assert_eq!(v, vec![0x0201, 0x0403])
     /// ```
     fn read(
-        input: &BitSlice<Msb0, u8>,
-        inner_ctx: Ctx,
-    ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+        input: &'a BitSlice<Msb0, u8>,
+        (byte_size, inner_ctx): (ByteSize, Ctx),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized,
     {
-        let (rest, val) = <T>::read(input, inner_ctx)?;
-        Ok((rest, Some(val)))
+        let bit_size = byte_size.checked_mul(8).ok_or_else(|| {
+            DekuError::Parse(error_msg!(
+                "bytes_read: {} bytes is too large to be counted in bits",
+                *byte_size
+            ))
+        })?;
+
+        Vec::read(input, (BitsRead(bit_size), inner_ctx))
     }
 }
 
-impl<T: DekuWrite<Ctx>, Ctx: Copy> DekuWrite<Ctx> for Option<T> {
-    /// Write T if Some
-    /// * **inner_ctx** - The context required by `T`.
-    /// # Examples
-    /// ```rust
-    /// # use deku::{ctx::Endian, DekuWrite, prelude::Lsb0};
-    /// # use bitvec::bitvec;
-    /// let data = Some(1u8);
-    /// let output = data.write(Endian::Big).unwrap();
-    /// assert_eq!(output, bitvec![0, 0, 0, 0, 0, 0, 0, 1])
+impl<'a, T: DekuRead<'a>> DekuRead<'a, ByteSize> for Vec<T> {
+    /// Read `T`s from input until the specified number of bytes are consumed, for types which
+    /// don't require context.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        byte_size: ByteSize,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Vec::read(input, (byte_size, ()))
+    }
+}
+
+impl<'a, T: DekuRead<'a, Ctx>, Ctx: Copy> DekuRead<'a, (ReadAll, Ctx)> for Vec<T> {
+    /// Read `T`s from input until the end of the input.
+    /// * `inner_ctx` - The context required by `T`. It will be passed to every `T`s when constructing.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![1u8, 2, 3, 4];
+    /// let (rest, v) = Vec::<u16>::read(input.view_bits(), (ReadAll, Endian::Little)).unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(v, vec![0x0201, 0x0403])
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (_, inner_ctx): (ReadAll, Ctx),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Vec::read(input, (BitsRead(input.len()), inner_ctx))
+    }
+}
+
+impl<'a, T: DekuRead<'a>> DekuRead<'a, ReadAll> for Vec<T> {
+    /// Read `T`s from input until the end of the input, for types which don't require context.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        read_all: ReadAll,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Vec::read(input, (read_all, ()))
+    }
+}
+
+impl<T: DekuWrite<Ctx>, Ctx: Copy> DekuWrite<Ctx> for Vec<T> {
+    /// Write all `T`s in a `Vec` to bits.
+    /// * **inner_ctx** - The context required by `T`.
+    /// # Examples
+    /// ```rust
+    /// # use deku::{ctx::Endian, DekuWrite, prelude::Lsb0};
+    /// # use bitvec::bitvec;
+    /// let data = vec![1u8];
+    /// let output = data.write(Endian::Big).unwrap();
+    /// assert_eq!(output, bitvec![0, 0, 0, 0, 0, 0, 0, 1])
+    /// ```
+    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut acc = BitVec::new();
+
+        for v in self {
+            let r = v.write(inner_ctx)?;
+            acc.extend(r);
+        }
+
+        Ok(acc)
+    }
+}
+
+/// Borrow `count` bytes from the input
+fn read_borrowed_bytes(
+    input: &BitSlice<Msb0, u8>,
+    count: Count,
+) -> Result<(&BitSlice<Msb0, u8>, &[u8]), DekuError> {
+    let count: usize = count.into();
+    let bit_size = count.checked_mul(8).ok_or_else(|| {
+        DekuError::Parse(error_msg!(
+            "count: {} bytes is too large to be counted in bits",
+            count
+        ))
+    })?;
+
+    if input.len() < bit_size {
+        return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+    }
+
+    #[cfg(feature = "limit")]
+    crate::limit::record_bits(bit_size)?;
+
+    let (bit_slice, rest) = input.split_at(bit_size);
+
+    // the bytes can only be borrowed if the input is byte aligned
+    let bytes: &[u8] = bit_slice.as_slice();
+    if bytes.len() != count {
+        return Err(DekuError::Parse(error_msg!(
+            "cannot borrow {} bytes from input which is not byte aligned",
+            count
+        )));
+    }
+
+    Ok((rest, bytes))
+}
+
+impl<'a> DekuRead<'a, (Count, ())> for &'a [u8] {
+    /// Borrow the specified number of bytes from input, the input must be byte aligned.
+    /// * `count` - the number of bytes you want to borrow.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![1u8, 2, 3, 4];
+    /// let (rest, v) = <&[u8]>::read(input.view_bits(), (Count(3), ())).unwrap();
+    /// assert_eq!(rest.len(), 8);
+    /// assert_eq!(v, &[1, 2, 3])
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (count, _): (Count, ()),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_bytes(input, count)
+    }
+}
+
+impl<'a> DekuRead<'a, (Count, Endian)> for &'a [u8] {
+    /// Borrow the specified number of bytes from input, endianness has no effect on bytes.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (count, _): (Count, Endian),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_bytes(input, count)
+    }
+}
+
+impl<'a> DekuRead<'a, Count> for &'a [u8] {
+    /// Borrow the specified number of bytes from input.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        count: Count,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_bytes(input, count)
+    }
+}
+
+/// Borrow the specified number of bytes from input as UTF-8, the input must be byte aligned
+fn read_borrowed_str(
+    input: &BitSlice<Msb0, u8>,
+    count: Count,
+) -> Result<(&BitSlice<Msb0, u8>, &str), DekuError> {
+    let (rest, bytes) = read_borrowed_bytes(input, count)?;
+    let value = core::str::from_utf8(bytes).map_err(|e| {
+        DekuError::Parse(error_msg!(
+            "encoding: invalid utf-8 at byte {}",
+            e.valid_up_to()
+        ))
+    })?;
+
+    Ok((rest, value))
+}
+
+impl<'a> DekuRead<'a, (Count, ())> for &'a str {
+    /// Borrow the specified number of bytes from input as UTF-8, the input must be byte aligned.
+    /// * `count` - the number of bytes you want to borrow.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = b"deku!".to_vec();
+    /// let (rest, v) = <&str>::read(input.view_bits(), (Count(4), ())).unwrap();
+    /// assert_eq!(rest.len(), 8);
+    /// assert_eq!(v, "deku")
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (count, _): (Count, ()),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_str(input, count)
+    }
+}
+
+impl<'a> DekuRead<'a, (Count, Endian)> for &'a str {
+    /// Borrow the specified number of bytes from input as UTF-8, endianness has no effect on
+    /// strings.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (count, _): (Count, Endian),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_str(input, count)
+    }
+}
+
+impl<'a> DekuRead<'a, Count> for &'a str {
+    /// Borrow the specified number of bytes from input as UTF-8.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        count: Count,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_str(input, count)
+    }
+}
+
+impl DekuWrite for str {
+    /// Write the UTF-8 bytes of the string.
+    fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.as_bytes().write(())
+    }
+}
+
+impl DekuWrite<Endian> for str {
+    /// Write the UTF-8 bytes of the string, endianness has no effect on strings.
+    fn write(&self, _: Endian) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.write(())
+    }
+}
+
+/// Borrow the rest of the input, which must be a whole number of bytes
+fn read_borrowed_rest(
+    input: &BitSlice<Msb0, u8>,
+) -> Result<(&BitSlice<Msb0, u8>, &[u8]), DekuError> {
+    if !input.len().is_multiple_of(8) {
+        return Err(DekuError::Parse(error_msg!(
+            "cannot borrow the rest of the input, {} bits is not a whole number of bytes",
+            input.len()
+        )));
+    }
+
+    read_borrowed_bytes(input, Count(input.len() / 8))
+}
+
+impl<'a> DekuRead<'a, (ReadAll, ())> for &'a [u8] {
+    /// Borrow the rest of the input, the input must be byte aligned.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![1u8, 2, 3, 4];
+    /// let (rest, v) = <&[u8]>::read(input.view_bits(), (ReadAll, ())).unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(v, &[1, 2, 3, 4])
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        _: (ReadAll, ()),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_rest(input)
+    }
+}
+
+impl<'a> DekuRead<'a, (ReadAll, Endian)> for &'a [u8] {
+    /// Borrow the rest of the input, endianness has no effect on bytes.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        _: (ReadAll, Endian),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_rest(input)
+    }
+}
+
+impl<'a> DekuRead<'a, ReadAll> for &'a [u8] {
+    /// Borrow the rest of the input.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        _: ReadAll,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        read_borrowed_rest(input)
+    }
+}
+
+impl<'a> DekuRead<'a, BitSize> for BitVec<Msb0, u8> {
+    /// Copy the specified number of bits from input, without interpreting them.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::prelude::*;
+    /// let input = vec![0b1010_0110u8];
+    /// let (rest, v) = BitVec::<Msb0, u8>::read(input.view_bits(), BitSize(3)).unwrap();
+    /// assert_eq!(rest.len(), 5);
+    /// assert_eq!(v, bitvec![Msb0, u8; 1, 0, 1])
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        bit_size: BitSize,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let bit_size: usize = bit_size.into();
+        if input.len() < bit_size {
+            return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+        }
+
+        #[cfg(feature = "limit")]
+        crate::limit::record_bits(bit_size)?;
+
+        let (bit_slice, rest) = input.split_at(bit_size);
+        Ok((rest, bit_slice.to_bitvec()))
+    }
+}
+
+impl<'a> DekuRead<'a, (Endian, BitSize)> for BitVec<Msb0, u8> {
+    /// Copy the specified number of bits from input, endianness has no effect on raw bits.
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (_, bit_size): (Endian, BitSize),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Self::read(input, bit_size)
+    }
+}
+
+impl DekuWrite<BitSize> for BitVec<Msb0, u8> {
+    /// Write the bits as they are, the length must be the specified number of bits.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuWrite;
+    /// # use bitvec::prelude::*;
+    /// let data = bitvec![Msb0, u8; 1, 0, 1];
+    /// let output = data.write(BitSize(3)).unwrap();
+    /// assert_eq!(output, data);
+    /// assert!(data.write(BitSize(4)).is_err());
+    /// ```
+    fn write(&self, bit_size: BitSize) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let bit_size: usize = bit_size.into();
+        if self.len() != bit_size {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "bit size of {} bits doesn't match the {} bits of the field",
+                self.len(),
+                bit_size
+            )));
+        }
+
+        Ok(self.clone())
+    }
+}
+
+impl DekuWrite<(Endian, BitSize)> for BitVec<Msb0, u8> {
+    /// Write the bits as they are, endianness has no effect on raw bits.
+    fn write(&self, (_, bit_size): (Endian, BitSize)) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.write(bit_size)
+    }
+}
+
+impl<T: DekuWrite<Ctx>, Ctx: Copy> DekuWrite<Ctx> for [T] {
+    /// Write all `T`s in a slice to bits.
+    /// * **inner_ctx** - The context required by `T`.
+    /// # Examples
+    /// ```rust
+    /// # use deku::{ctx::Endian, DekuWrite, prelude::Lsb0};
+    /// # use bitvec::bitvec;
+    /// let data = [1u8, 2];
+    /// let output = data[..].write(Endian::Big).unwrap();
+    /// assert_eq!(output, bitvec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0])
+    /// ```
+    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        let mut acc = BitVec::new();
+
+        for v in self {
+            let r = v.write(inner_ctx)?;
+            acc.extend(r);
+        }
+
+        Ok(acc)
+    }
+}
+
+impl<'a, T: DekuRead<'a, Ctx>, Ctx> DekuRead<'a, Ctx> for Option<T> {
+    /// Read a T from input and store as Some(T)
+    /// * `inner_ctx` - The context required by `T`. It will be passed to every `T`s when constructing.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![1u8, 2, 3, 4];
+    /// let (rest, v) = Option::<u32>::read(input.view_bits(), Endian::Little).unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(v, Some(0x04030201))
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        inner_ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let (rest, val) = <T>::read(input, inner_ctx)?;
+        Ok((rest, Some(val)))
+    }
+}
+
+impl<T: DekuWrite<Ctx>, Ctx> DekuWrite<Ctx> for Option<T> {
+    /// Write T if Some
+    /// * **inner_ctx** - The context required by `T`.
+    /// # Examples
+    /// ```rust
+    /// # use deku::{ctx::Endian, DekuWrite, prelude::Lsb0};
+    /// # use bitvec::bitvec;
+    /// let data = Some(1u8);
+    /// let output = data.write(Endian::Big).unwrap();
+    /// assert_eq!(output, bitvec![0, 0, 0, 0, 0, 0, 0, 1])
     /// ```
     fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
         if let Some(v) = self {
@@ -613,6 +1699,241 @@ impl<T: DekuWrite<Ctx>, Ctx: Copy> DekuWrite<Ctx> for Option<T> {
     }
 }
 
+impl<'a, Ctx> DekuRead<'a, Ctx> for bool
+where
+    u8: DekuRead<'a, Ctx>,
+{
+    /// Read a `u8`, any non-zero value is `true`. See [strict_bool](attributes/index.html#strict_bool)
+    /// to only accept `0` and `1`.
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![0b1000_0000u8];
+    /// let (rest, v) = bool::read(input.view_bits(), BitSize(1)).unwrap();
+    /// assert_eq!(rest.len(), 7);
+    /// assert!(v)
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        inner_ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let (rest, val) = u8::read(input, inner_ctx)?;
+        Ok((rest, val != 0))
+    }
+}
+
+impl<Ctx> DekuWrite<Ctx> for bool
+where
+    u8: DekuWrite<Ctx>,
+{
+    /// Write `1` if `true`, `0` otherwise, as a `u8`
+    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        u8::from(*self).write(inner_ctx)
+    }
+}
+
+impl DekuSize for bool {
+    const BIT_SIZE: usize = 8;
+}
+
+impl<'a, Ctx> DekuRead<'a, Ctx> for char
+where
+    u32: DekuRead<'a, Ctx>,
+{
+    /// Read a `u32`, which must be a Unicode scalar value: at most `0x10FFFF`, and not a
+    /// surrogate
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// let input = vec![0x00, 0x01, 0xF9, 0x80];
+    /// let (rest, v) = char::read(input.view_bits(), Endian::Big).unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(v, '🦀');
+    ///
+    /// let input = vec![0x00, 0xD8, 0x00];
+    /// assert!(char::read(input.view_bits(), (Endian::Big, BitSize(24))).is_err());
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        inner_ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let (rest, val) = u32::read(input, inner_ctx)?;
+        let val = core::char::from_u32(val).ok_or_else(|| {
+            DekuError::Parse(error_msg!("invalid unicode scalar value 0x{:X}", val))
+        })?;
+        Ok((rest, val))
+    }
+}
+
+impl<Ctx> DekuWrite<Ctx> for char
+where
+    u32: DekuWrite<Ctx>,
+{
+    /// Write the code point as a `u32`
+    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        u32::from(*self).write(inner_ctx)
+    }
+}
+
+impl DekuSize for char {
+    const BIT_SIZE: usize = 32;
+}
+
+// Read and write a `NonZero*` as its integer, which must not be zero
+macro_rules! ImplDekuNonZero {
+    ($typ:ty, $inner:ty) => {
+        impl<'a, Ctx> DekuRead<'a, Ctx> for $typ
+        where
+            $inner: DekuRead<'a, Ctx>,
+        {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                inner_ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+            where
+                Self: Sized,
+            {
+                let (rest, val) = <$inner>::read(input, inner_ctx)?;
+                let val = <$typ>::new(val).ok_or_else(|| {
+                    DekuError::Parse(error_msg!("{}: value must be non-zero", stringify!($typ)))
+                })?;
+                Ok((rest, val))
+            }
+        }
+
+        impl<Ctx> DekuWrite<Ctx> for $typ
+        where
+            $inner: DekuWrite<Ctx>,
+        {
+            fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+                self.get().write(inner_ctx)
+            }
+        }
+
+        impl DekuSize for $typ {
+            const BIT_SIZE: usize = <$inner as DekuSize>::BIT_SIZE;
+        }
+    };
+}
+
+ImplDekuNonZero!(NonZeroU8, u8);
+ImplDekuNonZero!(NonZeroU16, u16);
+ImplDekuNonZero!(NonZeroU32, u32);
+ImplDekuNonZero!(NonZeroU64, u64);
+ImplDekuNonZero!(NonZeroU128, u128);
+ImplDekuNonZero!(NonZeroUsize, usize);
+ImplDekuNonZero!(NonZeroI8, i8);
+ImplDekuNonZero!(NonZeroI16, i16);
+ImplDekuNonZero!(NonZeroI32, i32);
+ImplDekuNonZero!(NonZeroI64, i64);
+ImplDekuNonZero!(NonZeroI128, i128);
+ImplDekuNonZero!(NonZeroIsize, isize);
+
+// Read and write without `endian`, using `Endian::default`
+macro_rules! ImplDekuDefaultEndian {
+    ($typ:ty) => {
+        // Only have `bit_size`, set `endian` to `Endian::default`.
+        impl<'a> DekuRead<'a, BitSize> for $typ {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                bit_size: BitSize,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                #[cfg(feature = "audit")]
+                if *bit_size > 8 {
+                    crate::audit::record_default_endian(stringify!($typ));
+                }
+
+                let endian = Endian::default();
+
+                <$typ>::read(input, (endian, bit_size))
+            }
+        }
+
+        // No ctx, read and write with `Endian::default`
+        impl DekuCtxDefault for $typ {
+            type Ctx = Endian;
+
+            fn ctx_default() -> Self::Ctx {
+                #[cfg(feature = "audit")]
+                if core::mem::size_of::<$typ>() > 1 {
+                    crate::audit::record_default_endian(stringify!($typ));
+                }
+
+                Endian::default()
+            }
+        }
+
+        // Only have `bit_size` and `order`, set `endian` to `Endian::default`.
+        impl<'a> DekuRead<'a, (BitSize, Order)> for $typ {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                (bit_size, order): (BitSize, Order),
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                #[cfg(feature = "audit")]
+                if *bit_size > 8 {
+                    crate::audit::record_default_endian(stringify!($typ));
+                }
+
+                <$typ>::read(input, (Endian::default(), bit_size, order))
+            }
+        }
+
+        // Only have `order`, set `endian` to `Endian::default`.
+        impl<'a> DekuRead<'a, Order> for $typ {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                order: Order,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+                <$typ>::read(input, (BitSize::of::<$typ>(), order))
+            }
+        }
+
+        // Only have `bit_size`, set `endian` to `Endian::default`.
+        impl DekuWrite<BitSize> for $typ {
+            fn write(&self, bit_size: BitSize) -> Result<BitVec<Msb0, u8>, DekuError> {
+                #[cfg(feature = "audit")]
+                if *bit_size > 8 {
+                    crate::audit::record_default_endian(stringify!($typ));
+                }
+
+                <$typ>::write(self, (Endian::default(), bit_size))
+            }
+        }
+
+        // Only have `bit_size` and `order`, set `endian` to `Endian::default`.
+        impl DekuWrite<(BitSize, Order)> for $typ {
+            fn write(
+                &self,
+                (bit_size, order): (BitSize, Order),
+            ) -> Result<BitVec<Msb0, u8>, DekuError> {
+                #[cfg(feature = "audit")]
+                if *bit_size > 8 {
+                    crate::audit::record_default_endian(stringify!($typ));
+                }
+
+                <$typ>::write(self, (Endian::default(), bit_size, order))
+            }
+        }
+
+        // Only have `order`, set `endian` to `Endian::default`.
+        impl DekuWrite<Order> for $typ {
+            fn write(&self, order: Order) -> Result<BitVec<Msb0, u8>, DekuError> {
+                <$typ>::write(self, (BitSize::of::<$typ>(), order))
+            }
+        }
+    };
+}
+
 ImplDekuTraits!(u8);
 ImplDekuTraits!(u16);
 ImplDekuTraits!(u32);
@@ -628,12 +1949,30 @@ ImplDekuTraits!(isize);
 ImplDekuTraits!(f32);
 ImplDekuTraits!(f64);
 
+ImplDekuDefaultEndian!(u8);
+ImplDekuDefaultEndian!(u16);
+ImplDekuDefaultEndian!(u32);
+ImplDekuDefaultEndian!(u64);
+ImplDekuDefaultEndian!(u128);
+ImplDekuDefaultEndian!(usize);
+ImplDekuDefaultEndian!(i8);
+ImplDekuDefaultEndian!(i16);
+ImplDekuDefaultEndian!(i32);
+ImplDekuDefaultEndian!(i64);
+ImplDekuDefaultEndian!(i128);
+ImplDekuDefaultEndian!(isize);
+ImplDekuDefaultEndian!(f32);
+ImplDekuDefaultEndian!(f64);
+
 #[cfg(feature = "std")]
-impl<Ctx> DekuRead<Ctx> for Ipv4Addr
+impl<'a, Ctx> DekuRead<'a, Ctx> for Ipv4Addr
 where
-    u32: DekuRead<Ctx>,
+    u32: DekuRead<'a, Ctx>,
 {
-    fn read(input: &BitSlice<Msb0, u8>, ctx: Ctx) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized,
     {
@@ -654,11 +1993,14 @@ where
 }
 
 #[cfg(feature = "std")]
-impl<Ctx> DekuRead<Ctx> for Ipv6Addr
+impl<'a, Ctx> DekuRead<'a, Ctx> for Ipv6Addr
 where
-    u128: DekuRead<Ctx>,
+    u128: DekuRead<'a, Ctx>,
 {
-    fn read(input: &BitSlice<Msb0, u8>, ctx: Ctx) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
     where
         Self: Sized,
     {
@@ -678,6 +2020,62 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl DekuSize for Ipv4Addr {
+    const BIT_SIZE: usize = 32;
+}
+
+#[cfg(feature = "std")]
+impl DekuSize for Ipv6Addr {
+    const BIT_SIZE: usize = 128;
+}
+
+#[cfg(feature = "std")]
+impl<'a> DekuRead<'a, (Endian, BitSize)> for IpAddr {
+    /// Read an `Ipv4Addr` for a size of 32 bits, an `Ipv6Addr` for 128 bits, such as `bytes` set to
+    /// the length of the address held by a previous field
+    /// # Examples
+    /// ```rust
+    /// # use deku::ctx::*;
+    /// # use deku::DekuRead;
+    /// # use bitvec::view::BitView;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// let input = vec![192, 168, 0, 1];
+    /// let (rest, v) = IpAddr::read(input.view_bits(), (Endian::Big, BitSize(32))).unwrap();
+    /// assert!(rest.is_empty());
+    /// assert_eq!(v, IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)));
+    /// ```
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (endian, bit_size): (Endian, BitSize),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        match bit_size.0 {
+            32 => Ipv4Addr::read(input, endian).map(|(rest, ip)| (rest, IpAddr::V4(ip))),
+            128 => Ipv6Addr::read(input, endian).map(|(rest, ip)| (rest, IpAddr::V6(ip))),
+            len => Err(DekuError::Parse(error_msg!(
+                "invalid ip address length: {} bits, expected 32 or 128",
+                len
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> DekuRead<'a, BitSize> for IpAddr {
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        bit_size: BitSize,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        IpAddr::read(input, (Endian::default(), bit_size))
+    }
+}
+
 #[cfg(feature = "std")]
 impl<Ctx> DekuWrite<Ctx> for IpAddr
 where
@@ -692,7 +2090,48 @@ where
     }
 }
 
+// Read and write a date and time as the seconds since the Unix epoch, see `codecs::date_time`
+// for the other representations
+#[cfg(any(feature = "chrono", feature = "time"))]
+macro_rules! ImplDekuDateTime {
+    ($typ:ty) => {
+        impl<'a, Ctx> DekuRead<'a, Ctx> for $typ
+        where
+            i64: DekuRead<'a, Ctx>,
+        {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+            where
+                Self: Sized,
+            {
+                codecs::date_time::unix_secs::read(input, ctx)
+            }
+        }
+
+        impl<Ctx> DekuWrite<Ctx> for $typ
+        where
+            i64: DekuWrite<Ctx>,
+        {
+            fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+                codecs::date_time::unix_secs::write(self, ctx)
+            }
+        }
+
+        impl DekuSize for $typ {
+            const BIT_SIZE: usize = 64;
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+ImplDekuDateTime!(chrono::DateTime<chrono::Utc>);
+#[cfg(feature = "time")]
+ImplDekuDateTime!(time::OffsetDateTime);
+
 #[cfg(test)]
+#[allow(clippy::unusual_byte_groupings)]
 mod tests {
     use super::*;
 
@@ -784,10 +2223,11 @@ mod tests {
         case::normal_bits_12_le([0b1001_0110, 0b1110_0000, 0xCC, 0xDD ].as_ref(), Endian::Little, Some(12), 0b1110_1001_0110, bits![Msb0, u8; 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1, 1, 0, 1]),
         case::normal_bits_12_be([0b1001_0110, 0b1110_0000, 0xCC, 0xDD ].as_ref(), Endian::Big, Some(12), 0b1001_0110_1110, bits![Msb0, u8; 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1, 1, 0, 1]),
         case::normal_bit_6([0b1001_0110].as_ref(), Endian::Little, Some(6), 0b1001_01, bits![Msb0, u8; 1, 0,]),
-        #[should_panic(expected = "Parse(\"not enough data: expected 32 bits got 0 bits\")")]
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 32, got: 0 })")]
         case::not_enough_data([].as_ref(), Endian::Little, Some(32), 0xFF, bits![Msb0, u8;]),
-        #[should_panic(expected = "Parse(\"not enough data: expected 32 bits got 16 bits\")")]
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 32, got: 16 })")]
         case::not_enough_data([0xAA, 0xBB].as_ref(), Endian::Little, Some(32), 0xFF, bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
         #[should_panic(expected = "Parse(\"too much data: container of 32 bits cannot hold 64 bits\")")]
         case::too_much_data([0xAA, 0xBB, 0xCC, 0xDD, 0xAA, 0xBB, 0xCC, 0xDD].as_ref(), Endian::Little, Some(64), 0xFF, bits![Msb0, u8;]),
     )]
@@ -814,6 +2254,7 @@ mod tests {
         case::normal_be(0xDDCC_BBAA, Endian::Big, None, vec![0xDD, 0xCC, 0xBB, 0xAA]),
         case::bit_size_le_smaller(0x03AB, Endian::Little, Some(10), vec![0xAB, 0b11_000000]),
         case::bit_size_be_smaller(0x03AB, Endian::Big, Some(10), vec![0b11, 0xAB]),
+        #[cfg(not(feature = "static-errors"))]
         #[should_panic(expected = "InvalidParam(\"bit size 100 is larger then input 32\")")]
         case::bit_size_le_bigger(0x03AB, Endian::Little, Some(100), vec![0xAB, 0b11_000000]),
     )]
@@ -825,6 +2266,31 @@ mod tests {
         assert_eq!(expected, res_write);
     }
 
+    // A field read from the middle of a byte, `0xA B C D` starting at bit 4
+    #[rstest(
+        endian,
+        bit_size,
+        order,
+        expected,
+        case::full_be(Endian::Big, 16, Order::Msb0, 0xABCD),
+        case::full_le(Endian::Little, 16, Order::Msb0, 0xCDAB),
+        case::bits_12_be(Endian::Big, 12, Order::Msb0, 0xABC),
+        case::bits_12_le(Endian::Little, 12, Order::Msb0, 0xCAB),
+        case::bits_12_lsb(Endian::Big, 12, Order::Lsb0, 0x3D5),
+        case::bits_4_be(Endian::Big, 4, Order::Msb0, 0xA)
+    )]
+    fn test_bit_read_write_unaligned(endian: Endian, bit_size: usize, order: Order, expected: u16) {
+        let input = [0xFA, 0xBC, 0xDF];
+        let bit_slice = &input.view_bits::<Msb0>()[4..];
+
+        let (rest, res_read) = u16::read(bit_slice, (endian, BitSize(bit_size), order)).unwrap();
+        assert_eq!(expected, res_read);
+        assert_eq!(&bit_slice[bit_size..], rest);
+
+        let res_write = res_read.write((endian, BitSize(bit_size), order)).unwrap();
+        assert_eq!(&bit_slice[..bit_size], res_write.as_bitslice());
+    }
+
     #[rstest(input, endian, bit_size, expected, expected_rest, expected_write,
         case::normal([0xDD, 0xCC, 0xBB, 0xAA].as_ref(), Endian::Little, Some(32), 0xAABB_CCDD, bits![Msb0, u8;], vec![0xDD, 0xCC, 0xBB, 0xAA]),
     )]
@@ -856,17 +2322,72 @@ mod tests {
         assert_eq!(expected_write, res_write);
     }
 
+    #[rstest(input, endian, bit_size, order, expected, expected_rest, expected_write,
+        case::msb([0xAB, 0xCD].as_ref(), Endian::Little, 16, Order::Msb0, 0xCDAB, bits![Msb0, u8;], vec![0xAB, 0xCD]),
+        case::lsb_le([0xAB, 0xCD].as_ref(), Endian::Little, 16, Order::Lsb0, 0xB3D5, bits![Msb0, u8;], vec![0xAB, 0xCD]),
+        case::lsb_be([0xAB, 0xCD].as_ref(), Endian::Big, 16, Order::Lsb0, 0xD5B3, bits![Msb0, u8;], vec![0xAB, 0xCD]),
+        case::lsb_bits_4([0b1000_1111].as_ref(), Endian::Big, 4, Order::Lsb0, 0b0001, bits![Msb0, u8; 1, 1, 1, 1], vec![0b1000_0000]),
+        case::lsb_bits_12([0xAB, 0xCD].as_ref(), Endian::Little, 12, Order::Lsb0, 0x3D5, bits![Msb0, u8; 1, 1, 0, 1], vec![0xAB, 0xC0]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::not_enough_data([0xAB].as_ref(), Endian::Little, 16, Order::Lsb0, 0, bits![Msb0, u8;], vec![]),
+    )]
+    fn test_bit_order_read_write(
+        input: &[u8],
+        endian: Endian,
+        bit_size: usize,
+        order: Order,
+        expected: u16,
+        expected_rest: &BitSlice<Msb0, u8>,
+        expected_write: Vec<u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = u16::read(bit_slice, (endian, BitSize(bit_size), order)).unwrap();
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+
+        let res_write = res_read
+            .write((endian, BitSize(bit_size), order))
+            .unwrap()
+            .into_vec();
+        assert_eq!(expected_write, res_write);
+    }
+
+    #[rstest(input, endian, bit_size, expected,
+        case::bytes_2_le([0xFE, 0xFF].as_ref(), Endian::Little, 16, -2),
+        case::bytes_2_be([0xFF, 0xFE].as_ref(), Endian::Big, 16, -2),
+        case::bytes_3_positive([0x7F, 0xFF, 0xFF].as_ref(), Endian::Big, 24, 0x7F_FFFF),
+        case::bytes_3_negative([0x80, 0x00, 0x00].as_ref(), Endian::Big, 24, -0x80_0000),
+        case::bits_12_le([0xFF, 0b1000_0000].as_ref(), Endian::Little, 12, -0x701),
+        case::bits_5([0b1011_0000].as_ref(), Endian::Big, 5, -10),
+        case::bits_1([0b1000_0000].as_ref(), Endian::Big, 1, -1),
+        case::bytes_8([0xFF; 8].as_ref(), Endian::Big, 64, -1),
+    )]
+    fn test_signed_read_write(input: &[u8], endian: Endian, bit_size: usize, expected: i64) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = i64::read(bit_slice, (endian, BitSize(bit_size))).unwrap();
+        assert_eq!(expected, res_read);
+        assert_eq!(bit_slice.len() - bit_size, rest.len());
+
+        let res_write = res_read.write((endian, BitSize(bit_size))).unwrap();
+        assert_eq!(&bit_slice[..bit_size], res_write.as_bitslice());
+    }
+
     #[rstest(input,endian,bit_size,count,expected,expected_rest,
         case::count_0([0xAA].as_ref(), Endian::Little, Some(8), 0, vec![], bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0]),
         case::count_1([0xAA, 0xBB].as_ref(), Endian::Little, Some(8), 1, vec![0xAA], bits![Msb0, u8; 1, 0, 1, 1, 1, 0, 1, 1]),
         case::count_2([0xAA, 0xBB, 0xCC].as_ref(), Endian::Little, Some(8), 2, vec![0xAA, 0xBB], bits![Msb0, u8; 1, 1, 0, 0, 1, 1, 0, 0]),
         case::bits_6([0b0110_1001, 0b1110_1001].as_ref(), Endian::Little, Some(6), 2, vec![0b00_011010, 0b00_011110], bits![Msb0, u8; 1, 0, 0, 1]),
+        #[cfg(not(feature = "static-errors"))]
         #[should_panic(expected = "Parse(\"too much data: container of 8 bits cannot hold 9 bits\")")]
         case::not_enough_data([].as_ref(), Endian::Little, Some(9), 1, vec![], bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
         #[should_panic(expected = "Parse(\"too much data: container of 8 bits cannot hold 9 bits\")")]
         case::not_enough_data([0xAA].as_ref(), Endian::Little, Some(9), 1, vec![], bits![Msb0, u8;]),
-        #[should_panic(expected = "Parse(\"not enough data: expected 8 bits got 0 bits\")")]
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
         case::not_enough_data([0xAA].as_ref(), Endian::Little, Some(8), 2, vec![], bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
         #[should_panic(expected = "Parse(\"too much data: container of 8 bits cannot hold 9 bits\")")]
         case::too_much_data([0xAA, 0xBB].as_ref(), Endian::Little, Some(9), 1, vec![], bits![Msb0, u8;]),
     )]
@@ -891,6 +2412,123 @@ mod tests {
         assert_eq!(expected_rest, rest);
     }
 
+    #[rstest(input, offset, count, expected, expected_rest,
+        case::count_0([0xAA].as_ref(), 0, 0, [].as_ref(), bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0]),
+        case::count_1([0xAA, 0xBB].as_ref(), 0, 1, [0xAA].as_ref(), bits![Msb0, u8; 1, 0, 1, 1, 1, 0, 1, 1]),
+        case::offset([0xAA, 0xBB, 0xCC].as_ref(), 8, 2, [0xBB, 0xCC].as_ref(), bits![Msb0, u8;]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::not_enough_data([0xAA].as_ref(), 0, 2, [].as_ref(), bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"cannot borrow 1 bytes from input which is not byte aligned\")")]
+        case::unaligned([0xAA, 0xBB].as_ref(), 4, 1, [].as_ref(), bits![Msb0, u8;]),
+        #[cfg(feature = "static-errors")]
+        #[should_panic(expected = "Parse(\"cannot borrow {} bytes from input which is not byte aligned\")")]
+        case::unaligned_static([0xAA, 0xBB].as_ref(), 4, 1, [].as_ref(), bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"count: 18446744073709551615 bytes is too large to be counted in bits\")")]
+        case::overflow([0xAA, 0xBB].as_ref(), 0, usize::MAX, [].as_ref(), bits![Msb0, u8;]),
+    )]
+    fn test_borrowed_slice_read(
+        input: &[u8],
+        offset: usize,
+        count: usize,
+        expected: &[u8],
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = &input.view_bits::<Msb0>()[offset..];
+
+        let (rest, res_read) = <&[u8]>::read(bit_slice, Count(count)).unwrap();
+
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+
+        let res_write = res_read.write(()).unwrap().into_vec();
+        assert_eq!(expected, res_write);
+    }
+
+    #[rstest(input, offset, expected,
+        case::empty([].as_ref(), 0, [].as_ref()),
+        case::all([0xAA, 0xBB].as_ref(), 0, [0xAA, 0xBB].as_ref()),
+        case::offset([0xAA, 0xBB, 0xCC].as_ref(), 8, [0xBB, 0xCC].as_ref()),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"cannot borrow the rest of the input, 12 bits is not a whole number of bytes\")")]
+        case::unaligned([0xAA, 0xBB].as_ref(), 4, [].as_ref()),
+    )]
+    fn test_borrowed_slice_read_all(input: &[u8], offset: usize, expected: &[u8]) {
+        let bit_slice = &input.view_bits::<Msb0>()[offset..];
+
+        let (rest, res_read) = <&[u8]>::read(bit_slice, ReadAll).unwrap();
+
+        assert_eq!(expected, res_read);
+        assert!(rest.is_empty());
+    }
+
+    #[rstest(input, byte_size, expected, expected_rest,
+        case::empty([0xAA].as_ref(), 0, vec![], bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0]),
+        case::exact([0xAA, 0xBB, 0xCC, 0xDD].as_ref(), 4, vec![0xAABB, 0xCCDD], bits![Msb0, u8;]),
+        case::rest([0xAA, 0xBB, 0xCC].as_ref(), 2, vec![0xAABB], bits![Msb0, u8; 1, 1, 0, 0, 1, 1, 0, 0]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 24, got: 16 })")]
+        case::not_enough_data([0xAA, 0xBB].as_ref(), 3, vec![], bits![Msb0, u8;]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::straddle([0xAA, 0xBB, 0xCC, 0xDD].as_ref(), 3, vec![], bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"bytes_read: 18446744073709551615 bytes is too large to be counted in bits\")")]
+        case::overflow([0xAA, 0xBB].as_ref(), usize::MAX, vec![], bits![Msb0, u8;]),
+    )]
+    fn test_vec_read_byte_size(
+        input: &[u8],
+        byte_size: usize,
+        expected: Vec<u16>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) =
+            Vec::<u16>::read(bit_slice, (ByteSize(byte_size), Endian::Big)).unwrap();
+
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[rstest(input, bit_size, expected, expected_rest,
+        case::empty([0xAA].as_ref(), 0, vec![], bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0]),
+        case::exact([0b0110_1001].as_ref(), 8, vec![0b0110, 0b1001], bits![Msb0, u8;]),
+        case::rest([0b0110_1001, 0b1110_1001].as_ref(), 12, vec![0b0110, 0b1001, 0b1110], bits![Msb0, u8; 1, 0, 0, 1]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 12, got: 8 })")]
+        case::not_enough_data([0xAA].as_ref(), 12, vec![], bits![Msb0, u8;]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 4, got: 2 })")]
+        case::straddle([0xAA].as_ref(), 6, vec![], bits![Msb0, u8;]),
+    )]
+    fn test_vec_read_bit_size(
+        input: &[u8],
+        bit_size: usize,
+        expected: Vec<u8>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) =
+            Vec::<u8>::read(bit_slice, (BitsRead(bit_size), BitSize(4))).unwrap();
+
+        assert_eq!(expected, res_read);
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[rstest(input, expected,
+        case::empty([].as_ref(), vec![]),
+        case::items([0xAA, 0xBB, 0xCC, 0xDD].as_ref(), vec![0xAABB, 0xCCDD]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::straddle([0xAA, 0xBB, 0xCC].as_ref(), vec![]),
+    )]
+    fn test_vec_read_all(input: &[u8], expected: Vec<u16>) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = Vec::<u16>::read(bit_slice, (ReadAll, Endian::Big)).unwrap();
+
+        assert_eq!(expected, res_read);
+        assert!(rest.is_empty());
+    }
+
     #[rstest(input, endian, expected,
         case::normal(vec![0xAABB, 0xCCDD], Endian::Little, vec![0xBB, 0xAA, 0xDD, 0xCC]),
     )]
@@ -971,6 +2609,96 @@ mod tests {
         assert_eq!(input.to_vec(), res_write);
     }
 
+    #[rstest(input, endian, bit_size, expected,
+        case::ascii([0x41, 0x00, 0x00, 0x00].as_ref(), Endian::Little, 32, Ok('A')),
+        case::max([0x10, 0xFF, 0xFF].as_ref(), Endian::Big, 24, Ok('\u{10FFFF}')),
+        case::bits([0xE9, 0x00].as_ref(), Endian::Little, 16, Ok('é')),
+        #[cfg(not(feature = "static-errors"))]
+        case::too_large([0x11, 0x00, 0x00].as_ref(), Endian::Big, 24, Err(DekuError::Parse(error_msg!("invalid unicode scalar value 0x110000")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::surrogate([0xDF, 0xFF].as_ref(), Endian::Big, 16, Err(DekuError::Parse(error_msg!("invalid unicode scalar value 0xDFFF")))),
+    )]
+    fn test_char(input: &[u8], endian: Endian, bit_size: usize, expected: Result<char, DekuError>) {
+        let ctx = (endian, BitSize(bit_size));
+        let res_read = char::read(input.view_bits(), ctx).map(|(_, value)| value);
+        assert_eq!(expected, res_read);
+
+        if let Ok(value) = res_read {
+            let res_write: Vec<u8> = value.write(ctx).unwrap().into_vec();
+            assert_eq!(input.to_vec(), res_write);
+        }
+    }
+
+    #[rstest(input, endian, expected,
+        case::nonzero([0x00, 0x2A].as_ref(), Endian::Big, Ok(NonZeroU16::new(42).unwrap())),
+        case::little([0x2A, 0x00].as_ref(), Endian::Little, Ok(NonZeroU16::new(42).unwrap())),
+        #[cfg(not(feature = "static-errors"))]
+        case::zero([0x00, 0x00].as_ref(), Endian::Big, Err(DekuError::Parse(error_msg!("NonZeroU16: value must be non-zero")))),
+    )]
+    fn test_nonzero(input: &[u8], endian: Endian, expected: Result<NonZeroU16, DekuError>) {
+        let res_read = NonZeroU16::read(input.view_bits(), endian).map(|(_, value)| value);
+        assert_eq!(expected, res_read);
+
+        if let Ok(value) = res_read {
+            let res_write: Vec<u8> = value.write(endian).unwrap().into_vec();
+            assert_eq!(input.to_vec(), res_write);
+        }
+    }
+
+    #[test]
+    fn test_nonzero_signed() {
+        let input = [0b1111_0000];
+        let (rest, value) = NonZeroI8::read(input.view_bits(), BitSize(4)).unwrap();
+        assert_eq!(4, rest.len());
+        assert_eq!(-1, value.get());
+        assert_eq!(32, NonZeroI32::BIT_SIZE);
+        assert_eq!(
+            core::mem::size_of::<u32>(),
+            core::mem::size_of::<Option<NonZeroU32>>()
+        );
+    }
+
+    #[rstest(input, bit_size, expected,
+        case::v4([127, 0, 0, 1].as_ref(), 32, Ok(IpAddr::V4(Ipv4Addr::LOCALHOST))),
+        case::v6([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1].as_ref(), 128, Ok(IpAddr::V6(Ipv6Addr::LOCALHOST))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_len([127, 0, 0, 1].as_ref(), 24, Err(DekuError::Parse(error_msg!("invalid ip address length: 24 bits, expected 32 or 128")))),
+        case::not_enough_data([127, 0, 0].as_ref(), 32, Err(DekuError::Incomplete(NeedSize::new(32, 24)))),
+    )]
+    fn test_ip_addr_read(input: &[u8], bit_size: usize, expected: Result<IpAddr, DekuError>) {
+        let ctx = (Endian::Big, BitSize(bit_size));
+        let res_read = IpAddr::read(input.view_bits(), ctx).map(|(_, value)| value);
+        assert_eq!(expected, res_read);
+
+        if let Ok(value) = res_read {
+            let res_write: Vec<u8> = value.write(ctx).unwrap().into_vec();
+            assert_eq!(input.to_vec(), res_write);
+        }
+
+        #[cfg(not(feature = "static-errors"))]
+        {
+            let ip_addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+            assert_eq!(
+                Err(DekuError::InvalidParam(error_msg!(
+                    "bit size 128 is larger then input 32"
+                ))),
+                ip_addr.write((Endian::Big, BitSize(128)))
+            );
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_offset_date_time() {
+        let input = [0x5F, 0x5E, 0x10, 0x00];
+        let ctx = (Endian::Big, BitSize(32));
+        let (rest, value) = time::OffsetDateTime::read(input.view_bits(), ctx).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(0x5F5E_1000, value.unix_timestamp());
+        assert_eq!(input.to_vec(), value.write(ctx).unwrap().into_vec());
+        assert_eq!(64, time::OffsetDateTime::BIT_SIZE);
+    }
+
     #[test]
     fn test_ip_addr_write() {
         let ip_addr = IpAddr::V4(Ipv4Addr::new(145, 254, 160, 237));
@@ -0,0 +1,279 @@
+//! Limit the work done by a read, as a defense against malicious input
+//!
+//! Enabled with the `limit` feature. The size of the input bounds most reads, but not all of them:
+//! a `count` read from the input can ask for a huge number of elements which consume no bits, and
+//! `seek_*` or `offset` read the same bits again. [`read_limited`] reads a container, failing
+//! with a `DekuError::Parse` once the read goes over the [`Limits`]:
+//! - The number of elements read by containers, i.e. `Vec` or `TakeWhile`
+//! - The number of bits read, counting the bits read again
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::limit::{read_limited, Limits};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Empty {}
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     #[deku(endian = "big")]
+//!     count: u32,
+//!     #[deku(count = "count")]
+//!     items: Vec<Empty>,
+//! }
+//!
+//! let data: Vec<u8> = vec![0xFF, 0xFF, 0xFF, 0xFF];
+//!
+//! let value = read_limited::<DekuTest>((data.as_ref(), 0), Limits::new().max_elements(1024));
+//! # let value = value.map_err(DekuError::into_inner);
+//! # #[cfg(not(feature = "static-errors"))]
+//! assert_eq!(
+//!     Err(DekuError::Parse("limit exceeded: more than 1024 elements read".into())),
+//!     value
+//! );
+//! ```
+
+use crate::{DekuContainerRead, DekuError};
+use std::cell::RefCell;
+
+std::thread_local! {
+    /// Limits of the current read and the work done so far, `Some` within `read_limited`
+    static BUDGET: RefCell<Option<Budget>> = const { RefCell::new(None) };
+}
+
+/// Limits of a read, unlimited by default
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Limits {
+    max_elements: Option<usize>,
+    max_bits: Option<usize>,
+}
+
+impl Limits {
+    /// Create limits, unlimited until set
+    pub fn new() -> Self {
+        Limits::default()
+    }
+
+    /// Limit the number of elements read by containers
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = Some(max_elements);
+        self
+    }
+
+    /// Limit the number of bits read, counting the bits read again
+    pub fn max_bits(mut self, max_bits: usize) -> Self {
+        self.max_bits = Some(max_bits);
+        self
+    }
+}
+
+/// Work done by the current read
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    limits: Limits,
+    elements: usize,
+    bits: usize,
+}
+
+/// Restores the budget of an enclosing read once dropped, also when the read panics
+struct BudgetGuard {
+    previous: Option<Budget>,
+}
+
+impl Drop for BudgetGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        // the thread local is gone if the thread is exiting, nothing to restore
+        let _ = BUDGET.try_with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Record an element read by a container, called before reading it
+pub(crate) fn record_element() -> Result<(), DekuError> {
+    BUDGET.with(|budget| match budget.borrow_mut().as_mut() {
+        Some(budget) => {
+            budget.elements += 1;
+            match budget.limits.max_elements {
+                Some(max_elements) if budget.elements > max_elements => Err(DekuError::Parse(
+                    error_msg!("limit exceeded: more than {} elements read", max_elements),
+                )),
+                _ => Ok(()),
+            }
+        }
+        None => Ok(()),
+    })
+}
+
+/// Record bits read from the input
+pub(crate) fn record_bits(bits: usize) -> Result<(), DekuError> {
+    BUDGET.with(|budget| match budget.borrow_mut().as_mut() {
+        Some(budget) => {
+            budget.bits += bits;
+            match budget.limits.max_bits {
+                Some(max_bits) if budget.bits > max_bits => Err(DekuError::Parse(error_msg!(
+                    "limit exceeded: more than {} bits read",
+                    max_bits
+                ))),
+                _ => Ok(()),
+            }
+        }
+        None => Ok(()),
+    })
+}
+
+/// Read a `T` from `input` like `from_bytes`, failing once the read goes over `limits`, see the
+/// [module documentation](index.html)
+pub fn read_limited<'a, T>(
+    input: (&'a [u8], usize),
+    limits: Limits,
+) -> Result<((&'a [u8], usize), T), DekuError>
+where
+    T: DekuContainerRead<'a>,
+{
+    let budget = Budget {
+        limits,
+        elements: 0,
+        bits: 0,
+    };
+
+    let _guard = BudgetGuard {
+        previous: BUDGET.with(|current| current.borrow_mut().replace(budget)),
+    };
+
+    T::from_bytes(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::{Count, Endian};
+    use crate::DekuRead;
+    use bitvec::prelude::*;
+    use core::convert::TryFrom;
+    use rstest::rstest;
+    use std::vec::Vec;
+
+    /// A count followed by the `u16` elements
+    #[derive(Debug, PartialEq)]
+    struct Items(Vec<u16>);
+
+    impl<'a> DekuRead<'a> for Items {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, count) = u8::read(input, Endian::Big)?;
+            let (rest, items) = Vec::<u16>::read(rest, (Count(count.into()), Endian::Big))?;
+            Ok((rest, Items(items)))
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Items {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (rest, value) = Self::read(input.0.view_bits(), ())?;
+            Ok(((&input.0[input.0.len() - rest.len() / 8..], 0), value))
+        }
+    }
+
+    /// A `u64` count followed by the `u64` elements
+    #[derive(Debug, PartialEq)]
+    struct LargeItems(Vec<u64>);
+
+    impl<'a> DekuRead<'a> for LargeItems {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, count) = u64::read(input, Endian::Big)?;
+            let count = usize::try_from(count)?;
+            let (rest, items) = Vec::<u64>::read(rest, (Count(count), Endian::Big))?;
+            Ok((rest, LargeItems(items)))
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for LargeItems {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (rest, value) = Self::read(input.0.view_bits(), ())?;
+            Ok(((&input.0[input.0.len() - rest.len() / 8..], 0), value))
+        }
+    }
+
+    /// Panics when read
+    struct Panics;
+
+    impl<'a> DekuRead<'a> for Panics {
+        fn read(
+            _: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            panic!("read panics")
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Panics {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (_, value) = Self::read(input.0.view_bits(), ())?;
+            Ok((input, value))
+        }
+    }
+
+    #[rstest(limits, expected,
+        case::unlimited(Limits::new(), Ok(Items(vec![0xAABB, 0xCCDD]))),
+        case::within(Limits::new().max_elements(2).max_bits(40), Ok(Items(vec![0xAABB, 0xCCDD]))),
+        #[cfg(not(feature = "static-errors"))]
+        case::elements(
+            Limits::new().max_elements(1),
+            Err(DekuError::Parse(error_msg!("limit exceeded: more than 1 elements read"))),
+        ),
+        #[cfg(not(feature = "static-errors"))]
+        case::bits(
+            Limits::new().max_bits(39),
+            Err(DekuError::Parse(error_msg!("limit exceeded: more than 39 bits read"))),
+        ),
+    )]
+    fn test_read_limited(limits: Limits, expected: Result<Items, DekuError>) {
+        let input = [0x02, 0xAA, 0xBB, 0xCC, 0xDD];
+
+        let res_read = read_limited::<Items>((&input, 0), limits).map(|(_, value)| value);
+        assert_eq!(expected, res_read);
+    }
+
+    #[test]
+    fn test_read_limited_large_count() {
+        // the count isn't trusted to preallocate the elements
+        let input = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xAA];
+
+        let res_read = read_limited::<LargeItems>((&input, 0), Limits::new().max_elements(1024));
+        assert_eq!(
+            Err(DekuError::Incomplete(crate::error::NeedSize::new(64, 8))),
+            res_read.map(|(_, value)| value)
+        );
+    }
+
+    #[test]
+    fn test_read_limited_restores_budget() {
+        let input = [0x02, 0xAA, 0xBB, 0xCC, 0xDD];
+
+        let res_read = read_limited::<Items>((&input, 0), Limits::new().max_elements(1));
+        assert!(res_read.is_err());
+
+        // no limit outside of `read_limited`
+        let (_, value) = Items::from_bytes((&input, 0)).unwrap();
+        assert_eq!(Items(vec![0xAABB, 0xCCDD]), value);
+    }
+
+    #[test]
+    fn test_read_limited_restores_budget_on_panic() {
+        let input = [0x02, 0xAA, 0xBB, 0xCC, 0xDD];
+
+        let res = std::panic::catch_unwind(|| {
+            read_limited::<Panics>((&input, 0), Limits::new().max_elements(1))
+        });
+        assert!(res.is_err());
+
+        let (_, value) = Items::from_bytes((&input, 0)).unwrap();
+        assert_eq!(Items(vec![0xAABB, 0xCCDD]), value);
+    }
+}
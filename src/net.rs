@@ -0,0 +1,200 @@
+//! Link-layer address types: [`MacAddr`](struct.MacAddr.html) and [`Eui64`](struct.Eui64.html)
+//!
+//! The addresses are read and written as their bytes in transmission order, and are displayed and
+//! parsed as colon-separated hex bytes, e.g. `01:23:45:67:89:ab`.
+
+use crate::{DekuError, DekuRead, DekuSize, DekuWrite};
+use bitvec::prelude::*;
+use core::fmt;
+use core::str::FromStr;
+
+/// Error returned when parsing an address from a string fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseAddrError {}
+
+impl fmt::Display for ParseAddrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid address syntax")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseAddrError {}
+
+macro_rules! ImplAddr {
+    ($(#[$attr:meta])* $typ:ident, $len:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $typ(pub [u8; $len]);
+
+        impl $typ {
+            /// Bytes of the address, in transmission order
+            pub const fn octets(&self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $typ {
+            fn from(octets: [u8; $len]) -> Self {
+                $typ(octets)
+            }
+        }
+
+        impl From<$typ> for [u8; $len] {
+            fn from(addr: $typ) -> Self {
+                addr.0
+            }
+        }
+
+        impl fmt::Display for $typ {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for (i, byte) in self.0.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ":")?;
+                    }
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl FromStr for $typ {
+            type Err = ParseAddrError;
+
+            /// Parse colon or hyphen-separated hex bytes
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut octets = [0u8; $len];
+                parse_octets(s, &mut octets)?;
+                Ok($typ(octets))
+            }
+        }
+
+        impl<'a, Ctx> DekuRead<'a, Ctx> for $typ
+        where
+            [u8; $len]: DekuRead<'a, Ctx>,
+        {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+            where
+                Self: Sized,
+            {
+                let (rest, octets) = <[u8; $len]>::read(input, ctx)?;
+                Ok((rest, $typ(octets)))
+            }
+        }
+
+        impl<Ctx> DekuWrite<Ctx> for $typ
+        where
+            [u8; $len]: DekuWrite<Ctx>,
+        {
+            fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+                self.0.write(ctx)
+            }
+        }
+
+        impl DekuSize for $typ {
+            const BIT_SIZE: usize = $len * 8;
+        }
+    };
+}
+
+ImplAddr!(
+    /// 48-bit IEEE 802 MAC address
+    ///
+    /// # Examples
+    /// ```rust
+    /// use deku::net::MacAddr;
+    /// use deku::prelude::*;
+    ///
+    /// let mac: MacAddr = "01:23:45:67:89:AB".parse().unwrap();
+    /// assert_eq!(MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]), mac);
+    /// assert_eq!("01:23:45:67:89:ab", mac.to_string());
+    ///
+    /// let data = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB];
+    /// let (_, value) = MacAddr::read(data.view_bits(), ()).unwrap();
+    /// assert_eq!(mac, value);
+    /// ```
+    MacAddr,
+    6
+);
+
+ImplAddr!(
+    /// 64-bit IEEE EUI-64 address
+    ///
+    /// # Examples
+    /// ```rust
+    /// use deku::net::Eui64;
+    ///
+    /// let eui: Eui64 = "02-00-5e-10-00-00-00-01".parse().unwrap();
+    /// assert_eq!("02:00:5e:10:00:00:00:01", eui.to_string());
+    /// ```
+    Eui64,
+    8
+);
+
+/// Parse hex bytes separated by all colons or all hyphens, as many as `octets`
+fn parse_octets(s: &str, octets: &mut [u8]) -> Result<(), ParseAddrError> {
+    let separator = if s.contains('-') { '-' } else { ':' };
+
+    let mut parts = s.split(separator);
+    for octet in octets.iter_mut() {
+        let part = parts.next().ok_or(ParseAddrError {})?;
+        if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseAddrError {});
+        }
+        *octet = u8::from_str_radix(part, 16).map_err(|_| ParseAddrError {})?;
+    }
+
+    if parts.next().is_some() {
+        return Err(ParseAddrError {});
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest(input, expected,
+        case::colon("01:23:45:67:89:ab", Ok(MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]))),
+        case::hyphen("01-23-45-67-89-AB", Ok(MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]))),
+        case::mixed("01:23-45:67:89:ab", Err(ParseAddrError {})),
+        case::short("01:23:45:67:89", Err(ParseAddrError {})),
+        case::long("01:23:45:67:89:ab:cd", Err(ParseAddrError {})),
+        case::digits("01:23:45:67:89:a", Err(ParseAddrError {})),
+        case::sign("01:23:45:67:89:+a", Err(ParseAddrError {})),
+        case::not_hex("01:23:45:67:89:zz", Err(ParseAddrError {})),
+        case::empty("", Err(ParseAddrError {})),
+    )]
+    fn test_mac_addr_from_str(input: &str, expected: Result<MacAddr, ParseAddrError>) {
+        assert_eq!(expected, input.parse());
+    }
+
+    #[test]
+    fn test_mac_addr_read_write() {
+        let input = [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD];
+        let (rest, value) = MacAddr::read(input.view_bits(), ()).unwrap();
+        assert_eq!(MacAddr([0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]), value);
+        assert_eq!(input[6..].view_bits::<Msb0>(), rest);
+
+        assert_eq!(input[..6].to_vec(), value.write(()).unwrap().into_vec());
+    }
+
+    #[test]
+    fn test_eui64() {
+        let eui: Eui64 = "02:00:5e:10:00:00:00:01".parse().unwrap();
+        assert_eq!(Eui64([0x02, 0x00, 0x5E, 0x10, 0x00, 0x00, 0x00, 0x01]), eui);
+        assert_eq!("02:00:5e:10:00:00:00:01", eui.to_string());
+        assert_eq!(64, Eui64::BIT_SIZE);
+
+        let (rest, value) = Eui64::read(eui.0.view_bits(), ()).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(eui, value);
+        assert_eq!(eui.0.to_vec(), value.write(()).unwrap().into_vec());
+    }
+}
@@ -0,0 +1,417 @@
+//! Options of a read, chosen by the caller instead of the type definitions
+//!
+//! [`from_bytes_with_options`] reads a container like `from_bytes`, with [`ParseOptions`]:
+//! - `strict`: the whole input must be read, like `TryFrom<&[u8]>`
+//! - `lenient`: a failed `assert` or `assert_eq` is recorded as a warning and the read continues
+//! - `collect_warnings`: the warnings are returned with the value, see [`warn`]
+//! - `limits`: with the `limit` feature, the [limits](../limit/index.html) of the read
+//!
+//! The options of the current read are available to the attributes and custom readers with
+//! [`current`]. Options require the `std` feature, [`current`] returns the default options
+//! without it.
+//!
+//! Symmetrically, [`to_bytes_with_options`] writes a container like `to_bytes`, with
+//! [`WriteOptions`]:
+//! - `fill`: the byte written as padding in place of zeros, for the `pad_*`, `align_*`, `seek_*`,
+//!   `offset` and `limit_bytes` attributes and the last partial byte
+//! - `strict`: a value which doesn't fit in its `bits` or `bytes` is an error instead of being
+//!   truncated
+//!
+//! Placeholders, such as a length written before the data it counts, are set by `update` and
+//! `len_of`, before writing with [auto_update](../attributes/index.html#auto_update).
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::options::{from_bytes_with_options, ParseOptions};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     #[deku(assert = "*version <= 3")]
+//!     version: u8,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x04];
+//!
+//! assert!(DekuTest::from_bytes((data.as_ref(), 0)).is_err());
+//!
+//! let options = ParseOptions::new().lenient(true).collect_warnings(true);
+//! let output = from_bytes_with_options::<DekuTest>((data.as_ref(), 0), options).unwrap();
+//!
+//! assert_eq!(DekuTest { version: 4 }, output.value);
+//! assert_eq!(
+//!     vec!["field `version` failed assertion: *version <= 3".to_string()],
+//!     output.warnings
+//! );
+//! ```
+
+use crate::DekuError;
+#[cfg(feature = "std")]
+use crate::{DekuContainerRead, DekuContainerWrite};
+use alloc::borrow::Cow;
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+#[cfg(feature = "std")]
+use std::cell::{Cell, RefCell};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// Options of the current read and the warnings so far, `Some` within
+    /// `from_bytes_with_options`
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+
+    /// Options of the current write, `Some` within `to_bytes_with_options`
+    static WRITE_OPTIONS: Cell<Option<WriteOptions>> = const { Cell::new(None) };
+}
+
+/// Options of a read, the behavior of `from_bytes` by default
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ParseOptions {
+    /// The whole input must be read
+    pub strict: bool,
+    /// Failed assertions are warnings instead of errors
+    pub lenient: bool,
+    /// Warnings are returned with the value
+    pub collect_warnings: bool,
+    /// Limits of the read
+    #[cfg(feature = "limit")]
+    pub limits: Option<crate::limit::Limits>,
+}
+
+impl ParseOptions {
+    /// Create the options of `from_bytes`
+    pub fn new() -> Self {
+        ParseOptions::default()
+    }
+
+    /// Require the whole input to be read
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Record failed assertions as warnings instead of errors
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Return the warnings with the value
+    pub fn collect_warnings(mut self, collect_warnings: bool) -> Self {
+        self.collect_warnings = collect_warnings;
+        self
+    }
+
+    /// Limit the read
+    #[cfg(feature = "limit")]
+    pub fn limits(mut self, limits: crate::limit::Limits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+}
+
+/// Options of a write, the behavior of `to_bytes` by default
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WriteOptions {
+    /// Byte written as padding
+    pub fill: u8,
+    /// Values which don't fit in their size are errors
+    pub strict: bool,
+}
+
+impl WriteOptions {
+    /// Create the options of `to_bytes`
+    pub fn new() -> Self {
+        WriteOptions::default()
+    }
+
+    /// Write padding as `fill` bytes
+    pub fn fill(mut self, fill: u8) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Return an error for values which don't fit in their size, instead of truncating them
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+/// Options of the current read and the warnings so far
+#[cfg(feature = "std")]
+struct State {
+    options: ParseOptions,
+    warnings: Vec<String>,
+}
+
+/// Value read by [`from_bytes_with_options`]
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq)]
+pub struct ParseOutput<'a, T> {
+    /// Rest of the input and the bit offset in its first byte, as returned by `from_bytes`
+    pub rest: (&'a [u8], usize),
+    /// The value read
+    pub value: T,
+    /// The warnings, with `collect_warnings`
+    pub warnings: Vec<String>,
+}
+
+/// Options of the current read, the default options outside of [`from_bytes_with_options`]
+pub fn current() -> ParseOptions {
+    #[cfg(feature = "std")]
+    {
+        STATE.with(|state| {
+            state
+                .borrow()
+                .as_ref()
+                .map(|state| state.options)
+                .unwrap_or_default()
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        ParseOptions::default()
+    }
+}
+
+/// Record a warning, returned by [`from_bytes_with_options`] with `collect_warnings`
+pub fn warn<S: ToString>(message: S) {
+    #[cfg(feature = "std")]
+    STATE.with(|state| {
+        if let Some(state) = state.borrow_mut().as_mut() {
+            if state.options.collect_warnings {
+                state.warnings.push(message.to_string());
+            }
+        }
+    });
+
+    #[cfg(not(feature = "std"))]
+    let _ = message;
+}
+
+/// Return a failed assertion, or record it as a warning in `lenient` mode, called by the
+/// generated readers and writers
+pub fn assertion_failed(message: Cow<'static, str>) -> Result<(), DekuError> {
+    if current().lenient {
+        warn(message);
+        Ok(())
+    } else {
+        Err(DekuError::Assertion(message))
+    }
+}
+
+/// Options of the current write, the default options outside of [`to_bytes_with_options`]
+pub fn current_write() -> WriteOptions {
+    #[cfg(feature = "std")]
+    {
+        WRITE_OPTIONS
+            .with(|options| options.get())
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        WriteOptions::default()
+    }
+}
+
+/// Write `bits` bits of padding, the bits of the `fill` byte at their position in the bytes of
+/// `acc`, called by the generated writers
+pub fn fill(acc: &mut BitVec<Msb0, u8>, bits: usize) {
+    let fill = current_write().fill;
+    if fill == 0 {
+        acc.resize(acc.len() + bits, false);
+        return;
+    }
+
+    let fill = fill.view_bits::<Msb0>();
+    for _ in 0..bits {
+        let bit = fill[acc.len() % 8];
+        acc.push(bit);
+    }
+}
+
+/// Read a `T` from `input` like `from_bytes`, with `options`, see the
+/// [module documentation](index.html)
+#[cfg(feature = "std")]
+pub fn from_bytes_with_options<'a, T>(
+    input: (&'a [u8], usize),
+    options: ParseOptions,
+) -> Result<ParseOutput<'a, T>, DekuError>
+where
+    T: DekuContainerRead<'a>,
+{
+    let state = State {
+        options,
+        warnings: Vec::new(),
+    };
+
+    // restore the state of an enclosing read once done
+    let previous = STATE.with(|current| current.borrow_mut().replace(state));
+
+    #[cfg(feature = "limit")]
+    let res = match options.limits {
+        Some(limits) => crate::limit::read_limited::<T>(input, limits),
+        None => T::from_bytes(input),
+    };
+    #[cfg(not(feature = "limit"))]
+    let res = T::from_bytes(input);
+
+    let state = STATE.with(|current| core::mem::replace(&mut *current.borrow_mut(), previous));
+    let (rest, value) = res?;
+
+    if options.strict && !rest.0.is_empty() {
+        return Err(DekuError::Parse(error_msg!("Too much data")));
+    }
+
+    Ok(ParseOutput {
+        rest,
+        value,
+        warnings: state.map(|state| state.warnings).unwrap_or_default(),
+    })
+}
+
+/// Write `value` like `to_bytes`, with `options`, see the [module documentation](index.html)
+#[cfg(feature = "std")]
+pub fn to_bytes_with_options<T>(value: &T, options: WriteOptions) -> Result<Vec<u8>, DekuError>
+where
+    T: DekuContainerWrite,
+{
+    // restore the options of an enclosing write once done
+    let previous = WRITE_OPTIONS.with(|current| current.replace(Some(options)));
+    let res = value.to_bits().map(|mut acc| {
+        let pad = 8 * acc.len().div_ceil(8) - acc.len();
+        fill(&mut acc, pad);
+        acc.into_vec()
+    });
+    WRITE_OPTIONS.with(|current| current.set(previous));
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::{BitSize, Endian};
+    use crate::{DekuRead, DekuWrite};
+    use rstest::rstest;
+    use std::vec;
+
+    /// A byte which must be even
+    #[derive(Debug, PartialEq)]
+    struct Even(u8);
+
+    impl<'a> DekuRead<'a> for Even {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: (),
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            let (rest, value) = u8::read(input, Endian::Big)?;
+            if value % 2 != 0 {
+                assertion_failed("odd".into())?;
+            }
+            Ok((rest, Even(value)))
+        }
+    }
+
+    impl<'a> DekuContainerRead<'a> for Even {
+        fn from_bytes(input: (&'a [u8], usize)) -> Result<((&'a [u8], usize), Self), DekuError> {
+            let (rest, value) = Self::read(input.0.view_bits(), ())?;
+            Ok(((&input.0[input.0.len() - rest.len() / 8..], 0), value))
+        }
+    }
+
+    #[rstest(input, options, expected,
+        case::default(&[0x02, 0xFF], ParseOptions::new(), Ok((Even(0x02), vec![]))),
+        case::strict(
+            &[0x02, 0xFF],
+            ParseOptions::new().strict(true),
+            Err(DekuError::Parse(error_msg!("Too much data"))),
+        ),
+        case::assertion(&[0x03], ParseOptions::new(), Err(DekuError::Assertion(error_msg!("odd")))),
+        case::lenient(&[0x03], ParseOptions::new().lenient(true), Ok((Even(0x03), vec![]))),
+        case::warnings(
+            &[0x03],
+            ParseOptions::new().lenient(true).collect_warnings(true),
+            Ok((Even(0x03), vec!["odd".to_string()])),
+        ),
+    )]
+    fn test_from_bytes_with_options(
+        input: &[u8],
+        options: ParseOptions,
+        expected: Result<(Even, Vec<String>), DekuError>,
+    ) {
+        let res_read = from_bytes_with_options::<Even>((input, 0), options)
+            .map(|output| (output.value, output.warnings));
+        assert_eq!(expected, res_read);
+    }
+
+    #[test]
+    fn test_from_bytes_with_options_restores_state() {
+        let options = ParseOptions::new().lenient(true);
+        from_bytes_with_options::<Even>((&[0x03], 0), options).unwrap();
+
+        assert_eq!(ParseOptions::default(), current());
+        assert_eq!(
+            Err(DekuError::Assertion(error_msg!("odd"))),
+            Even::from_bytes((&[0x03], 0)).map(|(_, value)| value)
+        );
+    }
+
+    /// A nibble followed by 2 bits of padding
+    struct Nibble(u8);
+
+    impl DekuWrite for Nibble {
+        fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+            let mut acc: BitVec<Msb0, u8> = BitVec::new();
+            acc.extend(self.0.write((Endian::Big, BitSize(4)))?);
+            fill(&mut acc, 2);
+            Ok(acc)
+        }
+    }
+
+    impl DekuContainerWrite for Nibble {
+        fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
+            Ok(self.to_bits()?.into_vec())
+        }
+
+        fn to_bits(&self) -> Result<BitVec<Msb0, u8>, DekuError> {
+            self.write(())
+        }
+    }
+
+    #[rstest(input, options, expected,
+        case::default(Nibble(0x0A), WriteOptions::new(), Ok(vec![0b1010_0000])),
+        case::fill_ones(Nibble(0x0A), WriteOptions::new().fill(0xFF), Ok(vec![0b1010_1111])),
+        case::fill_position(Nibble(0x0A), WriteOptions::new().fill(0b0101_1010), Ok(vec![0b1010_1010])),
+        case::truncated(Nibble(0x1A), WriteOptions::new(), Ok(vec![0b1010_0000])),
+        #[cfg(not(feature = "static-errors"))]
+        case::strict(
+            Nibble(0x1A),
+            WriteOptions::new().strict(true),
+            Err(DekuError::InvalidParam(error_msg!("value 26 doesn't fit in 4 bits"))),
+        ),
+    )]
+    fn test_to_bytes_with_options(
+        input: Nibble,
+        options: WriteOptions,
+        expected: Result<Vec<u8>, DekuError>,
+    ) {
+        assert_eq!(expected, to_bytes_with_options(&input, options));
+    }
+
+    #[test]
+    fn test_to_bytes_with_options_restores_options() {
+        to_bytes_with_options(&Nibble(0x0A), WriteOptions::new().fill(0xFF)).unwrap();
+
+        assert_eq!(WriteOptions::default(), current_write());
+        assert_eq!(vec![0b1010_0000], Nibble(0x0A).to_bytes().unwrap());
+    }
+}
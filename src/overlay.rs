@@ -0,0 +1,245 @@
+//! An `Overlay` type for bytes with two possible interpretations
+//!
+//! Some formats interpret the same bytes differently depending on a field which comes after
+//! them. `Overlay<T, U>` retains the raw bytes when reading, either interpretation can then be
+//! materialized on demand with [`Overlay::left`] and [`Overlay::right`].
+//!
+//! When writing, the active interpretation set with [`Overlay::set_left`] or
+//! [`Overlay::set_right`] is written, otherwise the raw bytes are written back unchanged. Setting
+//! an interpretation also writes it to the raw bytes, so both interpretations materialize the new
+//! value.
+//!
+//! The number of bytes retained is given by the `count` attribute.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::ctx::Endian;
+//! # use deku::overlay::Overlay;
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Pair {
+//!     a: u8,
+//!     b: u8,
+//! }
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     #[deku(count = "2")]
+//!     payload: Overlay<Pair, u16>,
+//!     is_pair: u8,
+//! }
+//!
+//! let data: Vec<u8> = vec![0xAB, 0xCD, 0x01];
+//!
+//! let mut value = DekuTest::try_from(data.as_ref()).unwrap();
+//! assert_eq!(value.is_pair, 0x01);
+//!
+//! let pair = value.payload.left(()).unwrap();
+//! assert_eq!(Pair { a: 0xAB, b: 0xCD }, pair);
+//!
+//! value.payload.set_left(Pair { a: 0x01, b: 0x02 }, ()).unwrap();
+//! assert_eq!(0x0102, value.payload.right(Endian::Big).unwrap());
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(vec![0x01, 0x02, 0x01], value);
+//! ```
+
+use crate::ctx::Count;
+use crate::either::Either;
+use crate::error::NeedSize;
+use crate::{DekuError, DekuRead, DekuWrite};
+use bitvec::prelude::*;
+
+/// Raw bytes with two possible interpretations, see the [module documentation](index.html)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlay<T, U> {
+    raw: BitVec<Msb0, u8>,
+    active: Option<Either<T, U>>,
+}
+
+impl<T, U> Overlay<T, U> {
+    /// Create an `Overlay` over raw bits, without an active interpretation
+    pub fn new(raw: BitVec<Msb0, u8>) -> Self {
+        Self { raw, active: None }
+    }
+
+    /// The raw bits read
+    pub fn raw(&self) -> &BitSlice<Msb0, u8> {
+        self.raw.as_bitslice()
+    }
+
+    /// The active interpretation, if any
+    pub fn active(&self) -> Option<&Either<T, U>> {
+        self.active.as_ref()
+    }
+
+    /// Materialize the raw bits as a `T`
+    /// * **ctx** - The context required by `T`
+    pub fn left<'a, Ctx>(&'a self, ctx: Ctx) -> Result<T, DekuError>
+    where
+        T: DekuRead<'a, Ctx>,
+    {
+        let (_, value) = T::read(self.raw(), ctx)?;
+        Ok(value)
+    }
+
+    /// Materialize the raw bits as a `U`
+    /// * **ctx** - The context required by `U`
+    pub fn right<'a, Ctx>(&'a self, ctx: Ctx) -> Result<U, DekuError>
+    where
+        U: DekuRead<'a, Ctx>,
+    {
+        let (_, value) = U::read(self.raw(), ctx)?;
+        Ok(value)
+    }
+
+    /// Set `T` as the active interpretation, written instead of the raw bits, and replace the
+    /// raw bits by the value written
+    /// * **ctx** - The context required by `T`
+    pub fn set_left<Ctx>(&mut self, value: T, ctx: Ctx) -> Result<(), DekuError>
+    where
+        T: DekuWrite<Ctx>,
+    {
+        self.raw = value.write(ctx)?;
+        self.active = Some(Either::Left(value));
+        Ok(())
+    }
+
+    /// Set `U` as the active interpretation, written instead of the raw bits, and replace the
+    /// raw bits by the value written
+    /// * **ctx** - The context required by `U`
+    pub fn set_right<Ctx>(&mut self, value: U, ctx: Ctx) -> Result<(), DekuError>
+    where
+        U: DekuWrite<Ctx>,
+    {
+        self.raw = value.write(ctx)?;
+        self.active = Some(Either::Right(value));
+        Ok(())
+    }
+}
+
+impl<'a, T, U, Ctx> DekuRead<'a, (Count, Ctx)> for Overlay<T, U> {
+    /// Retain the specified number of bytes from input
+    /// * `count` - the number of bytes to retain
+    /// * `_ctx` - The context of the interpretations, provided again when materializing
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        (count, _ctx): (Count, Ctx),
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let count: usize = count.into();
+        let bit_size = count.checked_mul(8).ok_or_else(|| {
+            DekuError::Parse(error_msg!(
+                "overlay: {} bytes is too large to be counted in bits",
+                count
+            ))
+        })?;
+
+        if input.len() < bit_size {
+            return Err(DekuError::Incomplete(NeedSize::new(bit_size, input.len())));
+        }
+
+        let (raw, rest) = input.split_at(bit_size);
+
+        Ok((rest, Self::new(raw.to_bitvec())))
+    }
+}
+
+impl<'a, T, U> DekuRead<'a, Count> for Overlay<T, U> {
+    /// Retain the specified number of bytes from input
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        count: Count,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        Self::read(input, (count, ()))
+    }
+}
+
+impl<T, U, Ctx> DekuWrite<Ctx> for Overlay<T, U>
+where
+    T: DekuWrite<Ctx>,
+    U: DekuWrite<Ctx>,
+{
+    /// Write the active interpretation, or the raw bits if none
+    /// * **ctx** - The context required by `T` and `U`
+    fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        match &self.active {
+            Some(Either::Left(value)) => value.write(ctx),
+            Some(Either::Right(value)) => value.write(ctx),
+            None => Ok(self.raw.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    #[rstest(input, count, expected_raw, expected_rest,
+        case::count_0([0xAA].as_ref(), 0, bits![Msb0, u8;], bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0]),
+        case::count_1([0xAA, 0xBB].as_ref(), 1, bits![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 0], bits![Msb0, u8; 1, 0, 1, 1, 1, 0, 1, 1]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::not_enough_data([0xAA].as_ref(), 2, bits![Msb0, u8;], bits![Msb0, u8;]),
+        #[cfg(not(feature = "static-errors"))]
+        #[should_panic(expected = "Parse(\"overlay: 18446744073709551615 bytes is too large to be counted in bits\")")]
+        case::overflow([0xAA].as_ref(), usize::MAX, bits![Msb0, u8;], bits![Msb0, u8;]),
+    )]
+    fn test_overlay_read(
+        input: &[u8],
+        count: usize,
+        expected_raw: &BitSlice<Msb0, u8>,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = Overlay::<u8, i8>::read(bit_slice, Count(count)).unwrap();
+        assert_eq!(expected_raw, res_read.raw());
+        assert_eq!(None, res_read.active());
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[test]
+    fn test_overlay_interpretations() {
+        let input = [0xAA, 0xBB];
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (_, mut overlay) =
+            Overlay::<u16, [u8; 2]>::read(bit_slice, (Count(2), Endian::Big)).unwrap();
+
+        assert_eq!(0xAABB, overlay.left(Endian::Big).unwrap());
+        assert_eq!(0xBBAA, overlay.left(Endian::Little).unwrap());
+        assert_eq!([0xAA, 0xBB], overlay.right(()).unwrap());
+
+        // raw bits are written back
+        assert_eq!(
+            vec![0xAA, 0xBB],
+            overlay.write(Endian::Big).unwrap().into_vec()
+        );
+
+        overlay.set_left(0x0102, Endian::Big).unwrap();
+        assert_eq!(
+            vec![0x01, 0x02],
+            overlay.write(Endian::Big).unwrap().into_vec()
+        );
+        assert_eq!(0x0102, overlay.left(Endian::Big).unwrap());
+        assert_eq!([0x01, 0x02], overlay.right(()).unwrap());
+
+        overlay.set_right([0x03, 0x04], ()).unwrap();
+        assert_eq!(
+            vec![0x03, 0x04],
+            overlay.write(Endian::Big).unwrap().into_vec()
+        );
+        assert_eq!(0x0304, overlay.left(Endian::Big).unwrap());
+        assert_eq!([0x03, 0x04], overlay.right(()).unwrap());
+    }
+}
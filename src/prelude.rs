@@ -1,5 +1,10 @@
+#[cfg(feature = "hex")]
+pub use crate::hex::DekuContainerReadHex;
 pub use crate::{
-    error::DekuError, DekuContainerRead, DekuContainerWrite, DekuRead, DekuUpdate, DekuWrite,
+    deku_derive, deku_reader, deku_writer,
+    error::{DekuError, NeedSize},
+    DekuContainerRead, DekuContainerWrite, DekuCtxDefault, DekuEnumSize, DekuFfi, DekuPython,
+    DekuRead, DekuSize, DekuUpdate, DekuUpdateCtx, DekuView, DekuWrite,
 };
 pub use bitvec::{
     order::BitOrder, order::Lsb0, order::Msb0, slice::BitSlice, vec::BitVec, view::BitView,
@@ -0,0 +1,213 @@
+//! Round-trip test helpers, for the test matrix of a message type
+//!
+//! The helpers write a value, then read it back, and panic with a description of the first
+//! problem found:
+//! - [`assert_roundtrip`]: the value read equals the value written, and consumes all its bits
+//! - [`assert_bit_offsets`]: the same, from the bits written at each bit offset 1 to 7 within a
+//!   byte, followed by more bits which are left unread
+//! - [`assert_truncated`]: each input truncated before the last bit written is an error
+//!
+//! [`roundtrip_tests!`](../macro.roundtrip_tests.html) generates the tests of the three helpers
+//! with both endians, for a type read and written with an `Endian` ctx.
+//!
+//! A type which borrows from the input can't be read at a bit offset, and a type which reads
+//! until the end of the input, or has `default_if_eof` fields, reads truncated inputs.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::ctx::Endian;
+//! # use deku::roundtrip::{assert_bit_offsets, assert_roundtrip, assert_truncated};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! #[deku(ctx = "endian: Endian", endian = "endian")]
+//! struct DekuTest {
+//!     #[deku(bits = "4")]
+//!     field_a: u8,
+//!     field_b: u16,
+//! }
+//!
+//! let value = DekuTest { field_a: 0x0A, field_b: 0xBBCC };
+//! for endian in [Endian::Little, Endian::Big].iter().copied() {
+//!     assert_roundtrip(&value, endian);
+//!     assert_bit_offsets(&value, endian);
+//!     assert_truncated(&value, endian);
+//! }
+//! ```
+
+use crate::{DekuRead, DekuWrite};
+use bitvec::prelude::*;
+use core::fmt::Debug;
+
+/// Write the value, panicking on error
+fn write<T, Ctx>(value: &T, ctx: Ctx) -> BitVec<Msb0, u8>
+where
+    T: DekuWrite<Ctx> + Debug,
+    Ctx: Debug,
+{
+    let ctx_str = format!("{:?}", ctx);
+    match value.write(ctx) {
+        Ok(bits) => bits,
+        Err(e) => panic!("writing {:?} with {} failed: {}", value, ctx_str, e),
+    }
+}
+
+/// Read the value from `input`, checking it's equal to `expected` with `rest_len` bits left
+fn assert_read<T, Ctx>(
+    expected: &T,
+    input: &BitSlice<Msb0, u8>,
+    rest_len: usize,
+    ctx: Ctx,
+    what: &str,
+) where
+    T: for<'a> DekuRead<'a, Ctx> + PartialEq + Debug,
+    Ctx: Debug,
+{
+    let ctx_str = format!("{:?}", ctx);
+    match T::read(input, ctx) {
+        Ok((rest, value)) => {
+            assert_eq!(
+                *expected, value,
+                "reading {} with {}: value read differs from the value written",
+                what, ctx_str
+            );
+            assert_eq!(
+                rest_len,
+                rest.len(),
+                "reading {} with {}: {} bits left, expected {}",
+                what,
+                ctx_str,
+                rest.len(),
+                rest_len
+            );
+        }
+        Err(e) => panic!("reading {} with {} failed: {}", what, ctx_str, e),
+    }
+}
+
+/// Write the value, then read it back: the value read must be equal, and consume all the bits
+/// written
+pub fn assert_roundtrip<T, Ctx>(value: &T, ctx: Ctx)
+where
+    T: for<'a> DekuRead<'a, Ctx> + DekuWrite<Ctx> + PartialEq + Debug,
+    Ctx: Copy + Debug,
+{
+    let bits = write(value, ctx);
+    assert_read(value, &bits, 0, ctx, &format!("{:?}", value));
+}
+
+/// Write the value, then read it back from the bits written at each bit offset 1 to 7 within a
+/// byte, followed by a byte which must be left unread
+pub fn assert_bit_offsets<T, Ctx>(value: &T, ctx: Ctx)
+where
+    T: for<'a> DekuRead<'a, Ctx> + DekuWrite<Ctx> + PartialEq + Debug,
+    Ctx: Copy + Debug,
+{
+    let bits = write(value, ctx);
+
+    for offset in 1..8 {
+        let mut input: BitVec<Msb0, u8> = BitVec::repeat(true, offset);
+        input.extend(bits.iter().copied());
+        input.extend(BitVec::<Msb0, u8>::repeat(true, 8));
+
+        assert_read(
+            value,
+            &input[offset..],
+            8,
+            ctx,
+            &format!("{:?} at bit offset {}", value, offset),
+        );
+    }
+}
+
+/// Write the value, then read it back from each input truncated before the last bit written,
+/// which must be an error
+pub fn assert_truncated<T, Ctx>(value: &T, ctx: Ctx)
+where
+    T: for<'a> DekuRead<'a, Ctx> + DekuWrite<Ctx> + PartialEq + Debug,
+    Ctx: Copy + Debug,
+{
+    let bits = write(value, ctx);
+
+    for len in 0..bits.len() {
+        if let Ok((_, read)) = T::read(&bits[..len], ctx) {
+            panic!(
+                "reading {:?} with {:?} truncated to {} of {} bits didn't fail, read {:?}",
+                value,
+                ctx,
+                len,
+                bits.len(),
+                read
+            );
+        }
+    }
+}
+
+/// Generate the round-trip tests of values of a type read and written with an `Endian` ctx
+///
+/// A `#[cfg(test)]` module of the given name is generated, with the tests `roundtrip`, `bit_offsets` and
+/// `truncated`, checking each value with both endians using the [roundtrip](roundtrip/index.html)
+/// helpers.
+///
+/// # Example
+///
+/// ```rust
+/// # use deku::prelude::*;
+/// # use deku::ctx::Endian;
+/// #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+/// #[deku(ctx = "endian: Endian", endian = "endian")]
+/// struct DekuTest {
+///     field_a: u16,
+///     field_b: u32,
+/// }
+///
+/// deku::roundtrip_tests!(deku_test: DekuTest = [
+///     DekuTest { field_a: 0, field_b: 0 },
+///     DekuTest { field_a: 0xAABB, field_b: 0xCCDDEEFF },
+/// ]);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! roundtrip_tests {
+    ($name:ident: $ty:ty = [$($value:expr),+ $(,)?]) => {
+        #[cfg(test)]
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            fn values() -> ::std::vec::Vec<$ty> {
+                ::std::vec![$($value),+]
+            }
+
+            const ENDIANS: [$crate::ctx::Endian; 2] =
+                [$crate::ctx::Endian::Little, $crate::ctx::Endian::Big];
+
+            #[test]
+            fn roundtrip() {
+                for value in values() {
+                    for endian in ENDIANS.iter().copied() {
+                        $crate::roundtrip::assert_roundtrip(&value, endian);
+                    }
+                }
+            }
+
+            #[test]
+            fn bit_offsets() {
+                for value in values() {
+                    for endian in ENDIANS.iter().copied() {
+                        $crate::roundtrip::assert_bit_offsets(&value, endian);
+                    }
+                }
+            }
+
+            #[test]
+            fn truncated() {
+                for value in values() {
+                    for endian in ENDIANS.iter().copied() {
+                        $crate::roundtrip::assert_truncated(&value, endian);
+                    }
+                }
+            }
+        }
+    };
+}
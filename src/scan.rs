@@ -0,0 +1,276 @@
+//! Scan consecutive records for the ones matching a predicate, or backward for a signature
+//!
+//! [`scan`] walks a buffer of consecutive fixed-size records using their
+//! [`DekuView`](../trait.DekuView.html), yielding the bit offset of each record for which the
+//! predicate returns `true`. Only the fields accessed by the predicate are read, the other fields
+//! of the records are skipped.
+//!
+//! A trailing incomplete record is an error, after which the iteration ends.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::scan::scan;
+//! #[derive(DekuView)]
+//! struct Record {
+//!     kind: u8,
+//!     value: u16,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x01, 0xAA, 0xBB, 0x02, 0xCC, 0xDD, 0x01, 0xEE, 0xFF];
+//!
+//! let offsets = scan::<Record, _>(data.view_bits(), |view| Ok(view.kind()? == 0x01))
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(vec![0, 48], offsets);
+//! ```
+//!
+//! # Backward scan
+//!
+//! Some formats are located from the end of the input, such as the end of central directory
+//! record of ZIP archives, which is followed by a comment of variable length. [`scan_back`]
+//! searches the last bytes of the input for a signature, from the end, and reads the record found
+//! there.
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::scan::scan_back;
+//! #[derive(Debug, PartialEq, DekuRead)]
+//! #[deku(magic = b"PK\x05\x06", endian = "little")]
+//! struct EndOfCentralDirectory {
+//!     disk: u16,
+//!     directory_disk: u16,
+//!     disk_entries: u16,
+//!     entries: u16,
+//!     directory_size: u32,
+//!     directory_offset: u32,
+//!     comment_len: u16,
+//!     #[deku(count = "comment_len")]
+//!     comment: Vec<u8>,
+//! }
+//!
+//! let mut data: Vec<u8> = vec![0xAA; 64];
+//! data.extend(b"PK\x05\x06\x00\x00\x00\x00\x01\x00\x01\x00");
+//! data.extend(b"\x2E\x00\x00\x00\x40\x00\x00\x00\x02\x00hi");
+//!
+//! let (offset, eocd) = scan_back::<EndOfCentralDirectory>(&data, b"PK\x05\x06", 0xFFFF + 22)
+//!     .unwrap();
+//! assert_eq!(64, offset);
+//! assert_eq!(0x40, eocd.directory_offset);
+//! assert_eq!(b"hi".to_vec(), eocd.comment);
+//! ```
+
+use crate::{DekuError, DekuRead, DekuView};
+use bitvec::prelude::*;
+use core::marker::PhantomData;
+
+/// Iterator over the offsets of the matching records, see the [module documentation](index.html)
+pub struct Scan<'a, T, F> {
+    input: &'a BitSlice<Msb0, u8>,
+    offset: usize,
+    predicate: F,
+    done: bool,
+    phantom: PhantomData<T>,
+}
+
+/// Scan the records of `input` for the ones matching `predicate`
+/// * **input** - Consecutive records of type `T`
+/// * **predicate** - Called with the view of each record
+pub fn scan<'a, T, F>(input: &'a BitSlice<Msb0, u8>, predicate: F) -> Scan<'a, T, F>
+where
+    T: DekuView<'a>,
+    F: FnMut(&T::View) -> Result<bool, DekuError>,
+{
+    Scan {
+        input,
+        offset: 0,
+        predicate,
+        done: false,
+        phantom: PhantomData,
+    }
+}
+
+impl<'a, T, F> Iterator for Scan<'a, T, F>
+where
+    T: DekuView<'a>,
+    F: FnMut(&T::View) -> Result<bool, DekuError>,
+{
+    /// Bit offset of the record in the input
+    type Item = Result<usize, DekuError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // a record without size can't be walked over
+        if self.done || T::BIT_SIZE == 0 {
+            return None;
+        }
+
+        while self.offset < self.input.len() {
+            let offset = self.offset;
+            self.offset += T::BIT_SIZE;
+
+            let res = T::view(&self.input[offset..]).and_then(|view| (self.predicate)(&view));
+            match res {
+                Ok(true) => return Some(Ok(offset)),
+                Ok(false) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T, F> core::iter::FusedIterator for Scan<'a, T, F>
+where
+    T: DekuView<'a>,
+    F: FnMut(&T::View) -> Result<bool, DekuError>,
+{
+}
+
+/// Read the record starting with `signature` nearest to the end of `input`, within its last
+/// `max_window` bytes
+/// * **input** - Input bytes, the record may be followed by other bytes
+/// * **signature** - Bytes the record starts with
+/// * **max_window** - Bytes from the end of `input` to search
+///
+/// The candidates are tried from the end of `input`: a candidate which fails to read is skipped,
+/// such as a signature found in a comment. If no candidate reads, the error of the candidate
+/// nearest to the end is returned.
+///
+/// Returns the byte offset of the record in `input` and the record
+pub fn scan_back<'a, T>(
+    input: &'a [u8],
+    signature: &[u8],
+    max_window: usize,
+) -> Result<(usize, T), DekuError>
+where
+    T: DekuRead<'a, ()>,
+{
+    if signature.is_empty() {
+        return Err(DekuError::InvalidParam(error_msg!(
+            "scan_back: the signature is empty"
+        )));
+    }
+
+    let start = input.len().saturating_sub(max_window);
+    let mut first_err = None;
+
+    for offset in (start..=input.len().saturating_sub(signature.len())).rev() {
+        if !input[offset..].starts_with(signature) {
+            continue;
+        }
+
+        match T::read(input[offset..].view_bits(), ()) {
+            Ok((_, value)) => return Ok((offset, value)),
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+
+    Err(first_err.unwrap_or_else(|| {
+        DekuError::Parse(error_msg!(
+            "scan_back: signature not found in the last {} bytes",
+            input.len() - start
+        ))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use crate::error::NeedSize;
+    use crate::{DekuRead, DekuSize};
+    use rstest::rstest;
+
+    // Big endian `u16` records with an `u8` kind in the first byte
+    struct Record;
+
+    impl DekuSize for Record {
+        const BIT_SIZE: usize = 16;
+    }
+
+    impl<'a> DekuView<'a> for Record {
+        type View = &'a BitSlice<Msb0, u8>;
+
+        fn view(input: &'a BitSlice<Msb0, u8>) -> Result<Self::View, DekuError> {
+            if input.len() < Self::BIT_SIZE {
+                return Err(DekuError::Incomplete(NeedSize::new(
+                    Self::BIT_SIZE,
+                    input.len(),
+                )));
+            }
+
+            Ok(&input[..Self::BIT_SIZE])
+        }
+    }
+
+    fn kind(view: &&BitSlice<Msb0, u8>) -> Result<u8, DekuError> {
+        let (_, kind) = u8::read(&view[..8], Endian::Big)?;
+        Ok(kind)
+    }
+
+    #[rstest(input, expected,
+        case::empty([].as_ref(), vec![]),
+        case::none([0x02, 0xAA, 0x03, 0xBB].as_ref(), vec![]),
+        case::some([0x01, 0xAA, 0x02, 0xBB, 0x01, 0xCC].as_ref(), vec![0, 32]),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::trailing([0x01, 0xAA, 0x01].as_ref(), vec![]),
+    )]
+    fn test_scan(input: &[u8], expected: Vec<usize>) {
+        let res = scan::<Record, _>(input.view_bits(), |view| Ok(kind(view)? == 0x01))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(expected, res);
+    }
+
+    #[test]
+    fn test_scan_fused() {
+        let input = [0x01, 0xAA, 0x01];
+
+        let mut iter = scan::<Record, _>(input.view_bits(), |view| Ok(kind(view)? == 0x01));
+        assert_eq!(Some(0), iter.next().transpose().unwrap());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[rstest(input, max_window, expected,
+        case::end([0x00, 0xFE, 0x01].as_ref(), 8, Ok((1, (0xFE, 0x01)))),
+        case::last([0xFE, 0x01, 0xFE, 0x02, 0x00].as_ref(), 8, Ok((2, (0xFE, 0x02)))),
+        case::skip_incomplete([0xFE, 0x01, 0x00, 0xFE].as_ref(), 8, Ok((0, (0xFE, 0x01)))),
+        #[cfg(not(feature = "static-errors"))]
+        case::not_found([0x00, 0x01, 0x02].as_ref(), 8, Err(DekuError::Parse(error_msg!("scan_back: signature not found in the last 3 bytes")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::out_of_window([0xFE, 0x01, 0x00, 0x00].as_ref(), 3, Err(DekuError::Parse(error_msg!("scan_back: signature not found in the last 3 bytes")))),
+        case::incomplete([0x00, 0xFE].as_ref(), 8, Err(DekuError::Incomplete(NeedSize::new(8, 0)))),
+    )]
+    fn test_scan_back(
+        input: &[u8],
+        max_window: usize,
+        expected: Result<(usize, (u8, u8)), DekuError>,
+    ) {
+        assert_eq!(expected, scan_back::<(u8, u8)>(input, &[0xFE], max_window));
+    }
+
+    #[test]
+    fn test_scan_back_signature() {
+        let input = [0x50, 0x4B, 0x50, 0x4B, 0x05];
+        assert_eq!(
+            Ok((2, (0x50, 0x4B))),
+            scan_back::<(u8, u8)>(&input, &[0x50, 0x4B], 16)
+        );
+        assert_eq!(
+            Err(DekuError::InvalidParam(error_msg!(
+                "scan_back: the signature is empty"
+            ))),
+            scan_back::<(u8, u8)>(&input, &[], 16)
+        );
+    }
+}
@@ -3,21 +3,20 @@
 // Forked out DekuRead and DekuWrite impls for [T; N] into a separate file
 // since the list of impls is long
 
-use super::{DekuRead, DekuWrite};
+use super::{DekuRead, DekuSize, DekuWrite};
 use crate::error::DekuError;
 use bitvec::prelude::*;
-pub use deku_derive::*;
 
 macro_rules! ImplDekuSliceTraits {
     ($typ:ty, $count:expr) => {
-        impl<Ctx: Copy> DekuRead<Ctx> for [$typ; $count]
+        impl<'a, Ctx: Copy> DekuRead<'a, Ctx> for [$typ; $count]
         where
-            $typ: DekuRead<Ctx>,
+            $typ: DekuRead<'a, Ctx>,
         {
             fn read(
-                input: &BitSlice<Msb0, u8>,
+                input: &'a BitSlice<Msb0, u8>,
                 ctx: Ctx,
-            ) -> Result<(&BitSlice<Msb0, u8>, Self), DekuError>
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
             where
                 Self: Sized,
             {
@@ -33,6 +32,10 @@ macro_rules! ImplDekuSliceTraits {
             }
         }
 
+        impl DekuSize for [$typ; $count] {
+            const BIT_SIZE: usize = <$typ as DekuSize>::BIT_SIZE * $count;
+        }
+
         impl<Ctx: Copy> DekuWrite<Ctx> for [$typ; $count]
         where
             $typ: DekuWrite<Ctx>,
@@ -0,0 +1,370 @@
+//! Implementations of DekuRead and DekuWrite for `String` in an [`Encoding`]
+//!
+//! The `count` of a string is its number of code units, e.g. two bytes each in UTF-16. A string
+//! of a number of `bits` is fixed-width: the trailing [`Pad`] code units are trimmed when reading,
+//! and added up to the size when writing.
+
+use super::{DekuRead, DekuWrite};
+use crate::ctx::{BitSize, ByteSize, Count, Encoding, Endian, Pad, ReadAll};
+use crate::error::DekuError;
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use bitvec::prelude::*;
+
+/// Decode the bytes of a string in the encoding
+fn decode(bytes: &[u8], encoding: Encoding) -> Result<String, DekuError> {
+    match encoding {
+        Encoding::Utf8 => core::str::from_utf8(bytes).map(String::from).map_err(|e| {
+            DekuError::Parse(error_msg!(
+                "encoding: invalid utf-8 at byte {}",
+                e.valid_up_to()
+            ))
+        }),
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            if !bytes.len().is_multiple_of(2) {
+                return Err(DekuError::Parse(error_msg!(
+                    "encoding: {} bytes is not a whole number of utf-16 code units",
+                    bytes.len()
+                )));
+            }
+
+            let units = bytes.chunks(2).map(|unit| {
+                let unit = [unit[0], unit[1]];
+                if encoding == Encoding::Utf16Le {
+                    u16::from_le_bytes(unit)
+                } else {
+                    u16::from_be_bytes(unit)
+                }
+            });
+
+            let mut value = String::new();
+            let mut unit_index = 0;
+            for c in core::char::decode_utf16(units) {
+                let c = c.map_err(|_| {
+                    DekuError::Parse(error_msg!(
+                        "encoding: invalid utf-16 at code unit {}",
+                        unit_index
+                    ))
+                })?;
+                unit_index += c.len_utf16();
+                value.push(c);
+            }
+
+            Ok(value)
+        }
+        Encoding::Ascii => match bytes.iter().position(|b| !b.is_ascii()) {
+            Some(i) => Err(DekuError::Parse(error_msg!(
+                "encoding: invalid ascii at byte {}",
+                i
+            ))),
+            None => Ok(bytes.iter().copied().map(char::from).collect()),
+        },
+        Encoding::Latin1 => Ok(bytes.iter().copied().map(char::from).collect()),
+    }
+}
+
+/// Encode a string in the encoding
+fn encode(value: &str, encoding: Encoding) -> Result<Vec<u8>, DekuError> {
+    match encoding {
+        Encoding::Utf8 => Ok(value.as_bytes().to_vec()),
+        Encoding::Utf16Le => Ok(value.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+        Encoding::Utf16Be => Ok(value.encode_utf16().flat_map(u16::to_be_bytes).collect()),
+        Encoding::Ascii | Encoding::Latin1 => {
+            let max = if encoding == Encoding::Ascii {
+                0x7F
+            } else {
+                0xFF
+            };
+
+            value
+                .chars()
+                .map(|c| match c as u32 {
+                    c if c <= max => Ok(c as u8),
+                    _ => Err(DekuError::InvalidParam(error_msg!(
+                        "encoding: character {:?} can't be encoded in {:?}",
+                        c,
+                        encoding
+                    ))),
+                })
+                .collect()
+        }
+    }
+}
+
+/// Size of a string read
+enum Size {
+    /// Number of code units
+    Count(usize),
+    /// Number of bytes
+    Bytes(usize),
+    /// Until the end of the input
+    All,
+    /// Number of bits, padded with the code unit
+    Fixed(BitSize, Pad),
+}
+
+/// Number of bytes of a fixed-width string of `bit_size` bits in the encoding
+fn fixed_bytes(bit_size: BitSize, encoding: Encoding) -> Result<usize, Cow<'static, str>> {
+    let bit_size: usize = bit_size.into();
+    let unit_bits = encoding.code_unit_bytes() * 8;
+    if !bit_size.is_multiple_of(unit_bits) {
+        return Err(error_msg!(
+            "pad: {} bits is not a whole number of {:?} code units",
+            bit_size,
+            encoding
+        ));
+    }
+
+    Ok(bit_size / 8)
+}
+
+/// Bytes of the code unit `pad` in the encoding
+fn pad_unit(pad: Pad, encoding: Encoding) -> Vec<u8> {
+    match encoding {
+        Encoding::Utf16Le => [*pad, 0].to_vec(),
+        Encoding::Utf16Be => [0, *pad].to_vec(),
+        Encoding::Utf8 | Encoding::Ascii | Encoding::Latin1 => [*pad].to_vec(),
+    }
+}
+
+/// Read a string of the size in the encoding
+fn read_string(
+    input: &BitSlice<Msb0, u8>,
+    size: Size,
+    encoding: Encoding,
+) -> Result<(&BitSlice<Msb0, u8>, String), DekuError> {
+    let (rest, bytes) = match size {
+        Size::Count(count) => <Vec<u8>>::read(input, Count(count * encoding.code_unit_bytes()))?,
+        Size::Bytes(bytes) => <Vec<u8>>::read(input, ByteSize(bytes))?,
+        Size::All => <Vec<u8>>::read(input, ReadAll)?,
+        Size::Fixed(bit_size, pad) => {
+            let byte_size = fixed_bytes(bit_size, encoding).map_err(DekuError::Parse)?;
+            let (rest, mut bytes) = <Vec<u8>>::read(input, ByteSize(byte_size))?;
+
+            // trim the trailing pad code units
+            let unit = pad_unit(pad, encoding);
+            while bytes.ends_with(&unit) {
+                bytes.truncate(bytes.len() - unit.len());
+            }
+
+            (rest, bytes)
+        }
+    };
+
+    Ok((rest, decode(&bytes, encoding)?))
+}
+
+/// Write a string in the encoding, padded to the size of a fixed-width string
+fn write_string(
+    value: &str,
+    size: Option<(BitSize, Pad)>,
+    encoding: Encoding,
+) -> Result<BitVec<Msb0, u8>, DekuError> {
+    let mut bytes = encode(value, encoding)?;
+
+    if let Some((bit_size, pad)) = size {
+        let byte_size = fixed_bytes(bit_size, encoding).map_err(DekuError::InvalidParam)?;
+        if bytes.len() > byte_size {
+            return Err(DekuError::InvalidParam(error_msg!(
+                "pad: string of {} bytes is longer than {} bytes",
+                bytes.len(),
+                byte_size
+            )));
+        }
+
+        let unit = pad_unit(pad, encoding);
+        while bytes.len() < byte_size {
+            bytes.extend_from_slice(&unit);
+        }
+    }
+
+    Ok(BitVec::from_vec(bytes))
+}
+
+// Read and write a `String`, the `endian` of a container has no effect on strings
+macro_rules! ImplDekuStringTraits {
+    ($ctx:ty, $read_pat:pat => $size:expr, $encoding:expr) => {
+        impl<'a> DekuRead<'a, $ctx> for String {
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                $read_pat: $ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+            where
+                Self: Sized,
+            {
+                read_string(input, $size, $encoding)
+            }
+        }
+    };
+    ($ctx:ty, $read_pat:pat => $size:expr, $encoding:expr, write $write_size:expr) => {
+        ImplDekuStringTraits!($ctx, $read_pat => $size, $encoding);
+
+        impl DekuWrite<$ctx> for String {
+            fn write(&self, $read_pat: $ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+                write_string(self, $write_size, $encoding)
+            }
+        }
+    };
+}
+
+ImplDekuStringTraits!((Count, Encoding), (count, encoding) => Size::Count(*count), encoding);
+ImplDekuStringTraits!((Count, (Endian, Encoding)), (count, (_, encoding)) => Size::Count(*count), encoding);
+ImplDekuStringTraits!((ByteSize, Encoding), (bytes, encoding) => Size::Bytes(*bytes), encoding);
+ImplDekuStringTraits!((ByteSize, (Endian, Encoding)), (bytes, (_, encoding)) => Size::Bytes(*bytes), encoding);
+ImplDekuStringTraits!((ReadAll, Encoding), (_, encoding) => Size::All, encoding);
+ImplDekuStringTraits!((ReadAll, (Endian, Encoding)), (_, (_, encoding)) => Size::All, encoding);
+
+// A string of a number of bytes in UTF-8 without `encoding`, as read with `prefix`
+ImplDekuStringTraits!((ByteSize, ()), (bytes, _) => Size::Bytes(*bytes), Encoding::Utf8);
+ImplDekuStringTraits!((ByteSize, Endian), (bytes, _) => Size::Bytes(*bytes), Encoding::Utf8);
+
+// A fixed-width string, padded with nul bytes without `pad`, in UTF-8 without `encoding`
+ImplDekuStringTraits!(BitSize, bit_size => Size::Fixed(bit_size, Pad(0)), Encoding::Utf8, write Some((bit_size, Pad(0))));
+ImplDekuStringTraits!((Endian, BitSize), (_, bit_size) => Size::Fixed(bit_size, Pad(0)), Encoding::Utf8, write Some((bit_size, Pad(0))));
+ImplDekuStringTraits!((BitSize, Pad), (bit_size, pad) => Size::Fixed(bit_size, pad), Encoding::Utf8, write Some((bit_size, pad)));
+ImplDekuStringTraits!((Endian, BitSize, Pad), (_, bit_size, pad) => Size::Fixed(bit_size, pad), Encoding::Utf8, write Some((bit_size, pad)));
+ImplDekuStringTraits!((BitSize, Encoding), (bit_size, encoding) => Size::Fixed(bit_size, Pad(0)), encoding, write Some((bit_size, Pad(0))));
+ImplDekuStringTraits!((Endian, BitSize, Encoding), (_, bit_size, encoding) => Size::Fixed(bit_size, Pad(0)), encoding, write Some((bit_size, Pad(0))));
+ImplDekuStringTraits!((BitSize, Encoding, Pad), (bit_size, encoding, pad) => Size::Fixed(bit_size, pad), encoding, write Some((bit_size, pad)));
+ImplDekuStringTraits!((Endian, BitSize, Encoding, Pad), (_, bit_size, encoding, pad) => Size::Fixed(bit_size, pad), encoding, write Some((bit_size, pad)));
+
+impl DekuWrite for String {
+    fn write(&self, _: ()) -> Result<BitVec<Msb0, u8>, DekuError> {
+        write_string(self, None, Encoding::Utf8)
+    }
+}
+
+impl DekuWrite<Endian> for String {
+    fn write(&self, _: Endian) -> Result<BitVec<Msb0, u8>, DekuError> {
+        write_string(self, None, Encoding::Utf8)
+    }
+}
+
+impl DekuWrite<Encoding> for String {
+    fn write(&self, encoding: Encoding) -> Result<BitVec<Msb0, u8>, DekuError> {
+        write_string(self, None, encoding)
+    }
+}
+
+impl DekuWrite<(Endian, Encoding)> for String {
+    fn write(&self, (_, encoding): (Endian, Encoding)) -> Result<BitVec<Msb0, u8>, DekuError> {
+        write_string(self, None, encoding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+    use rstest::rstest;
+
+    #[rstest(input, encoding, expected,
+        case::utf8("hé".as_bytes(), Encoding::Utf8, Ok("hé")),
+        case::utf16le(&[b'h', 0, 0xE9, 0], Encoding::Utf16Le, Ok("hé")),
+        case::utf16be(&[0, b'h', 0, 0xE9], Encoding::Utf16Be, Ok("hé")),
+        case::surrogate_pair(&[0x3D, 0xD8, 0x00, 0xDE], Encoding::Utf16Le, Ok("\u{1F600}")),
+        case::ascii(b"hi", Encoding::Ascii, Ok("hi")),
+        case::latin1(&[b'h', 0xE9], Encoding::Latin1, Ok("hé")),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_utf8(&[b'h', 0xFF], Encoding::Utf8, Err(DekuError::Parse(error_msg!("encoding: invalid utf-8 at byte 1")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_utf16(&[b'h', 0, 0x00, 0xDE], Encoding::Utf16Le, Err(DekuError::Parse(error_msg!("encoding: invalid utf-16 at code unit 1")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::odd_utf16(&[b'h', 0, b'i'], Encoding::Utf16Le, Err(DekuError::Parse(error_msg!("encoding: 3 bytes is not a whole number of utf-16 code units")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::invalid_ascii(&[b'h', 0xE9], Encoding::Ascii, Err(DekuError::Parse(error_msg!("encoding: invalid ascii at byte 1")))),
+    )]
+    fn test_string_read(input: &[u8], encoding: Encoding, expected: Result<&str, DekuError>) {
+        let res_read = String::read(input.view_bits(), (ReadAll, encoding));
+        match res_read {
+            Ok((rest, value)) => {
+                assert!(rest.is_empty());
+                assert_eq!(expected, Ok(value.as_str()));
+            }
+            Err(e) => assert_eq!(expected, Err(e)),
+        }
+    }
+
+    #[rstest(input, encoding, count, expected_rest,
+        case::utf8(b"hi!".as_ref(), Encoding::Utf8, 2, bits![Msb0, u8; 0, 0, 1, 0, 0, 0, 0, 1]),
+        case::utf16(&[b'h', 0, b'i', 0], Encoding::Utf16Le, 1, bits![Msb0, u8; 0, 1, 1, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    )]
+    fn test_string_read_count(
+        input: &[u8],
+        encoding: Encoding,
+        count: usize,
+        expected_rest: &BitSlice<Msb0, u8>,
+    ) {
+        let (rest, _) = String::read(input.view_bits(), (Count(count), encoding)).unwrap();
+        assert_eq!(expected_rest, rest);
+    }
+
+    #[rstest(input, bit_size, encoding, pad, expected,
+        case::padded(b"ab  ", 32, Encoding::Utf8, Pad(b' '), Ok("ab")),
+        case::full(b"abcd", 32, Encoding::Ascii, Pad(b' '), Ok("abcd")),
+        case::empty(b"\0\0", 16, Encoding::Latin1, Pad(0), Ok("")),
+        // only the trailing code units are padding
+        case::inner_pad(b"a b ", 32, Encoding::Utf8, Pad(b' '), Ok("a b")),
+        case::utf16le(&[b'h', 0, b' ', 0], 32, Encoding::Utf16Le, Pad(b' '), Ok("h")),
+        case::utf16be(&[0, b'h', 0, 0], 32, Encoding::Utf16Be, Pad(0), Ok("h")),
+        #[cfg(not(feature = "static-errors"))]
+        case::partial_unit(&[b'h', 0, b' '], 24, Encoding::Utf16Le, Pad(b' '), Err(DekuError::Parse(error_msg!("pad: 24 bits is not a whole number of Utf16Le code units")))),
+        case::not_enough_data(b"ab", 32, Encoding::Utf8, Pad(b' '), Err(DekuError::Incomplete(crate::error::NeedSize::new(32, 16)))),
+    )]
+    fn test_string_read_fixed(
+        input: &[u8],
+        bit_size: usize,
+        encoding: Encoding,
+        pad: Pad,
+        expected: Result<&str, DekuError>,
+    ) {
+        let res_read = String::read(input.view_bits(), (BitSize(bit_size), encoding, pad));
+        match res_read {
+            Ok((rest, value)) => {
+                assert!(rest.is_empty());
+                assert_eq!(expected, Ok(value.as_str()));
+            }
+            Err(e) => assert_eq!(expected, Err(e)),
+        }
+    }
+
+    #[rstest(input, bit_size, encoding, pad, expected,
+        case::padded("ab", 32, Encoding::Utf8, Pad(b' '), Ok(b"ab  ".to_vec())),
+        case::full("abcd", 32, Encoding::Utf8, Pad(b' '), Ok(b"abcd".to_vec())),
+        case::utf16be("h", 32, Encoding::Utf16Be, Pad(b' '), Ok(vec![0, b'h', 0, b' '])),
+        #[cfg(not(feature = "static-errors"))]
+        case::too_long("abcde", 32, Encoding::Utf8, Pad(b' '), Err(DekuError::InvalidParam(error_msg!("pad: string of 5 bytes is longer than 4 bytes")))),
+    )]
+    fn test_string_write_fixed(
+        input: &str,
+        bit_size: usize,
+        encoding: Encoding,
+        pad: Pad,
+        expected: Result<Vec<u8>, DekuError>,
+    ) {
+        let res_write = input
+            .to_string()
+            .write((BitSize(bit_size), encoding, pad))
+            .map(|bits| bits.into_vec());
+        assert_eq!(expected, res_write);
+    }
+
+    #[rstest(input, encoding, expected,
+        case::utf8("hé", Encoding::Utf8, Ok("hé".as_bytes().to_vec())),
+        case::utf16le("h\u{1F600}", Encoding::Utf16Le, Ok(vec![b'h', 0, 0x3D, 0xD8, 0x00, 0xDE])),
+        case::utf16be("hé", Encoding::Utf16Be, Ok(vec![0, b'h', 0, 0xE9])),
+        case::ascii("hi", Encoding::Ascii, Ok(b"hi".to_vec())),
+        case::latin1("hé", Encoding::Latin1, Ok(vec![b'h', 0xE9])),
+        #[cfg(not(feature = "static-errors"))]
+        case::not_ascii("hé", Encoding::Ascii, Err(DekuError::InvalidParam(error_msg!("encoding: character 'é' can't be encoded in Ascii")))),
+        #[cfg(not(feature = "static-errors"))]
+        case::not_latin1("h€", Encoding::Latin1, Err(DekuError::InvalidParam(error_msg!("encoding: character '€' can't be encoded in Latin1")))),
+    )]
+    fn test_string_write(input: &str, encoding: Encoding, expected: Result<Vec<u8>, DekuError>) {
+        let res_write = input
+            .to_string()
+            .write(encoding)
+            .map(|bits| bits.into_vec());
+        assert_eq!(expected, res_write);
+    }
+}
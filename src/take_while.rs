@@ -0,0 +1,254 @@
+//! A `TakeWhile` container reading elements while a predicate holds
+//!
+//! Some formats have no count or terminator for a list of records: the list ends at the first
+//! record which belongs to the next section. `TakeWhile<T, F>` reads `T`s while the
+//! [`Predicate`] `F` holds for the element read. The first element for which it doesn't hold is
+//! put back: its bits aren't consumed, and are read by the next field. The list also ends at the
+//! end of the input. An element which consumes no bits, and for which the predicate holds, is an
+//! error.
+//!
+//! When writing, all the elements are written.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! # use deku::take_while::{Predicate, TakeWhile};
+//! # use std::convert::{TryInto, TryFrom};
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct Record {
+//!     kind: u8,
+//!     value: u8,
+//! }
+//!
+//! struct IsOption;
+//!
+//! impl Predicate<Record> for IsOption {
+//!     fn test(record: &Record) -> bool {
+//!         record.kind == 0x01
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! struct DekuTest {
+//!     options: TakeWhile<Record, IsOption>,
+//!     // the first record which isn't an option starts the data
+//!     data: Record,
+//! }
+//!
+//! let data: Vec<u8> = vec![0x01, 0xAA, 0x01, 0xBB, 0x02, 0xCC];
+//!
+//! let value = DekuTest::try_from(data.as_ref()).unwrap();
+//!
+//! assert_eq!(2, value.options.len());
+//! assert_eq!(Record { kind: 0x02, value: 0xCC }, value.data);
+//!
+//! let value: Vec<u8> = value.try_into().unwrap();
+//! assert_eq!(data, value);
+//! ```
+
+use crate::{DekuError, DekuRead, DekuWrite};
+use alloc::vec::Vec;
+use bitvec::prelude::*;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+/// Predicate over an element read by a [`TakeWhile`], implemented on a marker type
+pub trait Predicate<T> {
+    /// Does the element belong to the container
+    fn test(value: &T) -> bool;
+}
+
+/// Elements read while a predicate holds, see the [module documentation](index.html)
+pub struct TakeWhile<T, F> {
+    items: Vec<T>,
+    phantom: PhantomData<F>,
+}
+
+impl<T, F> TakeWhile<T, F> {
+    /// Create from the elements
+    pub fn new(items: Vec<T>) -> Self {
+        TakeWhile {
+            items,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the elements
+    pub fn into_inner(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T, F> From<Vec<T>> for TakeWhile<T, F> {
+    fn from(items: Vec<T>) -> Self {
+        TakeWhile::new(items)
+    }
+}
+
+impl<T, F> Deref for TakeWhile<T, F> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<T, F> DerefMut for TakeWhile<T, F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.items
+    }
+}
+
+// Implemented by hand, derives would require `F` to implement the traits
+impl<T: fmt::Debug, F> fmt::Debug for TakeWhile<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TakeWhile").field(&self.items).finish()
+    }
+}
+
+impl<T: Clone, F> Clone for TakeWhile<T, F> {
+    fn clone(&self) -> Self {
+        TakeWhile::new(self.items.clone())
+    }
+}
+
+impl<T: PartialEq, F> PartialEq for TakeWhile<T, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T, F> Default for TakeWhile<T, F> {
+    fn default() -> Self {
+        TakeWhile::new(Vec::new())
+    }
+}
+
+impl<'a, T, F, Ctx> DekuRead<'a, Ctx> for TakeWhile<T, F>
+where
+    T: DekuRead<'a, Ctx>,
+    F: Predicate<T>,
+    Ctx: Copy,
+{
+    /// Read `T`s until the predicate doesn't hold or the end of the input, the element for which
+    /// the predicate doesn't hold isn't consumed
+    /// * `inner_ctx` - The context required by `T`
+    fn read(
+        input: &'a BitSlice<Msb0, u8>,
+        inner_ctx: Ctx,
+    ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+    where
+        Self: Sized,
+    {
+        let mut items = Vec::new();
+        let mut rest = input;
+
+        while !rest.is_empty() {
+            #[cfg(feature = "limit")]
+            crate::limit::record_element()?;
+
+            let (new_rest, value) = T::read(rest, inner_ctx)?;
+            if !F::test(&value) {
+                // put the element back, `rest` is still before it
+                break;
+            }
+            // an element which consumes no bits would be read forever
+            if new_rest.len() == rest.len() {
+                return Err(DekuError::Parse(error_msg!(
+                    "element consumed no bits: can't read elements while the predicate holds"
+                )));
+            }
+
+            items.push(value);
+            rest = new_rest;
+        }
+
+        Ok((rest, TakeWhile::new(items)))
+    }
+}
+
+impl<T, F, Ctx> DekuWrite<Ctx> for TakeWhile<T, F>
+where
+    T: DekuWrite<Ctx>,
+    Ctx: Copy,
+{
+    /// Write all the elements
+    /// * `inner_ctx` - The context required by `T`
+    fn write(&self, inner_ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+        self.items.write(inner_ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    struct NonZero;
+
+    impl Predicate<u16> for NonZero {
+        fn test(value: &u16) -> bool {
+            *value != 0
+        }
+    }
+
+    #[rstest(input, expected, expected_rest,
+        case::stop([0xAA, 0xBB, 0x00, 0x00, 0xCC].as_ref(), vec![0xAABB], [0x00, 0x00, 0xCC].as_ref()),
+        case::end([0xAA, 0xBB, 0xCC, 0xDD].as_ref(), vec![0xAABB, 0xCCDD], [].as_ref()),
+        case::first([0x00, 0x00].as_ref(), vec![], [0x00, 0x00].as_ref()),
+        case::empty([].as_ref(), vec![], [].as_ref()),
+        #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+        case::incomplete([0xAA, 0xBB, 0xCC].as_ref(), vec![], [].as_ref()),
+    )]
+    fn test_take_while_read(input: &[u8], expected: Vec<u16>, expected_rest: &[u8]) {
+        let bit_slice = input.view_bits::<Msb0>();
+
+        let (rest, res_read) = TakeWhile::<u16, NonZero>::read(bit_slice, Endian::Big).unwrap();
+        assert_eq!(expected, res_read.into_inner());
+        assert_eq!(expected_rest.view_bits::<Msb0>(), rest);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Empty;
+
+    impl<'a> DekuRead<'a, Endian> for Empty {
+        fn read(
+            input: &'a BitSlice<Msb0, u8>,
+            _: Endian,
+        ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError> {
+            Ok((input, Empty))
+        }
+    }
+
+    struct Any;
+
+    impl Predicate<Empty> for Any {
+        fn test(_: &Empty) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_take_while_read_zero_size() {
+        let bit_slice = [0xAA].view_bits::<Msb0>();
+
+        let res_read = TakeWhile::<Empty, Any>::read(bit_slice, Endian::Big);
+        assert_eq!(
+            Err(DekuError::Parse(error_msg!(
+                "element consumed no bits: can't read elements while the predicate holds"
+            ))),
+            res_read.map(|(_, value)| value.into_inner())
+        );
+    }
+
+    #[test]
+    fn test_take_while_write() {
+        let value = TakeWhile::<u16, NonZero>::from(vec![0xAABB, 0xCCDD]);
+        let res_write = value.write(Endian::Big).unwrap().into_vec();
+        assert_eq!(vec![0xAA, 0xBB, 0xCC, 0xDD], res_write);
+    }
+}
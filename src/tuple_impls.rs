@@ -0,0 +1,109 @@
+//! Implementations of DekuRead and DekuWrite for tuples of up to 12 elements
+//!
+//! The ctx, i.e. the `endian`, is passed to every element of the tuple.
+
+use super::{DekuRead, DekuSize, DekuWrite};
+use crate::error::DekuError;
+use bitvec::prelude::*;
+
+macro_rules! ImplDekuTupleTraits {
+    ($($T:ident),+) => {
+        impl<'a, Ctx: Copy, $($T),+> DekuRead<'a, Ctx> for ($($T,)+)
+        where
+            $($T: DekuRead<'a, Ctx>,)+
+        {
+            #[allow(non_snake_case)]
+            fn read(
+                input: &'a BitSlice<Msb0, u8>,
+                ctx: Ctx,
+            ) -> Result<(&'a BitSlice<Msb0, u8>, Self), DekuError>
+            where
+                Self: Sized,
+            {
+                let rest = input;
+                $(
+                    let (rest, $T) = <$T>::read(rest, ctx)?;
+                )+
+
+                Ok((rest, ($($T,)+)))
+            }
+        }
+
+        impl<$($T: DekuSize),+> DekuSize for ($($T,)+) {
+            const BIT_SIZE: usize = 0 $(+ <$T as DekuSize>::BIT_SIZE)+;
+        }
+
+        impl<Ctx: Copy, $($T),+> DekuWrite<Ctx> for ($($T,)+)
+        where
+            $($T: DekuWrite<Ctx>,)+
+        {
+            #[allow(non_snake_case)]
+            fn write(&self, ctx: Ctx) -> Result<BitVec<Msb0, u8>, DekuError> {
+                let ($(ref $T,)+) = *self;
+
+                let mut acc = BitVec::new();
+                $(
+                    acc.extend($T.write(ctx)?);
+                )+
+
+                Ok(acc)
+            }
+        }
+    };
+}
+
+ImplDekuTupleTraits!(A);
+ImplDekuTupleTraits!(A, B);
+ImplDekuTupleTraits!(A, B, C);
+ImplDekuTupleTraits!(A, B, C, D);
+ImplDekuTupleTraits!(A, B, C, D, E);
+ImplDekuTupleTraits!(A, B, C, D, E, F);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G, H);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G, H, I);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G, H, I, J);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G, H, I, J, K);
+ImplDekuTupleTraits!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::ctx::Endian;
+    use rstest::rstest;
+
+    #[rstest(input, endian, expected,
+        case::single([0xAA, 0xBB].as_ref(), Endian::Big, (0xAABBu16,)),
+        case::big([0xAA, 0xBB, 0x01, 0x02, 0x03, 0x04].as_ref(), Endian::Big, (0xAABBu16, 0x01020304u32)),
+        case::little([0xAA, 0xBB, 0x01, 0x02, 0x03, 0x04].as_ref(), Endian::Little, (0xBBAAu16, 0x04030201u32)),
+    )]
+    fn test_tuple_endian<T>(input: &[u8], endian: Endian, expected: T)
+    where
+        T: for<'a> DekuRead<'a, Endian> + DekuWrite<Endian> + PartialEq + core::fmt::Debug,
+    {
+        let (rest, res_read) = T::read(input.view_bits(), endian).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(expected, res_read);
+
+        let res_write = res_read.write(endian).unwrap();
+        assert_eq!(input.to_vec(), res_write.into_vec());
+    }
+
+    #[test]
+    fn test_tuple_nested() {
+        let input = [0x01, 0xAA, 0xBB, 0x02, 0x03];
+
+        let (rest, res_read) =
+            <(u8, (u16, [u8; 2]))>::read(input.view_bits(), Endian::Little).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!((0x01, (0xBBAA, [0x02, 0x03])), res_read);
+
+        let res_write = res_read.write(Endian::Little).unwrap();
+        assert_eq!(input.to_vec(), res_write.into_vec());
+    }
+
+    #[test]
+    fn test_tuple_size() {
+        assert_eq!(56, <(u8, (u16, [u32; 1]))>::BIT_SIZE);
+    }
+}
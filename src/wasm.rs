@@ -0,0 +1,59 @@
+//! A facade for [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/) exports, reading and
+//! writing a container with the error as a `String`
+//!
+//! wasm-bindgen functions can't return a `DekuError`, but can return a `Result` with an error
+//! convertible to a `JsValue`, thrown as an exception in JavaScript. The functions of this module
+//! don't depend on wasm-bindgen, and build for `wasm32-unknown-unknown` with or without `std`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use deku::prelude::*;
+//! // #[wasm_bindgen]
+//! #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+//! pub struct DekuTest {
+//!     #[deku(bits = "4")]
+//!     pub field_a: u8,
+//!     #[deku(bits = "4")]
+//!     pub field_b: u8,
+//! }
+//!
+//! // #[wasm_bindgen]
+//! pub fn parse(input: &[u8]) -> Result<DekuTest, String> {
+//!     deku::wasm::from_bytes(input)
+//! }
+//!
+//! // #[wasm_bindgen]
+//! pub fn serialize(value: &DekuTest) -> Result<Vec<u8>, String> {
+//!     deku::wasm::to_bytes(value)
+//! }
+//!
+//! let value = parse(&[0xAB]).unwrap();
+//! assert_eq!(DekuTest { field_a: 0xA, field_b: 0xB }, value);
+//! assert_eq!(vec![0xAB], serialize(&value).unwrap());
+//!
+//! assert_eq!(
+//!     Err("Parse error: Too much data".to_string()),
+//!     parse(&[0xAB, 0xCD])
+//! );
+//! ```
+
+use crate::{DekuContainerWrite, DekuError};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::convert::TryFrom;
+
+/// Read the value from all the bytes of `input`, with the error as a string
+pub fn from_bytes<'a, T>(input: &'a [u8]) -> Result<T, String>
+where
+    T: TryFrom<&'a [u8], Error = DekuError>,
+{
+    T::try_from(input).map_err(|e| e.to_string())
+}
+
+/// Write the value to bytes, with the error as a string
+pub fn to_bytes<T: DekuContainerWrite>(value: &T) -> Result<Vec<u8>, String> {
+    value.to_bytes().map_err(|e| e.to_string())
+}
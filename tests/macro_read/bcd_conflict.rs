@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(bcd = "little", with = "deku::codecs::varint")]
+    a: u64,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(bcd, prefix = "u8")]
+    a: u32,
+}
+
+fn main() {}
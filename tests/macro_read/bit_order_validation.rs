@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+#[deku(bit_order = "order")]
+struct Test1 {
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use deku::prelude::*;
+
+// test `bits` on a `Vec` is an error, `bits_read` bounds the bits of the elements
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(bits = 4)]
+    a: Vec<u8>,
+}
+
+fn main() {}
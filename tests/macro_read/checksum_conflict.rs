@@ -0,0 +1,33 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(checksum = "deku::checksum::sum8", skip)]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(checksum = "deku::checksum::sum8", offset = "1")]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test3 {
+    #[deku(over = "1..")]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test4 {
+    #[deku(checksum = "deku::checksum::sum8", verify = "|_, _| Ok(())")]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test5 {
+    #[deku(verify = "|_, _| Ok(())", skip)]
+    a: u8,
+}
+
+fn main() {}
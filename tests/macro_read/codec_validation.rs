@@ -0,0 +1,27 @@
+use deku::prelude::*;
+
+// both `bits` and `bytes`
+#[deku_reader(type = "u8", bits = 4, bytes = 1)]
+fn test1(value: u8) -> Result<u8, DekuError> {
+    Ok(value)
+}
+
+// missing `type`
+#[deku_reader(endian = "big")]
+fn test2(value: u16) -> Result<u16, DekuError> {
+    Ok(value)
+}
+
+// not returning a `Result`
+#[deku_reader(type = "u8")]
+fn test3(value: u8) -> u8 {
+    value
+}
+
+// no value argument
+#[deku_writer(type = "u8")]
+fn test4() -> Result<u8, DekuError> {
+    Ok(0)
+}
+
+fn main() {}
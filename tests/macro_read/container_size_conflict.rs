@@ -0,0 +1,24 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    a: u8,
+    #[deku(count = "a", bytes_read = "a")]
+    b: Vec<u8>,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    a: u8,
+    #[deku(bytes_read = "a", bits_read = "a")]
+    b: Vec<u8>,
+}
+
+#[derive(DekuRead)]
+struct Test3 {
+    a: u8,
+    #[deku(bits_read = "a", read_all)]
+    b: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use deku::prelude::*;
+
+// test generics not supported
+#[derive(DekuPython)]
+struct Test1<T> {
+    a: T,
+}
+
+// test `ctx` requires `ctx_default`
+#[derive(DekuPython)]
+#[deku(ctx = "a: u8")]
+struct Test2 {
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use deku::prelude::*;
+
+// test field size must be known at compile time
+#[derive(DekuSize)]
+struct Test1 {
+    a: u8,
+    #[deku(count = "a")]
+    b: Vec<u8>,
+}
+
+// test `bits` must be an integer
+#[derive(DekuSize)]
+struct Test2 {
+    #[deku(bits = "1 + 1")]
+    a: u8,
+}
+
+// test generics not supported
+#[derive(DekuSize)]
+struct Test3<T> {
+    a: T,
+}
+
+// test variant fields size must be known at compile time
+#[derive(DekuSize)]
+#[deku(type = "u8")]
+enum Test4 {
+    #[deku(id = "1")]
+    A(#[deku(cond = "true")] Option<u8>),
+}
+
+// test variant `reader` not supported
+#[derive(DekuSize)]
+#[deku(type = "u8")]
+enum Test5 {
+    #[deku(id = "1", reader = "todo!()")]
+    A(u8),
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+use deku::prelude::*;
+
+// test `DekuView` only allowed on struct
+#[derive(DekuView)]
+#[deku(type = "u8")]
+enum Test1 {}
+
+// test `DekuView` field attributes
+#[derive(DekuView)]
+struct Test2 {
+    a: u8,
+    #[deku(count = "a")]
+    b: Vec<u8>,
+}
+
+// test `DekuView` with `view`
+#[derive(DekuView)]
+#[deku(view)]
+#[repr(C)]
+struct Test3 {
+    a: u8,
+}
+
+// test `DekuView` with a `bits` expression
+#[derive(DekuView)]
+struct Test4 {
+    #[deku(bits = 4)]
+    a: u8,
+    #[deku(bits = "a")]
+    b: u8,
+}
+
+// test `DekuView` with `magic`, the accessors would ignore it
+#[derive(DekuView)]
+#[deku(magic = b"MZ")]
+struct Test5 {
+    a: u16,
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+#[deku(explicit_endian)]
+struct Valid {
+    a: u8,
+    #[deku(endian = "big")]
+    b: u16,
+    #[deku(endian = "little", count = "2")]
+    c: Vec<u32>,
+    #[deku(skip)]
+    d: u64,
+}
+
+// test a multi-byte field without an endian is an error
+#[derive(DekuRead)]
+#[deku(explicit_endian)]
+struct Test1 {
+    a: u8,
+    b: u16,
+}
+
+// test a container of multi-byte elements without an endian is an error
+#[derive(DekuRead)]
+#[deku(explicit_endian)]
+struct Test2 {
+    #[deku(count = "2")]
+    a: Vec<f32>,
+}
+
+// test an enum type without an endian is an error
+#[derive(DekuRead)]
+#[deku(explicit_endian, type = "u16")]
+enum Test3 {
+    #[deku(id = "1")]
+    A,
+}
+
+fn main() {}
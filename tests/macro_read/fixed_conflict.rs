@@ -0,0 +1,21 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(fixed = "Q8.8", bytes = 2)]
+    a: f32,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(fixed = "Q0.16")]
+    a: f32,
+}
+
+#[derive(DekuRead)]
+struct Test3 {
+    #[deku(fixed = "UQ32.33")]
+    a: f64,
+}
+
+fn main() {}
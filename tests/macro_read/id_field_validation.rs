@@ -0,0 +1,32 @@
+use deku::prelude::*;
+
+// test `id_field` only allowed on enum variants
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(id_field)]
+    a: u8,
+}
+
+// test `id_field` not allowed on a variant with `id`
+#[derive(DekuRead)]
+#[deku(type = "u8")]
+enum Test2 {
+    #[deku(id = "1")]
+    A(#[deku(id_field)] u8),
+}
+
+// test `id_field` only allowed once
+#[derive(DekuRead)]
+#[deku(type = "u8")]
+enum Test3 {
+    A(#[deku(id_field)] u8, #[deku(id_field)] u8),
+}
+
+// test conflict `id_field` and `skip`
+#[derive(DekuRead)]
+#[deku(type = "u8")]
+enum Test4 {
+    A(#[deku(id_field, skip)] u8),
+}
+
+fn main() {}
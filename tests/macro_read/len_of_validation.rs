@@ -0,0 +1,27 @@
+use deku::prelude::*;
+
+// test `len_of` with `update`
+#[derive(DekuRead, DekuWrite)]
+struct Test1 {
+    #[deku(len_of = "b", update = "0")]
+    a: u8,
+    b: u8,
+}
+
+// test `len_offset` without `len_of`
+#[derive(DekuRead, DekuWrite)]
+struct Test2 {
+    #[deku(len_offset = "2")]
+    a: u8,
+    b: u8,
+}
+
+// test `len_of` unknown field
+#[derive(DekuRead, DekuWrite)]
+struct Test3 {
+    #[deku(len_of = "c")]
+    a: u8,
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(limit_bytes = "1", peek)]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(limit_bytes = "1", offset = "1")]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuWrite)]
+struct Test1 {
+    #[deku(map_write = "|a: &u8| Ok(*a)", normalize = "|a: &u8| *a")]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Inner<'a> {
+    #[deku(count = "1")]
+    data: &'a [u8],
+}
+
+// test a borrow nested in an `Option` has no owned equivalent
+#[derive(DekuRead)]
+#[deku(owned = "Test1Owned")]
+struct Test1<'a> {
+    a: u8,
+    #[deku(cond = "*a == 1", count = "1")]
+    b: Option<&'a [u8]>,
+}
+
+// test a struct borrowing from the input has no owned equivalent
+#[derive(DekuRead)]
+#[deku(owned = "Test2Owned")]
+struct Test2<'a> {
+    inner: Inner<'a>,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(pad_bits_before = "4", pad_bytes_before = "1")]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(pad_bits_after = "4", pad_bytes_after = "1")]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test3 {
+    #[deku(align_bits = "4", align_bytes = "1")]
+    a: u8,
+}
+
+fn main() {}
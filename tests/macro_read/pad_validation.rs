@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(pad = "b' '")]
+    a: String,
+}
+
+fn main() {}
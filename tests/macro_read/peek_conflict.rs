@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(peek, skip)]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    a: u8,
+    #[deku(prefix = "u8", count = "a")]
+    b: Vec<u8>,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(prefix = "u8", bytes = 2)]
+    a: String,
+}
+
+fn main() {}
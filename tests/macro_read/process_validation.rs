@@ -0,0 +1,21 @@
+use deku::prelude::*;
+
+fn xor(bytes: &[u8]) -> Result<Vec<u8>, DekuError> {
+    Ok(bytes.iter().map(|b| b ^ 0xFF).collect())
+}
+
+// `process_write` without `process`
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(process_write = "xor")]
+    a: u8,
+}
+
+// `process` without `process_write` can't be written
+#[derive(DekuWrite)]
+struct Test2 {
+    #[deku(process = "xor")]
+    a: u8,
+}
+
+fn main() {}
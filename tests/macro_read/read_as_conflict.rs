@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(read_as = "u8", map = "|a: u8| -> Result<_, DekuError> { Ok(a) }")]
+    a: u8,
+}
+
+fn main() {}
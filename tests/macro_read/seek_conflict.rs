@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(seek_from_current = "1", seek_rewind)]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(offset = "1", pad_bytes_before = "1")]
+    a: u8,
+}
+
+fn main() {}
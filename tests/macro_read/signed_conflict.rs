@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(signed = "ones_complement")]
+    a: i8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(signed = "magnitude", with = "deku::codecs::varint")]
+    a: i64,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(skip, skip_read)]
+    a: u8,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(skip_write, default = "1")]
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(strict_bool, with = "deku::codecs::strict_bool")]
+    a: bool,
+}
+
+fn main() {}
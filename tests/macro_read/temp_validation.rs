@@ -0,0 +1,34 @@
+use deku::prelude::*;
+
+// `temp` requires `deku_derive`
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(temp)]
+    a: u8,
+}
+
+// `temp_value` without `temp`
+#[deku_derive(DekuRead)]
+struct Test2 {
+    #[deku(temp_value = "1")]
+    a: u8,
+}
+
+// `temp` on an unnamed field
+#[deku_derive(DekuRead)]
+struct Test3(#[deku(temp)] u8);
+
+// `temp` without `temp_value` can't be written
+#[deku_derive(DekuRead, DekuWrite)]
+struct Test4 {
+    #[deku(temp)]
+    a: u8,
+}
+
+// unsupported derive
+#[deku_derive(DekuView)]
+struct Test5 {
+    a: u8,
+}
+
+fn main() {}
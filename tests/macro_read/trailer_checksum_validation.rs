@@ -0,0 +1,13 @@
+use deku::prelude::*;
+
+// test `trailer_checksum` only allowed on struct
+#[derive(DekuRead)]
+#[deku(type = "u8", trailer_checksum = "deku::checksum::crc16_ccitt")]
+enum Test1 {}
+
+// test `trailer_checksum` requires a field
+#[derive(DekuRead)]
+#[deku(trailer_checksum = "deku::checksum::crc16_ccitt")]
+struct Test2 {}
+
+fn main() {}
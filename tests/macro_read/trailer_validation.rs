@@ -0,0 +1,21 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+#[deku(trailer = "u8", trailer_checksum = "deku::checksum::sum8")]
+struct Test1 {
+    a: u8,
+    b: u8,
+}
+
+#[derive(DekuRead)]
+#[deku(trailer = "u8")]
+struct Test2 {}
+
+#[derive(DekuRead)]
+#[deku(type = "u8", trailer = "u8")]
+enum Test3 {
+    #[deku(id = "1")]
+    A,
+}
+
+fn main() {}
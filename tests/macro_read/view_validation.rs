@@ -0,0 +1,50 @@
+use deku::prelude::*;
+
+// test `view` requires `#[repr(C)]`
+#[derive(DekuRead)]
+#[deku(view)]
+struct Test1 {
+    a: u32,
+}
+
+// test `view` only allowed on struct
+#[derive(DekuRead)]
+#[deku(type = "u8", view)]
+enum Test2 {}
+
+// test `view` field attributes
+#[derive(DekuRead)]
+#[deku(view)]
+#[repr(C)]
+struct Test3 {
+    #[deku(bits = 4)]
+    a: u8,
+}
+
+// test `view` wire layout must match the native layout
+#[derive(DekuRead)]
+#[deku(view)]
+#[repr(C)]
+struct Test4 {
+    a: u8,
+    b: u32,
+}
+
+// test `view` with `magic`, the accessors would ignore it
+#[derive(DekuRead)]
+#[deku(view, magic = b"MZ")]
+#[repr(C)]
+struct Test5 {
+    a: u16,
+}
+
+// test `view` with `trailer_checksum`
+#[derive(DekuRead)]
+#[deku(view, trailer_checksum = "deku::checksum::sum8")]
+#[repr(C)]
+struct Test6 {
+    a: u8,
+    b: u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use deku::prelude::*;
+
+#[derive(DekuRead)]
+struct Test1 {
+    #[deku(with = "deku::codecs::varint", reader = "u64::read(rest, ())")]
+    a: u64,
+}
+
+#[derive(DekuRead)]
+struct Test2 {
+    #[deku(with = "deku::codecs::varint", count = "1")]
+    a: Vec<u64>,
+}
+
+fn main() {}
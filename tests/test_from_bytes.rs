@@ -12,17 +12,17 @@ fn test_from_bytes_struct() {
     assert_eq!(2, rest.len());
     assert_eq!(4, i);
 
-    let ((rest, i), ret_read) = TestDeku::from_bytes((&rest, i)).unwrap();
+    let ((rest, i), ret_read) = TestDeku::from_bytes((rest, i)).unwrap();
     assert_eq!(TestDeku(0b0110), ret_read);
     assert_eq!(1, rest.len());
     assert_eq!(0, i);
 
-    let ((rest, i), ret_read) = TestDeku::from_bytes((&rest, i)).unwrap();
+    let ((rest, i), ret_read) = TestDeku::from_bytes((rest, i)).unwrap();
     assert_eq!(TestDeku(0b0101), ret_read);
     assert_eq!(1, rest.len());
     assert_eq!(4, i);
 
-    let ((rest, i), ret_read) = TestDeku::from_bytes((&rest, i)).unwrap();
+    let ((rest, i), ret_read) = TestDeku::from_bytes((rest, i)).unwrap();
     assert_eq!(TestDeku(0b1010), ret_read);
     assert_eq!(0, rest.len());
     assert_eq!(0, i);
@@ -46,9 +46,68 @@ fn test_from_bytes_enum() {
     assert_eq!(1, rest.len());
     assert_eq!(0, i);
 
-    let ((rest, i), ret_read) = TestDeku::from_bytes((&rest, i)).unwrap();
+    let ((rest, i), ret_read) = TestDeku::from_bytes((rest, i)).unwrap();
     assert_eq!(TestDeku::VariantB(0b10), ret_read);
     assert_eq!(1, rest.len());
     assert_eq!(6, i);
     assert_eq!(0b0101_1010u8, rest[0]);
 }
+
+#[test]
+fn test_from_bytes_advance() {
+    use std::convert::TryFrom;
+
+    #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    #[deku(type = "u8")]
+    enum TestDeku {
+        #[deku(id = "0x01")]
+        VariantA(u8),
+        #[deku(id = "0x02")]
+        VariantB(#[deku(bits = "4")] u8),
+    }
+
+    let test_data: Vec<u8> = [0x01, 0xAA, 0x01, 0xBB, 0x02, 0xC0].to_vec();
+    let mut input = test_data.as_ref();
+
+    let ret_read = TestDeku::from_bytes_advance(&mut input).unwrap();
+    assert_eq!(TestDeku::VariantA(0xAA), ret_read);
+    assert_eq!(&test_data[2..], input);
+
+    let ret_read = TestDeku::try_from(&mut input).unwrap();
+    assert_eq!(TestDeku::VariantA(0xBB), ret_read);
+    assert_eq!(&test_data[4..], input);
+
+    // not byte aligned, the input isn't advanced
+    #[cfg(not(feature = "static-errors"))]
+    assert_eq!(
+        Err(DekuError::Parse(
+            "not byte aligned: read ends 4 bits into a byte".into()
+        )),
+        TestDeku::from_bytes_advance(&mut input)
+    );
+    assert_eq!(&test_data[4..], input);
+}
+
+#[test]
+fn test_to_slice() {
+    #[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    struct TestDeku(u16, #[deku(bits = 4)] u8);
+
+    let mut output = [0xFFu8; 4];
+    let written = TestDeku(0xAABB, 0x0C).to_slice(&mut output).unwrap();
+    assert_eq!(3, written);
+    assert_eq!([0xAA, 0xBB, 0xC0, 0xFF], output);
+
+    #[cfg(not(feature = "static-errors"))]
+    {
+        let mut output = [0xFFu8; 2];
+        assert_eq!(
+            Err(DekuError::InvalidParam(
+                "output too small: expected 3 bytes got 2 bytes".into()
+            )),
+            TestDeku(0xAABB, 0x0C).to_slice(&mut output)
+        );
+        assert_eq!([0xFF, 0xFF], output);
+    }
+}
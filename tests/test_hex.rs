@@ -0,0 +1,23 @@
+//! Run with `cargo test --features hex --test test_hex`
+#![cfg(feature = "hex")]
+
+use deku::prelude::*;
+use rstest::rstest;
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "little")]
+struct TestDeku(u16);
+
+#[rstest(input, expected,
+    case::exact("01 02", Ok(TestDeku(0x0201))),
+    case::too_much("01 02 03", Err(DekuError::Parse("Too much data".into()))),
+    case::not_enough("01", Err(DekuError::Incomplete(NeedSize::new(16, 8)))),
+    #[cfg(not(feature = "static-errors"))]
+    case::invalid("01 0x", Err(DekuError::Parse("invalid hex digit: 'x'".into()))),
+)]
+fn test_from_hex_str(input: &str, expected: Result<TestDeku, DekuError>) {
+    assert_eq!(
+        expected,
+        TestDeku::from_hex_str(input).map_err(DekuError::into_inner)
+    );
+}
@@ -1,3 +1,4 @@
+use bitvec::bitvec;
 use deku::prelude::*;
 use hexlit::hex;
 use rstest::rstest;
@@ -80,6 +81,23 @@ pub mod samples {
         VarDefault(u8, u8),
     }
 
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8", bits = "4")]
+    pub enum IdFieldEnumDeku {
+        #[deku(id = "1")]
+        VarA(#[deku(bits = "4")] u8),
+        #[deku(id_pat = "2..=3")]
+        VarB {
+            #[deku(id_field)]
+            id: u8,
+            #[deku(bits = "4")]
+            value: u8,
+            #[deku(cond = "*id == 3")]
+            ext: Option<u8>,
+        },
+        VarUnknown(#[deku(id_field)] u8, #[deku(bits = "4")] u8),
+    }
+
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
     pub struct VecCountDeku {
         #[deku(update = "self.vec_data.len()")]
@@ -88,6 +106,181 @@ pub mod samples {
         pub vec_data: Vec<u8>,
     }
 
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "extra: usize", ctx_default = "1")]
+    pub struct CountExprDeku {
+        pub len: u8,
+        #[deku(count = "*len as usize * 2")]
+        pub pairs: Vec<u8>,
+        #[deku(count = "pairs.len() / 2 + extra")]
+        pub tail: Vec<u8>,
+        pub delta: i8,
+        #[deku(count = "*delta + 1")]
+        pub rest: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "wide: bool")]
+    pub struct BitsExprDeku {
+        #[deku(bits = 4)]
+        pub width: u8,
+        #[deku(endian = "big", bits = "*width")]
+        pub value: u16,
+        #[deku(bits = "if wide { 4 } else { 8 }")]
+        pub flags: u8,
+        #[deku(bytes = "*width / 4")]
+        pub tail: u32,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct VarIntDeku {
+        #[deku(bits = 4)]
+        pub unsigned_len: u8,
+        #[deku(bits = 4)]
+        pub signed_len: u8,
+        #[deku(endian = "big", bytes = "*unsigned_len")]
+        pub unsigned: u64,
+        #[deku(endian = "big", bytes = "*signed_len")]
+        pub signed: i64,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "wide: bool")]
+    pub struct NegotiatedValue {
+        #[deku(endian = "big", bits = "if wide { 16 } else { 8 }")]
+        pub value: u16,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct NegotiatedDeku {
+        #[deku(bits = 1)]
+        pub wide: u8,
+        #[deku(bits = 7)]
+        pub count: u8,
+        #[deku(count = "count", ctx = "*wide != 0")]
+        pub values: Vec<NegotiatedValue>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct BytesExprDeku {
+        pub wide: u8,
+        #[deku(endian = "big", bytes = "if *wide != 0 { 4 } else { 2 }")]
+        pub value: u32,
+    }
+
+    /// Value stored as a big-endian `u16` shifted by `shift`
+    #[deku_reader(type = "u16", endian = "big")]
+    pub fn shifted_read(value: u16, shift: i32) -> Result<i32, DekuError> {
+        Ok(i32::from(value) - shift)
+    }
+
+    #[deku_writer(type = "u16", endian = "big")]
+    pub fn shifted_write(value: &i32, shift: i32) -> Result<u16, DekuError> {
+        u16::try_from(*value + shift)
+            .map_err(|_| DekuError::InvalidParam(format!("{} is out of range", value).into()))
+    }
+
+    /// Flag stored as the high bit of a nibble
+    #[deku_reader(type = "u8", bits = 4)]
+    pub fn nibble_flag_read(value: u8) -> Result<bool, DekuError> {
+        Ok(value & 0b1000 != 0)
+    }
+
+    #[deku_writer(type = "u8", bits = 4)]
+    pub fn nibble_flag_write(value: &bool) -> Result<u8, DekuError> {
+        Ok(if *value { 0b1000 } else { 0 })
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct CodecDeku {
+        pub shift: u8,
+        #[deku(
+            reader = "shifted_read(rest, i32::from(*shift))",
+            writer = "shifted_write(temperature, i32::from(*shift))"
+        )]
+        pub temperature: i32,
+        #[deku(
+            reader = "nibble_flag_read(rest)",
+            writer = "nibble_flag_write(flag_a)"
+        )]
+        pub flag_a: bool,
+        #[deku(
+            reader = "nibble_flag_read(rest)",
+            writer = "nibble_flag_write(flag_b)"
+        )]
+        pub flag_b: bool,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct MapWriteDeku {
+        #[deku(
+            map = "|field: u8| -> Result<_, DekuError> { Ok(field.to_string()) }",
+            map_write = "|field: &String| field.parse::<u8>().map_err(|e| DekuError::InvalidParam(e.to_string().into()))"
+        )]
+        pub field_a: String,
+        #[deku(
+            endian = "big",
+            map = "|field: u16| -> Result<_, DekuError> { Ok(f32::from(field) / 10.0) }",
+            map_write = "MapWriteDeku::tenths"
+        )]
+        pub field_b: f32,
+    }
+
+    impl MapWriteDeku {
+        fn tenths(field: &f32) -> Result<u16, DekuError> {
+            Ok((field * 10.0).round() as u16)
+        }
+    }
+
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    pub enum Level {
+        Low,
+        High,
+    }
+
+    impl TryFrom<u8> for Level {
+        type Error = ();
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Level::Low),
+                1 => Ok(Level::High),
+                _ => Err(()),
+            }
+        }
+    }
+
+    impl From<Level> for u8 {
+        fn from(level: Level) -> Self {
+            level as u8
+        }
+    }
+
+    #[derive(PartialEq, Debug, Clone, Copy)]
+    pub struct Millis(pub u32);
+
+    impl From<u16> for Millis {
+        fn from(value: u16) -> Self {
+            Millis(value.into())
+        }
+    }
+
+    impl TryFrom<Millis> for u16 {
+        type Error = core::num::TryFromIntError;
+
+        fn try_from(value: Millis) -> Result<Self, Self::Error> {
+            u16::try_from(value.0)
+        }
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ReadAsDeku {
+        #[deku(read_as = "u8")]
+        pub level: Level,
+        #[deku(endian = "big", read_as = "u16")]
+        pub duration: Millis,
+    }
+
     #[derive(PartialEq, Debug, DekuRead)]
     pub struct MapDeku {
         #[deku(map = "|field: u8| -> Result<_, DekuError> { Ok(field.to_string()) }")]
@@ -103,18 +296,18 @@ pub mod samples {
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    pub struct GenericStructDeku<T: deku::DekuWrite + deku::DekuRead>
+    pub struct GenericStructDeku<T: deku::DekuWrite + for<'a> deku::DekuRead<'a>>
     where
-        T: deku::DekuWrite + deku::DekuRead,
+        T: deku::DekuWrite + for<'a> deku::DekuRead<'a>,
     {
         pub field_a: T,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
     #[deku(type = "u8")]
-    pub enum GenericEnumDeku<T: deku::DekuRead + deku::DekuWrite>
+    pub enum GenericEnumDeku<T: for<'a> deku::DekuRead<'a> + deku::DekuWrite>
     where
-        T: deku::DekuWrite + deku::DekuRead,
+        T: deku::DekuWrite + for<'a> deku::DekuRead<'a>,
     {
         #[deku(id = "1")]
         VariantT(T),
@@ -128,6 +321,127 @@ pub mod samples {
         pub field_c: u8,
     }
 
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct SkipDirectionDeku {
+        pub field_a: u8,
+        #[deku(skip_read, default = "0xFF")]
+        pub field_b: u8,
+        #[deku(skip_write)]
+        pub field_c: u8,
+        #[deku(skip_read, cond = "*field_a == 0x01")]
+        pub field_d: Option<u8>,
+    }
+
+    #[derive(PartialEq, Debug, Clone, DekuRead, DekuWrite)]
+    #[deku(ctx = "unit: u8", ctx_default = "1", auto_update)]
+    pub struct UpdateCtxDeku {
+        #[deku(update = "self.data.len() / usize::from(unit)")]
+        pub count: u8,
+        #[deku(count = "usize::from(*count) * usize::from(unit)")]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite, DekuSize)]
+    #[deku(endian = "big", magic = b"S")]
+    pub struct SizeDeku {
+        pub field_a: u8,
+        #[deku(bits = "4")]
+        pub field_b: u8,
+        #[deku(bits = "4")]
+        pub field_c: u8,
+        pub field_d: u32,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite, DekuSize)]
+    #[deku(type = "u8", bits = "4")]
+    pub enum SizeEnumDeku {
+        #[deku(id = "1")]
+        VarA(#[deku(bits = "4")] u8),
+        #[deku(id = "2")]
+        VarB {
+            #[deku(bits = "4")]
+            field_a: u8,
+            field_b: SizeDeku,
+        },
+        #[deku(id = "3")]
+        VarC,
+        #[deku(id_pat = "4..=5")]
+        VarD(#[deku(bits = "4")] u8, #[deku(endian = "big")] u16),
+        VarUnknown(#[deku(id_field)] u8, #[deku(bits = "4")] u8),
+    }
+
+    #[derive(PartialEq, Debug, Clone, DekuRead, DekuWrite)]
+    #[deku(auto_update)]
+    pub struct AutoUpdateDeku {
+        #[deku(update = "self.data.len()")]
+        pub count: u8,
+        #[deku(count = "count")]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, Clone, DekuRead, DekuWrite)]
+    #[deku(type = "u8", auto_update)]
+    pub enum AutoUpdateEnumDeku {
+        #[deku(id = "1")]
+        VarA(
+            #[deku(update = "field_1.len()")] u8,
+            #[deku(count = "field_0")] Vec<u8>,
+        ),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct PeekDeku {
+        #[deku(peek, bits = "4")]
+        pub version: u8,
+        #[deku(endian = "big", cond = "*version == 2")]
+        pub header: Option<u16>,
+        pub data: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct PeekEnumDeku {
+        #[deku(peek)]
+        pub tag: u8,
+        #[deku(ctx = "*tag")]
+        pub payload: PeekPayloadDeku,
+    }
+
+    // the tag read by `PeekEnumDeku` is also part of the payload
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "tag: u8", id = "tag")]
+    pub enum PeekPayloadDeku {
+        #[deku(id = "0x01")]
+        Short(u8),
+        #[deku(id_pat = "_")]
+        Long(u8, u8),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct DefaultIfEofDeku {
+        pub field_a: u8,
+        #[deku(default_if_eof, default = "0xFF")]
+        pub field_b: u8,
+        #[deku(endian = "big", default_if_eof)]
+        pub field_c: u16,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(default_if_eof)]
+    pub struct DefaultIfEofTopDeku {
+        pub version: u8,
+        #[deku(default = "0x10")]
+        pub flags: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct WriteCondDeku {
+        pub flags: u8,
+        #[deku(write_cond = "*flags & 0x01 != 0")]
+        pub ext_a: u8,
+        #[deku(cond = "*flags & 0x02 != 0", write_cond = "*flags & 0x02 != 0")]
+        pub ext_b: Option<u8>,
+    }
+
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
     pub struct DefaultDeku {
         pub field_a: u8,
@@ -151,374 +465,3305 @@ pub mod samples {
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(ctx = "_a: u8, _b: u8")]
-    pub struct TopLevelCtxStruct {}
+    pub struct NormalizeDeku {
+        #[deku(normalize = "|v: &u8| *v.min(&0x0F)")]
+        pub field_a: u8,
+        pub count: u8,
+        #[deku(count = "count", normalize = "sorted")]
+        pub field_b: Vec<u8>,
+    }
+
+    fn sorted(v: &[u8]) -> Vec<u8> {
+        let mut v = v.to_vec();
+        v.sort_unstable();
+        v
+    }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(ctx = "a: u8, b: u8")]
-    pub struct SubTypeNeedCtx {
-        #[deku(
-            reader = "(|rest|{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c) as usize))})(rest)",
-            writer = "(|c|{u8::write(&(c-a-b), ())})(self.i as u8)"
-        )]
-        pub(crate) i: usize,
+    pub struct AssertDeku {
+        #[deku(assert = "*version <= 3")]
+        pub version: u8,
+        #[deku(assert = "*field_a != *version")]
+        pub field_a: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(ctx = "a: u8, b: u8", ctx_default = "1, 2")]
-    pub struct TopLevelCtxStructDefault {
-        #[deku(cond = "a == 1")]
-        pub a: Option<u8>,
-        #[deku(cond = "b == 1")]
-        pub b: Option<u8>,
+    #[deku(assert = "self.header_len as usize == self.options.len() + 2")]
+    pub struct ContainerAssertDeku {
+        pub header_len: u8,
+        pub count: u8,
+        #[deku(count = "count")]
+        pub options: Vec<u8>,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    pub struct FieldLevelCtxStruct {
-        pub a: u8,
-        pub b: u8,
-        #[deku(ctx = "a + 1, *b")]
-        pub c: SubTypeNeedCtx,
+    pub struct AssertEqDeku {
+        #[deku(endian = "big", assert_eq = "0x1234")]
+        pub field_a: u16,
+        pub field_b: u8,
+        #[deku(assert_eq = "*field_b")]
+        pub field_c: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(type = "u8", ctx = "a: u8, b: u8")]
-    pub enum TopLevelCtxEnum {
-        #[deku(id = "1")]
-        VariantA(
-            #[deku(
-                reader = "(|rest|{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c)))})(rest)",
-                writer = "(|c|{u8::write(&(c-a-b), ())})(field_0)"
-            )]
-            u8,
-        ),
+    #[deku(endian = "big")]
+    pub struct EndianPropagationDeku {
+        pub count: u8,
+        #[deku(count = "count")]
+        pub vec: Vec<u16>,
+        pub array: [u32; 2],
+        pub tuple: (u16, (u8, u32)),
+        #[deku(endian = "little")]
+        pub tuple_le: (u16, [u16; 1]),
+        #[deku(endian = "little", bits = "12", count = "1")]
+        pub vec_bits_le: Vec<u16>,
+        #[deku(bits = "4")]
+        pub padding: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(type = "u8", ctx = "a: u8, b: u8", ctx_default = "1,2")]
-    pub enum TopLevelCtxEnumDefault {
-        #[deku(id = "1")]
-        VariantA(
-            #[deku(
-                reader = "(|rest|{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c)))})(rest)",
-                writer = "(|c|{u8::write(&(c-a-b), ())})(field_0)"
-            )]
-            u8,
-        ),
+    pub struct PadBeforeDeku {
+        #[deku(bits = "4")]
+        pub field_a: u8,
+        #[deku(pad_bits_before = "4")]
+        pub field_b: u8,
+        pub len: u8,
+        #[deku(pad_bytes_before = "*len")]
+        pub field_c: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(type = "u8")]
-    pub enum VariantLevelCtxEnum {
-        #[deku(id = "1")]
-        VariantA {
-            a: u8,
-            b: u8,
-            #[deku(ctx = "*a, *b")]
-            c: SubTypeNeedCtx,
-        },
+    pub struct PadOverflowDeku {
+        pub len: u64,
+        #[deku(pad_bytes_before = "*len")]
+        pub field_a: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    pub struct StructEnumId {
-        pub my_id: u8,
-        pub data: u8,
-        #[deku(ctx = "*my_id")]
-        pub enum_from_id: EnumId,
+    pub struct BytesOverflowDeku {
+        pub len: u64,
+        #[deku(bytes = "*len")]
+        pub field_a: String,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(ctx = "my_id: u8", id = "my_id")]
-    pub enum EnumId {
-        #[deku(id = "1")]
-        VarA(u8),
-        #[deku(id = "2")]
-        VarB,
+    pub struct PadAfterDeku {
+        pub len: u8,
+        // pad to a 4-byte boundary
+        #[deku(count = "len", pad_bytes_after = "(4 - (1 + *len as usize) % 4) % 4")]
+        pub data: Vec<u8>,
+        #[deku(bits = "4", pad_bits_after = "4")]
+        pub field_b: u8,
     }
 
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    pub struct EnumTypeEndian {
+    pub struct CrossByteDeku {
+        #[deku(bits = 4)]
+        pub field_a: u8,
         #[deku(endian = "big")]
-        pub t: EnumTypeEndianCtx,
+        pub field_b: u16,
+        #[deku(bits = 12, endian = "big")]
+        pub field_c: u16,
+        #[deku(bits = 12, endian = "little")]
+        pub field_d: u16,
+        #[deku(bits = 4)]
+        pub field_e: u8,
     }
 
+    /// IEEE 802.11 frame control, duration and sequence control, packed least significant bit
+    /// first
     #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
-    #[deku(type = "u32", endian = "endian", ctx = "endian: deku::ctx::Endian")]
-    pub enum EnumTypeEndianCtx {
-        #[deku(id = "0xDEADBEEF")]
-        VarA(u8),
+    #[deku(bit_order = "lsb", endian = "little")]
+    pub struct Ieee80211Deku {
+        #[deku(bits = 2)]
+        pub version: u8,
+        #[deku(bits = 2)]
+        pub frame_type: u8,
+        #[deku(bits = 4)]
+        pub subtype: u8,
+        #[deku(bits = 1)]
+        pub to_ds: u8,
+        #[deku(bits = 1)]
+        pub from_ds: u8,
+        #[deku(bits = 6)]
+        pub flags: u8,
+        pub duration: u16,
+        #[deku(bits = 4)]
+        pub fragment: u8,
+        #[deku(bits = 12)]
+        pub sequence: u16,
     }
-}
 
-#[test]
-#[should_panic(expected = r#"Parse("Too much data")"#)]
-fn test_too_much_data() {
-    let test_data = [0u8; 100].as_ref();
-    samples::UnNamedDeku::try_from(test_data).unwrap();
-}
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(bit_order = "lsb")]
+    pub struct LsbDeku {
+        #[deku(bits = "4")]
+        pub field_a: u8,
+        #[deku(bits = "4")]
+        pub field_b: u8,
+        #[deku(bits = "12", endian = "little")]
+        pub field_c: u16,
+        #[deku(bits = "4")]
+        pub field_d: u8,
+        #[deku(endian = "big")]
+        pub field_e: u16,
+        #[deku(bits = "3", bit_order = "msb")]
+        pub field_f: u8,
+    }
 
-#[test]
-fn test_unnamed_struct() {
-    let test_data: Vec<u8> = [
-        0xFF,
-        0b1001_0110,
-        0xAA,
-        0xBB,
-        0xCC,
-        0xDD,
-        0b1001_0110,
-        0xCC,
-        0xDD,
-        0x02,
-        0xBE,
-        0xEF,
-    ]
-    .to_vec();
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LsbFieldDeku {
+        #[deku(bits = "4", bit_order = "lsb")]
+        pub field_a: u8,
+        #[deku(bits = "4")]
+        pub field_b: u8,
+    }
 
-    // Read
-    let ret_read = samples::UnNamedDeku::try_from(test_data.as_ref()).unwrap();
-    assert_eq!(
-        samples::UnNamedDeku(
-            0xFF,
-            0b0000_0010,
-            0b0001_0110,
-            0xBBAA,
-            0xCCDD,
-            samples::NestedDeku {
-                nest_a: 0b00_100101,
-                nest_b: 0b10,
-                inner: samples::DoubleNestedDeku { data: 0xDDCC }
-            },
-            0x02,
-            vec![0xBE, 0xEF],
-        ),
-        ret_read
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "bit_order: deku::ctx::Order")]
+    pub struct LsbNestedDeku {
+        #[deku(bits = "4", bit_order = "bit_order")]
+        pub field_a: u8,
+        #[deku(bits = "4", bit_order = "bit_order")]
+        pub field_b: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(bit_order = "lsb")]
+    pub struct LsbParentDeku {
+        pub nested: LsbNestedDeku,
+        pub field_c: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LsbListDeku {
+        #[deku(read_all)]
+        pub items: Vec<LsbParentDeku>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(bit_order = "lsb")]
+    pub struct LsbReadAllDeku {
+        #[deku(read_all)]
+        pub data: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LsbUnalignedDeku {
+        #[deku(bits = "4")]
+        pub field_a: u8,
+        pub parent: LsbParentDeku,
+        #[deku(bits = "4")]
+        pub field_b: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8", bits = "2", bit_order = "lsb")]
+    pub enum LsbEnumDeku {
+        #[deku(id = "1")]
+        VarA(#[deku(bits = "6")] u8),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct OffsetDeku {
+        pub data_offset: u8,
+        pub len: u8,
+        #[deku(offset = "*data_offset", count = "len")]
+        pub data: Vec<u8>,
+        pub field_c: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct OffsetOverflowDeku {
+        pub data_offset: u64,
+        #[deku(offset = "*data_offset")]
+        pub data: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LimitDeku {
+        pub len: u8,
+        #[deku(limit_bytes = "*len", read_all)]
+        pub items: Vec<u8>,
+        #[deku(limit_bytes = "3")]
+        pub field_a: u8,
+        pub field_b: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LimitLengthOverflowDeku {
+        pub len: u64,
+        #[deku(limit_bytes = "*len", read_all)]
+        pub items: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct LimitOverflowDeku {
+        #[deku(endian = "big", limit_bytes = "1")]
+        pub field_a: u16,
+    }
+
+    pub fn xor(bytes: &[u8]) -> Result<Vec<u8>, DekuError> {
+        Ok(bytes.iter().map(|b| b ^ 0xFF).collect())
+    }
+
+    /// Run-length pairs of `(count, byte)`
+    pub fn rle_decode(bytes: &[u8]) -> Result<Vec<u8>, DekuError> {
+        if !bytes.len().is_multiple_of(2) {
+            return Err(DekuError::Parse("rle: odd number of bytes".into()));
+        }
+        Ok(bytes
+            .chunks(2)
+            .flat_map(|pair| core::iter::repeat_n(pair[1], pair[0].into()))
+            .collect())
+    }
+
+    pub fn rle_encode(bytes: &[u8]) -> Result<Vec<u8>, DekuError> {
+        let mut encoded: Vec<u8> = vec![];
+        for &b in bytes {
+            match encoded.len() {
+                len if len >= 2 && encoded[len - 1] == b && encoded[len - 2] < 0xFF => {
+                    encoded[len - 2] += 1
+                }
+                _ => encoded.extend_from_slice(&[1, b]),
+            }
+        }
+        Ok(encoded)
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ProcessDeku {
+        pub len: u8,
+        #[deku(limit_bytes = "*len", process = "xor", process_write = "xor")]
+        pub field_a: u8,
+        pub field_b: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ProcessAllDeku {
+        pub field_a: u8,
+        #[deku(process = "rle_decode", process_write = "rle_encode", read_all)]
+        pub items: Vec<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct EncodingDeku {
+        pub len: u8,
+        #[deku(encoding = "utf16be", count = "len")]
+        pub field_a: String,
+        #[deku(encoding = "ascii", bytes_read = "2")]
+        pub field_b: String,
+        #[deku(encoding = "utf8", read_all)]
+        pub field_c: String,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct PadDeku {
+        #[deku(bytes = "4", pad = "b' '")]
+        pub field_a: String,
+        #[deku(bytes = "2")]
+        pub field_b: String,
+        #[deku(bytes = "4", encoding = "utf16le", pad = "b' '")]
+        pub field_c: String,
+        pub field_d: u16,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct PrefixDeku {
+        #[deku(prefix = "u8")]
+        pub field_a: String,
+        #[deku(prefix = "u16")]
+        pub field_b: Vec<u8>,
+        #[deku(prefix = "u8", encoding = "utf16le")]
+        pub field_c: String,
+        #[deku(prefix = "u8")]
+        pub field_d: Vec<u16>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct SeekDeku {
+        pub gap: u8,
+        #[deku(seek_from_current = "*gap")]
+        pub field_a: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct SeekOverflowDeku {
+        pub gap: i64,
+        #[deku(seek_from_current = "*gap")]
+        pub field_a: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct AlignDeku {
+        pub len: u8,
+        #[deku(count = "len")]
+        pub data: Vec<u8>,
+        #[deku(align_bytes = "4")]
+        pub field_a: u8,
+        #[deku(bits = "3")]
+        pub field_b: u8,
+        #[deku(align_bits = "4", bits = "4")]
+        pub field_c: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct AlignCheckDeku {
+        #[deku(bits = 4, assert = "!deku::is_byte_aligned()")]
+        pub len: u8,
+        #[deku(bits = "*len", assert = "deku::is_byte_aligned()")]
+        pub value: u8,
+        #[deku(bits = 3)]
+        pub flags: u8,
+        #[deku(bits = 5, align_after)]
+        pub stop: u8,
+        #[deku(align_before)]
+        pub tail: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct BcdDeku {
+        #[deku(bcd, bytes = "3")]
+        pub field_a: u32,
+        #[deku(bcd = "little", bytes = "2")]
+        pub field_b: u16,
+        #[deku(bcd)]
+        pub field_c: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct FixedDeku {
+        #[deku(fixed = "Q8.8", endian = "big")]
+        pub field_a: f32,
+        #[deku(fixed = "UQ0.8")]
+        pub field_b: f64,
+        #[deku(fixed = "Q2.2")]
+        pub field_c: f32,
+        #[deku(bits = 4)]
+        pub field_d: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct RawBitsDeku {
+        #[deku(bits = 4)]
+        pub len: u8,
+        #[deku(bits = "*len as usize")]
+        pub data: BitVec<Msb0, u8>,
+        #[deku(bits = 4)]
+        pub tail: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct StrictBoolDeku {
+        #[deku(bits = 1)]
+        pub field_a: bool,
+        #[deku(bits = 7, strict_bool)]
+        pub field_b: bool,
+        pub field_c: bool,
+        #[deku(strict_bool, endian = "big")]
+        pub field_d: bool,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct IpAddrDeku {
+        pub v4: std::net::Ipv4Addr,
+        pub len: u8,
+        #[deku(bytes = "*len as usize")]
+        pub addr: std::net::IpAddr,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite, DekuSize)]
+    #[deku(endian = "big")]
+    pub struct TrailerFooterDeku {
+        pub crc: u16,
+        #[deku(bits = 4)]
+        pub flags: u8,
+        #[deku(bits = 4)]
+        pub version: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(trailer = "TrailerFooterDeku")]
+    pub struct TrailerDeku {
+        pub kind: u8,
+        #[deku(read_all)]
+        pub payload: Vec<u8>,
+        pub footer: TrailerFooterDeku,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct SignedDeku {
+        #[deku(bits = 5, signed = "twos_complement")]
+        pub field_a: i8,
+        #[deku(bits = 5, signed = "magnitude")]
+        pub field_b: i8,
+        #[deku(bits = 14, signed = "magnitude", endian = "big")]
+        pub field_c: i32,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct WideDeku {
+        #[deku(bits = 3)]
+        pub field_a: u8,
+        #[deku(bits = 96)]
+        pub field_b: u128,
+        #[deku(bits = 72, endian = "little")]
+        pub field_c: i128,
+        #[deku(bytes = 9)]
+        pub field_d: u128,
+        #[deku(bits = 5)]
+        pub field_e: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "format: deku::ctx::FixedPoint")]
+    pub struct FixedCtxDeku {
+        #[deku(fixed = "format", endian = "little")]
+        pub field_a: f64,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8")]
+    pub enum SeekEnumDeku {
+        #[deku(id = "0x01")]
+        VarA(#[deku(seek_from_current = "-1")] u8, u8),
+        #[deku(id = "0x02")]
+        VarB(#[deku(seek_rewind)] u8),
+        #[deku(id = "0x03")]
+        VarC(#[deku(seek_from_current = "-2")] u8),
+    }
+
+    #[deku_derive(DekuRead, DekuWrite)]
+    #[derive(PartialEq, Debug)]
+    pub struct TempDeku {
+        #[deku(temp, temp_value = "items.len() as u8")]
+        pub len: u8,
+        #[deku(count = "len")]
+        pub items: Vec<u8>,
+    }
+
+    #[deku_derive(DekuRead, DekuWrite)]
+    #[derive(PartialEq, Debug)]
+    pub struct TempConstDeku {
+        #[deku(endian = "big", temp, temp_value = "0u16")]
+        pub reserved: u16,
+        #[deku(bits = "1", temp, temp_value = "value.is_some() as u8")]
+        pub has_value: u8,
+        #[deku(bits = "7", temp, temp_value = "0x2C")]
+        pub separator: u8,
+        #[deku(cond = "*has_value == 1")]
+        pub value: Option<u8>,
+    }
+
+    #[deku_derive(DekuRead, DekuWrite)]
+    #[derive(PartialEq, Debug)]
+    #[deku(type = "u8")]
+    pub enum TempEnumDeku {
+        #[deku(id = "0x01")]
+        VarA {
+            #[deku(bits = "4", pad_bits_after = "4", temp, temp_value = "*flag as u8")]
+            flag_bits: u8,
+            #[deku(skip, default = "*flag_bits == 1")]
+            flag: bool,
+        },
+        #[deku(id = "0x02")]
+        VarB(u8),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(magic = b"\x7FELF")]
+    pub struct MagicDeku {
+        pub field_a: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(magic = b"DK", type = "u8")]
+    pub enum MagicEnumDeku {
+        #[deku(id = "0x01")]
+        VarA(u8),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ReadAllDeku {
+        pub header: u8,
+        #[deku(endian = "big", read_all)]
+        pub items: Vec<u16>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ZeroSizeDeku {}
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ReadAllZeroSizeDeku {
+        #[deku(read_all)]
+        pub items: Vec<ZeroSizeDeku>,
+    }
+
+    /// The length of the payload, `len` includes the 3 bytes of the header
+    pub fn payload_len(len: u16) -> Result<u16, DekuError> {
+        len.checked_sub(3).ok_or_else(|| {
+            DekuError::Parse(format!("len: {} is shorter than the header", len).into())
+        })
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct LenOfDeku {
+        // the length includes the header: `len` and `tag`
+        #[deku(len_of = "payload", len_offset = "3")]
+        pub len: u16,
+        pub tag: u8,
+        #[deku(bytes_read = "payload_len(*len)?")]
+        pub payload: Vec<u16>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8")]
+    pub enum LenOfEnumDeku {
+        #[deku(id = "0x01")]
+        VarA {
+            // the length excludes the 2 bytes of the trailer
+            #[deku(len_of = "data", len_offset = "-2")]
+            len: u8,
+            #[deku(bytes_read = "usize::from(*len) + 2")]
+            data: Vec<u8>,
+        },
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct BitsReadDeku {
+        #[deku(bits = 4)]
+        pub len: u8,
+        #[deku(bits = 2, bits_read = "len")]
+        pub items: Vec<u8>,
+        #[deku(bits = 4)]
+        pub after: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(trailer_checksum = "deku::checksum::crc16_ccitt", endian = "big")]
+    pub struct TrailerChecksumDeku {
+        pub field_a: u8,
+        #[deku(count = "field_a")]
+        pub field_b: Vec<u8>,
+        pub crc: u16,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(trailer_checksum = "deku::checksum::crc16_ccitt")]
+    pub struct UnalignedChecksumDeku {
+        #[deku(bits = 4)]
+        pub field_a: u8,
+        pub crc: u16,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(magic = b"DK", endian = "big", wire_doc)]
+    pub struct WireDocDeku {
+        /// Version of the format
+        pub version: u8,
+        /// Flags | reserved
+        #[deku(bits = "4", endian = "little")]
+        pub flags: u8,
+        #[deku(bits = "4")]
+        pub len: u8,
+        /// Payload
+        #[deku(count = "len", cond = "*flags != 0")]
+        pub data: Option<Vec<u16>>,
+        #[deku(skip)]
+        pub cached: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8", wire_doc)]
+    pub enum WireDocEnumDeku {
+        #[deku(id = "0x01")]
+        VarA(u16),
+        #[deku(id_pat = "_")]
+        VarB {
+            #[deku(id_field)]
+            id: u8,
+            /// Value
+            value: u32,
+        },
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct HeaderChecksumDeku {
+        pub len: u8,
+        #[deku(checksum = "deku::checksum::sum8", over = "2..")]
+        pub sum: u8,
+        #[deku(count = "len")]
+        pub data: Vec<u8>,
+    }
+
+    /// Keyed sum of the bytes, standing for a MAC
+    pub fn signature_sign(key: u8, data: &[u8]) -> Result<u8, DekuError> {
+        if key == 0 {
+            return Err(DekuError::InvalidParam("signature: no key".into()));
+        }
+        Ok(data.iter().fold(key, |acc, b| acc.wrapping_add(*b)))
+    }
+
+    pub fn signature_verify(key: u8, data: &[u8], signature: &u8) -> Result<(), DekuError> {
+        if signature_sign(key, data)? != *signature {
+            return Err(DekuError::Parse("signature mismatch".into()));
+        }
+        Ok(())
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "key: u8", ctx_default = "0x10")]
+    pub struct SignatureDeku {
+        pub len: u8,
+        #[deku(count = "len")]
+        pub data: Vec<u8>,
+        #[deku(
+            verify = "|data, signature| signature_verify(key, data, signature)",
+            sign = "|data| signature_sign(key, data)",
+            over = "..1 + *len as usize"
+        )]
+        pub signature: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(endian = "big")]
+    pub struct FieldChecksumDeku {
+        pub field_a: u8,
+        #[deku(checksum = "deku::checksum::crc16_ccitt")]
+        pub crc: u16,
+        pub field_b: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ChecksumRangeDeku {
+        #[deku(checksum = "deku::checksum::crc8", over = "0..4")]
+        pub crc: u8,
+        pub field_a: u8,
+    }
+
+    #[derive(Default, PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct BytesReadDeku {
+        #[deku(update = "self.items.len() * 2")]
+        pub len: u8,
+        #[deku(endian = "big", bytes_read = "len")]
+        pub items: Vec<u16>,
+        pub after: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct NestedContainerDeku {
+        pub count: u8,
+        #[deku(cond = "*count != 0", count = "count")]
+        pub opt_vec: Option<Vec<u8>>,
+        #[deku(endian = "big", cond = "*count != 0", count = "count")]
+        pub opt_vec_endian: Option<Vec<u16>>,
+        #[deku(count = "count")]
+        pub vec_opt: Vec<Option<u8>>,
+        #[deku(endian = "big")]
+        pub opt_array: Option<[u16; 2]>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct FlaggedEntriesDeku {
+        #[deku(bits = "1", update = "self.entries.is_some() as u8")]
+        pub has_entries: u8,
+        #[deku(bits = "7", update = "self.entries.as_ref().map_or(0, |e| e.len())")]
+        pub count: u8,
+        #[deku(endian = "big", cond = "*has_entries == 1", count = "count")]
+        pub entries: Option<Vec<u16>>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "_a: u8, _b: u8")]
+    pub struct TopLevelCtxStruct {}
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "key: Vec<u8>")]
+    pub struct NonCopyCtxStruct {
+        #[deku(
+            map = "|v: u8| -> Result<_, DekuError> { Ok(v ^ key[0]) }",
+            writer = "(self.a ^ key[0]).write(())"
+        )]
+        pub a: u8,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct OptionCtxDeku {
+        #[deku(endian = "big", bits = "12")]
+        pub opt_bits: Option<u16>,
+        #[deku(bits = "4")]
+        pub key: u8,
+        #[deku(endian = "little")]
+        pub opt_endian: Option<u32>,
+        #[deku(ctx = "*key, 1")]
+        pub opt_ctx: Option<SubTypeNeedCtx>,
+        #[deku(ctx = "vec![*key]")]
+        pub opt_non_copy_ctx: Option<NonCopyCtxStruct>,
+        #[deku(endian = "big", cond = "*key != 0", bits = "4", count = "2")]
+        pub opt_vec_bits: Option<Vec<u16>>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "a: u8, b: u8")]
+    pub struct SubTypeNeedCtx {
+        #[deku(
+            reader = "{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c) as usize))}",
+            writer = "{let c = self.i as u8; u8::write(&(c-a-b), ())}"
+        )]
+        pub(crate) i: usize,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "a: u8, b: u8", ctx_default = "1, 2")]
+    pub struct TopLevelCtxStructDefault {
+        #[deku(cond = "a == 1")]
+        pub a: Option<u8>,
+        #[deku(cond = "b == 1")]
+        pub b: Option<u8>,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct FieldLevelCtxStruct {
+        pub a: u8,
+        pub b: u8,
+        #[deku(ctx = "a + 1, *b")]
+        pub c: SubTypeNeedCtx,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8", ctx = "a: u8, b: u8")]
+    pub enum TopLevelCtxEnum {
+        #[deku(id = "1")]
+        VariantA(
+            #[deku(
+                reader = "{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c)))}",
+                writer = "{let c = field_0; u8::write(&(c-a-b), ())}"
+            )]
+            u8,
+        ),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8", ctx = "a: u8, b: u8", ctx_default = "1,2")]
+    pub enum TopLevelCtxEnumDefault {
+        #[deku(id = "1")]
+        VariantA(
+            #[deku(
+                reader = "{u8::read(rest,()).map(|(slice,c)|(slice,(a+b+c)))}",
+                writer = "{let c = field_0; u8::write(&(c-a-b), ())}"
+            )]
+            u8,
+        ),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u8")]
+    pub enum VariantLevelCtxEnum {
+        #[deku(id = "1")]
+        VariantA {
+            a: u8,
+            b: u8,
+            #[deku(ctx = "*a, *b")]
+            c: SubTypeNeedCtx,
+        },
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct StructEnumId {
+        pub my_id: u8,
+        pub data: u8,
+        #[deku(ctx = "*my_id")]
+        pub enum_from_id: EnumId,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(ctx = "my_id: u8", id = "my_id")]
+    pub enum EnumId {
+        #[deku(id = "1")]
+        VarA(u8),
+        #[deku(id = "2")]
+        VarB,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct EnumTypeEndian {
+        #[deku(endian = "big")]
+        pub t: EnumTypeEndianCtx,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(type = "u32", endian = "endian", ctx = "endian: deku::ctx::Endian")]
+    pub enum EnumTypeEndianCtx {
+        #[deku(id = "0xDEADBEEF")]
+        VarA(u8),
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(view, endian = "big")]
+    #[repr(C)]
+    pub struct ViewDeku {
+        pub field_a: u32,
+        #[deku(endian = "little")]
+        pub field_b: u16,
+        pub field_c: [u8; 2],
+    }
+
+    #[derive(DekuView)]
+    #[deku(endian = "big")]
+    pub struct DekuViewDeku {
+        #[deku(bits = 4)]
+        pub field_a: u8,
+        #[deku(bits = 4)]
+        pub field_b: u8,
+        #[deku(bytes = 2)]
+        pub field_c: u32,
+        #[deku(endian = "little")]
+        pub field_d: u16,
+        pub field_e: [u8; 2],
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(owned = "BorrowedDekuOwned", owned_derive = "PartialEq, Debug")]
+    pub struct BorrowedDeku<'a> {
+        pub count: u8,
+        #[deku(count = "count")]
+        pub data: &'a [u8],
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(
+        endian = "big",
+        owned = "BorrowedStrDekuOwned",
+        owned_derive = "PartialEq, Debug"
+    )]
+    pub struct BorrowedStrDeku<'a> {
+        pub len: u16,
+        #[deku(count = "len")]
+        pub name: &'a str,
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    pub struct ReadAllBorrowedDeku<'a> {
+        pub header: u8,
+        #[deku(read_all)]
+        pub payload: &'a [u8],
+    }
+
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    #[deku(owned = "OwnedDekuOwned", owned_derive = "PartialEq, Debug")]
+    pub struct OwnedDeku {
+        pub count: u8,
+        #[deku(count = "count")]
+        pub data: Vec<u8>,
+    }
+}
+
+#[test]
+#[should_panic(expected = r#"Parse("Too much data")"#)]
+fn test_too_much_data() {
+    let test_data = [0u8; 100].as_ref();
+    samples::UnNamedDeku::try_from(test_data).unwrap();
+}
+
+#[test]
+fn test_unnamed_struct() {
+    let test_data: Vec<u8> = [
+        0xFF,
+        0b1001_0110,
+        0xAA,
+        0xBB,
+        0xCC,
+        0xDD,
+        0b1001_0110,
+        0xCC,
+        0xDD,
+        0x02,
+        0xBE,
+        0xEF,
+    ]
+    .to_vec();
+
+    // Read
+    let ret_read = samples::UnNamedDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::UnNamedDeku(
+            0xFF,
+            0b0000_0010,
+            0b0001_0110,
+            0xBBAA,
+            0xCCDD,
+            samples::NestedDeku {
+                nest_a: 0b00_100101,
+                nest_b: 0b10,
+                inner: samples::DoubleNestedDeku { data: 0xDDCC }
+            },
+            0x02,
+            vec![0xBE, 0xEF],
+        ),
+        ret_read
+    );
+
+    // Write
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+fn test_named_struct() {
+    let test_data: Vec<u8> = [
+        0xFF,
+        0b1001_0110,
+        0xAA,
+        0xBB,
+        0xCC,
+        0xDD,
+        0b1001_0110,
+        0xCC,
+        0xDD,
+        0x02,
+        0xBE,
+        0xEF,
+    ]
+    .to_vec();
+
+    // Read
+    let ret_read = samples::NamedDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::NamedDeku {
+            field_a: 0xFF,
+            field_b: 0b0000_0010,
+            field_c: 0b0001_0110,
+            field_d: 0xBBAA,
+            field_e: 0xCCDD,
+            field_f: samples::NestedDeku {
+                nest_a: 0b00_100101,
+                nest_b: 0b10,
+                inner: samples::DoubleNestedDeku { data: 0xDDCC }
+            },
+            vec_len: 0x02,
+            vec_data: vec![0xBE, 0xEF]
+        },
+        ret_read
+    );
+
+    // Write
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[rstest(input,expected,
+    case(&hex!("01AB"), samples::EnumDeku::VarA(0xAB)),
+    case(&hex!("0269"), samples::EnumDeku::VarB(0b0110, 0b1001)),
+    case(&hex!("0302AABB"), samples::EnumDeku::VarC{field_a: 0x02, field_b: vec![0xAA, 0xBB]}),
+    case(&hex!("0402AABB"), samples::EnumDeku::VarD(0x02, vec![0xAA, 0xBB])),
+
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"Could not match enum variant id = 255 on enum `EnumDeku`\")")]
+    case(&hex!("FFAB"), samples::EnumDeku::VarA(0xFF))
+)]
+fn test_enum(input: &[u8], expected: samples::EnumDeku) {
+    let ret_read = samples::EnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_enum_update() {
+    // Named variant
+    let test_data: Vec<u8> = hex!("0302AABB").to_vec();
+    let mut ret_read = samples::EnumDeku::try_from(test_data.as_ref()).unwrap();
+    if let samples::EnumDeku::VarC { field_b, .. } = &mut ret_read {
+        field_b.push(0xCC);
+    }
+    ret_read.update().unwrap();
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(hex!("0303AABBCC").to_vec(), ret_write);
+
+    // Unnamed variant
+    let test_data: Vec<u8> = hex!("0402AABB").to_vec();
+    let mut ret_read = samples::EnumDeku::try_from(test_data.as_ref()).unwrap();
+    if let samples::EnumDeku::VarD(_, field_1) = &mut ret_read {
+        field_1.pop();
+    }
+    ret_read.update().unwrap();
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(hex!("0401AA").to_vec(), ret_write);
+
+    // Variants without updates are untouched
+    let mut ret_read = samples::EnumDeku::VarA(0xAB);
+    ret_read.update().unwrap();
+    assert_eq!(samples::EnumDeku::VarA(0xAB), ret_read);
+}
+
+#[rstest(input,expected,
+    case(&hex!("01AB"), samples::EnumDekuDefault::VarA(0xAB)),
+    case(&hex!("FFAB"), samples::EnumDekuDefault::VarDefault(0xFF, 0xAB)),
+
+    #[should_panic(expected = "Parse(\"Too much data\")")]
+    case(&hex!("FFFFFF"), samples::EnumDekuDefault::VarA(0xAB)),
+)]
+fn test_enum_default(input: &[u8], expected: samples::EnumDekuDefault) {
+    let ret_read = samples::EnumDekuDefault::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case(&hex!("1A"), samples::IdFieldEnumDeku::VarA(0x0A)),
+    case(&hex!("2A"), samples::IdFieldEnumDeku::VarB { id: 0x02, value: 0x0A, ext: None }),
+    case(&hex!("3A FF"), samples::IdFieldEnumDeku::VarB { id: 0x03, value: 0x0A, ext: Some(0xFF) }),
+    case(&hex!("FA"), samples::IdFieldEnumDeku::VarUnknown(0x0F, 0x0A)),
+)]
+fn test_id_field_enum(input: &[u8], expected: samples::IdFieldEnumDeku) {
+    let ret_read = samples::IdFieldEnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+#[should_panic(
+    expected = "InvalidParam(\"Enum variant id = 4 doesn't match the `id_pat` of `IdFieldEnumDeku::VarB`\")"
+)]
+fn test_id_field_enum_id_pat_mismatch() {
+    let value = samples::IdFieldEnumDeku::VarB {
+        id: 0x04,
+        value: 0x0A,
+        ext: None,
+    };
+
+    let _: Vec<u8> = value.try_into().unwrap();
+}
+
+#[test]
+fn test_dynamic_vec_count() {
+    let test_data: Vec<u8> = [0x02, 0xAA, 0xBB].to_vec();
+
+    // Read
+    let mut ret_read = samples::VecCountDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::VecCountDeku {
+            count: 0x02,
+            vec_data: vec![0xAA, 0xBB]
+        },
+        ret_read
+    );
+
+    // Add an item to the vec
+    ret_read.vec_data.push(0xFF);
+    ret_read.update().unwrap();
+
+    // Write
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!([0x03, 0xAA, 0xBB, 0xFF].to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::normal(
+        &hex!("01 AABB CCDD 01 EEFF"),
+        samples::CountExprDeku { len: 1, pairs: vec![0xAA, 0xBB], tail: vec![0xCC, 0xDD], delta: 1, rest: vec![0xEE, 0xFF] },
+    ),
+    case::empty(
+        &hex!("00 CC FF"),
+        samples::CountExprDeku { len: 0, pairs: vec![], tail: vec![0xCC], delta: -1, rest: vec![] },
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"error parsing int: out of range integral type conversion attempted\")")]
+    case::negative_count(
+        &hex!("00 CC FE"),
+        samples::CountExprDeku { len: 0, pairs: vec![], tail: vec![0xCC], delta: -2, rest: vec![] },
+    ),
+)]
+fn test_count_expr_deku(input: &[u8], expected: samples::CountExprDeku) {
+    let ret_read = samples::CountExprDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_count_expr_ctx_deku() {
+    let input = hex!("00 CCDD 00 FF");
+    let (rest, ret_read) = samples::CountExprDeku::read(input.view_bits(), 2).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(
+        samples::CountExprDeku {
+            len: 0,
+            pairs: vec![],
+            tail: vec![0xCC, 0xDD],
+            delta: 0,
+            rest: vec![0xFF]
+        },
+        ret_read
+    );
+}
+
+#[rstest(input, wide, expected,
+    case(&hex!("4A F1 23"), false, samples::BitsExprDeku { width: 4, value: 0xA, flags: 0xF1, tail: 0x23 }),
+    case(&hex!("81 23 45 67"), true, samples::BitsExprDeku { width: 8, value: 0x12, flags: 0x3, tail: 0x6745 }),
+)]
+fn test_bits_expr_deku(input: &[u8], wide: bool, expected: samples::BitsExprDeku) {
+    let (rest, ret_read) = samples::BitsExprDeku::read(input.view_bits(), wide).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(expected, ret_read);
+
+    let ret_write = ret_read.write(wide).unwrap();
+    assert_eq!(input.view_bits::<Msb0>(), ret_write.as_bitslice());
+}
+
+#[rstest(input, expected,
+    case(&hex!("11 FF 7F"), samples::VarIntDeku { unsigned_len: 1, signed_len: 1, unsigned: 0xFF, signed: 0x7F }),
+    case(&hex!("13 FF FFFFFE"), samples::VarIntDeku { unsigned_len: 1, signed_len: 3, unsigned: 0xFF, signed: -2 }),
+    case(&hex!("82 8000000000000000 8000"), samples::VarIntDeku { unsigned_len: 8, signed_len: 2, unsigned: 1 << 63, signed: -0x8000 }),
+    case(&hex!("08 FFFFFFFFFFFFFFFF"), samples::VarIntDeku { unsigned_len: 0, signed_len: 8, unsigned: 0, signed: -1 }),
+)]
+fn test_var_int_deku(input: &[u8], expected: samples::VarIntDeku) {
+    let ret_read = samples::VarIntDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(value, expected,
+    case(-0x80, Ok(hex!("01 80").to_vec())),
+    case(0x7F, Ok(hex!("01 7F").to_vec())),
+    #[cfg(not(feature = "static-errors"))]
+    case(0x80, Err(DekuError::InvalidParam("value 128 doesn't fit in 8 bits".into()))),
+    #[cfg(not(feature = "static-errors"))]
+    case(-0x81, Err(DekuError::InvalidParam("value -129 doesn't fit in 8 bits".into()))),
+)]
+fn test_var_int_deku_strict(value: i64, expected: Result<Vec<u8>, DekuError>) {
+    let value = samples::VarIntDeku {
+        unsigned_len: 0,
+        signed_len: 1,
+        unsigned: 0,
+        signed: value,
+    };
+
+    let options = deku::options::WriteOptions::new().strict(true);
+    assert_eq!(
+        expected,
+        deku::options::to_bytes_with_options(&value, options)
+    );
+}
+
+#[rstest(input, expected,
+    case(&hex!("02 12 34"), samples::NegotiatedDeku {
+        wide: 0,
+        count: 2,
+        values: vec![samples::NegotiatedValue { value: 0x12 }, samples::NegotiatedValue { value: 0x34 }],
+    }),
+    case(&hex!("82 1234 5678"), samples::NegotiatedDeku {
+        wide: 1,
+        count: 2,
+        values: vec![samples::NegotiatedValue { value: 0x1234 }, samples::NegotiatedValue { value: 0x5678 }],
+    }),
+)]
+fn test_negotiated_deku(input: &[u8], expected: samples::NegotiatedDeku) {
+    let ret_read = samples::NegotiatedDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_negotiated_deku_too_wide() {
+    let value = samples::NegotiatedDeku {
+        wide: 0,
+        count: 1,
+        values: vec![samples::NegotiatedValue { value: 0x1FF }],
+    };
+
+    let options = deku::options::WriteOptions::new().strict(true);
+    assert_eq!(
+        DekuError::InvalidParam("value 511 doesn't fit in 8 bits".into()),
+        deku::options::to_bytes_with_options(&value, options).unwrap_err()
+    );
+}
+
+#[rstest(input, expected,
+    case(&hex!("00 ABCD"), samples::BytesExprDeku { wide: 0, value: 0xABCD }),
+    case(&hex!("01 0102 ABCD"), samples::BytesExprDeku { wide: 1, value: 0x0102_ABCD }),
+)]
+fn test_bytes_expr_deku(input: &[u8], expected: samples::BytesExprDeku) {
+    let ret_read = samples::BytesExprDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+#[should_panic(expected = "error parsing int: out of range integral type conversion attempted")]
+fn test_bits_expr_deku_error() {
+    #[derive(PartialEq, Debug, DekuRead)]
+    struct NegativeBitsDeku {
+        width: i8,
+        #[deku(bits = "*width")]
+        value: u8,
+    }
+
+    NegativeBitsDeku::try_from(hex!("FF 00").as_ref()).unwrap();
+}
+
+#[test]
+fn test_map_write_deku() {
+    let test_data: Vec<u8> = hex!("01 007B").to_vec();
+
+    let ret_read = samples::MapWriteDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::MapWriteDeku {
+            field_a: "1".to_string(),
+            field_b: 12.3,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+#[should_panic(expected = "InvalidParam(\"invalid digit found in string\")")]
+fn test_map_write_deku_error() {
+    let val = samples::MapWriteDeku {
+        field_a: "a".to_string(),
+        field_b: 0.0,
+    };
+
+    let _: Vec<u8> = val.try_into().unwrap();
+}
+
+#[test]
+fn test_read_as_deku() {
+    let test_data: Vec<u8> = hex!("01 0102").to_vec();
+
+    let ret_read = samples::ReadAsDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::ReadAsDeku {
+            level: samples::Level::High,
+            duration: samples::Millis(0x0102),
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+#[should_panic(expected = "Parse(\"error converting `u8` to `Level`\")")]
+fn test_read_as_deku_read_error() {
+    let test_data: Vec<u8> = hex!("02 0102").to_vec();
+
+    let _ret_read = samples::ReadAsDeku::try_from(test_data.as_ref()).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "InvalidParam(\"error converting `Millis` to `u16`\")")]
+fn test_read_as_deku_write_error() {
+    let val = samples::ReadAsDeku {
+        level: samples::Level::Low,
+        duration: samples::Millis(0x10000),
+    };
+
+    let _: Vec<u8> = val.try_into().unwrap();
+}
+
+#[test]
+fn test_codec_deku() {
+    let test_data: Vec<u8> = hex!("28 0014 80").to_vec();
+
+    let ret_read = samples::CodecDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::CodecDeku {
+            shift: 0x28,
+            temperature: -20,
+            flag_a: true,
+            flag_b: false,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+#[should_panic(expected = "InvalidParam(\"-50 is out of range\")")]
+fn test_codec_deku_error() {
+    let val = samples::CodecDeku {
+        shift: 0x28,
+        temperature: -50,
+        flag_a: false,
+        flag_b: false,
+    };
+
+    let _: Vec<u8> = val.try_into().unwrap();
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+#[should_panic(
+    expected = "Parse(\"error parsing int: out of range integral type conversion attempted\")"
+)]
+fn test_dynamic_vec_count_error() {
+    let mut val = samples::VecCountDeku {
+        count: 0x02,
+        vec_data: vec![0xAA, 0xBB],
+    };
+
+    // `count` is a u8, add u8::MAX ++ items and try to update
+    for _ in 0..u8::MAX {
+        val.vec_data.push(0xFF);
+    }
+    val.update().unwrap();
+}
+
+#[test]
+fn test_map() {
+    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+
+    let ret_read = samples::MapDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::MapDeku {
+            field_a: "1".to_string(),
+            field_b: "2".to_string(),
+        },
+        ret_read
+    );
+}
+
+#[test]
+fn test_generic_struct_deku() {
+    let test_data: Vec<u8> = [0x01].to_vec();
+
+    let ret_read = samples::GenericStructDeku::<u8>::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(samples::GenericStructDeku::<u8> { field_a: 0x01 }, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+fn test_generic_enum_deku() {
+    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+
+    let ret_read = samples::GenericEnumDeku::<u8>::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(samples::GenericEnumDeku::<u8>::VariantT(0x02), ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+fn test_skip_deku() {
+    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+
+    let ret_read = samples::SkipDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::SkipDeku {
+            field_a: 0x01,
+            field_b: None,
+            field_c: 0x02,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[rstest(input, expected, expected_write,
+    case(&hex!("01 02"), samples::SkipDirectionDeku { field_a: 0x01, field_b: 0xFF, field_c: 0x02, field_d: None }, hex!("01 FF").to_vec()),
+    case(&hex!("00 02 03"), samples::SkipDirectionDeku { field_a: 0x00, field_b: 0xFF, field_c: 0x02, field_d: Some(0x03) }, hex!("00 FF 03").to_vec()),
+)]
+fn test_skip_direction_deku(
+    input: &[u8],
+    expected: samples::SkipDirectionDeku,
+    expected_write: Vec<u8>,
+) {
+    let ret_read = samples::SkipDirectionDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[test]
+fn test_update_ctx_deku() {
+    let mut value = samples::UpdateCtxDeku {
+        count: 0,
+        data: vec![0xAA, 0xBB, 0xCC, 0xDD],
+    };
+
+    // default ctx
+    value.update().unwrap();
+    assert_eq!(4, value.count);
+
+    value.update_ctx(2).unwrap();
+    assert_eq!(2, value.count);
+
+    // `auto_update` with the ctx written with
+    value.count = 0;
+    let ret_write = value.write(4).unwrap();
+    assert_eq!(hex!("01 AABBCCDD").to_vec(), ret_write.into_vec());
+
+    let ret_write = value.to_bytes().unwrap();
+    assert_eq!(hex!("04 AABBCCDD").to_vec(), ret_write);
+}
+
+#[test]
+fn test_size_deku() {
+    assert_eq!(56, samples::SizeDeku::BIT_SIZE);
+
+    let value = samples::SizeDeku::try_from(hex!("53 01 23 00000004").as_ref()).unwrap();
+    assert_eq!(samples::SizeDeku::BIT_SIZE, value.to_bits().unwrap().len());
+}
+
+#[rstest(input,
+    case(&hex!("1A")),
+    case(&hex!("2A 53 01 23 00000004")),
+    case(&hex!("30")),
+    case(&hex!("4A BBBB")),
+    case(&hex!("FA")),
+)]
+fn test_size_enum_deku(input: &[u8]) {
+    assert_eq!(4, samples::SizeEnumDeku::ID_BIT_SIZE);
+    assert_eq!(4, samples::SizeEnumDeku::MIN_BIT_SIZE);
+    assert_eq!(64, samples::SizeEnumDeku::MAX_BIT_SIZE);
+    assert_eq!(0, samples::SizeEnumDeku::MIN_VARIANT_BIT_SIZE);
+    assert_eq!(60, samples::SizeEnumDeku::MAX_VARIANT_BIT_SIZE);
+
+    // every variant fits in the largest size
+    let (_, value) = samples::SizeEnumDeku::from_bytes((input, 0)).unwrap();
+    let bits = value.to_bits().unwrap();
+    assert!(samples::SizeEnumDeku::MIN_BIT_SIZE <= bits.len());
+    assert!(bits.len() <= samples::SizeEnumDeku::MAX_BIT_SIZE);
+}
+
+#[test]
+fn test_auto_update_deku() {
+    let value = samples::AutoUpdateDeku {
+        count: 0,
+        data: vec![0xAA, 0xBB],
+    };
+
+    let ret_write = value.to_bytes().unwrap();
+    assert_eq!(hex!("02 AABB").to_vec(), ret_write);
+
+    let ret_write = value.write(()).unwrap();
+    assert_eq!(hex!("02 AABB").to_vec(), ret_write.into_vec());
+
+    // the value itself isn't updated
+    assert_eq!(0, value.count);
+
+    let value = samples::AutoUpdateEnumDeku::VarA(0, vec![0xAA]);
+    let ret_write: Vec<u8> = value.try_into().unwrap();
+    assert_eq!(hex!("01 01 AA").to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::header(&hex!("2ABC 01"), samples::PeekDeku { version: 2, header: Some(0x2ABC), data: 0x01 }),
+    case::no_header(&hex!("1A"), samples::PeekDeku { version: 1, header: None, data: 0x1A }),
+)]
+fn test_peek_deku(input: &[u8], expected: samples::PeekDeku) {
+    let ret_read = samples::PeekDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::short(&hex!("01"), samples::PeekEnumDeku { tag: 0x01, payload: samples::PeekPayloadDeku::Short(0x01) }),
+    case::long(&hex!("05 AA"), samples::PeekEnumDeku { tag: 0x05, payload: samples::PeekPayloadDeku::Long(0x05, 0xAA) }),
+)]
+fn test_peek_enum_deku(input: &[u8], expected: samples::PeekEnumDeku) {
+    let ret_read = samples::PeekEnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected, expected_write,
+    case::full(&hex!("01 02 0304"), samples::DefaultIfEofDeku { field_a: 0x01, field_b: 0x02, field_c: 0x0304 }, hex!("01 02 0304").to_vec()),
+    case::no_c(&hex!("01 02"), samples::DefaultIfEofDeku { field_a: 0x01, field_b: 0x02, field_c: 0x0000 }, hex!("01 02 0000").to_vec()),
+    case::no_b(&hex!("01"), samples::DefaultIfEofDeku { field_a: 0x01, field_b: 0xFF, field_c: 0x0000 }, hex!("01 FF 0000").to_vec()),
+
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::partial_c(&hex!("01 02 03"), samples::DefaultIfEofDeku { field_a: 0x01, field_b: 0x02, field_c: 0x0000 }, vec![]),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
+    case::no_a(&[], samples::DefaultIfEofDeku { field_a: 0x00, field_b: 0xFF, field_c: 0x0000 }, vec![]),
+)]
+fn test_default_if_eof_deku(
+    input: &[u8],
+    expected: samples::DefaultIfEofDeku,
+    expected_write: Vec<u8>,
+) {
+    let ret_read = samples::DefaultIfEofDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected,
+    case::full(&hex!("02 03"), samples::DefaultIfEofTopDeku { version: 0x02, flags: 0x03 }),
+    case::no_flags(&hex!("02"), samples::DefaultIfEofTopDeku { version: 0x02, flags: 0x10 }),
+    case::empty(&[], samples::DefaultIfEofTopDeku { version: 0x00, flags: 0x10 }),
+)]
+fn test_default_if_eof_top_deku(input: &[u8], expected: samples::DefaultIfEofTopDeku) {
+    let ret_read = samples::DefaultIfEofTopDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+}
+
+#[rstest(input, flags, expected_write,
+    case(&hex!("03 AA BB"), 0x03, hex!("03 AA BB").to_vec()),
+    case(&hex!("03 AA BB"), 0x01, hex!("01 AA").to_vec()),
+    case(&hex!("03 AA BB"), 0x00, hex!("00").to_vec()),
+    case(&hex!("00 AA"), 0x00, hex!("00").to_vec()),
+)]
+fn test_write_cond_deku(input: &[u8], flags: u8, expected_write: Vec<u8>) {
+    let mut ret_read = samples::WriteCondDeku::try_from(input).unwrap();
+    assert_eq!(0xAA, ret_read.ext_a);
+
+    ret_read.flags = flags;
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[test]
+fn test_default_deku() {
+    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+
+    let ret_read = samples::DefaultDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::DefaultDeku {
+            field_a: 0x01,
+            field_b: 0x05,
+            field_c: 0x02,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[test]
+fn test_skip_cond_deku() {
+    let test_data: Vec<u8> = [0x01].to_vec();
+
+    let ret_read = samples::SkipCondDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::SkipCondDeku {
+            field_a: 0x01,
+            field_b: 0x05, // default
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(vec![0x01], ret_write);
+}
+
+#[test]
+fn test_cond_deku() {
+    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+
+    let ret_read = samples::CondDeku::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(
+        samples::CondDeku {
+            field_a: 0x01,
+            field_b: Some(0x02),
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data, ret_write);
+}
+
+#[rstest(input, expected,
+    case::entries(
+        &hex!("82 AABB CCDD"),
+        samples::FlaggedEntriesDeku { has_entries: 1, count: 2, entries: Some(vec![0xAABB, 0xCCDD]) },
+    ),
+    case::empty_entries(
+        &hex!("80"),
+        samples::FlaggedEntriesDeku { has_entries: 1, count: 0, entries: Some(vec![]) },
+    ),
+    case::no_entries(
+        &hex!("02"),
+        samples::FlaggedEntriesDeku { has_entries: 0, count: 2, entries: None },
+    ),
+)]
+fn test_flagged_entries_deku(input: &[u8], expected: samples::FlaggedEntriesDeku) {
+    let ret_read = samples::FlaggedEntriesDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_flagged_entries_deku_update() {
+    let mut val = samples::FlaggedEntriesDeku {
+        has_entries: 0,
+        count: 0,
+        entries: Some(vec![0xAABB]),
+    };
+    val.update().unwrap();
+    assert_eq!(1, val.has_entries);
+    assert_eq!(1, val.count);
+
+    let ret_write: Vec<u8> = val.try_into().unwrap();
+    assert_eq!(hex!("81 AABB").to_vec(), ret_write);
+
+    let mut val = samples::FlaggedEntriesDeku {
+        has_entries: 1,
+        count: 2,
+        entries: None,
+    };
+    val.update().unwrap();
+    assert_eq!(0, val.has_entries);
+    assert_eq!(0, val.count);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("00"), samples::TempDeku { items: vec![] }),
+    case::items(&hex!("02 AABB"), samples::TempDeku { items: vec![0xAA, 0xBB] }),
+)]
+fn test_temp_deku(input: &[u8], expected: samples::TempDeku) {
+    let ret_read = samples::TempDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::value(&hex!("0000 AC AA"), samples::TempConstDeku { value: Some(0xAA) }),
+    case::no_value(&hex!("0000 2C"), samples::TempConstDeku { value: None }),
+)]
+fn test_temp_const_deku(input: &[u8], expected: samples::TempConstDeku) {
+    let ret_read = samples::TempConstDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::flag(&hex!("01 10"), samples::TempEnumDeku::VarA { flag: true }),
+    case::no_flag(&hex!("01 00"), samples::TempEnumDeku::VarA { flag: false }),
+    case::other(&hex!("02 AA"), samples::TempEnumDeku::VarB(0xAA)),
+)]
+fn test_temp_enum_deku(input: &[u8], expected: samples::TempEnumDeku) {
+    let ret_read = samples::TempEnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::key(
+        &hex!("ABC3 04030201 05 0C AB"),
+        samples::OptionCtxDeku {
+            opt_bits: Some(0x0ABC),
+            key: 0x03,
+            opt_endian: Some(0x01020304),
+            opt_ctx: Some(samples::SubTypeNeedCtx { i: 0x09 }),
+            opt_non_copy_ctx: Some(samples::NonCopyCtxStruct { a: 0x0F }),
+            opt_vec_bits: Some(vec![0x0A, 0x0B]),
+        },
+    ),
+    case::no_key(
+        &hex!("ABC0 04030201 05 0F"),
+        samples::OptionCtxDeku {
+            opt_bits: Some(0x0ABC),
+            key: 0x00,
+            opt_endian: Some(0x01020304),
+            opt_ctx: Some(samples::SubTypeNeedCtx { i: 0x06 }),
+            opt_non_copy_ctx: Some(samples::NonCopyCtxStruct { a: 0x0F }),
+            opt_vec_bits: None,
+        },
+    ),
+)]
+fn test_option_ctx_deku(input: &[u8], expected: samples::OptionCtxDeku) {
+    let ret_read = samples::OptionCtxDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::count_1(
+        &hex!("01 AA BBCC DD 0102 0304"),
+        samples::NestedContainerDeku {
+            count: 1,
+            opt_vec: Some(vec![0xAA]),
+            opt_vec_endian: Some(vec![0xBBCC]),
+            vec_opt: vec![Some(0xDD)],
+            opt_array: Some([0x0102, 0x0304]),
+        },
+    ),
+    case::count_0(
+        &hex!("00 0102 0304"),
+        samples::NestedContainerDeku {
+            count: 0,
+            opt_vec: None,
+            opt_vec_endian: None,
+            vec_opt: vec![],
+            opt_array: Some([0x0102, 0x0304]),
+        },
+    ),
+)]
+fn test_nested_container_deku(input: &[u8], expected: samples::NestedContainerDeku) {
+    let ret_read = samples::NestedContainerDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("00 FF"), samples::BytesReadDeku { len: 0, items: vec![], after: 0xFF }),
+    case::items(
+        &hex!("04 AABB CCDD FF"),
+        samples::BytesReadDeku { len: 4, items: vec![0xAABB, 0xCCDD], after: 0xFF },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::straddle(&hex!("03 AABB CCDD FF"), samples::BytesReadDeku::default()),
+)]
+fn test_bytes_read_deku(input: &[u8], expected: samples::BytesReadDeku) {
+    let ret_read = samples::BytesReadDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected_write,
+    case::canonical(
+        samples::NormalizeDeku { field_a: 0x01, count: 3, field_b: vec![0x01, 0x02, 0x03] },
+        hex!("01 03 010203").to_vec(),
+    ),
+    case::normalized(
+        samples::NormalizeDeku { field_a: 0xFF, count: 3, field_b: vec![0x03, 0x01, 0x02] },
+        hex!("0F 03 010203").to_vec(),
+    ),
+)]
+fn test_normalize_deku(input: samples::NormalizeDeku, expected_write: Vec<u8>) {
+    let ret_write: Vec<u8> = input.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+
+    // reading is not affected
+    let ret_read = samples::NormalizeDeku::try_from(hex!("FF 02 0201").as_ref()).unwrap();
+    assert_eq!(
+        samples::NormalizeDeku {
+            field_a: 0xFF,
+            count: 2,
+            field_b: vec![0x02, 0x01]
+        },
+        ret_read
+    );
+}
+
+#[rstest(input, expected,
+    case::normal(&hex!("03 AA"), samples::AssertDeku { version: 0x03, field_a: 0xAA }),
+    #[should_panic(expected = "Assertion(\"field `version` failed assertion: *version <= 3\")")]
+    case::first_field(&hex!("04 AA"), samples::AssertDeku { version: 0x04, field_a: 0xAA }),
+    #[should_panic(expected = "Assertion(\"field `field_a` failed assertion: *field_a != *version\")")]
+    case::other_field(&hex!("03 03"), samples::AssertDeku { version: 0x03, field_a: 0x03 }),
+)]
+fn test_assert_deku(input: &[u8], expected: samples::AssertDeku) {
+    let ret_read = samples::AssertDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::assert_version(
+        samples::AssertDeku { version: 0x04, field_a: 0xAA },
+        "field `version` failed assertion: *version <= 3",
+    ),
+    case::assert_field_a(
+        samples::AssertDeku { version: 0x03, field_a: 0x03 },
+        "field `field_a` failed assertion: *field_a != *version",
+    ),
+)]
+fn test_assert_deku_write(input: samples::AssertDeku, expected: &str) {
+    let ret_write: Result<Vec<u8>, _> = input.try_into();
+    assert_eq!(
+        Err(DekuError::Assertion(expected.to_string().into())),
+        ret_write
+    );
+}
+
+#[rstest(input, expected,
+    case::normal(&hex!("04 02 AABB"), samples::ContainerAssertDeku { header_len: 4, count: 2, options: vec![0xAA, 0xBB] }),
+    #[should_panic(expected = "Assertion(\"`ContainerAssertDeku` failed assertion: self.header_len as usize == self.options.len() + 2\")")]
+    case::mismatch(&hex!("05 02 AABB"), samples::ContainerAssertDeku { header_len: 5, count: 2, options: vec![0xAA, 0xBB] }),
+)]
+fn test_container_assert_deku(input: &[u8], expected: samples::ContainerAssertDeku) {
+    let ret_read = samples::ContainerAssertDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_container_assert_deku_write() {
+    let input = samples::ContainerAssertDeku {
+        header_len: 2,
+        count: 1,
+        options: vec![0xAA],
+    };
+
+    let ret_write: Result<Vec<u8>, _> = input.try_into();
+    assert_eq!(
+        Err(DekuError::Assertion(
+            "`ContainerAssertDeku` failed assertion: self.header_len as usize == self.options.len() + 2".into()
+        )),
+        ret_write
+    );
+}
+
+#[rstest(input, expected,
+    case::normal(&hex!("1234 AA AA"), samples::AssertEqDeku { field_a: 0x1234, field_b: 0xAA, field_c: 0xAA }),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Assertion(\"field `field_a` failed assertion: expected 4660 got 4661\")")]
+    case::constant(&hex!("1235 AA AA"), samples::AssertEqDeku { field_a: 0x1235, field_b: 0xAA, field_c: 0xAA }),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Assertion(\"field `field_c` failed assertion: expected 170 got 187\")")]
+    case::other_field(&hex!("1234 AA BB"), samples::AssertEqDeku { field_a: 0x1234, field_b: 0xAA, field_c: 0xBB }),
+)]
+fn test_assert_eq_deku(input: &[u8], expected: samples::AssertEqDeku) {
+    let ret_read = samples::AssertEqDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_assert_eq_deku_write() {
+    let input = samples::AssertEqDeku {
+        field_a: 0x1234,
+        field_b: 0xAA,
+        field_c: 0xBB,
+    };
+    let ret_write: Result<Vec<u8>, _> = input.try_into();
+    assert_eq!(
+        Err(DekuError::Assertion(
+            "field `field_c` failed assertion: expected 170 got 187".into()
+        )),
+        ret_write
+    );
+}
+
+#[test]
+fn test_endian_propagation_deku() {
+    let input = hex!(
+        "02 AABB CCDD"
+        "01020304 05060708"
+        "1122 33 44556677"
+        "2211 4433"
+        "CDA0"
+    );
+    let expected = samples::EndianPropagationDeku {
+        count: 2,
+        vec: vec![0xAABB, 0xCCDD],
+        array: [0x01020304, 0x05060708],
+        tuple: (0x1122, (0x33, 0x44556677)),
+        tuple_le: (0x1122, [0x3344]),
+        vec_bits_le: vec![0x0ACD],
+        padding: 0,
+    };
+
+    let ret_read = samples::EndianPropagationDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected, expected_write,
+    case::padded(
+        &hex!("01 AA FFFF B0"),
+        samples::PadAfterDeku { len: 1, data: vec![0xAA], field_b: 0x0B },
+        hex!("01 AA 0000 B0").to_vec(),
+    ),
+    case::aligned(
+        &hex!("03 AABBCC BF"),
+        samples::PadAfterDeku { len: 3, data: vec![0xAA, 0xBB, 0xCC], field_b: 0x0B },
+        hex!("03 AABBCC B0").to_vec(),
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::not_enough_data(
+        &hex!("01 AA 00"),
+        samples::PadAfterDeku { len: 1, data: vec![0xAA], field_b: 0x0B },
+        vec![],
+    ),
+)]
+fn test_pad_after_deku(input: &[u8], expected: samples::PadAfterDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::PadAfterDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected,
+    case::normal(
+        &hex!("A3 AB5C 1234 06"),
+        samples::LsbDeku { field_a: 0x3, field_b: 0xA, field_c: 0xCAB, field_d: 0x5, field_e: 0x1234, field_f: 0b011 },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 3, got: 0 })")]
+    case::not_enough_data(
+        &hex!("A3 AB5C 1234"),
+        samples::LsbDeku { field_a: 0x3, field_b: 0xA, field_c: 0xCAB, field_d: 0x5, field_e: 0x1234, field_f: 0b011 },
+    ),
+)]
+fn test_lsb_deku(input: &[u8], expected: samples::LsbDeku) {
+    let ret_read = samples::LsbDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_cross_byte_deku() {
+    let input = hex!("1A BC DE F0 12 34");
+    let ret_read = samples::CrossByteDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(
+        samples::CrossByteDeku {
+            field_a: 0x1,
+            field_b: 0xABCD,
+            field_c: 0xEF0,
+            field_d: 0x312,
+            field_e: 0x4,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_ieee80211_deku() {
+    let input = hex!("88 01 3A 01 10 32");
+    let ret_read = samples::Ieee80211Deku::try_from(input.as_ref()).unwrap();
+    assert_eq!(
+        samples::Ieee80211Deku {
+            version: 0,
+            frame_type: 2,
+            subtype: 8,
+            to_ds: 1,
+            from_ds: 0,
+            flags: 0,
+            duration: 0x013A,
+            fragment: 0,
+            sequence: 0x321,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_lsb_field_deku() {
+    let input = [0b1000_0001];
+    let ret_read = samples::LsbFieldDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(
+        samples::LsbFieldDeku {
+            field_a: 0b0001,
+            field_b: 0b0001
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_lsb_parent_deku() {
+    let input = hex!("A3 CC");
+    let ret_read = samples::LsbParentDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(
+        samples::LsbParentDeku {
+            nested: samples::LsbNestedDeku {
+                field_a: 0x3,
+                field_b: 0xA
+            },
+            field_c: 0xCC,
+        },
+        ret_read
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_lsb_enum_deku() {
+    let input = [0b1010_1001];
+    let ret_read = samples::LsbEnumDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(samples::LsbEnumDeku::VarA(0b10_1010), ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_lsb_list_deku() {
+    let input: Vec<u8> = (0..200).collect();
+    let ret_read = samples::LsbListDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(100, ret_read.items.len());
+    for (item, bytes) in ret_read.items.iter().zip(input.chunks(2)) {
+        assert_eq!(&samples::LsbParentDeku::try_from(bytes).unwrap(), item);
+    }
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input, ret_write);
+}
+
+#[test]
+fn test_lsb_read_all_deku() {
+    let input: Vec<u8> = (0..20).collect();
+    let ret_read = samples::LsbReadAllDeku::try_from(input.as_ref()).unwrap();
+    assert_eq!(input, ret_read.data);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input, ret_write);
+}
+
+#[test]
+fn test_lsb_unaligned_deku() {
+    let input = hex!("1A 3C C0");
+    assert_eq!(
+        Err(DekuError::Parse(
+            "bit_order: an lsb container must start on a byte boundary".into()
+        )),
+        samples::LsbUnalignedDeku::try_from(input.as_ref()).map_err(DekuError::into_inner)
+    );
+}
+
+#[rstest(input, expected, expected_rest, expected_write,
+    case::after(
+        &hex!("04 02 CC FF AABB"),
+        samples::OffsetDeku { data_offset: 4, len: 2, data: vec![0xAA, 0xBB], field_c: 0xCC },
+        hex!("FF AABB").as_ref(),
+        Ok(hex!("04 02 CC 00 AABB").to_vec()),
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    case::overlap(
+        &hex!("01 01 CC"),
+        samples::OffsetDeku { data_offset: 1, len: 1, data: vec![0x01], field_c: 0xCC },
+        &[],
+        Err(DekuError::InvalidParam("offset: field at bit 8 overlaps the 24 bits written before it".into())),
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"offset: can't read at bit 40 of 24 bits\")")]
+    case::past_end(
+        &hex!("05 00 CC"),
+        samples::OffsetDeku { data_offset: 5, len: 0, data: vec![], field_c: 0xCC },
+        &[],
+        Ok(vec![]),
+    ),
+)]
+fn test_offset_deku(
+    input: &[u8],
+    expected: samples::OffsetDeku,
+    expected_rest: &[u8],
+    expected_write: Result<Vec<u8>, DekuError>,
+) {
+    let ((rest, _), ret_read) = samples::OffsetDeku::from_bytes((input, 0)).unwrap();
+    assert_eq!(expected, ret_read);
+    assert_eq!(expected_rest, rest);
+
+    let ret_write: Result<Vec<u8>, DekuError> = ret_read.try_into();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_offset_overflow() {
+    let expected = DekuError::Parse(
+        "offset: 18446744073709551615 bytes is too large to be counted in bits".into(),
     );
 
-    // Write
+    let input = hex!("FFFFFFFFFFFFFFFF AA");
+    assert_eq!(
+        expected,
+        samples::OffsetOverflowDeku::try_from(input.as_ref())
+            .unwrap_err()
+            .into_inner()
+    );
+
+    let value = samples::OffsetOverflowDeku {
+        data_offset: u64::MAX,
+        data: 0xAA,
+    };
+    assert_eq!(expected, value.to_bytes().unwrap_err());
+}
+
+#[test]
+fn test_align_deku_fill() {
+    let input = samples::AlignDeku {
+        len: 1,
+        data: vec![0xAA],
+        field_a: 0xBB,
+        field_b: 0b101,
+        field_c: 0b0110,
+    };
+
+    let options = deku::options::WriteOptions::new().fill(0xFF);
+    let ret_write = deku::options::to_bytes_with_options(&input, options).unwrap();
+    assert_eq!(hex!("01 AA FFFF BB B6").to_vec(), ret_write);
+}
+
+#[rstest(input, expected, expected_write,
+    case::limit(
+        &hex!("02 AABB CC FFFF DD"),
+        samples::LimitDeku { len: 2, items: vec![0xAA, 0xBB], field_a: 0xCC, field_b: 0xDD },
+        hex!("02 AABB CC 0000 DD").to_vec(),
+    ),
+    case::empty(
+        &hex!("00 CC 0000 DD"),
+        samples::LimitDeku { len: 0, items: vec![], field_a: 0xCC, field_b: 0xDD },
+        hex!("00 CC 0000 DD").to_vec(),
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 24, got: 16 })")]
+    case::past_end(&hex!("03 AABB"), samples::LimitDeku { len: 3, items: vec![], field_a: 0, field_b: 0 }, vec![]),
+)]
+fn test_limit_deku(input: &[u8], expected: samples::LimitDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::LimitDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    assert_eq!(expected_write, ret_write);
 }
 
 #[test]
-fn test_named_struct() {
-    let test_data: Vec<u8> = [
-        0xFF,
-        0b1001_0110,
-        0xAA,
-        0xBB,
-        0xCC,
-        0xDD,
-        0b1001_0110,
-        0xCC,
-        0xDD,
-        0x02,
-        0xBE,
-        0xEF,
-    ]
-    .to_vec();
+fn test_limit_deku_overflow() {
+    let ret_read =
+        samples::LimitOverflowDeku::try_from(hex!("AABB").as_ref()).map_err(DekuError::into_inner);
+    assert_eq!(Err(DekuError::Incomplete(NeedSize::new(16, 8))), ret_read);
 
-    // Read
-    let ret_read = samples::NamedDeku::try_from(test_data.as_ref()).unwrap();
+    #[cfg(not(feature = "static-errors"))]
+    {
+        let ret_write: Result<Vec<u8>, _> =
+            samples::LimitOverflowDeku { field_a: 0xAABB }.try_into();
+        assert_eq!(
+            Err(DekuError::InvalidParam(
+                "limit_bytes: 16 bits written, more than the limit of 8 bits".into()
+            )),
+            ret_write
+        );
+    }
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_limit_length_overflow() {
+    let expected = DekuError::Parse(
+        "limit_bytes: 18446744073709551615 bytes is too large to be counted in bits".into(),
+    );
+
+    let input = hex!("FFFFFFFFFFFFFFFF AA");
+    assert_eq!(
+        expected,
+        samples::LimitLengthOverflowDeku::try_from(input.as_ref())
+            .unwrap_err()
+            .into_inner()
+    );
+
+    let value = samples::LimitLengthOverflowDeku {
+        len: u64::MAX,
+        items: vec![0xAA],
+    };
+    assert_eq!(expected, value.to_bytes().unwrap_err());
+}
+
+#[rstest(input, expected, expected_write,
+    case::process(
+        &hex!("01 55 CC"),
+        samples::ProcessDeku { len: 1, field_a: 0xAA, field_b: 0xCC },
+        hex!("01 55 CC").to_vec(),
+    ),
+    // the processed bytes the field leaves are skipped
+    case::rest_of_window(
+        &hex!("02 55 00 CC"),
+        samples::ProcessDeku { len: 2, field_a: 0xAA, field_b: 0xCC },
+        hex!("02 55 00 CC").to_vec(),
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
+    case::empty(&hex!("00 CC"), samples::ProcessDeku { len: 0, field_a: 0, field_b: 0 }, vec![]),
+)]
+fn test_process_deku(input: &[u8], expected: samples::ProcessDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::ProcessDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("AA"), samples::ProcessAllDeku { field_a: 0xAA, items: vec![] }),
+    case::runs(
+        &hex!("AA 03BB 01CC"),
+        samples::ProcessAllDeku { field_a: 0xAA, items: vec![0xBB, 0xBB, 0xBB, 0xCC] },
+    ),
+    #[should_panic(expected = "Parse(\"rle: odd number of bytes\")")]
+    case::process_error(&hex!("AA 03"), samples::ProcessAllDeku { field_a: 0xAA, items: vec![] }),
+)]
+fn test_process_all_deku(input: &[u8], expected: samples::ProcessAllDeku) {
+    let ret_read = samples::ProcessAllDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::encoding(
+        &hex!("02 0068 00E9 6869 C3A9"),
+        samples::EncodingDeku { len: 2, field_a: "hé".to_string(), field_b: "hi".to_string(), field_c: "é".to_string() },
+    ),
+    case::empty(
+        &hex!("00 6869"),
+        samples::EncodingDeku { len: 0, field_a: "".to_string(), field_b: "hi".to_string(), field_c: "".to_string() },
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"encoding: invalid ascii at byte 1\")")]
+    case::invalid(&hex!("00 68E9"), samples::EncodingDeku { len: 0, field_a: "".to_string(), field_b: "".to_string(), field_c: "".to_string() }),
+)]
+fn test_encoding_deku(input: &[u8], expected: samples::EncodingDeku) {
+    let ret_read = samples::EncodingDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::padded(
+        &hex!("61202020 6200 6200 2000 AABB"),
+        samples::PadDeku { field_a: "a".to_string(), field_b: "b".to_string(), field_c: "b".to_string(), field_d: 0xAABB },
+    ),
+    case::full(
+        &hex!("61626364 6162 6100 6200 AABB"),
+        samples::PadDeku { field_a: "abcd".to_string(), field_b: "ab".to_string(), field_c: "ab".to_string(), field_d: 0xAABB },
+    ),
+    case::empty(
+        &hex!("20202020 0000 2000 2000 AABB"),
+        samples::PadDeku { field_a: "".to_string(), field_b: "".to_string(), field_c: "".to_string(), field_d: 0xAABB },
+    ),
+)]
+fn test_pad_deku(input: &[u8], expected: samples::PadDeku) {
+    let ret_read = samples::PadDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_pad_deku_too_long() {
+    let ret_write: Result<Vec<u8>, _> = samples::PadDeku {
+        field_a: "abcde".to_string(),
+        field_b: "".to_string(),
+        field_c: "".to_string(),
+        field_d: 0,
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "pad: string of 5 bytes is longer than 4 bytes".into()
+        )),
+        ret_write
+    );
+}
+
+#[rstest(input, expected,
+    case::empty(
+        &hex!("00 0000 00 00"),
+        samples::PrefixDeku { field_a: "".to_string(), field_b: vec![], field_c: "".to_string(), field_d: vec![] },
+    ),
+    case::normal(
+        &hex!("04 6465 6B75 0002 AABB 04 6800 6900 04 0001 0002"),
+        samples::PrefixDeku { field_a: "deku".to_string(), field_b: vec![0xAA, 0xBB], field_c: "hi".to_string(), field_d: vec![1, 2] },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 40, got: 32 })")]
+    case::not_enough_data(
+        &hex!("05 6465 6B75"),
+        samples::PrefixDeku { field_a: "".to_string(), field_b: vec![], field_c: "".to_string(), field_d: vec![] },
+    ),
+)]
+fn test_prefix_deku(input: &[u8], expected: samples::PrefixDeku) {
+    let ret_read = samples::PrefixDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_prefix_deku_too_long() {
+    let ret_write: Result<Vec<u8>, _> = samples::PrefixDeku {
+        field_a: "a".repeat(256),
+        field_b: vec![],
+        field_c: "".to_string(),
+        field_d: vec![],
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "prefix: length 256 does not fit in a `u8`".into()
+        )),
+        ret_write
+    );
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_encoding_deku_write_error() {
+    let ret_write: Result<Vec<u8>, _> = samples::EncodingDeku {
+        len: 0,
+        field_a: "".to_string(),
+        field_b: "hé".to_string(),
+        field_c: "".to_string(),
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "encoding: character 'é' can't be encoded in Ascii".into()
+        )),
+        ret_write
+    );
+}
+
+#[rstest(input, expected, expected_write,
+    case::no_gap(&hex!("00 AA"), samples::SeekDeku { gap: 0, field_a: 0xAA }, hex!("00 AA").to_vec()),
+    case::gap(&hex!("02 FFFF AA"), samples::SeekDeku { gap: 2, field_a: 0xAA }, hex!("02 0000 AA").to_vec()),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"seek_from_current: can't seek to bit 32 of 24 bits\")")]
+    case::past_end(&hex!("03 FF AA"), samples::SeekDeku { gap: 3, field_a: 0xAA }, vec![]),
+)]
+fn test_seek_deku(input: &[u8], expected: samples::SeekDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::SeekDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_seek_overflow() {
+    let input = hex!("FFFFFFFFFFFFFF7F AA");
+    assert_eq!(
+        DekuError::Parse("seek_from_current: can't seek by 9223372036854775807 bytes".into()),
+        samples::SeekOverflowDeku::try_from(input.as_ref())
+            .unwrap_err()
+            .into_inner()
+    );
+
+    let value = samples::SeekOverflowDeku {
+        gap: i64::MAX,
+        field_a: 0xAA,
+    };
+    assert_eq!(
+        DekuError::Parse(
+            "seek_from_current: 9223372036854775807 bytes is too large to be counted in bits"
+                .into()
+        ),
+        value.to_bytes().unwrap_err()
+    );
+}
+
+#[rstest(input, expected, expected_write,
+    case::pad(
+        &hex!("01 AA FFFF BB B6"),
+        samples::AlignDeku { len: 1, data: vec![0xAA], field_a: 0xBB, field_b: 0b101, field_c: 0b0110 },
+        hex!("01 AA 0000 BB A6").to_vec(),
+    ),
+    case::aligned(
+        &hex!("03 AABBCC DD A6"),
+        samples::AlignDeku { len: 3, data: vec![0xAA, 0xBB, 0xCC], field_a: 0xDD, field_b: 0b101, field_c: 0b0110 },
+        hex!("03 AABBCC DD A6").to_vec(),
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::past_end(&hex!("01 AA FF"), samples::AlignDeku { len: 1, data: vec![0xAA], field_a: 0, field_b: 0, field_c: 0 }, vec![]),
+)]
+fn test_align_deku(input: &[u8], expected: samples::AlignDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::AlignDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected,
+    case::aligned(&hex!("4A 5F BB"), samples::AlignCheckDeku { len: 4, value: 0xA, flags: 0b010, stop: 0b11111, tail: 0xBB }),
+    #[should_panic(expected = "Assertion(\"field `value` failed assertion: deku::is_byte_aligned()\")")]
+    case::unaligned(&hex!("3A 5F BB"), samples::AlignCheckDeku { len: 3, value: 0x5, flags: 0, stop: 0, tail: 0 }),
+)]
+fn test_align_check_deku(input: &[u8], expected: samples::AlignCheckDeku) {
+    let ret_read = samples::AlignCheckDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_align_check_deku_write() {
+    let ret_write: Result<Vec<u8>, _> = samples::AlignCheckDeku {
+        len: 3,
+        value: 0x5,
+        flags: 0,
+        stop: 0,
+        tail: 0,
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::Assertion(
+            "field `value` failed assertion: deku::is_byte_aligned()".into()
+        )),
+        ret_write
+    );
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_align_check_deku_unaligned() {
+    #[derive(PartialEq, Debug, DekuRead, DekuWrite)]
+    struct UnalignedDeku {
+        #[deku(bits = 4)]
+        field_a: u8,
+        #[deku(align_before)]
+        field_b: u8,
+        #[deku(bits = 2, align_after)]
+        field_c: u8,
+    }
+
+    let expected = || {
+        DekuError::Assertion(
+            "field `field_b` failed assertion: not byte aligned before the field, at bit 4".into(),
+        )
+    };
+    assert_eq!(
+        Err(expected()),
+        UnalignedDeku::try_from(hex!("AB CD").as_ref()).map_err(DekuError::into_inner)
+    );
+
+    let value = UnalignedDeku {
+        field_a: 0xA,
+        field_b: 0xBC,
+        field_c: 0b11,
+    };
+    assert_eq!(Err(expected()), value.to_bytes());
+
+    // with `lenient`, the failed checks are recorded as warnings
+    let options = deku::options::ParseOptions::new()
+        .lenient(true)
+        .collect_warnings(true);
+    let output = deku::options::from_bytes_with_options::<UnalignedDeku>(
+        (hex!("AB CD").as_ref(), 0),
+        options,
+    )
+    .unwrap();
+    assert_eq!(value, output.value);
+    assert_eq!(
+        vec![
+            "field `field_b` failed assertion: not byte aligned before the field, at bit 4"
+                .to_string(),
+            "field `field_c` failed assertion: not byte aligned after the field, at bit 14"
+                .to_string(),
+        ],
+        output.warnings
+    );
+}
+
+#[rstest(input, expected,
+    case::zero(
+        &hex!("000000 0000 00"),
+        samples::BcdDeku { field_a: 0, field_b: 0, field_c: 0 },
+    ),
+    case::normal(
+        &hex!("123456 2143 99"),
+        samples::BcdDeku { field_a: 123456, field_b: 1234, field_c: 99 },
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"bcd: invalid digit 0xF at nibble 5\")")]
+    case::invalid_digit(
+        &hex!("12345F 0000 00"),
+        samples::BcdDeku { field_a: 0, field_b: 0, field_c: 0 },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::not_enough_data(
+        &hex!("123456 21"),
+        samples::BcdDeku { field_a: 0, field_b: 0, field_c: 0 },
+    ),
+)]
+fn test_bcd_deku(input: &[u8], expected: samples::BcdDeku) {
+    let ret_read = samples::BcdDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_bcd_deku_too_large() {
+    let ret_write: Result<Vec<u8>, _> = samples::BcdDeku {
+        field_a: 1_000_000,
+        field_b: 0,
+        field_c: 0,
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "bcd: value 1000000 doesn't fit in 6 digits".into()
+        )),
+        ret_write
+    );
+}
+
+#[rstest(input, expected,
+    case::zero(
+        &hex!("0000 00 00"),
+        samples::FixedDeku { field_a: 0.0, field_b: 0.0, field_c: 0.0, field_d: 0 },
+    ),
+    case::normal(
+        &hex!("FE80 C0 9A"),
+        samples::FixedDeku { field_a: -1.5, field_b: 0.75, field_c: -1.75, field_d: 0xA },
+    ),
+    case::limits(
+        &hex!("7FFF FF 7F"),
+        samples::FixedDeku { field_a: 127.0 + 255.0 / 256.0, field_b: 255.0 / 256.0, field_c: 1.75, field_d: 0xF },
+    ),
+)]
+fn test_fixed_deku(input: &[u8], expected: samples::FixedDeku) {
+    let ret_read = samples::FixedDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_fixed_deku_out_of_range() {
+    let ret_write: Result<Vec<u8>, _> = samples::FixedDeku {
+        field_a: 0.0,
+        field_b: 0.0,
+        field_c: 2.0,
+        field_d: 0,
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "fixed_point: value 2 out of range of Q2.2".into()
+        )),
+        ret_write
+    );
+}
+
+#[test]
+fn test_fixed_ctx_deku() {
+    let format = "Q16.16".parse().unwrap();
+    let input = hex!("0080 FEFF");
+    let (rest, ret_read) = samples::FixedCtxDeku::read(input.view_bits(), format).unwrap();
+    assert!(rest.is_empty());
+    assert_eq!(samples::FixedCtxDeku { field_a: -1.5 }, ret_read);
+
+    let ret_write = ret_read.write(format).unwrap();
+    assert_eq!(input.to_vec(), ret_write.into_vec());
+}
+
+#[rstest(input, expected,
+    case::empty(
+        &hex!("0A"),
+        samples::RawBitsDeku { len: 0, data: BitVec::new(), tail: 0xA },
+    ),
+    case::byte(
+        &hex!("8A BC"),
+        samples::RawBitsDeku { len: 8, data: bitvec![Msb0, u8; 1, 0, 1, 0, 1, 0, 1, 1], tail: 0xC },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 12, got: 4 })")]
+    case::not_enough_data(
+        &hex!("CA"),
+        samples::RawBitsDeku { len: 0, data: BitVec::new(), tail: 0 },
+    ),
+)]
+fn test_raw_bits_deku(input: &[u8], expected: samples::RawBitsDeku) {
+    let ret_read = samples::RawBitsDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_raw_bits_deku_write_len_mismatch() {
+    let value = samples::RawBitsDeku {
+        len: 2,
+        data: bitvec![Msb0, u8; 1, 0, 1],
+        tail: 0,
+    };
+    let ret_write: Result<Vec<u8>, _> = value.try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "bit size of 3 bits doesn't match the 2 bits of the field".into()
+        )),
+        ret_write
+    );
+}
+
+#[rstest(input, expected, expected_write,
+    case::all_false(
+        &hex!("00 00 00"),
+        samples::StrictBoolDeku { field_a: false, field_b: false, field_c: false, field_d: false },
+        hex!("00 00 00").to_vec(),
+    ),
+    case::all_true(
+        &hex!("81 01 01"),
+        samples::StrictBoolDeku { field_a: true, field_b: true, field_c: true, field_d: true },
+        hex!("81 01 01").to_vec(),
+    ),
+    // not strict: any non-zero value is true, written as 1
+    case::lenient(
+        &hex!("80 FF 00"),
+        samples::StrictBoolDeku { field_a: true, field_b: false, field_c: true, field_d: false },
+        hex!("80 01 00").to_vec(),
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"strict_bool: invalid value 0x2, expected 0 or 1\")")]
+    case::strict_invalid(
+        &hex!("02 00 00"),
+        samples::StrictBoolDeku { field_a: false, field_b: false, field_c: false, field_d: false },
+        vec![],
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"strict_bool: invalid value 0xFF, expected 0 or 1\")")]
+    case::strict_invalid_byte(
+        &hex!("00 00 FF"),
+        samples::StrictBoolDeku { field_a: false, field_b: false, field_c: false, field_d: false },
+        vec![],
+    ),
+)]
+fn test_strict_bool_deku(input: &[u8], expected: samples::StrictBoolDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::StrictBoolDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected,
+    case::normal(
+        &hex!("01 AABBCC 1234 52"),
+        samples::TrailerDeku {
+            kind: 0x01,
+            payload: vec![0xAA, 0xBB, 0xCC],
+            footer: samples::TrailerFooterDeku { crc: 0x1234, flags: 0x5, version: 0x2 },
+        },
+    ),
+    case::empty_payload(
+        &hex!("01 1234 52"),
+        samples::TrailerDeku {
+            kind: 0x01,
+            payload: vec![],
+            footer: samples::TrailerFooterDeku { crc: 0x1234, flags: 0x5, version: 0x2 },
+        },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 8, got: 0 })")]
+    case::no_fields(
+        &hex!("1234 52"),
+        samples::TrailerDeku {
+            kind: 0,
+            payload: vec![],
+            footer: samples::TrailerFooterDeku { crc: 0, flags: 0, version: 0 },
+        },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 24, got: 16 })")]
+    case::not_enough_data(
+        &hex!("1234"),
+        samples::TrailerDeku {
+            kind: 0,
+            payload: vec![],
+            footer: samples::TrailerFooterDeku { crc: 0, flags: 0, version: 0 },
+        },
+    ),
+)]
+fn test_trailer_deku(input: &[u8], expected: samples::TrailerDeku) {
+    let ret_read = samples::TrailerDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+#[should_panic(expected = "Parse(\"trailer: 8 bits left between the fields and the trailer\")")]
+fn test_trailer_gap() {
+    #[derive(PartialEq, Debug, DekuRead)]
+    #[deku(trailer = "u8")]
+    struct Test {
+        a: u8,
+        b: u8,
+    }
+
+    Test::try_from(hex!("01 02 03").as_ref()).unwrap();
+}
+
+#[rstest(input, expected,
+    case::v4(
+        &hex!("7F000001 04 C0A80001"),
+        samples::IpAddrDeku {
+            v4: std::net::Ipv4Addr::LOCALHOST,
+            len: 4,
+            addr: std::net::IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 0, 1)),
+        },
+    ),
+    case::v6(
+        &hex!("7F000001 10 00000000000000000000000000000001"),
+        samples::IpAddrDeku {
+            v4: std::net::Ipv4Addr::LOCALHOST,
+            len: 16,
+            addr: std::net::IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+        },
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"invalid ip address length: 64 bits, expected 32 or 128\")")]
+    case::invalid_len(
+        &hex!("7F000001 08 0000000000000001"),
+        samples::IpAddrDeku {
+            v4: std::net::Ipv4Addr::LOCALHOST,
+            len: 0,
+            addr: std::net::IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+        },
+    ),
+)]
+fn test_ip_addr_deku(input: &[u8], expected: samples::IpAddrDeku) {
+    let ret_read = samples::IpAddrDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::positive(
+        &hex!("18C0 0F"),
+        samples::SignedDeku { field_a: 3, field_b: 3, field_c: 15 },
+    ),
+    case::negative(
+        &hex!("EF E0 0F"),
+        samples::SignedDeku { field_a: -3, field_b: -15, field_c: -15 },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 14, got: 6 })")]
+    case::not_enough_data(
+        &hex!("18C0"),
+        samples::SignedDeku { field_a: 0, field_b: 0, field_c: 0 },
+    ),
+)]
+fn test_signed_deku(input: &[u8], expected: samples::SignedDeku) {
+    let ret_read = samples::SignedDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::zero(
+        &hex!("00 000000000000000000000000 000000000000000000 000000000000000000"),
+        samples::WideDeku { field_a: 0, field_b: 0, field_c: 0, field_d: 0, field_e: 0 },
+    ),
+    case::normal(
+        &hex!("202468ACF13579BDE02468AC FFFFFFFFFFFFFFFFFF E02468ACF13579BDE0 3F"),
+        samples::WideDeku {
+            field_a: 0b001,
+            field_b: 0x0123_4567_89AB_CDEF_0123_4567,
+            // 72 bits of ones, sign-extended
+            field_c: -1,
+            field_d: 0x01_2345_6789_ABCD_EF01,
+            field_e: 0x1F,
+        },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 96, got: 93 })")]
+    case::not_enough_data(
+        &hex!("20 2468ACF13579BDE02468AC"),
+        samples::WideDeku { field_a: 0, field_b: 0, field_c: 0, field_d: 0, field_e: 0 },
+    ),
+)]
+fn test_wide_deku(input: &[u8], expected: samples::WideDeku) {
+    let ret_read = samples::WideDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[rstest(input, expected, expected_write,
+    case::seek_back(
+        &hex!("01 AA"),
+        samples::SeekEnumDeku::VarA(0x01, 0xAA),
+        Err(DekuError::InvalidParam("seek_from_current: can't seek backwards by 1 bytes when writing".into())),
+    ),
+    case::rewind(
+        &hex!("02"),
+        samples::SeekEnumDeku::VarB(0x02),
+        Err(DekuError::InvalidParam("seek_rewind: can't seek backwards by 8 bits when writing".into())),
+    ),
+    #[should_panic(expected = "Parse(\"seek_from_current: can't seek to bit -8 of 16 bits\")")]
+    case::before_start(&hex!("03 AA"), samples::SeekEnumDeku::VarC(0xAA), Ok(vec![])),
+)]
+fn test_seek_enum_deku(
+    input: &[u8],
+    expected: samples::SeekEnumDeku,
+    expected_write: Result<Vec<u8>, DekuError>,
+) {
+    let ret_read = samples::SeekEnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Result<Vec<u8>, DekuError> = ret_read.try_into();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[rstest(input, expected, expected_write,
+    case::normal(
+        &hex!("A0 BB 02 0000 CC"),
+        samples::PadBeforeDeku { field_a: 0x0A, field_b: 0xBB, len: 2, field_c: 0xCC },
+        hex!("A0 BB 02 0000 CC").to_vec(),
+    ),
+    case::nonzero_padding(
+        &hex!("AF BB 01 FF CC"),
+        samples::PadBeforeDeku { field_a: 0x0A, field_b: 0xBB, len: 1, field_c: 0xCC },
+        hex!("A0 BB 01 00 CC").to_vec(),
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 24, got: 16 })")]
+    case::not_enough_data(
+        &hex!("A0 BB 03 0000"),
+        samples::PadBeforeDeku { field_a: 0x0A, field_b: 0xBB, len: 3, field_c: 0xCC },
+        vec![],
+    ),
+)]
+fn test_pad_before_deku(input: &[u8], expected: samples::PadBeforeDeku, expected_write: Vec<u8>) {
+    let ret_read = samples::PadBeforeDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(expected_write, ret_write);
+}
+
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_pad_bytes_overflow() {
+    let expected = DekuError::Parse(
+        "padding: 18446744073709551615 bytes is too large to be counted in bits".into(),
+    );
+
+    let input = hex!("FFFFFFFFFFFFFFFF AA");
     assert_eq!(
-        samples::NamedDeku {
-            field_a: 0xFF,
-            field_b: 0b0000_0010,
-            field_c: 0b0001_0110,
-            field_d: 0xBBAA,
-            field_e: 0xCCDD,
-            field_f: samples::NestedDeku {
-                nest_a: 0b00_100101,
-                nest_b: 0b10,
-                inner: samples::DoubleNestedDeku { data: 0xDDCC }
-            },
-            vec_len: 0x02,
-            vec_data: vec![0xBE, 0xEF]
-        },
-        ret_read
+        expected,
+        samples::PadOverflowDeku::try_from(input.as_ref())
+            .unwrap_err()
+            .into_inner()
     );
 
-    // Write
-    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    let value = samples::PadOverflowDeku {
+        len: u64::MAX,
+        field_a: 0xAA,
+    };
+    assert_eq!(expected, value.to_bytes().unwrap_err());
 }
 
-#[rstest(input,expected,
-    case(&hex!("01AB"), samples::EnumDeku::VarA(0xAB)),
-    case(&hex!("0269"), samples::EnumDeku::VarB(0b0110, 0b1001)),
-    case(&hex!("0302AABB"), samples::EnumDeku::VarC{field_a: 0x02, field_b: vec![0xAA, 0xBB]}),
-    case(&hex!("0402AABB"), samples::EnumDeku::VarD(0x02, vec![0xAA, 0xBB])),
+#[cfg(not(feature = "static-errors"))]
+#[test]
+fn test_bytes_overflow() {
+    let expected = DekuError::Parse(
+        "bytes: 2305843009213693953 bytes is too large to be counted in bits".into(),
+    );
 
-    #[should_panic(expected = "Parse(\"Could not match enum variant id = 255 on enum `EnumDeku`\")")]
-    case(&hex!("FFAB"), samples::EnumDeku::VarA(0xFF))
+    let input = hex!("0100000000000020 AA");
+    assert_eq!(
+        expected,
+        samples::BytesOverflowDeku::try_from(input.as_ref())
+            .unwrap_err()
+            .into_inner()
+    );
+
+    let value = samples::BytesOverflowDeku {
+        len: 0x2000_0000_0000_0001,
+        field_a: "a".to_string(),
+    };
+    assert_eq!(expected, value.to_bytes().unwrap_err());
+}
+
+#[rstest(input, expected,
+    case::normal(&hex!("7F454C46 AA"), samples::MagicDeku { field_a: 0xAA }),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Magic(\"expected [7f, 45, 4c, 46] got [7f, 45, 4c, 47]\")")]
+    case::mismatch(&hex!("7F454C47 AA"), samples::MagicDeku { field_a: 0xAA }),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 32, got: 16 })")]
+    case::not_enough_data(&hex!("7F45"), samples::MagicDeku { field_a: 0xAA }),
 )]
-fn test_enum(input: &[u8], expected: samples::EnumDeku) {
-    let ret_read = samples::EnumDeku::try_from(input).unwrap();
+fn test_magic_deku(input: &[u8], expected: samples::MagicDeku) {
+    let ret_read = samples::MagicDeku::try_from(input).unwrap();
     assert_eq!(expected, ret_read);
 
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
     assert_eq!(input.to_vec(), ret_write);
 }
 
-#[rstest(input,expected,
-    case(&hex!("01AB"), samples::EnumDekuDefault::VarA(0xAB)),
-    case(&hex!("FFAB"), samples::EnumDekuDefault::VarDefault(0xFF, 0xAB)),
+#[rstest(input, expected,
+    case::normal(&hex!("444B 01 AA"), samples::MagicEnumDeku::VarA(0xAA)),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Magic(\"expected [44, 4b] got [44, 4c]\")")]
+    case::mismatch(&hex!("444C 01 AA"), samples::MagicEnumDeku::VarA(0xAA)),
+)]
+fn test_magic_enum_deku(input: &[u8], expected: samples::MagicEnumDeku) {
+    let ret_read = samples::MagicEnumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
 
-    #[should_panic(expected = "Parse(\"Too much data\")")]
-    case(&hex!("FFFFFF"), samples::EnumDekuDefault::VarA(0xAB)),
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("AA"), samples::ReadAllDeku { header: 0xAA, items: vec![] }),
+    case::items(
+        &hex!("AA 0102 0304"),
+        samples::ReadAllDeku { header: 0xAA, items: vec![0x0102, 0x0304] },
+    ),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 16, got: 8 })")]
+    case::straddle(&hex!("AA 0102 03"), samples::ReadAllDeku { header: 0xAA, items: vec![] }),
 )]
-fn test_enum_default(input: &[u8], expected: samples::EnumDekuDefault) {
-    let ret_read = samples::EnumDekuDefault::try_from(input).unwrap();
+fn test_read_all_deku(input: &[u8], expected: samples::ReadAllDeku) {
+    let ret_read = samples::ReadAllDeku::try_from(input).unwrap();
     assert_eq!(expected, ret_read);
 
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
     assert_eq!(input.to_vec(), ret_write);
 }
 
+#[rstest(input, expected,
+    case::empty(&hex!(""), samples::ReadAllZeroSizeDeku { items: vec![] }),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"element consumed no bits: can't read elements until 8 bits are consumed, use `count`\")")]
+    case::zero_size(&hex!("AA"), samples::ReadAllZeroSizeDeku { items: vec![] }),
+)]
+fn test_read_all_zero_size_deku(input: &[u8], expected: samples::ReadAllZeroSizeDeku) {
+    let ret_read = samples::ReadAllZeroSizeDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("0003 AA"), samples::LenOfDeku { len: 3, tag: 0xAA, payload: vec![] }),
+    case::items(
+        &hex!("0007 AA 0102 0304"),
+        samples::LenOfDeku { len: 7, tag: 0xAA, payload: vec![0x0102, 0x0304] },
+    ),
+)]
+fn test_len_of_deku(input: &[u8], expected: samples::LenOfDeku) {
+    let mut ret_read = samples::LenOfDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+
+    ret_read.payload.push(0x0506);
+    ret_read.update().unwrap();
+    assert_eq!(expected.len + 2, ret_read.len);
+
+    ret_read.payload.clear();
+    ret_read.update().unwrap();
+    assert_eq!(3, ret_read.len);
+}
+
 #[test]
-fn test_dynamic_vec_count() {
-    let test_data: Vec<u8> = [0x02, 0xAA, 0xBB].to_vec();
+fn test_len_of_deku_short() {
+    // the length can't be shorter than the header
+    assert_eq!(
+        Err(DekuError::Parse("len: 2 is shorter than the header".into())),
+        samples::LenOfDeku::try_from(hex!("0002 AA").as_ref()).map_err(DekuError::into_inner)
+    );
+}
 
-    // Read
-    let mut ret_read = samples::VecCountDeku::try_from(test_data.as_ref()).unwrap();
+#[test]
+fn test_len_of_enum_deku() {
+    let input = hex!("01 01 AABBCC");
+
+    let mut ret_read = samples::LenOfEnumDeku::try_from(input.as_ref()).unwrap();
     assert_eq!(
-        samples::VecCountDeku {
-            count: 0x02,
-            vec_data: vec![0xAA, 0xBB]
+        samples::LenOfEnumDeku::VarA {
+            len: 1,
+            data: vec![0xAA, 0xBB, 0xCC]
         },
         ret_read
     );
 
-    // Add an item to the vec
-    ret_read.vec_data.push(0xFF);
+    let samples::LenOfEnumDeku::VarA { data, .. } = &mut ret_read;
+    data.push(0xDD);
     ret_read.update().unwrap();
 
-    // Write
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!([0x03, 0xAA, 0xBB, 0xFF].to_vec(), ret_write);
+    assert_eq!(hex!("01 02 AABBCCDD").to_vec(), ret_write);
 }
 
+#[cfg(not(feature = "static-errors"))]
 #[test]
 #[should_panic(
     expected = "Parse(\"error parsing int: out of range integral type conversion attempted\")"
 )]
-fn test_dynamic_vec_count_error() {
-    let mut val = samples::VecCountDeku {
-        count: 0x02,
-        vec_data: vec![0xAA, 0xBB],
+fn test_len_of_enum_deku_error() {
+    let mut value = samples::LenOfEnumDeku::VarA {
+        len: 0,
+        data: vec![0xAA],
     };
-
-    // `count` is a u8, add u8::MAX ++ items and try to update
-    for _ in 0..std::u8::MAX {
-        val.vec_data.push(0xFF);
-    }
-    val.update().unwrap();
+    value.update().unwrap();
 }
 
-#[test]
-fn test_map() {
-    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+#[rstest(input, expected,
+    case::empty(
+        &[0b0000_1010],
+        samples::BitsReadDeku { len: 0, items: vec![], after: 0b1010 },
+    ),
+    case::items(
+        &[0b1000_0110, 0b1110_1111],
+        samples::BitsReadDeku { len: 8, items: vec![0b01, 0b10, 0b11, 0b10], after: 0b1111 },
+    ),
+)]
+fn test_bits_read_deku(input: &[u8], expected: samples::BitsReadDeku) {
+    let ret_read = samples::BitsReadDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
 
-    let ret_read = samples::MapDeku::try_from(test_data.as_ref()).unwrap();
-    assert_eq!(
-        samples::MapDeku {
-            field_a: "1".to_string(),
-            field_b: "2".to_string(),
-        },
-        ret_read
-    );
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
 }
 
 #[test]
-fn test_generic_struct_deku() {
-    let test_data: Vec<u8> = [0x01].to_vec();
+#[should_panic(expected = "Incomplete(NeedSize { expected: 2, got: 1 })")]
+fn test_bits_read_deku_straddle() {
+    samples::BitsReadDeku::try_from([0b0011_0110, 0b1111_0000].as_ref()).unwrap();
+}
 
-    let ret_read = samples::GenericStructDeku::<u8>::try_from(test_data.as_ref()).unwrap();
-    assert_eq!(samples::GenericStructDeku::<u8> { field_a: 0x01 }, ret_read);
+#[rstest(input, expected,
+    case::empty(&hex!("00 E1F0"), samples::TrailerChecksumDeku { field_a: 0, field_b: vec![], crc: 0xE1F0 }),
+    case::items(
+        &hex!("02 AABB 46F9"),
+        samples::TrailerChecksumDeku { field_a: 2, field_b: vec![0xAA, 0xBB], crc: 0x46F9 },
+    ),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"checksum mismatch: expected 18169 got 18168\")")]
+    case::mismatch(&hex!("02 AABB 46F8"), samples::TrailerChecksumDeku { field_a: 0, field_b: vec![], crc: 0 }),
+)]
+fn test_trailer_checksum_deku(input: &[u8], expected: samples::TrailerChecksumDeku) {
+    let ret_read = samples::TrailerChecksumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
 
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    assert_eq!(input.to_vec(), ret_write);
 }
 
 #[test]
-fn test_generic_enum_deku() {
-    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+fn test_trailer_checksum_deku_write() {
+    // the stored checksum is ignored
+    let value = samples::TrailerChecksumDeku {
+        field_a: 2,
+        field_b: vec![0xAA, 0xBB],
+        crc: 0,
+    };
 
-    let ret_read = samples::GenericEnumDeku::<u8>::try_from(test_data.as_ref()).unwrap();
-    assert_eq!(samples::GenericEnumDeku::<u8>::VariantT(0x02), ret_read);
+    let ret_write: Vec<u8> = value.try_into().unwrap();
+    assert_eq!(hex!("02 AABB 46F9").to_vec(), ret_write);
+}
 
-    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+#[cfg(not(feature = "static-errors"))]
+#[rstest(input,
+    #[should_panic(expected = "Parse(\"checksum: 4 bits read is not a multiple of 8\")")]
+    case::read(&hex!("1234 56")),
+)]
+fn test_trailer_checksum_unaligned(input: &[u8]) {
+    samples::UnalignedChecksumDeku::from_bytes((input, 0)).unwrap();
 }
 
+#[cfg(not(feature = "static-errors"))]
 #[test]
-fn test_skip_deku() {
-    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+#[should_panic(expected = "InvalidParam(\"checksum: 4 bits written is not a multiple of 8\")")]
+fn test_trailer_checksum_unaligned_write() {
+    let value = samples::UnalignedChecksumDeku { field_a: 1, crc: 0 };
+    value.to_bytes().unwrap();
+}
 
-    let ret_read = samples::SkipDeku::try_from(test_data.as_ref()).unwrap();
+#[test]
+fn test_wire_doc_deku() {
     assert_eq!(
-        samples::SkipDeku {
-            field_a: 0x01,
-            field_b: None,
-            field_c: 0x02,
-        },
-        ret_read
+        "| Field | Type | Bits | Endian | Description |\n\
+         |---|---|---|---|---|\n\
+         | magic | `[u8; 2]` | 16 |  | magic `b\"DK\"` |\n\
+         | version | `u8` | 8 | big | Version of the format |\n\
+         | flags | `u8` | 4 | little | Flags \\| reserved |\n\
+         | len | `u8` | 4 | big |  |\n\
+         | data | `Option<Vec<u16>>` | variable | big | Payload (if `* flags != 0`) |\n",
+        samples::WireDocDeku::WIRE_FORMAT
+    );
+
+    assert_eq!(
+        "| Field | Type | Bits | Endian | Description |\n\
+         |---|---|---|---|---|\n\
+         | id | `u8` | 8 | native | variant id |\n\
+         | VarA::0 | `u16` | 16 | native |  |\n\
+         | VarB::value | `u32` | 32 | native | Value |\n",
+        samples::WireDocEnumDeku::WIRE_FORMAT
     );
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("00 00"), samples::HeaderChecksumDeku { len: 0, sum: 0, data: vec![] }),
+    case::data(&hex!("02 03 0102"), samples::HeaderChecksumDeku { len: 2, sum: 0x03, data: vec![0x01, 0x02] }),
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"checksum mismatch: expected 3 got 4\")")]
+    case::mismatch(&hex!("02 04 0102"), samples::HeaderChecksumDeku { len: 0, sum: 0, data: vec![] }),
+)]
+fn test_header_checksum_deku(input: &[u8], expected: samples::HeaderChecksumDeku) {
+    let ret_read = samples::HeaderChecksumDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
 
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    assert_eq!(input.to_vec(), ret_write);
 }
 
 #[test]
-fn test_default_deku() {
-    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
+fn test_header_checksum_deku_write() {
+    // the stored checksum is ignored
+    let value = samples::HeaderChecksumDeku {
+        len: 2,
+        sum: 0,
+        data: vec![0xFF, 0x02],
+    };
 
-    let ret_read = samples::DefaultDeku::try_from(test_data.as_ref()).unwrap();
+    let ret_write: Vec<u8> = value.try_into().unwrap();
+    assert_eq!(hex!("02 01 FF02").to_vec(), ret_write);
+}
+
+#[test]
+fn test_field_checksum_deku() {
+    // computed over the whole struct, with the checksum zeroed
+    let input = hex!("AA 10AD BB");
+
+    let ret_read = samples::FieldChecksumDeku::try_from(input.as_ref()).unwrap();
     assert_eq!(
-        samples::DefaultDeku {
-            field_a: 0x01,
-            field_b: 0x05,
-            field_c: 0x02,
+        samples::FieldChecksumDeku {
+            field_a: 0xAA,
+            crc: 0x10AD,
+            field_b: 0xBB
         },
         ret_read
     );
 
     let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    assert_eq!(input.to_vec(), ret_write);
 }
 
-#[test]
-fn test_skip_cond_deku() {
-    let test_data: Vec<u8> = [0x01].to_vec();
+#[rstest(input, expected,
+    case::empty(&hex!("00 10"), samples::SignatureDeku { len: 0, signature: 0x10, data: vec![] }),
+    case::data(&hex!("02 0102 15"), samples::SignatureDeku { len: 2, signature: 0x15, data: vec![0x01, 0x02] }),
+    #[should_panic(expected = "Parse(\"signature mismatch\")")]
+    case::mismatch(&hex!("02 0103 15"), samples::SignatureDeku { len: 0, signature: 0, data: vec![] }),
+)]
+fn test_signature_deku(input: &[u8], expected: samples::SignatureDeku) {
+    let ret_read = samples::SignatureDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
 
-    let ret_read = samples::SkipCondDeku::try_from(test_data.as_ref()).unwrap();
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_signature_deku_key() {
+    // the stored signature is ignored, the signature is computed with the key of the ctx
+    let value = samples::SignatureDeku {
+        len: 1,
+        data: vec![0x01],
+        signature: 0,
+    };
     assert_eq!(
-        samples::SkipCondDeku {
-            field_a: 0x01,
-            field_b: 0x05, // default
-        },
-        ret_read
+        hex!("01 01 22").to_vec(),
+        value.write(0x20).unwrap().into_vec()
     );
 
-    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(vec![0x01], ret_write);
+    let (_, ret_read) = samples::SignatureDeku::read(hex!("01 01 22").view_bits(), 0x20).unwrap();
+    assert_eq!(0x22, ret_read.signature);
+
+    assert_eq!(
+        Err(DekuError::InvalidParam("signature: no key".into())),
+        value.write(0)
+    );
 }
 
+#[cfg(not(feature = "static-errors"))]
 #[test]
-fn test_cond_deku() {
-    let test_data: Vec<u8> = [0x01, 0x02].to_vec();
-
-    let ret_read = samples::CondDeku::try_from(test_data.as_ref()).unwrap();
+fn test_checksum_range() {
+    let ret_read =
+        samples::ChecksumRangeDeku::try_from(hex!("00AA").as_ref()).map_err(DekuError::into_inner);
     assert_eq!(
-        samples::CondDeku {
-            field_a: 0x01,
-            field_b: Some(0x02),
-        },
+        Err(DekuError::Parse(
+            "checksum: range `0 .. 4` is outside of the 2 bytes of the container".into()
+        )),
         ret_read
     );
 
-    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
-    assert_eq!(test_data, ret_write);
+    let ret_write: Result<Vec<u8>, _> = samples::ChecksumRangeDeku {
+        crc: 0,
+        field_a: 0xAA,
+    }
+    .try_into();
+    assert_eq!(
+        Err(DekuError::InvalidParam(
+            "checksum: range `0 .. 4` is outside of the 2 bytes of the container".into()
+        )),
+        ret_write
+    );
 }
 
 #[test]
@@ -630,6 +3875,175 @@ fn test_enum_endian_ctx() {
     assert_eq!(ret_write, test_data)
 }
 
+#[test]
+fn test_owned() {
+    let test_data = [0x02u8, 0xAA, 0xBB];
+
+    let ret_read = samples::OwnedDeku::try_from(test_data.as_ref()).unwrap();
+    let owned = ret_read.to_owned();
+    assert_eq!(
+        samples::OwnedDekuOwned {
+            count: 0x02,
+            data: vec![0xAA, 0xBB],
+        },
+        owned
+    );
+
+    // The owned struct can be read and written on its own
+    let ret_read = samples::OwnedDekuOwned::try_from(test_data.as_ref()).unwrap();
+    assert_eq!(owned, ret_read);
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data.to_vec(), ret_write);
+}
+
+#[test]
+fn test_borrowed() {
+    let test_data: Vec<u8> = [0x02, 0xAA, 0xBB, 0xCC].to_vec();
+
+    let ((rest, i), ret_read) = samples::BorrowedDeku::from_bytes((&test_data, 0)).unwrap();
+    assert_eq!(&[0xAA, 0xBB], ret_read.data);
+    assert_eq!(test_data[1..].as_ptr(), ret_read.data.as_ptr());
+    assert_eq!((&[0xCCu8][..], 0), (rest, i));
+
+    let test_data = &test_data[..3];
+    let ret_read = samples::BorrowedDeku::try_from(test_data).unwrap();
+
+    assert_eq!(
+        samples::BorrowedDekuOwned {
+            count: 0x02,
+            data: vec![0xAA, 0xBB],
+        },
+        ret_read.to_owned()
+    );
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(test_data.to_vec(), ret_write);
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("0000"), Ok(samples::BorrowedStrDeku { len: 0, name: "" })),
+    case::name(&hex!("0003 686579"), Ok(samples::BorrowedStrDeku { len: 3, name: "hey" })),
+    #[cfg(not(feature = "static-errors"))]
+    case::invalid_utf8(&hex!("0002 68FF"), Err(DekuError::Parse("encoding: invalid utf-8 at byte 1".into()))),
+)]
+fn test_borrowed_str_deku(input: &[u8], expected: Result<samples::BorrowedStrDeku, DekuError>) {
+    let ret_read = samples::BorrowedStrDeku::try_from(input).map_err(DekuError::into_inner);
+    assert_eq!(expected, ret_read);
+
+    if let Ok(ret_read) = ret_read {
+        assert_eq!(input[2..].as_ptr(), ret_read.name.as_ptr());
+        assert_eq!(
+            samples::BorrowedStrDekuOwned {
+                len: ret_read.len,
+                name: ret_read.name.to_string(),
+            },
+            ret_read.to_owned()
+        );
+
+        let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+        assert_eq!(input.to_vec(), ret_write);
+    }
+}
+
+#[rstest(input, expected,
+    case::empty(&hex!("AA"), samples::ReadAllBorrowedDeku { header: 0xAA, payload: &[] }),
+    case::payload(&hex!("AA BBCCDD"), samples::ReadAllBorrowedDeku { header: 0xAA, payload: &hex!("BBCCDD") }),
+)]
+fn test_read_all_borrowed_deku(input: &[u8], expected: samples::ReadAllBorrowedDeku) {
+    let ret_read = samples::ReadAllBorrowedDeku::try_from(input).unwrap();
+    assert_eq!(expected, ret_read);
+    assert_eq!(input[1..].as_ptr(), ret_read.payload.as_ptr());
+
+    let ret_write: Vec<u8> = ret_read.try_into().unwrap();
+    assert_eq!(input.to_vec(), ret_write);
+}
+
+#[test]
+fn test_view() {
+    #[repr(C, align(4))]
+    struct Aligned([u8; 12]);
+
+    let test_data = Aligned(hex!("01020304 AABB CCDD FFFFFFFF"));
+
+    let view = samples::ViewDeku::view(&test_data.0).unwrap();
+    assert_eq!(0x01020304, view.field_a().unwrap());
+    assert_eq!(0xBBAA, view.field_b().unwrap());
+    assert_eq!([0xCC, 0xDD], view.field_c().unwrap());
+
+    assert_eq!(64, <samples::ViewDeku as DekuSize>::BIT_SIZE);
+}
+
+#[test]
+fn test_deku_view() {
+    let test_data: Vec<u8> = hex!("12 AABB CCDD EEFF 00").to_vec();
+
+    let view = samples::DekuViewDeku::view(test_data.view_bits()).unwrap();
+    assert_eq!(0x01, view.field_a().unwrap());
+    assert_eq!(0x02, view.field_b().unwrap());
+    assert_eq!(0xAABB, view.field_c().unwrap());
+    assert_eq!(0xDDCC, view.field_d().unwrap());
+    assert_eq!([0xEE, 0xFF], view.field_e().unwrap());
+
+    assert_eq!(56, <samples::DekuViewDeku as DekuSize>::BIT_SIZE);
+}
+
+#[rstest(mask, expected,
+    case::none(0, samples::DekuViewDekuPartial::default()),
+    case::one(
+        samples::DekuViewDekuPartial::FIELD_C,
+        samples::DekuViewDekuPartial { field_c: Some(0xAABB), ..Default::default() }
+    ),
+    case::some(
+        samples::DekuViewDekuPartial::FIELD_B | samples::DekuViewDekuPartial::FIELD_E,
+        samples::DekuViewDekuPartial {
+            field_b: Some(0x02),
+            field_e: Some([0xEE, 0xFF]),
+            ..Default::default()
+        }
+    ),
+    case::all(
+        u64::MAX,
+        samples::DekuViewDekuPartial {
+            field_a: Some(0x01),
+            field_b: Some(0x02),
+            field_c: Some(0xAABB),
+            field_d: Some(0xDDCC),
+            field_e: Some([0xEE, 0xFF]),
+        }
+    ),
+)]
+fn test_deku_view_partial(mask: u64, expected: samples::DekuViewDekuPartial) {
+    let test_data: Vec<u8> = hex!("12 AABB CCDD EEFF 00").to_vec();
+
+    let view = samples::DekuViewDeku::view(test_data.view_bits()).unwrap();
+    assert_eq!(expected, view.partial(mask).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "Incomplete(NeedSize { expected: 56, got: 48 })")]
+fn test_deku_view_error() {
+    let test_data: Vec<u8> = hex!("12 AABB CCDD EE").to_vec();
+
+    samples::DekuViewDeku::view(test_data.view_bits()).unwrap();
+}
+
+#[rstest(input,
+    #[cfg(not(feature = "static-errors"))]
+    #[should_panic(expected = "Parse(\"input is not aligned to 4 bytes, required by `ViewDeku`\")")]
+    case::not_aligned(1..9),
+    #[should_panic(expected = "Incomplete(NeedSize { expected: 64, got: 32 })")]
+    case::not_enough_data(0..4),
+)]
+fn test_view_error(input: core::ops::Range<usize>) {
+    #[repr(C, align(4))]
+    struct Aligned([u8; 12]);
+
+    let test_data = Aligned([0; 12]);
+
+    samples::ViewDeku::view(&test_data.0[input]).unwrap();
+}
+
 #[test]
 fn test_compile() {
     let t = trybuild::TestCases::new();
@@ -0,0 +1,78 @@
+//! Run with `cargo test --features pretty-errors --test test_pretty_errors`
+#![cfg(feature = "pretty-errors")]
+
+use deku::prelude::*;
+use hexlit::hex;
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct TestDeku {
+    field_a: u16,
+    field_b: u32,
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(endian = "big")]
+struct LongDeku {
+    data: [u8; 10],
+    #[deku(bits = "4")]
+    field_a: u8,
+    field_b: u32,
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(type = "u8")]
+enum TestEnum {
+    #[deku(id = "0x01")]
+    VarA(TestDeku),
+}
+
+#[test]
+fn test_pretty_errors_struct() {
+    let input = hex!("01 02 AB CD");
+    let err = TestDeku::from_bytes((input.as_ref(), 0)).unwrap_err();
+
+    assert_eq!(
+        DekuError::Context {
+            bit_offset: 16,
+            window_start: 0,
+            window: input.to_vec(),
+            error: Box::new(DekuError::Incomplete(NeedSize::new(32, 16))),
+        },
+        err
+    );
+    assert_eq!(
+        "Incomplete error: not enough data: expected 32 bits got 16 bits at byte 2\n\
+         00000000: 01 02 ab cd\n\
+         \x20               ^^",
+        err.to_string()
+    );
+    assert_eq!(
+        DekuError::Incomplete(NeedSize::new(32, 16)),
+        err.into_inner()
+    );
+}
+
+#[test]
+fn test_pretty_errors_window() {
+    let input = hex!("00 01 02 03 04 05 06 07 08 09 0A");
+    let err = LongDeku::from_bytes((input.as_ref(), 0)).unwrap_err();
+
+    assert_eq!(
+        "Incomplete error: not enough data: expected 32 bits got 4 bits at byte 10 bit 4\n\
+         00000002: 02 03 04 05 06 07 08 09 0a\n\
+         \x20                                 ^^",
+        err.to_string()
+    );
+
+    #[cfg(not(feature = "static-errors"))]
+    {
+        let err = TestEnum::from_bytes((&input[9..], 0)).unwrap_err();
+        assert_eq!(
+            "Parse error: Could not match enum variant id = 9 on enum `TestEnum` at byte 0\n\
+             00000000: 09 0a\n\
+             \x20         ^^",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use deku::ctx::Endian;
+use deku::prelude::*;
+use deku::roundtrip::{assert_bit_offsets, assert_roundtrip, assert_truncated};
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(ctx = "endian: Endian", endian = "endian")]
+struct DekuTest {
+    #[deku(bits = "4")]
+    field_a: u8,
+    #[deku(bits = "12")]
+    field_b: u16,
+    count: u8,
+    #[deku(count = "count")]
+    items: Vec<u32>,
+}
+
+deku::roundtrip_tests!(deku_test: DekuTest = [
+    DekuTest { field_a: 0, field_b: 0, count: 0, items: vec![] },
+    DekuTest { field_a: 0x0A, field_b: 0x0BCD, count: 2, items: vec![0xAABBCCDD, 0x01] },
+]);
+
+deku::roundtrip_tests!(primitive: u32 = [0, 0xAABBCCDD]);
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(ctx = "_endian: Endian")]
+struct EofDeku {
+    field_a: u8,
+    #[deku(default_if_eof)]
+    field_b: u8,
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(ctx = "_endian: Endian")]
+struct NormalizeDeku {
+    #[deku(map = "|v: u8| -> Result<_, DekuError> { Ok(v.min(0x0F)) }")]
+    field_a: u8,
+}
+
+#[test]
+#[should_panic(
+    expected = "reading EofDeku { field_a: 1, field_b: 2 } with Little truncated to 8 of 16 bits didn't fail, read EofDeku { field_a: 1, field_b: 0 }"
+)]
+fn test_truncated_read() {
+    assert_truncated(
+        &EofDeku {
+            field_a: 0x01,
+            field_b: 0x02,
+        },
+        Endian::Little,
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "reading NormalizeDeku { field_a: 255 } with Big: value read differs from the value written"
+)]
+fn test_roundtrip_differs() {
+    assert_roundtrip(&NormalizeDeku { field_a: 0xFF }, Endian::Big);
+}
+
+#[test]
+fn test_bit_offsets() {
+    assert_bit_offsets(
+        &EofDeku {
+            field_a: 0x01,
+            field_b: 0x02,
+        },
+        Endian::Big,
+    );
+}
@@ -0,0 +1,91 @@
+//! Run with `cargo test --features static-errors --test test_static_errors`
+#![cfg(feature = "static-errors")]
+
+use deku::prelude::*;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(magic = b"dk")]
+struct MagicDeku {
+    field_a: u8,
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+#[deku(type = "u8")]
+enum IdDeku {
+    #[deku(id = "0x01")]
+    VarA(u8),
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct AssertDeku {
+    #[deku(assert = "*field_a < 3")]
+    field_a: u8,
+    #[deku(assert_eq = "0xAB")]
+    field_b: u8,
+}
+
+#[derive(Debug, PartialEq, DekuRead, DekuWrite)]
+struct PadDeku {
+    len: u64,
+    #[deku(pad_bytes_before = "*len")]
+    field_a: u8,
+}
+
+fn assert_static(ret: Result<impl std::fmt::Debug, DekuError>, expected: DekuError) {
+    let err = ret.unwrap_err().into_inner();
+    let msg = match &err {
+        DekuError::Parse(msg)
+        | DekuError::InvalidParam(msg)
+        | DekuError::Magic(msg)
+        | DekuError::Assertion(msg) => msg,
+        err => panic!("unexpected error {:?}", err),
+    };
+    assert!(matches!(msg, Cow::Borrowed(_)), "allocated: {:?}", msg);
+    assert_eq!(expected, err);
+}
+
+#[test]
+fn test_static_magic() {
+    assert_static(
+        MagicDeku::try_from(&[b'd', b'x', 0x01][..]),
+        DekuError::Magic("expected {:02x?} got {:02x?}".into()),
+    );
+}
+
+#[test]
+fn test_static_id() {
+    assert_static(
+        IdDeku::try_from(&[0x02, 0x01][..]),
+        DekuError::Parse("Could not match enum variant id = {:?} on enum `IdDeku`".into()),
+    );
+}
+
+#[test]
+fn test_static_assert() {
+    assert_static(
+        AssertDeku::try_from(&[0x03, 0xAB][..]),
+        DekuError::Assertion("field `field_a` failed assertion: *field_a < 3".into()),
+    );
+    assert_static(
+        AssertDeku::try_from(&[0x01, 0xAC][..]),
+        DekuError::Assertion("field `field_b` failed assertion: expected {:?} got {:?}".into()),
+    );
+}
+
+#[test]
+fn test_static_padding() {
+    assert_static(
+        PadDeku::try_from(&[0xFF; 9][..]),
+        DekuError::Parse("padding: {} bytes is too large to be counted in bits".into()),
+    );
+}
+
+#[test]
+fn test_static_library() {
+    assert_static(
+        u8::try_from(256u16).map_err(DekuError::from),
+        DekuError::Parse("error parsing int: {}".into()),
+    );
+}